@@ -54,7 +54,7 @@ TARGET_CRATES := libc std green rustuv native flate arena glob term semver \
 		 workcache url log
 HOST_CRATES := syntax rustc rustdoc fourcc hexfloat
 CRATES := $(TARGET_CRATES) $(HOST_CRATES)
-TOOLS := compiletest rustdoc rustc
+TOOLS := compiletest rustdoc rustc bindgen
 
 DEPS_std := libc native:rustrt native:compiler-rt native:backtrace
 DEPS_green := std rand native:context_switch
@@ -88,9 +88,11 @@ DEPS_log := std sync
 TOOL_DEPS_compiletest := test green rustuv getopts
 TOOL_DEPS_rustdoc := rustdoc native
 TOOL_DEPS_rustc := rustc native
+TOOL_DEPS_bindgen := syntax getopts collections native
 TOOL_SOURCE_compiletest := $(S)src/compiletest/compiletest.rs
 TOOL_SOURCE_rustdoc := $(S)src/driver/driver.rs
 TOOL_SOURCE_rustc := $(S)src/driver/driver.rs
+TOOL_SOURCE_bindgen := $(S)src/bindgen/bindgen.rs
 
 ################################################################################
 # You should not need to edit below this line
@@ -48,6 +48,7 @@ PKG_FILES := \
     $(S)man                                    \
     $(addprefix $(S)src/,                      \
       README.md                                \
+      bindgen                                  \
       compiletest                              \
       doc                                      \
       driver                                   \
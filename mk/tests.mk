@@ -900,7 +900,8 @@ $$(call TEST_OK_FILE,$(1),$(2),$(3),rmake): \
 
 $(3)/test/run-make/%-$(1)-T-$(2)-H-$(3).ok: \
 		$(S)src/test/run-make/%/Makefile \
-		$$(CSREQ$(1)_T_$(2)_H_$(3))
+		$$(CSREQ$(1)_T_$(2)_H_$(3)) \
+		$$(HBIN$(1)_H_$(3))/bindgen$$(X_$(3))
 	@rm -rf $(3)/test/run-make/$$*
 	@mkdir -p $(3)/test/run-make/$$*
 	$$(Q)$$(CFG_PYTHON) $(S)src/etc/maketest.py $$(dir $$<) \
@@ -909,6 +910,7 @@ $(3)/test/run-make/%-$(1)-T-$(2)-H-$(3).ok: \
 	    $(3)/test/run-make/$$* \
 	    "$$(CC_$(3)) $$(CFG_GCCISH_CFLAGS_$(3))" \
 	    $$(HBIN$(1)_H_$(3))/rustdoc$$(X_$(3)) \
+	    $$(HBIN$(1)_H_$(3))/bindgen$$(X_$(3)) \
 	    "$$(TESTNAME)" \
 	    "$$(RPATH_VAR$(1)_T_$(2)_H_$(3))"
 	@touch $$@
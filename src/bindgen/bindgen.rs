@@ -0,0 +1,115 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Generates Rust bindings from C headers.
+//!
+//! The declarations of a header are turned into a Rust module: functions and
+//! variables go into an `extern` block, structs, enums and typedefs become
+//! Rust types and `#define`d integers become statics. Preprocessor
+//! conditionals and includes are not evaluated, so headers which depend on
+//! them should be run through `cc -E -dD` first.
+
+#![crate_id = "bindgen#0.11-pre"]
+#![crate_type = "bin"]
+#![feature(managed_boxes)]
+
+extern crate syntax;
+extern crate getopts;
+extern crate collections;
+
+use std::io;
+use std::io::File;
+use std::os;
+use std::str;
+
+pub mod lexer;
+pub mod parser;
+pub mod gen;
+
+pub fn main() {
+    os::set_exit_status(main_args(os::args()));
+}
+
+pub fn opts() -> Vec<getopts::OptGroup> {
+    use getopts::*;
+    vec!(
+        optflag("h", "help", "show this help message"),
+        optopt("o", "output", "where to place the output, instead of stdout", "PATH"),
+        optopt("", "libc", "path of the module defining the C types, `libc` by default",
+               "PATH")
+    )
+}
+
+pub fn usage(argv0: &str) {
+    println!("{}",
+             getopts::usage(format!("{} [options] <header>", argv0),
+                            opts().as_slice()));
+}
+
+pub fn main_args(args: &[~str]) -> int {
+    let matches = match getopts::getopts(args.tail(), opts().as_slice()) {
+        Ok(m) => m,
+        Err(err) => {
+            let _ = writeln!(&mut io::stderr(), "{}", err.to_err_msg());
+            return 1;
+        }
+    };
+    if matches.opt_present("h") || matches.opt_present("help") {
+        usage(args[0]);
+        return 0;
+    }
+    if matches.free.len() != 1 {
+        let _ = writeln!(&mut io::stderr(), "expected a single header to act on");
+        return 1;
+    }
+    let input = Path::new(matches.free.get(0).as_slice());
+    let libc = matches.opt_str("libc").unwrap_or(~"libc");
+
+    let src = match File::open(&input).read_to_end() {
+        Ok(bytes) => match str::from_utf8(bytes.as_slice()) {
+            Some(src) => src.to_owned(),
+            None => {
+                let _ = writeln!(&mut io::stderr(), "{} is not UTF-8", input.display());
+                return 1;
+            }
+        },
+        Err(e) => {
+            let _ = writeln!(&mut io::stderr(), "couldn't read {}: {}", input.display(), e);
+            return 1;
+        }
+    };
+    let toks = match lexer::tokenize(src.as_slice()) {
+        Ok(toks) => toks,
+        Err(e) => {
+            let _ = writeln!(&mut io::stderr(), "{}:{}", input.display(), e);
+            return 1;
+        }
+    };
+    let (decls, warnings) = parser::parse(toks);
+    for warning in warnings.iter() {
+        let _ = writeln!(&mut io::stderr(), "{}:{}", input.display(), warning);
+    }
+    let (module, warnings) = gen::generate(decls.as_slice(), libc.as_slice());
+    for warning in warnings.iter() {
+        let _ = writeln!(&mut io::stderr(), "{}:{}", input.display(), warning);
+    }
+
+    let res = match matches.opt_str("o") {
+        Some(output) => File::create(&Path::new(output)).write_str(module.as_slice()),
+        None => io::stdout().write_str(module.as_slice()),
+    };
+    match res {
+        Ok(()) => 0,
+        Err(e) => {
+            let _ = writeln!(&mut io::stderr(), "couldn't write the bindings: {}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,363 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Translation of C declarations into a Rust module.
+//!
+//! Items are built with the AST builder used by syntax extensions and
+//! printed with the pretty printer, so the output is formatted like any other
+//! pretty printed Rust. Types which aren't defined by the header are
+//! imported from `libc`.
+
+use std::i32;
+use std::u32;
+use collections::{HashMap, HashSet};
+
+use syntax::abi;
+use syntax::ast;
+use syntax::ast_util;
+use syntax::codemap::{respan, DUMMY_SP};
+use syntax::ext::base::{CrateLoader, ExtCtxt, MacroCrate};
+use syntax::ext::build::AstBuilder;
+use syntax::ext::expand::ExpansionConfig;
+use syntax::parse;
+use syntax::parse::token;
+use syntax::print::pprust;
+
+use parser::{Decl, Typedef, Struct, OpaqueStruct, Enum, Constant, Function, Variable};
+use parser::{Type, Void, Prim, Named, Pointer, Array, Func, FnSig};
+
+struct NoLoader;
+
+impl CrateLoader for NoLoader {
+    fn load_crate(&mut self, _: &ast::ViewItem) -> MacroCrate {
+        fail!("bindgen doesn't load crates")
+    }
+}
+
+struct Gen<'a> {
+    cx: ExtCtxt<'a>,
+    /// The types defined by the header.
+    defined: HashSet<~str>,
+    /// The names which need to be imported from `libc`.
+    imports: HashSet<~str>,
+    warnings: Vec<~str>,
+}
+
+/// Generates a Rust module binding the declarations of a header, `libc` being
+/// the path of the module providing the C types. Returns the source of the
+/// module along with warnings about declarations which were skipped.
+pub fn generate(decls: &[(uint, Decl)], libc: &str) -> (~str, Vec<~str>) {
+    let sess = parse::new_parse_sess();
+    let mut loader = NoLoader;
+    let cx = ExtCtxt::new(&sess, Vec::new(), ExpansionConfig {
+        loader: &mut loader,
+        deriving_hash_type_parameter: false,
+        crate_id: from_str("bindgen").unwrap(),
+    });
+    let mut gen = Gen {
+        cx: cx,
+        defined: HashSet::new(),
+        imports: HashSet::new(),
+        warnings: Vec::new(),
+    };
+    for &(_, ref decl) in decls.iter() {
+        match *decl {
+            Typedef(ref name, _) | Struct(ref name, _) | OpaqueStruct(ref name) |
+            Enum(Some(ref name), _) => {
+                gen.defined.insert(name.clone());
+            }
+            _ => {}
+        }
+    }
+
+    let mut items = Vec::new();
+    let mut foreign_items = Vec::new();
+    // Like C, which keeps struct tags apart from everything else, Rust keeps
+    // types and values apart, so `struct stat` and the function `stat` can
+    // both be bound.
+    let mut types = HashMap::new();
+    let mut values = HashMap::new();
+    for &(line, ref decl) in decls.iter() {
+        // C declares structs before defining them, and mentions them without
+        // declaring them at all.
+        match *decl {
+            OpaqueStruct(ref name) if decls.iter().any(|&(_, ref d)| {
+                match *d { Struct(ref n, _) => n == name, _ => false }
+            }) => continue,
+            _ => {}
+        }
+        let (name, earlier) = match *decl {
+            Typedef(ref name, _) | Struct(ref name, _) | OpaqueStruct(ref name) |
+            Enum(Some(ref name), _) => (name.clone(), declare(&mut types, name, decl)),
+            Constant(ref name, _, _) | Function(ref name, _) |
+            Variable(ref name, _) => (name.clone(), declare(&mut values, name, decl)),
+            Enum(None, _) => (~"", None),
+        };
+        match earlier {
+            // C allows redeclarations.
+            Some(earlier) if *earlier == *decl => continue,
+            Some(_) => {
+                gen.warnings.push(format!("{}: skipping `{}`: it is already declared differently",
+                                          line, name));
+                continue
+            }
+            None => {}
+        }
+        match gen.decl(decl, &mut items, &mut foreign_items) {
+            Ok(()) => {}
+            Err(e) => gen.warnings.push(format!("{}: skipping `{}`: {}", line, name, e)),
+        }
+    }
+
+    let mut out = StrBuf::new();
+    let mut imports: Vec<~str> = gen.imports.iter().map(|s| s.clone()).collect();
+    if !imports.is_empty() {
+        imports.sort();
+        let imports: Vec<ast::Ident> = imports.iter().map(|s| ident(s.as_slice())).collect();
+        let path = libc.split_str("::").map(|s| ident(s)).collect();
+        let vi = gen.cx.view_use_list(DUMMY_SP, ast::Inherited, path, imports.as_slice());
+        out.push_str(pprust::to_str(|s| s.print_view_item(&vi)).trim());
+    }
+    if !foreign_items.is_empty() {
+        let node = ast::ItemForeignMod(ast::ForeignMod {
+            abi: abi::C,
+            view_items: Vec::new(),
+            items: foreign_items,
+        });
+        items.push(gen.cx.item(DUMMY_SP, token::special_idents::invalid, Vec::new(), node));
+    }
+    // Consecutive one line items of the same kind are kept together,
+    // everything else is separated by blank lines.
+    let mut last = None;
+    for item in items.iter() {
+        let s = pprust::item_to_str(&**item);
+        let s = s.trim();
+        let kind = match item.node {
+            ast::ItemTy(..) => Some(0),
+            ast::ItemStatic(..) => Some(1),
+            _ => None,
+        };
+        if !out.as_slice().is_empty() {
+            out.push_str(if last.is_some() && last == kind && !s.contains_char('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        out.push_str(s);
+        last = if s.contains_char('\n') { None } else { kind };
+    }
+    out.push_str("\n");
+    (out.into_owned(), gen.warnings)
+}
+
+/// Records the declaration of `name` in a namespace, returning the earlier
+/// declaration of the same name if there is one.
+fn declare<'a>(namespace: &mut HashMap<~str, &'a Decl>, name: &~str,
+               decl: &'a Decl) -> Option<&'a Decl> {
+    match namespace.find(name) {
+        Some(&earlier) => return Some(earlier),
+        None => {}
+    }
+    namespace.insert(name.clone(), decl);
+    None
+}
+
+/// Makes an identifier from a C name, avoiding Rust keywords.
+fn ident(name: &str) -> ast::Ident {
+    let id = token::str_to_ident(name);
+    if token::is_any_keyword(&token::IDENT(id, false)) {
+        token::str_to_ident(format!("{}_", name).as_slice())
+    } else {
+        id
+    }
+}
+
+fn is_func(ty: &Type) -> bool {
+    match *ty { Func(..) => true, _ => false }
+}
+
+fn public(item: @ast::Item) -> @ast::Item {
+    @ast::Item { vis: ast::Public, ..(*item).clone() }
+}
+
+impl<'a> Gen<'a> {
+    fn decl(&mut self, decl: &Decl, items: &mut Vec<@ast::Item>,
+            foreign_items: &mut Vec<@ast::ForeignItem>) -> Result<(), ~str> {
+        match *decl {
+            Typedef(ref name, ref ty) => {
+                let ty = try!(self.ty(ty));
+                items.push(public(self.cx.item_ty(DUMMY_SP, ident(name.as_slice()), ty)));
+            }
+            Struct(ref name, ref fields) => {
+                let mut struct_fields = Vec::new();
+                for &(ref field, ref ty) in fields.iter() {
+                    let mut field_ty = try!(self.ty(ty));
+                    // Function pointers may be NULL, which Rust's function
+                    // types can't be.
+                    match *ty {
+                        Pointer(ref pointee, _) if is_func(&**pointee) => {
+                            let path = self.cx.path_all(DUMMY_SP, false,
+                                                        vec!(token::str_to_ident("Option")),
+                                                        Vec::new(), vec!(field_ty));
+                            field_ty = self.cx.ty_path(path, None);
+                        }
+                        _ => {}
+                    }
+                    struct_fields.push(respan(DUMMY_SP, ast::StructField_ {
+                        kind: ast::NamedField(ident(field.as_slice()), ast::Public),
+                        id: ast::DUMMY_NODE_ID,
+                        ty: field_ty,
+                        attrs: Vec::new(),
+                    }));
+                }
+                let def = ast::StructDef { fields: struct_fields, ctor_id: None };
+                items.push(public(self.cx.item_struct(DUMMY_SP, ident(name.as_slice()), def)));
+            }
+            OpaqueStruct(ref name) => {
+                // An empty enum can only be used through pointers, like the
+                // incomplete type it stands for.
+                let def = ast::EnumDef { variants: Vec::new() };
+                items.push(public(self.cx.item_enum(DUMMY_SP, ident(name.as_slice()), def)));
+            }
+            Enum(ref name, ref variants) => {
+                // Enumerators are ints, but enums with no negative ones are
+                // unsigned.
+                let ty = match *name {
+                    Some(ref name) => {
+                        let repr = if variants.iter().any(|&(_, v)| v < 0) {
+                            "c_int"
+                        } else {
+                            "c_uint"
+                        };
+                        let repr = self.path_ty(repr, true);
+                        items.push(public(self.cx.item_ty(DUMMY_SP, ident(name.as_slice()), repr)));
+                        self.path_ty(name.as_slice(), false)
+                    }
+                    None => self.path_ty("c_int", true),
+                };
+                for &(ref variant, v) in variants.iter() {
+                    let lit = ast::LitIntUnsuffixed(v);
+                    items.push(self.constant(variant.as_slice(), ty.clone(), lit));
+                }
+            }
+            Constant(ref name, v, unsigned) => {
+                // `long` is only 32 bits wide on some targets, so constants
+                // which don't fit an `int` are `long long`s.
+                let (ty, lit) = if unsigned {
+                    if v >= 0 && v <= u32::MAX as i64 || v < 0 && v >= i32::MIN as i64 {
+                        ("c_uint", ast::LitIntUnsuffixed(v as u32 as i64))
+                    } else {
+                        ("c_ulonglong", ast::LitUint(v as u64, ast::TyU64))
+                    }
+                } else if v >= i32::MIN as i64 && v <= i32::MAX as i64 {
+                    ("c_int", ast::LitIntUnsuffixed(v))
+                } else {
+                    ("c_longlong", ast::LitIntUnsuffixed(v))
+                };
+                let ty = self.path_ty(ty, true);
+                items.push(self.constant(name.as_slice(), ty, lit));
+            }
+            Function(ref name, ref sig) => {
+                let decl = try!(self.fn_decl(sig, true));
+                foreign_items.push(@ast::ForeignItem {
+                    ident: ident(name.as_slice()),
+                    attrs: Vec::new(),
+                    node: ast::ForeignItemFn(decl, ast_util::empty_generics()),
+                    id: ast::DUMMY_NODE_ID,
+                    span: DUMMY_SP,
+                    vis: ast::Public,
+                });
+            }
+            Variable(ref name, ref ty) => {
+                let ty = try!(self.ty(ty));
+                foreign_items.push(@ast::ForeignItem {
+                    ident: ident(name.as_slice()),
+                    attrs: Vec::new(),
+                    node: ast::ForeignItemStatic(ty, false),
+                    id: ast::DUMMY_NODE_ID,
+                    span: DUMMY_SP,
+                    vis: ast::Public,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn constant(&self, name: &str, ty: ast::P<ast::Ty>, lit: ast::Lit_) -> @ast::Item {
+        let expr = self.cx.expr_lit(DUMMY_SP, lit);
+        public(self.cx.item(DUMMY_SP, ident(name), Vec::new(),
+                            ast::ItemStatic(ty, ast::MutImmutable, expr)))
+    }
+
+    fn path_ty(&mut self, name: &str, import: bool) -> ast::P<ast::Ty> {
+        if import {
+            self.imports.insert(name.to_owned());
+        }
+        self.cx.ty_ident(DUMMY_SP, ident(name))
+    }
+
+    fn ty(&mut self, ty: &Type) -> Result<ast::P<ast::Ty>, ~str> {
+        match *ty {
+            Void => Ok(self.cx.ty_nil()),
+            Prim(name) => Ok(self.path_ty(name, name.starts_with("c_"))),
+            Named(ref name) => {
+                let import = !self.defined.contains(name);
+                Ok(self.path_ty(name.as_slice(), import))
+            }
+            Pointer(ref pointee, is_const) => {
+                let pointee = match **pointee {
+                    Void => self.path_ty("c_void", true),
+                    // Rust's function types are already pointers.
+                    Func(ref sig) => {
+                        let decl = try!(self.fn_decl(&**sig, false));
+                        return Ok(self.cx.ty(DUMMY_SP, ast::TyBareFn(@ast::BareFnTy {
+                            fn_style: ast::NormalFn,
+                            abi: abi::C,
+                            lifetimes: Vec::new(),
+                            decl: decl,
+                        })))
+                    }
+                    ref pointee => try!(self.ty(pointee)),
+                };
+                let mutbl = if is_const { ast::MutImmutable } else { ast::MutMutable };
+                Ok(self.cx.ty(DUMMY_SP, ast::TyPtr(self.cx.ty_mt(pointee, mutbl))))
+            }
+            Array(ref elem, len) => {
+                let elem = try!(self.ty(&**elem));
+                let len = self.cx.expr_lit(DUMMY_SP, ast::LitIntUnsuffixed(len as i64));
+                Ok(self.cx.ty(DUMMY_SP, ast::TyFixedLengthVec(elem, len)))
+            }
+            Func(..) => Err(~"function types can only be bound through pointers"),
+        }
+    }
+
+    /// Builds the declaration of a function, or of a function pointer type if
+    /// `named` is false.
+    fn fn_decl(&mut self, sig: &FnSig, named: bool) -> Result<ast::P<ast::FnDecl>, ~str> {
+        let mut inputs = Vec::new();
+        for (i, &(ref name, ref ty)) in sig.params.iter().enumerate() {
+            let name = match *name {
+                _ if !named => token::special_idents::invalid,
+                Some(ref name) => ident(name.as_slice()),
+                None => ident(format!("arg{}", i).as_slice()),
+            };
+            let ty = try!(self.ty(ty));
+            inputs.push(self.cx.arg(DUMMY_SP, name, ty));
+        }
+        let output = try!(self.ty(&sig.ret));
+        Ok(ast::P(ast::FnDecl {
+            inputs: inputs,
+            output: output,
+            cf: ast::Return,
+            variadic: sig.variadic,
+        }))
+    }
+}
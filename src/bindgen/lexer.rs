@@ -0,0 +1,254 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tokenizer for C headers.
+//!
+//! The preprocessor is not run: object-like `#define`s are kept as a single
+//! token holding their replacement list so that integer constants can be
+//! bound, and every other directive is skipped.
+
+use std::char;
+use std::num;
+
+#[deriving(Clone, Eq, Show)]
+pub enum Token {
+    Ident(~str),
+    /// An integer or character literal, and whether it has a `u` suffix.
+    Int(u64, bool),
+    Punct(char),
+    Shl,
+    Shr,
+    Ellipsis,
+    /// `#define NAME replacement...`
+    Define(~str, Vec<Token>),
+    /// A string, floating point or otherwise unusable literal.
+    Literal(~str),
+}
+
+#[deriving(Clone, Show)]
+pub struct TokenAndLine {
+    pub tok: Token,
+    pub line: uint,
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: uint,
+    line: uint,
+}
+
+pub fn tokenize(src: &str) -> Result<Vec<TokenAndLine>, ~str> {
+    let mut lexer = Lexer { chars: src.chars().collect(), pos: 0, line: 1 };
+    let mut toks = Vec::new();
+    loop {
+        try!(lexer.skip_whitespace(false));
+        let line = lexer.line;
+        if lexer.peek(0).is_none() {
+            return Ok(toks);
+        }
+        let tok = if lexer.peek(0) == Some('#') {
+            lexer.bump();
+            match try!(lexer.directive()) {
+                Some(tok) => tok,
+                None => continue,
+            }
+        } else {
+            try!(lexer.token())
+        };
+        toks.push(TokenAndLine { tok: tok, line: line });
+    }
+}
+
+impl Lexer {
+    fn peek(&self, n: uint) -> Option<char> {
+        if self.pos + n < self.chars.len() {
+            Some(*self.chars.get(self.pos + n))
+        } else {
+            None
+        }
+    }
+
+    fn bump(&mut self) -> char {
+        let c = *self.chars.get(self.pos);
+        if c == '\n' {
+            self.line += 1;
+        }
+        self.pos += 1;
+        c
+    }
+
+    /// Consumes the next character of a character or string literal, which
+    /// must not run into the end of its line.
+    fn bump_literal(&mut self, what: &str) -> Result<char, ~str> {
+        match self.peek(0) {
+            None | Some('\n') => self.err(format!("unterminated {}", what).as_slice()),
+            Some(_) => Ok(self.bump()),
+        }
+    }
+
+    fn err<T>(&self, msg: &str) -> Result<T, ~str> {
+        Err(format!("{}: {}", self.line, msg))
+    }
+
+    /// Skips whitespace, comments and escaped newlines. Inside a directive
+    /// this stops at the newline ending it and returns whether it was found.
+    fn skip_whitespace(&mut self, in_directive: bool) -> Result<bool, ~str> {
+        loop {
+            match (self.peek(0), self.peek(1)) {
+                (Some('\n'), _) if in_directive => return Ok(true),
+                (Some('\\'), Some('\n')) => { self.bump(); self.bump(); }
+                (Some('/'), Some('/')) => {
+                    while self.peek(0).is_some() && self.peek(0) != Some('\n') {
+                        self.bump();
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.bump();
+                    self.bump();
+                    loop {
+                        match (self.peek(0), self.peek(1)) {
+                            (None, _) => return self.err("unterminated comment"),
+                            (Some('*'), Some('/')) => break,
+                            _ => { self.bump(); }
+                        }
+                    }
+                    self.bump();
+                    self.bump();
+                }
+                (Some(c), _) if char::is_whitespace(c) => { self.bump(); }
+                (None, _) => return Ok(in_directive),
+                _ => return Ok(false),
+            }
+        }
+    }
+
+    /// Lexes a preprocessor directive, the `#` having been consumed.
+    fn directive(&mut self) -> Result<Option<Token>, ~str> {
+        if try!(self.skip_whitespace(true)) {
+            return Ok(None);
+        }
+        match try!(self.token()) {
+            Ident(ref name) if name.as_slice() == "define" => {}
+            _ => {
+                // Other directives are skipped without being tokenized.
+                while !try!(self.skip_whitespace(true)) {
+                    self.bump();
+                }
+                return Ok(None);
+            }
+        }
+        let mut toks = Vec::new();
+        let mut function_like = false;
+        while !try!(self.skip_whitespace(true)) {
+            toks.push(try!(self.token()));
+            // The parameters of a function-like macro directly follow its
+            // name.
+            if toks.len() == 1 && self.peek(0) == Some('(') {
+                function_like = true;
+            }
+        }
+        let mut toks = toks.move_iter();
+        match toks.next() {
+            Some(Ident(name)) if !function_like => {
+                Ok(Some(Define(name, toks.collect())))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn token(&mut self) -> Result<Token, ~str> {
+        let c = self.bump();
+        match c {
+            'a'..'z' | 'A'..'Z' | '_' => {
+                let mut s = StrBuf::from_char(1, c);
+                loop {
+                    match self.peek(0) {
+                        Some(c) if char::is_alphanumeric(c) || c == '_' => {
+                            s.push_char(self.bump());
+                        }
+                        _ => break,
+                    }
+                }
+                Ok(Ident(s.into_owned()))
+            }
+            '0'..'9' => {
+                let mut s = StrBuf::from_char(1, c);
+                loop {
+                    match self.peek(0) {
+                        Some(c) if char::is_alphanumeric(c) || c == '.' => {
+                            s.push_char(self.bump());
+                        }
+                        _ => break,
+                    }
+                }
+                Ok(match parse_int(s.as_slice()) {
+                    Some((v, unsigned)) => Int(v, unsigned),
+                    None => Literal(s.into_owned()),
+                })
+            }
+            '\'' => {
+                let v = match try!(self.bump_literal("character literal")) {
+                    '\\' => match try!(self.bump_literal("character literal")) {
+                        'n' => '\n' as u64,
+                        't' => '\t' as u64,
+                        'r' => '\r' as u64,
+                        '0' => 0,
+                        c => c as u64,
+                    },
+                    c => c as u64,
+                };
+                if self.peek(0) != Some('\'') {
+                    return self.err("unsupported character literal");
+                }
+                self.bump();
+                Ok(Int(v, false))
+            }
+            '"' => {
+                let mut s = StrBuf::from_char(1, '"');
+                loop {
+                    match self.peek(0) {
+                        None | Some('\n') => return self.err("unterminated string"),
+                        Some('"') => break,
+                        Some('\\') => { s.push_char(self.bump()); }
+                        _ => {}
+                    }
+                    s.push_char(try!(self.bump_literal("string")));
+                }
+                s.push_char(self.bump());
+                Ok(Literal(s.into_owned()))
+            }
+            '.' if self.peek(0) == Some('.') && self.peek(1) == Some('.') => {
+                self.bump();
+                self.bump();
+                Ok(Ellipsis)
+            }
+            '<' if self.peek(0) == Some('<') => { self.bump(); Ok(Shl) }
+            '>' if self.peek(0) == Some('>') => { self.bump(); Ok(Shr) }
+            c => Ok(Punct(c)),
+        }
+    }
+}
+
+/// Parses a C integer literal, returning its value and whether it is
+/// explicitly unsigned.
+fn parse_int(s: &str) -> Option<(u64, bool)> {
+    let suffixes: &[char] = &['u', 'U', 'l', 'L'];
+    let digits = s.trim_right_chars(&suffixes);
+    let suffix = s.slice_from(digits.len());
+    let unsigned = suffix.contains_char('u') || suffix.contains_char('U');
+    let v = if digits.starts_with("0x") || digits.starts_with("0X") {
+        num::from_str_radix(digits.slice_from(2), 16)
+    } else if digits.len() > 1 && digits.starts_with("0") {
+        num::from_str_radix(digits.slice_from(1), 8)
+    } else {
+        num::from_str_radix(digits, 10)
+    };
+    v.map(|v| (v, unsigned))
+}
@@ -0,0 +1,761 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Parser for the declarations found in C headers.
+//!
+//! Only what is needed to bind a library is understood: function prototypes,
+//! `extern` variables, structs, enums, typedefs and integer constants.
+//! Declarations which can't be bound (unions, bitfields, function
+//! definitions...) are skipped with a warning.
+
+use collections::HashMap;
+
+use lexer::{Token, TokenAndLine, Ident, Int, Punct, Shl, Shr, Ellipsis, Define};
+
+#[deriving(Clone, Eq, Show)]
+pub enum Type {
+    Void,
+    /// A builtin type, by the name `liblibc` or Rust gives it.
+    Prim(&'static str),
+    /// A typedef name, or the tag of a struct or enum.
+    Named(~str),
+    /// A pointer, and whether what it points to is `const`.
+    Pointer(~Type, bool),
+    Array(~Type, u64),
+    Func(~FnSig),
+}
+
+#[deriving(Clone, Eq, Show)]
+pub struct FnSig {
+    pub ret: Type,
+    pub params: Vec<(Option<~str>, Type)>,
+    pub variadic: bool,
+}
+
+#[deriving(Clone, Eq, Show)]
+pub enum Decl {
+    Typedef(~str, Type),
+    Struct(~str, Vec<(~str, Type)>),
+    /// A struct which is declared or mentioned but whose fields are never
+    /// given.
+    OpaqueStruct(~str),
+    /// An enum and its enumerators; anonymous enums only define constants.
+    Enum(Option<~str>, Vec<(~str, i64)>),
+    /// An integer constant from a `#define`, and whether it is unsigned.
+    Constant(~str, i64, bool),
+    Function(~str, FnSig),
+    Variable(~str, Type),
+}
+
+/// One step in building the type of a declarator.
+enum Derivation {
+    /// A pointer, and whether the pointer itself is `const`.
+    DerivedPointer(bool),
+    DerivedArray(Option<u64>),
+    DerivedFunction(Vec<(Option<~str>, Type)>, bool),
+}
+
+pub struct Parser {
+    toks: Vec<Token>,
+    lines: Vec<uint>,
+    pos: uint,
+    /// The values of the constants and enumerators seen so far, and whether
+    /// they are unsigned.
+    constants: HashMap<~str, (i64, bool)>,
+    decls: Vec<Decl>,
+    pub warnings: Vec<~str>,
+}
+
+/// Parses the declarations in a header, returning them with the lines they
+/// start on, along with warnings about those which were skipped.
+pub fn parse(toks: Vec<TokenAndLine>) -> (Vec<(uint, Decl)>, Vec<~str>) {
+    let mut p = Parser {
+        lines: toks.iter().map(|t| t.line).collect(),
+        toks: toks.move_iter().map(|t| t.tok).collect(),
+        pos: 0,
+        constants: HashMap::new(),
+        decls: Vec::new(),
+        warnings: Vec::new(),
+    };
+    let mut lines = Vec::new();
+    while p.pos < p.toks.len() {
+        let start = p.pos;
+        let ndecls = p.decls.len();
+        match p.declaration() {
+            Ok(()) => {
+                let line = *p.lines.get(start);
+                lines.grow(p.decls.len() - ndecls, &line);
+            }
+            Err(e) => {
+                p.warnings.push(format!("{}: skipping declaration: {}",
+                                        *p.lines.get(start), e));
+                p.decls.truncate(ndecls);
+                p.pos = start;
+                p.skip_declaration();
+            }
+        }
+    }
+    (lines.move_iter().zip(p.decls.move_iter()).collect(), p.warnings)
+}
+
+fn keyword(tok: &Token, kw: &str) -> bool {
+    match *tok {
+        Ident(ref s) => s.as_slice() == kw,
+        _ => false,
+    }
+}
+
+/// The Rust names of the fixed width integer types of `stdint.h`.
+fn stdint(name: &str) -> Option<&'static str> {
+    match name {
+        "int8_t" => Some("i8"),
+        "int16_t" => Some("i16"),
+        "int32_t" => Some("i32"),
+        "int64_t" => Some("i64"),
+        "uint8_t" => Some("u8"),
+        "uint16_t" => Some("u16"),
+        "uint32_t" => Some("u32"),
+        "uint64_t" => Some("u64"),
+        _ => None,
+    }
+}
+
+impl Parser {
+    fn peek<'a>(&'a self, n: uint) -> Option<&'a Token> {
+        if self.pos + n < self.toks.len() {
+            Some(self.toks.get(self.pos + n))
+        } else {
+            None
+        }
+    }
+
+    fn peek_is(&self, tok: &Token) -> bool {
+        self.peek(0) == Some(tok)
+    }
+
+    fn peek_keyword(&self, kw: &str) -> bool {
+        self.peek(0).map_or(false, |t| keyword(t, kw))
+    }
+
+    fn peek_ident(&self) -> bool {
+        match self.peek(0) {
+            Some(&Ident(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn bump(&mut self) -> Token {
+        let tok = (*self.toks.get(self.pos)).clone();
+        self.pos += 1;
+        tok
+    }
+
+    fn eat(&mut self, tok: &Token) -> bool {
+        if self.peek_is(tok) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, tok: &Token) -> Result<(), ~str> {
+        if self.eat(tok) {
+            Ok(())
+        } else {
+            Err(format!("expected `{}`, found {}", tok_to_str(tok), self.found()))
+        }
+    }
+
+    fn found(&self) -> ~str {
+        match self.peek(0) {
+            Some(tok) => format!("`{}`", tok_to_str(tok)),
+            None => ~"end of file",
+        }
+    }
+
+    fn ident(&mut self) -> Result<~str, ~str> {
+        if !self.peek_ident() {
+            return Err(format!("expected identifier, found {}", self.found()))
+        }
+        match self.bump() {
+            Ident(s) => Ok(s),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Skips a parenthesized group, such as the arguments of an
+    /// `__attribute__`.
+    fn skip_group(&mut self) {
+        let mut depth = 0;
+        while self.pos < self.toks.len() {
+            match self.bump() {
+                Punct('(') | Punct('{') | Punct('[') => depth += 1,
+                Punct(')') | Punct('}') | Punct(']') => {
+                    depth -= 1;
+                    if depth <= 0 {
+                        return
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips to the end of the current declaration after an error.
+    fn skip_declaration(&mut self) {
+        while self.pos < self.toks.len() {
+            match self.peek(0).unwrap().clone() {
+                Punct(';') => {
+                    self.bump();
+                    return
+                }
+                Punct('(') | Punct('{') | Punct('[') => self.skip_group(),
+                Define(..) => return,
+                _ => { self.bump(); }
+            }
+        }
+    }
+
+    /// Skips compiler specific annotations, which don't affect bindings.
+    fn skip_attributes(&mut self) {
+        loop {
+            let attribute = match self.peek(0) {
+                Some(&Ident(ref s)) => match s.as_slice() {
+                    "__attribute__" | "__asm__" | "__asm" | "__declspec" => true,
+                    _ => false,
+                },
+                _ => false,
+            };
+            if !attribute {
+                return
+            }
+            self.bump();
+            self.skip_group();
+        }
+    }
+
+    fn declaration(&mut self) -> Result<(), ~str> {
+        match self.bump() {
+            Define(name, body) => {
+                self.define(name, body);
+                return Ok(())
+            }
+            // Headers often wrap their declarations in `extern "C" { ... }`
+            // for C++, the braces of which can be ignored.
+            Punct(';') | Punct('}') => return Ok(()),
+            Ident(ref s) if s.as_slice() == "extern" && self.peek(1) == Some(&Punct('{')) => {
+                self.bump();
+                self.bump();
+                return Ok(())
+            }
+            _ => self.pos -= 1,
+        }
+
+        let typedef = self.eat(&Ident(~"typedef"));
+        let start = self.decls.len();
+        let (base, is_const) = try!(self.specifiers());
+        if self.eat(&Punct(';')) {
+            return self.finish_anonymous(start);
+        }
+        loop {
+            let (name, ty) = try!(self.declarator(base.clone(), is_const, false));
+            let name = match name {
+                Some(name) => name,
+                None => return Err(format!("expected a name, found {}", self.found())),
+            };
+            self.skip_attributes();
+            if anonymous(&ty) && !(typedef && ty == Named(~"")) {
+                return Err(~"anonymous types are only supported in typedefs")
+            }
+            if typedef {
+                match ty {
+                    Named(ref tag) if tag.is_empty() => self.name_anonymous(start, &name),
+                    // `typedef struct foo foo;` needs no alias in Rust.
+                    Named(ref tag) if *tag == name => {}
+                    ty => self.decls.push(Typedef(name, ty)),
+                }
+            } else {
+                match ty {
+                    Func(sig) => {
+                        if self.peek_is(&Punct('{')) {
+                            // Function definitions have nothing to bind.
+                            self.skip_group();
+                            return Ok(())
+                        }
+                        self.decls.push(Function(name, *sig));
+                    }
+                    ty => self.decls.push(Variable(name, ty)),
+                }
+            }
+            if !self.eat(&Punct(',')) {
+                break
+            }
+        }
+        try!(self.expect(&Punct(';')));
+        self.finish_anonymous(start)
+    }
+
+    /// Records an object-like macro if its body is an integer constant.
+    fn define(&mut self, name: ~str, body: Vec<Token>) {
+        let mut p = Parser {
+            toks: body,
+            lines: Vec::new(),
+            pos: 0,
+            constants: HashMap::new(),
+            decls: Vec::new(),
+            warnings: Vec::new(),
+        };
+        ::std::mem::swap(&mut p.constants, &mut self.constants);
+        let value = if p.toks.is_empty() { None } else { p.const_expr().ok() };
+        ::std::mem::swap(&mut p.constants, &mut self.constants);
+        match value {
+            Some((v, unsigned)) if p.pos == p.toks.len() => {
+                self.constants.insert(name.clone(), (v, unsigned));
+                self.decls.push(Constant(name, v, unsigned));
+            }
+            _ => {}
+        }
+    }
+
+    /// Gives a struct or enum defined without a tag the name of the typedef
+    /// it was defined in.
+    fn name_anonymous(&mut self, start: uint, name: &~str) {
+        for decl in self.decls.mut_slice_from(start).mut_iter() {
+            match *decl {
+                Struct(ref mut n, _) => if n.is_empty() { *n = name.clone() },
+                Enum(ref mut n, _) => if n.is_none() { *n = Some(name.clone()) },
+                _ => {}
+            }
+        }
+    }
+
+    fn finish_anonymous(&mut self, start: uint) -> Result<(), ~str> {
+        for decl in self.decls.slice_from(start).iter() {
+            match *decl {
+                Struct(ref n, _) if n.is_empty() => {
+                    return Err(~"anonymous structs are not supported")
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses declaration specifiers, returning the base type of the
+    /// declarators which follow and whether it is `const`.
+    fn specifiers(&mut self) -> Result<(Type, bool), ~str> {
+        let mut is_const = false;
+        let mut signed = None;
+        let mut short = false;
+        let mut longs = 0;
+        let mut base: Option<~str> = None;
+        let mut ty = None;
+        loop {
+            self.skip_attributes();
+            let word = match self.peek(0) {
+                Some(&Ident(ref s)) => s.clone(),
+                _ => break,
+            };
+            match word.as_slice() {
+                "const" => is_const = true,
+                "volatile" | "restrict" | "__restrict" | "__restrict__" |
+                "extern" | "static" | "inline" | "__inline" | "__inline__" |
+                "register" | "auto" | "__extension__" => {}
+                "signed" | "__signed__" => signed = Some(true),
+                "unsigned" => signed = Some(false),
+                "short" => short = true,
+                "long" => longs += 1,
+                "void" | "char" | "int" | "float" | "double" | "_Bool" | "bool" => {
+                    base = Some(word.clone())
+                }
+                "struct" | "union" | "enum" => {
+                    self.bump();
+                    ty = Some(try!(self.tagged(word.as_slice())));
+                    continue
+                }
+                // The first identifier which isn't a keyword names the type,
+                // any later one is the declarator.
+                _ if ty.is_none() && base.is_none() && signed.is_none() &&
+                     !short && longs == 0 => {
+                    ty = Some(match stdint(word.as_slice()) {
+                        Some(prim) => Prim(prim),
+                        None => Named(word.clone()),
+                    })
+                }
+                _ => break,
+            }
+            self.bump();
+        }
+
+        let integer = |s: &'static str, u: &'static str| {
+            Prim(if signed == Some(false) { u } else { s })
+        };
+        let modified = signed.is_some() || short || longs > 0;
+        let ty = match (ty, base) {
+            (Some(_), Some(_)) => return Err(~"conflicting type specifiers"),
+            (Some(_), None) if modified => return Err(~"conflicting type specifiers"),
+            (Some(ty), None) => ty,
+            (None, None) if !modified => {
+                return Err(format!("expected a type, found {}", self.found()))
+            }
+            (None, base) => match (base.as_ref().map(|s| s.as_slice()), short, longs) {
+                (Some("void"), false, 0) if signed.is_none() => Void,
+                (Some("_Bool"), false, 0) | (Some("bool"), false, 0) if signed.is_none() => {
+                    Prim("bool")
+                }
+                (Some("float"), false, 0) if signed.is_none() => Prim("c_float"),
+                (Some("double"), false, 0) if signed.is_none() => Prim("c_double"),
+                (Some("double"), false, 1) if signed.is_none() => {
+                    return Err(~"`long double` is not supported")
+                }
+                (Some("char"), false, 0) => match signed {
+                    None => Prim("c_char"),
+                    Some(true) => Prim("c_schar"),
+                    Some(false) => Prim("c_uchar"),
+                },
+                (Some("int"), true, 0) | (None, true, 0) => integer("c_short", "c_ushort"),
+                (Some("int"), false, 0) | (None, false, 0) => integer("c_int", "c_uint"),
+                (Some("int"), false, 1) | (None, false, 1) => integer("c_long", "c_ulong"),
+                (Some("int"), false, 2) | (None, false, 2) => {
+                    integer("c_longlong", "c_ulonglong")
+                }
+                _ => return Err(~"invalid combination of type specifiers"),
+            },
+        };
+        Ok((ty, is_const))
+    }
+
+    /// Parses what follows `struct`, `union` or `enum`, recording the
+    /// definition if there is one.
+    fn tagged(&mut self, kind: &str) -> Result<Type, ~str> {
+        self.skip_attributes();
+        let tag = if self.peek_ident() { Some(try!(self.ident())) } else { None };
+        if !self.eat(&Punct('{')) {
+            return match tag {
+                Some(tag) => {
+                    // A struct may be mentioned without ever being defined,
+                    // and is then only usable through pointers.
+                    if kind == "struct" {
+                        self.decls.push(OpaqueStruct(tag.clone()));
+                    }
+                    Ok(Named(tag))
+                }
+                None => Err(format!("expected `\\{`, found {}", self.found())),
+            }
+        }
+
+        match kind {
+            "struct" => {
+                let fields = try!(self.fields());
+                let name = tag.unwrap_or(~"");
+                self.decls.push(Struct(name.clone(), fields));
+                Ok(Named(name))
+            }
+            "enum" => {
+                let variants = try!(self.enumerators());
+                self.decls.push(Enum(tag.clone(), variants));
+                Ok(Named(tag.unwrap_or(~"")))
+            }
+            _ => Err(~"unions are not supported"),
+        }
+    }
+
+    /// Parses the fields of a struct, after the opening brace.
+    fn fields(&mut self) -> Result<Vec<(~str, Type)>, ~str> {
+        let mut fields = Vec::new();
+        while !self.eat(&Punct('}')) {
+            let (base, is_const) = try!(self.specifiers());
+            loop {
+                let (name, ty) = try!(self.declarator(base.clone(), is_const, false));
+                if self.peek_is(&Punct(':')) {
+                    return Err(~"bitfields are not supported")
+                }
+                let name = match name {
+                    Some(name) => name,
+                    None => return Err(~"anonymous fields are not supported"),
+                };
+                match ty {
+                    Func(..) => return Err(format!("field `{}` has a function type", name)),
+                    ref ty if anonymous(ty) => {
+                        return Err(~"anonymous structs and enums are not supported")
+                    }
+                    _ => {}
+                }
+                fields.push((name, ty));
+                if !self.eat(&Punct(',')) {
+                    break
+                }
+            }
+            try!(self.expect(&Punct(';')));
+        }
+        Ok(fields)
+    }
+
+    /// Parses the enumerators of an enum, after the opening brace.
+    fn enumerators(&mut self) -> Result<Vec<(~str, i64)>, ~str> {
+        let mut variants = Vec::new();
+        let mut next = 0;
+        while !self.eat(&Punct('}')) {
+            let name = try!(self.ident());
+            if self.eat(&Punct('=')) {
+                let (v, _) = try!(self.const_expr());
+                next = v;
+            }
+            self.constants.insert(name.clone(), (next, false));
+            variants.push((name, next));
+            next += 1;
+            if !self.eat(&Punct(',')) {
+                try!(self.expect(&Punct('}')));
+                break
+            }
+        }
+        Ok(variants)
+    }
+
+    /// Parses a declarator, which may be abstract, returning the name it
+    /// declares and its type.
+    fn declarator(&mut self, base: Type, is_const: bool, param: bool)
+                  -> Result<(Option<~str>, Type), ~str> {
+        let (name, derivations) = try!(self.derivations());
+        let mut ty = base;
+        let mut is_const = is_const;
+        for d in derivations.move_iter() {
+            match d {
+                DerivedPointer(c) => {
+                    ty = Pointer(~ty, is_const);
+                    is_const = c;
+                }
+                DerivedArray(len) => {
+                    ty = match len {
+                        Some(len) => Array(~ty, len),
+                        // Only parameters can leave out the length, as they
+                        // are passed as pointers.
+                        None if param => Array(~ty, 0),
+                        None => return Err(~"arrays of unknown size are not supported"),
+                    };
+                }
+                DerivedFunction(params, variadic) => {
+                    ty = Func(~FnSig { ret: ty, params: params, variadic: variadic });
+                    is_const = false;
+                }
+            }
+        }
+        if param {
+            // Arrays and functions are passed as pointers.
+            ty = match ty {
+                Array(elem, _) => Pointer(elem, is_const),
+                Func(sig) => Pointer(~Func(sig), false),
+                ty => ty,
+            };
+        }
+        Ok((name, ty))
+    }
+
+    /// Parses the pointers, array lengths and parameter lists making up a
+    /// declarator, in the order they apply to its base type.
+    fn derivations(&mut self) -> Result<(Option<~str>, Vec<Derivation>), ~str> {
+        let mut derivations = Vec::new();
+        while self.eat(&Punct('*')) {
+            let mut is_const = false;
+            loop {
+                match self.peek(0) {
+                    Some(&Ident(ref s)) => match s.as_slice() {
+                        "const" => is_const = true,
+                        "volatile" | "restrict" | "__restrict" | "__restrict__" => {}
+                        _ => break,
+                    },
+                    _ => break,
+                }
+                self.bump();
+            }
+            derivations.push(DerivedPointer(is_const));
+        }
+        self.skip_attributes();
+
+        // A parenthesized declarator applies after the suffixes following
+        // it, as in `int (*f)(int)`.
+        let (name, nested) = if self.peek_is(&Punct('(')) && self.peek(1) == Some(&Punct('*')) {
+            self.bump();
+            let nested = try!(self.derivations());
+            try!(self.expect(&Punct(')')));
+            nested
+        } else {
+            if self.peek_ident() {
+                (Some(try!(self.ident())), Vec::new())
+            } else {
+                (None, Vec::new())
+            }
+        };
+
+        let mut suffixes = Vec::new();
+        loop {
+            if self.eat(&Punct('[')) {
+                if self.eat(&Punct(']')) {
+                    suffixes.push(DerivedArray(None));
+                } else {
+                    let (len, _) = try!(self.const_expr());
+                    if len < 0 {
+                        return Err(~"negative array length")
+                    }
+                    try!(self.expect(&Punct(']')));
+                    suffixes.push(DerivedArray(Some(len as u64)));
+                }
+            } else if self.eat(&Punct('(')) {
+                let (params, variadic) = try!(self.params());
+                suffixes.push(DerivedFunction(params, variadic));
+            } else {
+                break
+            }
+        }
+        // `int a[2][3]` is an array of two arrays of three ints.
+        suffixes.reverse();
+        derivations.push_all_move(suffixes);
+        derivations.push_all_move(nested);
+        Ok((name, derivations))
+    }
+
+    /// Parses the parameters of a function, after the opening parenthesis.
+    fn params(&mut self) -> Result<(Vec<(Option<~str>, Type)>, bool), ~str> {
+        let mut params = Vec::new();
+        if self.eat(&Punct(')')) {
+            return Ok((params, false))
+        }
+        if self.peek_keyword("void") && self.peek(1) == Some(&Punct(')')) {
+            self.bump();
+            self.bump();
+            return Ok((params, false))
+        }
+        loop {
+            if self.eat(&Ellipsis) {
+                try!(self.expect(&Punct(')')));
+                return Ok((params, true))
+            }
+            let (base, is_const) = try!(self.specifiers());
+            params.push(try!(self.declarator(base, is_const, true)));
+            if !self.eat(&Punct(',')) {
+                try!(self.expect(&Punct(')')));
+                return Ok((params, false))
+            }
+        }
+    }
+
+    /// Evaluates an integer constant expression, returning its value and
+    /// whether it is unsigned.
+    fn const_expr(&mut self) -> Result<(i64, bool), ~str> {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, min_prec: uint) -> Result<(i64, bool), ~str> {
+        let (mut lhs, mut unsigned) = try!(self.unary());
+        loop {
+            let prec = match self.peek(0) {
+                Some(&Punct('|')) => 1,
+                Some(&Punct('^')) => 2,
+                Some(&Punct('&')) => 3,
+                Some(&Shl) | Some(&Shr) => 4,
+                Some(&Punct('+')) | Some(&Punct('-')) => 5,
+                Some(&Punct('*')) | Some(&Punct('/')) | Some(&Punct('%')) => 6,
+                _ => return Ok((lhs, unsigned)),
+            };
+            if prec <= min_prec {
+                return Ok((lhs, unsigned))
+            }
+            let op = self.bump();
+            let (rhs, u) = try!(self.binary(prec));
+            unsigned |= u;
+            lhs = match op {
+                Punct('|') => lhs | rhs,
+                Punct('^') => lhs ^ rhs,
+                Punct('&') => lhs & rhs,
+                Shl => lhs << rhs,
+                Shr => lhs >> rhs,
+                Punct('+') => lhs + rhs,
+                Punct('-') => lhs - rhs,
+                Punct('*') => lhs * rhs,
+                Punct('/') | Punct('%') if rhs == 0 => return Err(~"division by zero"),
+                Punct('/') => lhs / rhs,
+                Punct('%') => lhs % rhs,
+                _ => unreachable!(),
+            };
+        }
+    }
+
+    fn unary(&mut self) -> Result<(i64, bool), ~str> {
+        if self.pos == self.toks.len() {
+            return Err(~"expected a constant, found end of file")
+        }
+        match self.bump() {
+            Int(v, unsigned) => Ok((v as i64, unsigned)),
+            Punct('-') => self.unary().map(|(v, u)| (-v, u)),
+            Punct('+') => self.unary(),
+            Punct('~') => self.unary().map(|(v, u)| (!v, u)),
+            Punct('(') => {
+                if self.is_cast() {
+                    // Casts are ignored, the value is all that matters.
+                    while !self.eat(&Punct(')')) {
+                        self.bump();
+                    }
+                    return self.unary()
+                }
+                let v = try!(self.const_expr());
+                try!(self.expect(&Punct(')')));
+                Ok(v)
+            }
+            Ident(name) => match self.constants.find(&name) {
+                Some(&v) => Ok(v),
+                None => Err(format!("`{}` is not a constant", name)),
+            },
+            tok => {
+                self.pos -= 1;
+                Err(format!("expected a constant, found `{}`", tok_to_str(&tok)))
+            }
+        }
+    }
+
+    /// Whether the tokens after an opening parenthesis are a type name.
+    fn is_cast(&self) -> bool {
+        let mut n = 0;
+        loop {
+            match self.peek(n) {
+                Some(&Ident(ref s)) if !self.constants.contains_key(s) => {}
+                Some(&Punct('*')) if n > 0 => {}
+                Some(&Punct(')')) => return n > 0,
+                _ => return false,
+            }
+            n += 1;
+        }
+    }
+}
+
+/// Whether a type refers to a struct or enum defined without a tag.
+fn anonymous(ty: &Type) -> bool {
+    match *ty {
+        Named(ref name) => name.is_empty(),
+        Pointer(ref ty, _) | Array(ref ty, _) => anonymous(&**ty),
+        Func(ref sig) => {
+            anonymous(&sig.ret) || sig.params.iter().any(|&(_, ref ty)| anonymous(ty))
+        }
+        Void | Prim(_) => false,
+    }
+}
+
+fn tok_to_str(tok: &Token) -> ~str {
+    match *tok {
+        Ident(ref s) => s.clone(),
+        Int(v, _) => v.to_str(),
+        Punct(c) => c.to_str(),
+        Shl => ~"<<",
+        Shr => ~">>",
+        Ellipsis => ~"...",
+        Define(ref name, _) => format!("\\#define {}", name),
+        ::lexer::Literal(ref s) => s.clone(),
+    }
+}
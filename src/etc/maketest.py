@@ -22,8 +22,9 @@ os.putenv('RUSTC', os.path.abspath(sys.argv[3]))
 os.putenv('TMPDIR', os.path.abspath(sys.argv[4]))
 os.putenv('CC', sys.argv[5])
 os.putenv('RUSTDOC', os.path.abspath(sys.argv[6]))
-filt = sys.argv[7]
-ldpath = sys.argv[8]
+os.putenv('BINDGEN', os.path.abspath(sys.argv[7]))
+filt = sys.argv[8]
+ldpath = sys.argv[9]
 if ldpath != '':
     os.putenv(ldpath.split('=')[0], ldpath.split('=')[1])
 
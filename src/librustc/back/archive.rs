@@ -40,7 +40,25 @@ fn run_ar(sess: &Session, args: &str, cwd: Option<&Path>,
           paths: &[&Path]) -> ProcessOutput {
     let ar = get_ar_prog(sess);
 
-    let mut args = vec!(args.to_owned());
+    // Unless told otherwise, ar records when and by whom each member was
+    // added to the archive. GNU, LLVM and FreeBSD ar are told with the `D`
+    // modifier; the cctools ar used on OSX hosts doesn't know it, but leaves
+    // the dates out when `ZERO_AR_DATE` is set.
+    let mut env = None;
+    let args = match args.chars().next() {
+        Some('r') | Some('c') | Some('d') | Some('s') if sess.opts.deterministic => {
+            if cfg!(target_os = "macos") {
+                let mut vars = Vec::from_slice(os::env().as_slice());
+                vars.push((~"ZERO_AR_DATE", ~"1"));
+                env = Some(vars);
+                args.to_owned()
+            } else {
+                format!("{}D", args)
+            }
+        }
+        _ => args.to_owned(),
+    };
+    let mut args = vec!(args);
     let paths = paths.iter().map(|p| p.as_str().unwrap().to_owned());
     args.extend(paths);
     debug!("{} {}", ar, args.connect(" "));
@@ -52,6 +70,7 @@ fn run_ar(sess: &Session, args: &str, cwd: Option<&Path>,
         program: ar.as_slice(),
         args: args.as_slice(),
         cwd: cwd.map(|a| &*a),
+        env: env.as_ref().map(|e| e.as_slice()),
         .. ProcessConfig::new()
     }) {
         Ok(mut prog) => {
@@ -0,0 +1,231 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reuse of translated functions between builds (`-Z incremental=DIR`).
+//!
+//! Every build leaves two files behind in the cache directory: the
+//! unoptimized LLVM module it translated (`<crate>.bc`) and an index mapping
+//! the symbol name of each non-generic function to its fingerprint (see
+//! `back::svh::fn_fingerprint`). On the next build, a function whose
+//! fingerprint is unchanged is only declared by trans rather than
+//! translated, and its definition is linked in from the previous module
+//! once translation is done.
+//!
+//! For this to work, symbol names have to stay the same from one build to
+//! the next. In incremental mode they are therefore not salted with the
+//! crate's SVH or disambiguated by node id (see `link::mangle_exported_name`),
+//! and items are never given internal linkage, so that a reused body always
+//! refers to the current definition of anything it uses.
+
+use back::link;
+use back::svh;
+use driver::session::Session;
+use lib::llvm::{llvm, ModuleRef};
+
+use collections::{HashMap, HashSet};
+use libc;
+use serialize::{json, Encodable, Decodable};
+use std::c_str::{CString, ToCStr};
+use std::cell::RefCell;
+use std::io;
+use std::num;
+use syntax::ast;
+
+#[deriving(Encodable, Decodable)]
+struct Index {
+    items: Vec<IndexEntry>,
+}
+
+// Fingerprints are written as hex strings, JSON numbers being doubles.
+#[deriving(Encodable, Decodable)]
+struct IndexEntry {
+    symbol: ~str,
+    fingerprint: ~str,
+}
+
+pub struct IncrementalCache {
+    dir: Path,
+    crate_name: ~str,
+    interface: u64,
+    // Fingerprints from the previous build, by symbol name.
+    previous: HashMap<~str, u64>,
+    // Fingerprints of everything defined by this build, written out by
+    // `save`.
+    current: RefCell<HashMap<~str, u64>>,
+    // Stable symbol names which have already been handed out.
+    claimed_symbols: RefCell<HashSet<~str>>,
+    // Symbols of the functions whose previous translation is reused.
+    reused: RefCell<HashSet<~str>>,
+}
+
+impl IncrementalCache {
+    pub fn load(sess: &Session, dir: &Path, crate_name: &str,
+                krate: &ast::Crate) -> IncrementalCache {
+        let mut upstream = Vec::new();
+        sess.cstore.iter_crate_data(|cnum, _| {
+            upstream.push(sess.cstore.get_crate_hash(cnum));
+        });
+        upstream.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+
+        let mut cache = IncrementalCache {
+            dir: dir.clone(),
+            crate_name: crate_name.to_owned(),
+            interface: svh::interface_fingerprint(krate, upstream.as_slice(),
+                                                  codegen_options(sess).as_slice()),
+            previous: HashMap::new(),
+            current: RefCell::new(HashMap::new()),
+            claimed_symbols: RefCell::new(HashSet::new()),
+            reused: RefCell::new(HashSet::new()),
+        };
+
+        // Without the module from the previous build there is nothing to
+        // reuse, whatever the index says.
+        if !cache.bitcode_path().exists() {
+            return cache
+        }
+        let index = io::File::open(&cache.index_path()).read_to_str();
+        let index = match index.ok().and_then(|s| json::from_str(s).ok()) {
+            Some(json) => {
+                let mut decoder = json::Decoder::new(json);
+                let index: json::DecodeResult<Index> = Decodable::decode(&mut decoder);
+                index.ok()
+            }
+            None => None,
+        };
+        match index {
+            Some(index) => {
+                for entry in index.items.move_iter() {
+                    match num::from_str_radix(entry.fingerprint, 16) {
+                        Some(fp) => { cache.previous.insert(entry.symbol, fp); }
+                        None => {}
+                    }
+                }
+            }
+            None => {
+                sess.warn(format!("ignoring unreadable incremental compilation \
+                                   index `{}`", cache.index_path().display()));
+            }
+        }
+        cache
+    }
+
+    fn bitcode_path(&self) -> Path {
+        self.dir.join(format!("{}.bc", self.crate_name))
+    }
+
+    fn index_path(&self) -> Path {
+        self.dir.join(format!("{}.index.json", self.crate_name))
+    }
+
+    /// Record the fingerprint of the function with the given symbol name and
+    /// source, returning whether the previous build's translation of it can
+    /// be reused.
+    pub fn try_reuse(&self, symbol: &str, source: &str) -> bool {
+        let fp = svh::fn_fingerprint(self.interface, source);
+        self.current.borrow_mut().insert(symbol.to_owned(), fp);
+        let fresh = self.previous.find_equiv(&symbol) == Some(&fp);
+        if fresh {
+            self.reused.borrow_mut().insert(symbol.to_owned());
+        }
+        fresh
+    }
+
+    /// Whether `sym`, a symbol name derived from an item's path and type
+    /// alone, is still free. Only the first of several items sharing a path
+    /// (e.g. functions of the same name in different blocks) gets it.
+    pub fn claim_symbol(&self, sym: &str) -> bool {
+        self.claimed_symbols.borrow_mut().insert(sym.to_owned())
+    }
+
+    /// Link the definitions of all reused functions into `llmod` from the
+    /// previous build's module. Anything `llmod` already defines takes
+    /// precedence over the cached copy, and nothing else of the previous
+    /// build is kept, so that deleted functions don't linger in the cache.
+    pub fn link_cached(&self, sess: &Session, llmod: ModuleRef) {
+        let reused = self.reused.borrow();
+        if reused.is_empty() {
+            return
+        }
+        let bc = match io::File::open(&self.bitcode_path()).read_to_end() {
+            Ok(bc) => bc,
+            Err(e) => {
+                sess.fatal(format!("failed to read incremental compilation \
+                                    cache `{}`: {}",
+                                   self.bitcode_path().display(), e));
+            }
+        };
+        let cstrs: Vec<CString> = reused.iter().map(|s| s.to_c_str()).collect();
+        let syms: Vec<*libc::c_char> = cstrs.iter().map(|c| c.with_ref(|p| p)).collect();
+        unsafe {
+            let ptr = bc.as_slice().as_ptr();
+            if !llvm::LLVMRustLinkInCachedBitcode(llmod,
+                                                  ptr as *libc::c_char,
+                                                  bc.len() as libc::size_t,
+                                                  syms.as_ptr(),
+                                                  syms.len() as libc::size_t) {
+                link::llvm_err(sess, format!("failed to link in the incremental \
+                                              compilation cache `{}` (remove it \
+                                              and rebuild)",
+                                             self.bitcode_path().display()));
+            }
+        }
+        if sess.time_passes() {
+            println!("reused {} of {} functions from the incremental cache",
+                     reused.len(), self.current.borrow().len());
+        }
+    }
+
+    /// Write out `llmod` and the fingerprints of the functions it defines
+    /// for the next build to pick up.
+    pub fn save(&self, sess: &Session, llmod: ModuleRef) {
+        // Write the module first: an index without its module is ignored,
+        // whereas a stale index would cause the wrong bodies to be reused.
+        let _ = io::fs::unlink(&self.index_path());
+        let written = unsafe {
+            self.bitcode_path().with_c_str(|buf| {
+                llvm::LLVMWriteBitcodeToFile(llmod, buf) == 0
+            })
+        };
+        if !written {
+            sess.warn(format!("failed to write incremental compilation cache `{}`",
+                              self.bitcode_path().display()));
+            return
+        }
+
+        let index = Index {
+            items: self.current.borrow().iter().map(|(symbol, fp)| {
+                IndexEntry {
+                    symbol: symbol.clone(),
+                    fingerprint: format!("{:x}", *fp),
+                }
+            }).collect(),
+        };
+        let result = io::File::create(&self.index_path()).and_then(|mut file| {
+            let mut encoder = json::Encoder::new(&mut file as &mut io::Writer);
+            index.encode(&mut encoder)
+        });
+        match result {
+            Ok(()) => {}
+            Err(e) => {
+                sess.warn(format!("failed to write incremental compilation \
+                                   index `{}`: {}", self.index_path().display(), e));
+            }
+        }
+    }
+}
+
+// Everything besides the source which affects how a function is translated:
+// the target and the options controlling code generation.
+fn codegen_options(sess: &Session) -> ~str {
+    let opts = &sess.opts;
+    format!("{} {:?} {:?} {:?} {} {} {} {} {:?}",
+            opts.target_triple, opts.target_spec, opts.optimize, opts.debuginfo,
+            opts.gc, opts.debugging_opts, opts.test, opts.freestanding, opts.cg)
+}
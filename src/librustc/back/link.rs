@@ -51,6 +51,8 @@ pub enum OutputType {
     OutputTypeAssembly,
     OutputTypeLlvmAssembly,
     OutputTypeObject,
+    OutputTypeMetadata,
+    OutputTypeCHeader,
     OutputTypeExe,
 }
 
@@ -88,23 +90,26 @@ pub fn WriteOutputFile(
 pub mod write {
 
     use back::lto;
-    use back::link::{WriteOutputFile, OutputType};
+    use back::link::{WriteOutputFile, OutputType, link_codegen_units};
     use back::link::{OutputTypeAssembly, OutputTypeBitcode};
     use back::link::{OutputTypeExe, OutputTypeLlvmAssembly};
-    use back::link::{OutputTypeObject};
+    use back::link::{OutputTypeCHeader, OutputTypeMetadata, OutputTypeObject};
     use driver::driver::{CrateTranslation, OutputFilenames};
     use driver::session::{NoDebugInfo, Session};
     use driver::session;
     use lib::llvm::llvm;
     use lib::llvm::{ModuleRef, TargetMachineRef, PassManagerRef};
     use lib;
+    use metadata::{csearch, cstore};
     use util::common::time;
     use syntax::abi;
 
-    use std::c_str::ToCStr;
-    use std::io::Process;
+    use std::c_str::{ToCStr, CString};
+    use std::io::{fs, Process};
     use libc::{c_uint, c_int};
+    use std::ptr;
     use std::str;
+    use std::task;
 
     // On android, we by default compile for armv7 processors. This enables
     // things like double word CAS instructions (rather than emulating them)
@@ -139,86 +144,24 @@ pub mod write {
                 })
             }
 
-            let opt_level = match sess.opts.optimize {
-              session::No => lib::llvm::CodeGenLevelNone,
-              session::Less => lib::llvm::CodeGenLevelLess,
-              session::Default => lib::llvm::CodeGenLevelDefault,
-              session::Aggressive => lib::llvm::CodeGenLevelAggressive,
-            };
-            let use_softfp = sess.opts.cg.soft_float;
-
-            // FIXME: #11906: Omitting frame pointers breaks retrieving the value of a parameter.
-            // FIXME: #11954: mac64 unwinding may not work with fp elim
-            let no_fp_elim = (sess.opts.debuginfo != NoDebugInfo) ||
-                             (sess.targ_cfg.os == abi::OsMacos &&
-                              sess.targ_cfg.arch == abi::X86_64);
-
-            let reloc_model = match sess.opts.cg.relocation_model.as_slice() {
-                "pic" => lib::llvm::RelocPIC,
-                "static" => lib::llvm::RelocStatic,
-                "default" => lib::llvm::RelocDefault,
-                "dynamic-no-pic" => lib::llvm::RelocDynamicNoPic,
-                _ => {
-                    sess.err(format!("{} is not a valid relocation mode",
-                             sess.opts.cg.relocation_model));
-                    sess.abort_if_errors();
-                    return;
-                }
-            };
-
-            let tm = sess.targ_cfg.target_strs.target_triple.with_c_str(|t| {
-                sess.opts.cg.target_cpu.with_c_str(|cpu| {
-                    target_feature(sess).with_c_str(|features| {
-                        llvm::LLVMRustCreateTargetMachine(
-                            t, cpu, features,
-                            lib::llvm::CodeModelDefault,
-                            reloc_model,
-                            opt_level,
-                            true,
-                            use_softfp,
-                            no_fp_elim
-                        )
-                    })
-                })
-            });
-
-            // Create the two optimizing pass managers. These mirror what clang
-            // does, and are by populated by LLVM's default PassManagerBuilder.
-            // Each manager has a different set of passes, but they also share
-            // some common passes.
-            let fpm = llvm::LLVMCreateFunctionPassManagerForModule(llmod);
-            let mpm = llvm::LLVMCreatePassManager();
-
-            // If we're verifying or linting, add them to the function pass
-            // manager.
-            let addpass = |pass: &str| {
-                pass.with_c_str(|s| llvm::LLVMRustAddPass(fpm, s))
-            };
-            if !sess.no_verify() { assert!(addpass("verify")); }
-
-            if !sess.opts.cg.no_prepopulate_passes {
-                llvm::LLVMRustAddAnalysisPasses(tm, fpm, llmod);
-                llvm::LLVMRustAddAnalysisPasses(tm, mpm, llmod);
-                populate_llvm_passes(fpm, mpm, llmod, opt_level);
+            if sess.opts.cg.profile_use.is_some() && sess.opts.optimize == session::No {
+                sess.warn("-C profile-use has no effect without optimization");
             }
 
-            for pass in sess.opts.cg.passes.iter() {
-                pass.with_c_str(|s| {
-                    if !llvm::LLVMRustAddPass(mpm, s) {
-                        sess.warn(format!("unknown pass {}, ignoring", *pass));
-                    }
-                })
+            if sess.thin_lto() {
+                time(sess.time_passes(), "thin lto imports", (), |()|
+                     lto::import(sess, llmod));
             }
 
-            // Finally, run the actual optimization passes
-            time(sess.time_passes(), "llvm function passes", (), |()|
-                 llvm::LLVMRustRunFunctionPassManager(fpm, llmod));
-            time(sess.time_passes(), "llvm module passes", (), |()|
-                 llvm::LLVMRunPassManager(mpm, llmod));
+            let units = codegen_units(sess, output_types);
+            let config = module_config(sess);
+            let tm = create_target_machine(&config);
 
-            // Deallocate managers that we're now done with
-            llvm::LLVMDisposePassManager(fpm);
-            llvm::LLVMDisposePassManager(mpm);
+            // With several codegen units, each of them is optimized by the
+            // task which emits it.
+            if units == 1 {
+                optimize_module(&config, tm, llmod);
+            }
 
             // Emit the bytecode if we're either saving our temporaries or
             // emitting an rlib. Whenever an rlib is created, the bytecode is
@@ -232,8 +175,30 @@ pub mod write {
             }
 
             if sess.lto() {
+                // A static library is only called into through its C ABI,
+                // whereas anything reachable may be used by Rust code. Crates
+                // linking to a dylib use it in place of the upstream crates
+                // linked into it, so the symbols of those crates' items stay
+                // exported as well.
+                let crate_types = sess.crate_types.borrow();
+                let only_staticlib = crate_types.iter().all(|t| {
+                    *t == session::CrateTypeStaticlib
+                });
+                let exported = if only_staticlib {
+                    trans.c_symbols.clone()
+                } else {
+                    let mut exported = trans.reachable.clone();
+                    if crate_types.contains(&session::CrateTypeDylib) {
+                        let crates = sess.cstore.get_used_crates(cstore::RequireStatic);
+                        for (cnum, _) in crates.move_iter() {
+                            exported.push_all_move(
+                                csearch::get_item_symbols(&sess.cstore, cnum));
+                        }
+                    }
+                    exported
+                };
                 time(sess.time_passes(), "all lto passes", (), |()|
-                     lto::run(sess, llmod, tm, trans.reachable.as_slice()));
+                     lto::run(sess, llmod, tm, exported.as_slice()));
 
                 if sess.opts.cg.save_temps {
                     output.with_extension("lto.bc").with_c_str(|buf| {
@@ -242,25 +207,6 @@ pub mod write {
                 }
             }
 
-            // A codegen-specific pass manager is used to generate object
-            // files for an LLVM module.
-            //
-            // Apparently each of these pass managers is a one-shot kind of
-            // thing, so we create a new one for each type of output. The
-            // pass manager passed to the closure should be ensured to not
-            // escape the closure itself, and the manager should only be
-            // used once.
-            fn with_codegen(tm: TargetMachineRef, llmod: ModuleRef,
-                            f: |PassManagerRef|) {
-                unsafe {
-                    let cpm = llvm::LLVMCreatePassManager();
-                    llvm::LLVMRustAddAnalysisPasses(tm, cpm, llmod);
-                    llvm::LLVMRustAddLibraryInfo(cpm, llmod);
-                    f(cpm);
-                    llvm::LLVMDisposePassManager(cpm);
-                }
-            }
-
             let mut object_file = None;
             let mut needs_metadata = false;
             for output_type in output_types.iter() {
@@ -298,6 +244,10 @@ pub mod write {
                     OutputTypeObject => {
                         object_file = Some(path);
                     }
+                    // Written out by the driver, it doesn't need LLVM.
+                    OutputTypeMetadata => {}
+                    // Written out during translation.
+                    OutputTypeCHeader => {}
                     OutputTypeExe => {
                         object_file = Some(output.temp_path(OutputTypeObject));
                         needs_metadata = true;
@@ -307,6 +257,9 @@ pub mod write {
 
             time(sess.time_passes(), "codegen passes", (), |()| {
                 match object_file {
+                    Some(ref path) if units > 1 => {
+                        run_codegen_units(sess, trans, &config, units, path);
+                    }
                     Some(ref path) => {
                         with_codegen(tm, llmod, |cpm| {
                             WriteOutputFile(sess, tm, cpm, llmod, path,
@@ -334,6 +287,297 @@ pub mod write {
         }
     }
 
+    // Everything that the optimization and code generation of a module
+    // depends on, taken out of the session so that it can be sent to the
+    // tasks emitting codegen units.
+    #[deriving(Clone)]
+    struct ModuleConfig {
+        triple: ~str,
+        cpu: ~str,
+        features: ~str,
+        reloc_model: lib::llvm::RelocMode,
+        opt_level: lib::llvm::CodeGenOptLevel,
+        use_softfp: bool,
+        no_fp_elim: bool,
+        segmented_stacks: bool,
+        verify: bool,
+        prepopulate_passes: bool,
+        profile_guided: bool,
+        passes: Vec<~str>,
+        time_passes: bool,
+    }
+
+    fn module_config(sess: &Session) -> ModuleConfig {
+        let opt_level = match sess.opts.optimize {
+          session::No => lib::llvm::CodeGenLevelNone,
+          session::Less => lib::llvm::CodeGenLevelLess,
+          session::Default => lib::llvm::CodeGenLevelDefault,
+          session::Aggressive => lib::llvm::CodeGenLevelAggressive,
+        };
+
+        // FIXME: #11906: Omitting frame pointers breaks retrieving the value of a parameter.
+        // FIXME: #11954: mac64 unwinding may not work with fp elim
+        let no_fp_elim = (sess.opts.debuginfo != NoDebugInfo) ||
+                         (sess.targ_cfg.os == abi::OsMacos &&
+                          sess.targ_cfg.arch == abi::X86_64);
+
+        let reloc_model_name = match sess.opts.cg.relocation_model {
+            Some(ref model) => model.as_slice(),
+            None => sess.targ_cfg.relocation_model.as_slice(),
+        };
+        let reloc_model = match reloc_model_name {
+            "pic" => lib::llvm::RelocPIC,
+            "static" => lib::llvm::RelocStatic,
+            "default" => lib::llvm::RelocDefault,
+            "dynamic-no-pic" => lib::llvm::RelocDynamicNoPic,
+            _ => {
+                sess.err(format!("{} is not a valid relocation mode",
+                         reloc_model_name));
+                sess.abort_if_errors();
+                unreachable!();
+            }
+        };
+
+        // Unknown passes are reported here, once, rather than by every pass
+        // manager they would be added to.
+        let passes = sess.opts.cg.passes.iter().filter(|pass| unsafe {
+            let pm = llvm::LLVMCreatePassManager();
+            let known = pass.with_c_str(|s| llvm::LLVMRustAddPass(pm, s));
+            llvm::LLVMDisposePassManager(pm);
+            if !known {
+                sess.warn(format!("unknown pass {}, ignoring", **pass));
+            }
+            known
+        }).map(|pass| pass.clone()).collect();
+
+        ModuleConfig {
+            triple: sess.targ_cfg.target_strs.target_triple.clone(),
+            cpu: sess.opts.cg.target_cpu.clone(),
+            features: target_feature(sess).to_owned(),
+            reloc_model: reloc_model,
+            opt_level: opt_level,
+            use_softfp: sess.opts.cg.soft_float,
+            no_fp_elim: no_fp_elim,
+            // Stack growth is checked against limits the runtime maintains.
+            segmented_stacks: sess.has_runtime(),
+            verify: !sess.no_verify(),
+            prepopulate_passes: !sess.opts.cg.no_prepopulate_passes,
+            profile_guided: sess.opts.cg.profile_use.is_some(),
+            passes: passes,
+            time_passes: sess.time_passes(),
+        }
+    }
+
+    unsafe fn create_target_machine(config: &ModuleConfig) -> TargetMachineRef {
+        config.triple.with_c_str(|t| {
+            config.cpu.with_c_str(|cpu| {
+                config.features.with_c_str(|features| {
+                    llvm::LLVMRustCreateTargetMachine(
+                        t, cpu, features,
+                        lib::llvm::CodeModelDefault,
+                        config.reloc_model,
+                        config.opt_level,
+                        config.segmented_stacks,
+                        config.use_softfp,
+                        config.no_fp_elim
+                    )
+                })
+            })
+        })
+    }
+
+    unsafe fn optimize_module(config: &ModuleConfig, tm: TargetMachineRef,
+                              llmod: ModuleRef) {
+        // Create the two optimizing pass managers. These mirror what clang
+        // does, and are by populated by LLVM's default PassManagerBuilder.
+        // Each manager has a different set of passes, but they also share
+        // some common passes.
+        let fpm = llvm::LLVMCreateFunctionPassManagerForModule(llmod);
+        let mpm = llvm::LLVMCreatePassManager();
+
+        // If we're verifying or linting, add them to the function pass
+        // manager.
+        let addpass = |pass: &str| {
+            pass.with_c_str(|s| llvm::LLVMRustAddPass(fpm, s))
+        };
+        if config.verify { assert!(addpass("verify")); }
+
+        if config.prepopulate_passes {
+            llvm::LLVMRustAddAnalysisPasses(tm, fpm, llmod);
+            llvm::LLVMRustAddAnalysisPasses(tm, mpm, llmod);
+            populate_llvm_passes(fpm, mpm, llmod, config.opt_level,
+                                 config.profile_guided);
+        }
+
+        for pass in config.passes.iter() {
+            pass.with_c_str(|s| llvm::LLVMRustAddPass(mpm, s));
+        }
+
+        // Finally, run the actual optimization passes
+        time(config.time_passes, "llvm function passes", (), |()|
+             llvm::LLVMRustRunFunctionPassManager(fpm, llmod));
+        time(config.time_passes, "llvm module passes", (), |()|
+             llvm::LLVMRunPassManager(mpm, llmod));
+
+        // Deallocate managers that we're now done with
+        llvm::LLVMDisposePassManager(fpm);
+        llvm::LLVMDisposePassManager(mpm);
+    }
+
+    // A codegen-specific pass manager is used to generate object
+    // files for an LLVM module.
+    //
+    // Apparently each of these pass managers is a one-shot kind of
+    // thing, so we create a new one for each type of output. The
+    // pass manager passed to the closure should be ensured to not
+    // escape the closure itself, and the manager should only be
+    // used once.
+    fn with_codegen(tm: TargetMachineRef, llmod: ModuleRef,
+                    f: |PassManagerRef|) {
+        unsafe {
+            let cpm = llvm::LLVMCreatePassManager();
+            llvm::LLVMRustAddAnalysisPasses(tm, cpm, llmod);
+            llvm::LLVMRustAddLibraryInfo(cpm, llmod);
+            f(cpm);
+            llvm::LLVMDisposePassManager(cpm);
+        }
+    }
+
+    /// The number of codegen units the crate is actually split into. Only the
+    /// object file can be produced from several units; LTO and every other
+    /// kind of output need the crate in a single module.
+    pub fn codegen_units(sess: &Session, output_types: &[OutputType]) -> uint {
+        let units = sess.opts.cg.codegen_units;
+        if units == 1 {
+            return 1
+        }
+        if sess.lto() {
+            sess.warn("ignoring -C codegen-units, which can't be used with LTO");
+            return 1
+        }
+        for output_type in output_types.iter() {
+            match *output_type {
+                OutputTypeObject | OutputTypeMetadata | OutputTypeCHeader |
+                OutputTypeExe => {}
+                _ => {
+                    sess.warn("ignoring -C codegen-units, which can only be \
+                               used when emitting object files or linking");
+                    return 1
+                }
+            }
+        }
+        units
+    }
+
+    // Split the crate into `units` modules, each optimized and emitted on its
+    // own task, and link the resulting objects together into `obj`.
+    unsafe fn run_codegen_units(sess: &Session, trans: &CrateTranslation,
+                                config: &ModuleConfig, units: uint, obj: &Path) {
+        // Every task reads the whole crate back into its own context and
+        // keeps only the definitions belonging to its unit, so everything
+        // that may be referenced from another unit has to be made visible
+        // to it first. The prefix keeps the exposed names from clashing with
+        // those of other crates.
+        let prefix = format!("{}.{}.", trans.link.crateid.name,
+                             trans.link.crate_hash.as_str());
+        prefix.with_c_str(|p| llvm::LLVMRustPrepareCodegenUnits(trans.module, p));
+        let bc = obj.with_extension("units.bc");
+        let written = bc.with_c_str(|buf| {
+            llvm::LLVMWriteBitcodeToFile(trans.module, buf) == 0
+        });
+        if !written {
+            sess.fatal(format!("failed to write bitcode to {}", bc.display()));
+        }
+
+        let (tx, rx) = channel();
+        let mut objects = Vec::new();
+        for unit in range(0, units) {
+            let mut config = config.clone();
+            config.time_passes = false;
+            let bc = bc.clone();
+            let unit_obj = obj.with_extension(format!("{}.o", unit));
+            objects.push(unit_obj.clone());
+            let tx = tx.clone();
+            task::task().named(format!("codegen unit {}", unit)).spawn(proc() {
+                tx.send(codegen_unit(&config, &bc, units, unit, &unit_obj));
+            });
+        }
+        drop(tx);
+
+        for _ in range(0, units) {
+            match rx.recv_opt() {
+                Some(Ok(())) => {}
+                Some(Err(msg)) => sess.err(msg),
+                None => {
+                    sess.err("a codegen task failed");
+                    break
+                }
+            }
+        }
+        sess.abort_if_errors();
+
+        time(sess.time_passes(), "linking codegen units", (), |()|
+             link_codegen_units(sess, objects.as_slice(), obj));
+
+        if !sess.opts.cg.save_temps {
+            for object in objects.iter() {
+                let _ = fs::unlink(object);
+            }
+            let _ = fs::unlink(&bc);
+        }
+    }
+
+    fn codegen_unit(config: &ModuleConfig, bc: &Path, units: uint,
+                    unit: uint, obj: &Path) -> Result<(), ~str> {
+        unsafe {
+            let llcx = llvm::LLVMContextCreate();
+            let llmod = bc.with_c_str(|bc| {
+                llvm::LLVMRustLoadCodegenUnit(llcx, bc, units as c_uint,
+                                              unit as c_uint)
+            });
+            if llmod.is_null() {
+                llvm::LLVMContextDispose(llcx);
+                return Err(format!("failed to load codegen unit {}: {}",
+                                   unit, last_llvm_error()));
+            }
+
+            let tm = create_target_machine(config);
+            optimize_module(config, tm, llmod);
+            let mut written = false;
+            with_codegen(tm, llmod, |cpm| {
+                written = obj.with_c_str(|output| {
+                    llvm::LLVMRustWriteOutputFile(tm, cpm, llmod, output,
+                                                  lib::llvm::ObjectFile)
+                });
+            });
+            let result = if written {
+                Ok(())
+            } else {
+                Err(format!("could not write output: {}", last_llvm_error()))
+            };
+
+            llvm::LLVMRustDisposeTargetMachine(tm);
+            llvm::LLVMDisposeModule(llmod);
+            llvm::LLVMContextDispose(llcx);
+            result
+        }
+    }
+
+    // LLVM keeps the last error of each thread, and nothing between a failed
+    // call and this can move the task to another thread, so the error is
+    // that of the call which just failed.
+    fn last_llvm_error() -> ~str {
+        unsafe {
+            let cstr = llvm::LLVMRustGetLastError();
+            if cstr == ptr::null() {
+                ~"unknown error"
+            } else {
+                let err = CString::new(cstr, false);
+                str::from_utf8_lossy(err.as_bytes()).as_slice().to_owned()
+            }
+        }
+    }
+
     pub fn run_assembler(sess: &Session, outputs: &OutputFilenames) {
         let cc = super::get_cc_prog(sess);
         let assembly = outputs.temp_path(OutputTypeAssembly);
@@ -428,7 +672,8 @@ pub mod write {
     unsafe fn populate_llvm_passes(fpm: lib::llvm::PassManagerRef,
                                    mpm: lib::llvm::PassManagerRef,
                                    llmod: ModuleRef,
-                                   opt: lib::llvm::CodeGenOptLevel) {
+                                   opt: lib::llvm::CodeGenOptLevel,
+                                   profile_guided: bool) {
         // Create the PassManagerBuilder for LLVM. We configure it with
         // reasonable defaults and prepare it to actually populate the pass
         // manager.
@@ -438,6 +683,11 @@ pub mod write {
                 // Don't add lifetime intrinsics at O0
                 llvm::LLVMRustAddAlwaysInlinePass(builder, false);
             }
+            // A profile marks the functions worth inlining, so only those
+            // which are hinted (and tiny ones) are inlined.
+            lib::llvm::CodeGenLevelLess if profile_guided => {
+                llvm::LLVMPassManagerBuilderUseInlinerWithThreshold(builder, 0);
+            }
             lib::llvm::CodeGenLevelLess => {
                 llvm::LLVMRustAddAlwaysInlinePass(builder, true);
             }
@@ -558,9 +808,16 @@ fn symbol_hash(tcx: &ty::ctxt,
     symbol_hasher.reset();
     symbol_hasher.input_str(link_meta.crateid.name);
     symbol_hasher.input_str("-");
-    symbol_hasher.input_str(link_meta.crate_hash.as_str());
-    symbol_hasher.input_str("-");
-    symbol_hasher.input_str(encoder::encoded_ty(tcx, t));
+    if tcx.sess.opts.incremental.is_none() {
+        symbol_hasher.input_str(link_meta.crate_hash.as_str());
+        symbol_hasher.input_str("-");
+        symbol_hasher.input_str(encoder::encoded_ty(tcx, t));
+    } else {
+        // Symbol names must survive edits to the crate when they are looked
+        // up in the incremental cache, so leave out the SVH and the node ids
+        // which the encoded type refers to local items by.
+        symbol_hasher.input_str(ppaux::ty_to_str(tcx, t));
+    }
     // Prefix with 'h' so that it never blends into adjacent digits
     let mut hash = StrBuf::from_str("h");
     hash.push_str(truncated_hash_result(symbol_hasher));
@@ -686,6 +943,21 @@ pub fn mangle_exported_name(ccx: &CrateContext, path: PathElems,
                             t: ty::t, id: ast::NodeId) -> ~str {
     let mut hash = StrBuf::from_owned_str(get_symbol_hash(ccx, t));
 
+    // In incremental mode the node id can't be part of the name, as it
+    // changes whenever something before the item is edited. Only fall back
+    // to it when the path is ambiguous.
+    match ccx.incremental {
+        Some(ref cache) => {
+            let sym = exported_name(path.clone(),
+                                    hash.as_slice(),
+                                    ccx.link_meta.crateid.version_or_default());
+            if cache.claim_symbol(sym) {
+                return sym
+            }
+        }
+        None => {}
+    }
+
     // Paths can be completely identical for different nodes,
     // e.g. `fn foo() { { fn a() {} } { fn a() {} } }`, so we
     // generate unique characters from the node id. For now
@@ -732,6 +1004,10 @@ pub fn get_cc_prog(sess: &Session) -> ~str {
         Some(ref linker) => return linker.to_owned(),
         None => {}
     }
+    match sess.targ_cfg.linker {
+        Some(ref linker) => return linker.to_owned(),
+        None => {}
+    }
 
     // In the future, FreeBSD will use clang as default compiler.
     // It would be flexible to use cc (system's default C compiler)
@@ -773,6 +1049,36 @@ fn get_system_tool(sess: &Session, tool: &str) -> ~str {
     }
 }
 
+/// Combine the object files of a crate's codegen units into the single object
+/// file the rest of the linking process works with, by means of a
+/// relocatable link.
+pub fn link_codegen_units(sess: &Session, objects: &[Path], out: &Path) {
+    let cc = get_cc_prog(sess);
+
+    // FIXME (#9639): This needs to handle non-utf8 paths
+    let mut args = vec!(~"-nostdlib", ~"-Wl,-r",
+                        ~"-o", out.as_str().unwrap().to_owned());
+    args.extend(objects.iter().map(|obj| obj.as_str().unwrap().to_owned()));
+
+    debug!("{} '{}'", cc, args.connect("' '"));
+    match Process::output(cc, args.as_slice()) {
+        Ok(prog) => {
+            if !prog.status.success() {
+                sess.err(format!("linking with `{}` failed: {}", cc, prog.status));
+                sess.note(format!("{} arguments: '{}'", cc, args.connect("' '")));
+                let mut note = prog.error.clone();
+                note.push_all(prog.output.as_slice());
+                sess.note(str::from_utf8(note.as_slice()).unwrap().to_owned());
+                sess.abort_if_errors();
+            }
+        },
+        Err(e) => {
+            sess.err(format!("could not exec the linker `{}`: {}", cc, e));
+            sess.abort_if_errors();
+        }
+    }
+}
+
 fn remove(sess: &Session, path: &Path) {
     match fs::unlink(path) {
         Ok(..) => {}
@@ -837,6 +1143,12 @@ pub fn filename_for_input(sess: &Session, crate_type: session::CrateType,
     }
 }
 
+/// The file `--emit=metadata` writes the crate's metadata to. It is named
+/// like an rlib so that the loader can find it when checking dependents.
+pub fn metadata_filename(id: &CrateId, out_filename: &Path) -> Path {
+    out_filename.with_filename(format!("lib{}.rmeta", output_lib_filename(id)))
+}
+
 fn link_binary_output(sess: &Session,
                       trans: &CrateTranslation,
                       crate_type: session::CrateType,
@@ -998,8 +1310,10 @@ fn link_rlib<'a>(sess: &'a Session,
 // metadata file).
 fn link_staticlib(sess: &Session, obj_filename: &Path, out_filename: &Path) {
     let mut a = link_rlib(sess, None, obj_filename, out_filename);
-    a.add_native_library("morestack").unwrap();
-    a.add_native_library("compiler-rt").unwrap();
+    if sess.has_runtime() {
+        a.add_native_library("morestack").unwrap();
+        a.add_native_library("compiler-rt").unwrap();
+    }
 
     let crates = sess.cstore.get_used_crates(cstore::RequireStatic);
     for &(cnum, ref path) in crates.iter() {
@@ -1113,7 +1427,12 @@ fn link_args(sess: &Session,
     // line, but inserting this farther to the left makes the
     // "rust_stack_exhausted" symbol an outstanding undefined symbol, which
     // flags libstd as a required library (or whatever provides the symbol).
-    args.push(~"-lmorestack");
+    //
+    // Freestanding crates and targets without a runtime have neither this
+    // nor compiler-rt below.
+    if sess.has_runtime() {
+        args.push(~"-lmorestack");
+    }
 
     // When linking a dynamic library, we put the metadata into a section of the
     // executable. This metadata is in a separate object file from the main
@@ -1264,7 +1583,9 @@ fn link_args(sess: &Session,
     //
     // This is the end of the command line, so this library is used to resolve
     // *all* undefined symbols in all other libraries, and this is intentional.
-    args.push(~"-lcompiler-rt");
+    if sess.has_runtime() {
+        args.push(~"-lcompiler-rt");
+    }
 
     // Finally add all the linker arguments provided on the command line along
     // with any #[link_args] attributes found inside the crate
@@ -1331,15 +1652,22 @@ fn add_upstream_rust_crates(args: &mut Vec<~str>, sess: &Session,
     // With this limitation, we expose a compiler default linkage type and an
     // option to reverse that preference. The current behavior looks like:
     //
-    // * If a dylib is being created, upstream dependencies must be dylibs
+    // * If a dylib is being created, upstream dependencies must be dylibs,
+    //   unless it is built with LTO, which takes them all in as rlibs
+    // * Crates already linked into a dylib built with LTO are not linked again
     // * If nothing else is specified, static linking is preferred
     // * If the -C prefer-dynamic flag is given, dynamic linking is preferred
     // * If one form of linking fails, the second is also attempted
     // * If both forms fail, then we emit an error message
 
-    let dynamic = get_deps(&sess.cstore, cstore::RequireDynamic);
-    let statik = get_deps(&sess.cstore, cstore::RequireStatic);
+    let included = lto_included_crates(&sess.cstore);
+    let dynamic = get_deps(&sess.cstore, cstore::RequireDynamic, &included);
+    let statik = get_deps(&sess.cstore, cstore::RequireStatic, &included);
     match (dynamic, statik, sess.opts.cg.prefer_dynamic, dylib) {
+        (_, Some(deps), false, true) if sess.lto() => {
+            add_static_crates(args, sess, tmpdir, deps)
+        }
+
         (_, Some(deps), false, false) => {
             add_static_crates(args, sess, tmpdir, deps)
         }
@@ -1395,13 +1723,39 @@ fn add_upstream_rust_crates(args: &mut Vec<~str>, sess: &Session,
         }
     }
 
+    // The crates which a dylib built with LTO has linked into itself, along
+    // with everything else they depend on. That dylib exports them all, so
+    // linking to it in turn provides them.
+    fn lto_included_crates(cstore: &cstore::CStore) -> Vec<ast::CrateNum> {
+        fn include(cstore: &cstore::CStore, cnum: ast::CrateNum,
+                   included: &mut Vec<ast::CrateNum>) {
+            let meta = cstore.get_crate_data(cnum);
+            for (_, &dep) in meta.cnum_map.borrow().iter() {
+                if included.contains(&dep) { continue }
+                included.push(dep);
+                include(cstore, dep, included);
+            }
+        }
+        let mut included = Vec::new();
+        for (cnum, _) in cstore.get_used_crates(cstore::RequireDynamic).move_iter() {
+            if csearch::get_lto_includes_deps(cstore, cnum) {
+                include(cstore, cnum, &mut included);
+            }
+        }
+        included
+    }
+
     // Attempts to find all dependencies with a certain linkage preference,
     // returning `None` if not all libraries could be found with that
-    // preference.
-    fn get_deps(cstore: &cstore::CStore,  preference: cstore::LinkagePreference)
+    // preference. The crates in `included` are skipped.
+    fn get_deps(cstore: &cstore::CStore, preference: cstore::LinkagePreference,
+                included: &Vec<ast::CrateNum>)
             -> Option<Vec<(ast::CrateNum, Path)> >
     {
-        let crates = cstore.get_used_crates(preference);
+        let crates: Vec<(ast::CrateNum, Option<Path>)> =
+            cstore.get_used_crates(preference).move_iter().filter(|&(cnum, _)| {
+                !included.contains(&cnum)
+            }).collect();
         if crates.iter().all(|&(_, ref p)| p.is_some()) {
             Some(crates.move_iter().map(|(a, b)| (a, b.unwrap())).collect())
         } else {
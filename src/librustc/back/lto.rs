@@ -17,25 +17,20 @@ use util::common::time;
 
 use libc;
 use flate;
+use std::c_vec::CVec;
 
+/// Merges the bitcode of every upstream crate into `llmod` and optimizes the
+/// result. Everything but the symbols in `reachable` is internalized.
 pub fn run(sess: &session::Session, llmod: ModuleRef,
            tm: TargetMachineRef, reachable: &[~str]) {
     if sess.opts.cg.prefer_dynamic {
         sess.err("cannot prefer dynamic linking when performing LTO");
-        sess.note("only 'staticlib' and 'bin' outputs are supported with LTO");
+        sess.note("LTO needs the rlibs of all upstream crates");
         sess.abort_if_errors();
     }
 
     // Make sure we actually can run LTO
-    for crate_type in sess.crate_types.borrow().iter() {
-        match *crate_type {
-            session::CrateTypeExecutable | session::CrateTypeStaticlib => {}
-            _ => {
-                sess.fatal("lto can only be run for executables and \
-                            static library outputs");
-            }
-        }
-    }
+    check_crate_types(sess, "lto");
 
     // For each of our upstream dependencies, find the corresponding rlib and
     // load the bitcode from the archive. Then merge it into the current LLVM
@@ -50,16 +45,7 @@ pub fn run(sess: &session::Session, llmod: ModuleRef,
             }
         };
 
-        let archive = ArchiveRO::open(&path).expect("wanted an rlib");
-        debug!("reading {}", name);
-        let bc = time(sess.time_passes(), format!("read {}.bc.deflate", name), (), |_|
-                      archive.read(format!("{}.bc.deflate", name)));
-        let bc = bc.expect("missing compressed bytecode in archive!");
-        let bc = time(sess.time_passes(), format!("inflate {}.bc", name), (), |_|
-                      match flate::inflate_bytes(bc) {
-                          Some(bc) => bc,
-                          None => sess.fatal(format!("failed to decompress bc of `{}`", name))
-                      });
+        let bc = read_bitcode(sess, name, &path);
         let ptr = bc.as_slice().as_ptr();
         debug!("linking {}", name);
         time(sess.time_passes(), format!("ll link {}", name), (), |()| unsafe {
@@ -112,3 +98,70 @@ pub fn run(sess: &session::Session, llmod: ModuleRef,
     }
     debug!("lto done");
 }
+
+// Functions of upstream crates at most this many instructions long are
+// imported by thin LTO, as are those hinted for inlining.
+static IMPORT_INSTRUCTION_LIMIT: uint = 100;
+
+/// Thin LTO (`-Z thin-lto`): rather than merging the whole of every upstream
+/// crate into `llmod`, import only the bodies of the functions it calls
+/// which are worth inlining: those marked `#[inline]` or found hot by a
+/// profile (see `-C profile-use`), and small ones. The imported bodies are
+/// only there to be inlined, and the upstream crates are still linked as
+/// usual. This runs before `llmod` is optimized, so that the inliner sees
+/// the imports. Crates only available as dylibs have no bitcode to import
+/// from and are skipped.
+pub fn import(sess: &session::Session, llmod: ModuleRef) {
+    check_crate_types(sess, "thin-lto");
+
+    let crates = sess.cstore.get_used_crates(cstore::RequireStatic);
+    for (cnum, path) in crates.move_iter() {
+        let path = match path {
+            Some(p) => p,
+            None => continue,
+        };
+        let name = sess.cstore.get_crate_data(cnum).name.clone();
+        let bc = read_bitcode(sess, name, &path);
+        let ptr = bc.as_slice().as_ptr();
+        let imported = time(sess.time_passes(), format!("import from {}", name), (),
+                            |()| unsafe {
+            llvm::LLVMRustImportFunctions(llmod, ptr as *libc::c_char,
+                                          bc.len() as libc::size_t,
+                                          IMPORT_INSTRUCTION_LIMIT as libc::c_uint)
+        });
+        if imported < 0 {
+            link::llvm_err(sess, format!("failed to import from bc of `{}`", name));
+        }
+        debug!("imported {} functions from {}", imported, name);
+    }
+}
+
+// LTO produces a single object file containing the upstream crates (or,
+// thin, which is optimized with them), which has no place in an rlib.
+fn check_crate_types(sess: &session::Session, flag: &str) {
+    for crate_type in sess.crate_types.borrow().iter() {
+        match *crate_type {
+            session::CrateTypeExecutable | session::CrateTypeStaticlib |
+            session::CrateTypeDylib => {}
+            session::CrateTypeRlib => {
+                sess.fatal(format!("-Z {} can only be used for executables, \
+                                    static libraries and dynamic libraries",
+                                   flag));
+            }
+        }
+    }
+}
+
+// The bitcode stored in the rlib of crate `name`.
+fn read_bitcode(sess: &session::Session, name: &str, path: &Path) -> CVec<u8> {
+    let archive = ArchiveRO::open(path).expect("wanted an rlib");
+    debug!("reading {}", name);
+    let bc = time(sess.time_passes(), format!("read {}.bc.deflate", name), (), |_|
+                  archive.read(format!("{}.bc.deflate", name)));
+    let bc = bc.expect("missing compressed bytecode in archive!");
+    time(sess.time_passes(), format!("inflate {}.bc", name), (), |_|
+         match flate::inflate_bytes(bc) {
+             Some(bc) => bc,
+             None => sess.fatal(format!("failed to decompress bc of `{}`", name))
+         })
+}
@@ -51,6 +51,8 @@ use std::hash::Hash;
 use std::hash::sip::SipState;
 use std::iter::range_step;
 use syntax::ast;
+use syntax::parse::token;
+use syntax::print::pprust;
 
 #[deriving(Clone, Eq)]
 pub struct Svh {
@@ -110,3 +112,83 @@ impl fmt::Show for Svh {
         f.pad(self.as_str())
     }
 }
+
+/// Hash everything in the crate that the translation of a non-generic
+/// function may depend on, other than the function's own body.
+///
+/// This is the basis of the per-function fingerprints used by `-Z
+/// incremental`. Unlike the SVH it hashes the pretty-printed source rather
+/// than the AST, so that it depends on neither spans nor node ids: editing
+/// the body of one function must not change the fingerprint of another.
+/// The bodies of generic functions are included since they are instantiated
+/// alongside their callers, as are the SVHs of all upstream crates, the
+/// crate's configuration and `options`, which describes the target and the
+/// options affecting code generation.
+pub fn interface_fingerprint(krate: &ast::Crate, upstream: &[Svh], options: &str) -> u64 {
+    let mut state = SipState::new();
+    options.hash(&mut state);
+    for svh in upstream.iter() {
+        svh.as_str().hash(&mut state);
+    }
+    for mi in krate.config.iter() {
+        pprust::meta_item_to_str(*mi).hash(&mut state);
+    }
+    for attr in krate.attrs.iter() {
+        pprust::attribute_to_str(attr).hash(&mut state);
+    }
+    hash_mod(&mut state, &krate.module);
+    return state.result();
+
+    fn hash_mod(state: &mut SipState, m: &ast::Mod) {
+        // Imports decide what the paths in unchanged bodies refer to.
+        for vi in m.view_items.iter() {
+            pprust::to_str(|s| s.print_view_item(vi)).hash(state);
+        }
+        for item in m.items.iter() {
+            hash_item(state, *item);
+        }
+    }
+
+    fn hash_item(state: &mut SipState, item: &ast::Item) {
+        for attr in item.attrs.iter() {
+            pprust::attribute_to_str(attr).hash(state);
+        }
+        match item.node {
+            ast::ItemMod(ref m) => {
+                token::get_ident(item.ident).get().hash(state);
+                hash_mod(state, m);
+            }
+            ast::ItemFn(decl, fn_style, _, ref generics, _)
+                    if !generics.is_type_parameterized() => {
+                pprust::fun_to_str(decl, fn_style, item.ident,
+                                   None, generics).hash(state);
+            }
+            ast::ItemImpl(ref generics, ref trait_ref, ty, ref methods)
+                    if !generics.is_type_parameterized() => {
+                for trait_ref in trait_ref.iter() {
+                    pprust::path_to_str(&trait_ref.path).hash(state);
+                }
+                pprust::ty_to_str(ty).hash(state);
+                for m in methods.iter() {
+                    if m.generics.is_type_parameterized() {
+                        pprust::to_str(|s| s.print_method(*m)).hash(state);
+                    } else {
+                        pprust::fun_to_str(m.decl, m.fn_style, m.ident,
+                                           Some(m.explicit_self.node),
+                                           &m.generics).hash(state);
+                    }
+                }
+            }
+            _ => pprust::item_to_str(item).hash(state),
+        }
+    }
+}
+
+/// The fingerprint of a single function: its full source, including any
+/// items nested within it, combined with the crate's interface fingerprint.
+pub fn fn_fingerprint(interface: u64, source: &str) -> u64 {
+    let mut state = SipState::new();
+    interface.hash(&mut state);
+    source.hash(&mut state);
+    state.result()
+}
@@ -0,0 +1,177 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Target descriptions loaded from JSON files (`--target path/to/foo.json`).
+//!
+//! A target which rustc doesn't know about can be described by a JSON object
+//! instead of a triple:
+//!
+//!     {
+//!         "llvm-target": "thumbv7m-none-eabi",
+//!         "data-layout": "e-p:32:32:32-i1:8:8-...",
+//!         "target-pointer-width": 32,
+//!         "arch": "arm",
+//!         "os": "linux",
+//!         "target-family": "none",
+//!         "linker": "arm-none-eabi-gcc",
+//!         "cc-args": ["-mcpu=cortex-m3", "-nostartfiles"],
+//!         "relocation-model": "static",
+//!         "has-runtime": false
+//!     }
+//!
+//! The first four keys are required. `os` selects the object file and
+//! linking conventions to follow and defaults to the one named by the LLVM
+//! target; `target-family` is the `cfg(target_family)` of the target and
+//! defaults to that of `os`. `linker` and `relocation-model` are overridden
+//! by the codegen options of the same name. A target without a runtime
+//! doesn't link in `morestack` or `compiler-rt`.
+
+use driver::driver::{get_arch, get_os};
+
+use serialize::json;
+use std::io;
+use syntax::abi;
+
+#[deriving(Clone)]
+pub struct TargetSpec {
+    pub llvm_target: ~str,
+    pub data_layout: ~str,
+    pub pointer_width: uint,
+    pub arch: abi::Architecture,
+    pub os: abi::Os,
+    pub target_family: Option<~str>,
+    pub linker: Option<~str>,
+    pub cc_args: Vec<~str>,
+    pub relocation_model: Option<~str>,
+    pub has_runtime: bool,
+}
+
+/// Whether `--target` names a target description rather than a triple.
+pub fn is_spec_path(target: &str) -> bool {
+    target.ends_with(".json")
+}
+
+impl TargetSpec {
+    /// Read the target description at `path`.
+    pub fn load(path: &Path) -> Result<TargetSpec, ~str> {
+        let src = match io::File::open(path).read_to_str() {
+            Ok(src) => src,
+            Err(e) => {
+                return Err(format!("couldn't read target specification `{}`: {}",
+                                   path.display(), e))
+            }
+        };
+        let json = match json::from_str(src) {
+            Ok(json) => json,
+            Err(json::ParseError(msg, line, col)) => {
+                return Err(format!("{}:{}:{}: {}", path.display(), line, col, msg))
+            }
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        TargetSpec::from_json(&json).map_err(|msg| {
+            format!("invalid target specification `{}`: {}", path.display(), msg)
+        })
+    }
+
+    pub fn from_json(json: &json::Json) -> Result<TargetSpec, ~str> {
+        if !json.is_object() {
+            return Err(~"expected a JSON object")
+        }
+
+        let llvm_target = try!(req_string(json, "llvm-target"));
+        let data_layout = try!(req_string(json, "data-layout"));
+        let pointer_width = match try!(req(json, "target-pointer-width")).as_number() {
+            Some(w) if w == 32.0 => 32,
+            Some(w) if w == 64.0 => 64,
+            _ => return Err(~"`target-pointer-width` must be 32 or 64"),
+        };
+        let arch = try!(req_string(json, "arch"));
+        let arch = match get_arch(arch) {
+            Some(arch) => arch,
+            None => return Err(format!("unknown architecture `{}`", arch)),
+        };
+        let os = match try!(opt_string(json, "os")) {
+            Some(os) => match get_os(os) {
+                Some(os) => os,
+                None => return Err(format!("unknown operating system `{}`", os)),
+            },
+            None => match get_os(llvm_target) {
+                Some(os) => os,
+                None => {
+                    return Err(format!("`{}` names no known operating system, \
+                                        so `os` must be given", llvm_target))
+                }
+            },
+        };
+
+        let cc_args = match json.find(&~"cc-args") {
+            None => Vec::new(),
+            Some(args) => {
+                let args = match args.as_list() {
+                    Some(args) => args,
+                    None => return Err(~"`cc-args` must be a list of strings"),
+                };
+                let mut v = Vec::new();
+                for arg in args.iter() {
+                    match arg.as_string() {
+                        Some(arg) => v.push(arg.to_owned()),
+                        None => return Err(~"`cc-args` must be a list of strings"),
+                    }
+                }
+                v
+            }
+        };
+        let has_runtime = match json.find(&~"has-runtime") {
+            None => true,
+            Some(b) => match b.as_boolean() {
+                Some(b) => b,
+                None => return Err(~"`has-runtime` must be a boolean"),
+            },
+        };
+
+        Ok(TargetSpec {
+            llvm_target: llvm_target.to_owned(),
+            data_layout: data_layout.to_owned(),
+            pointer_width: pointer_width,
+            arch: arch,
+            os: os,
+            target_family: try!(opt_string(json, "target-family")).map(|s| s.to_owned()),
+            linker: try!(opt_string(json, "linker")).map(|s| s.to_owned()),
+            cc_args: cc_args,
+            relocation_model: try!(opt_string(json, "relocation-model"))
+                                  .map(|s| s.to_owned()),
+            has_runtime: has_runtime,
+        })
+    }
+}
+
+fn req<'a>(json: &'a json::Json, key: &str) -> Result<&'a json::Json, ~str> {
+    match json.find(&key.to_owned()) {
+        Some(value) => Ok(value),
+        None => Err(format!("missing `{}`", key)),
+    }
+}
+
+fn req_string<'a>(json: &'a json::Json, key: &str) -> Result<&'a str, ~str> {
+    match try!(req(json, key)).as_string() {
+        Some(s) => Ok(s),
+        None => Err(format!("`{}` must be a string", key)),
+    }
+}
+
+fn opt_string<'a>(json: &'a json::Json, key: &str) -> Result<Option<&'a str>, ~str> {
+    match json.find(&key.to_owned()) {
+        None => Ok(None),
+        Some(value) => match value.as_string() {
+            Some(s) => Ok(Some(s)),
+            None => Err(format!("`{}` must be a string", key)),
+        },
+    }
+}
@@ -0,0 +1,2718 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Error codes and their extended explanations.
+//!
+//! Errors reported by resolve, typeck and borrowck carry a stable code,
+//! which is shown next to the message and can be looked up with
+//! `rustc --explain CODE`. Every code is declared here together with its
+//! explanation, and the compiler refers to the code through the static of
+//! the same name, so a code can't be used without being explained.
+//!
+//! Errors which can only mean that the compiler itself went wrong, such as a
+//! region which inference left unresolved, are reported without a code.
+//!
+//! Codes are never reused: when an error goes away, its code is retired
+//! rather than given to another one.
+
+macro_rules! register_diagnostics(
+    ($($code:ident: $explanation:expr),+) => (
+        $(pub static $code: &'static str = stringify!($code);)+
+
+        static DIAGNOSTICS: &'static [(&'static str, &'static str)] = &[
+            $((stringify!($code), $explanation)),+
+        ];
+    )
+)
+
+/// The extended explanation of the error code `code`, if there is one.
+pub fn explain(code: &str) -> Option<&'static str> {
+    DIAGNOSTICS.iter().find(|&&(c, _)| c == code).map(|&(_, text)| text.trim())
+}
+
+register_diagnostics!(
+
+// Name resolution
+
+E0001: r##"
+Two items of the same kind were given the same name in the same module.
+Types, traits and modules share one namespace, and functions, statics and
+enum variants share another, so no two of either kind may have the same
+name.
+
+Erroneous code example:
+
+    fn connect() {}
+    fn connect() {}
+
+Give every item a name of its own.
+
+Corrected code example:
+
+    fn connect() {}
+    fn reconnect() {}
+"##,
+
+E0002: r##"
+A `use` declaration names something which doesn't exist, or goes through a
+module which can't be found. Paths in `use` declarations are relative to the
+crate root, not to the current module, and other crates must be brought in
+with `extern crate` before anything can be imported from them.
+
+Erroneous code example:
+
+    mod net {
+        pub fn connect() {}
+    }
+
+    use net::disconnect;
+
+Corrected code example:
+
+    mod net {
+        pub fn connect() {}
+    }
+
+    use net::connect;
+"##,
+
+E0003: r##"
+A path goes through something which is not a module. Only modules (and, for
+static methods, traits) can appear before a `::` in a path; in particular
+nothing can be imported out of a type or out of an `impl`.
+
+Erroneous code example:
+
+    fn net() {}
+
+    use net::connect;
+
+Corrected code example:
+
+    mod net {
+        pub fn connect() {}
+    }
+
+    use net::connect;
+"##,
+
+E0004: r##"
+A function item declared inside another function used one of the outer
+function's local variables. Function items don't capture their environment;
+only closures do.
+
+Erroneous code example:
+
+    fn count() -> int {
+        let start = 10;
+        fn next() -> int { start + 1 }
+        next()
+    }
+
+Use a closure, or pass the value as an argument.
+
+Corrected code example:
+
+    fn count() -> int {
+        let start = 10;
+        let next = || start + 1;
+        next()
+    }
+"##,
+
+E0005: r##"
+An item declared inside a generic function or impl used one of the outer
+type parameters. Nested items don't inherit the generics of the item they
+are declared in, so they have to declare their own.
+
+Erroneous code example:
+
+    fn wrap<T>(x: T) -> ~T {
+        fn boxed(y: T) -> ~T { ~y }
+        boxed(x)
+    }
+
+Corrected code example:
+
+    fn wrap<T>(x: T) -> ~T {
+        fn boxed<U>(y: U) -> ~U { ~y }
+        boxed(x)
+    }
+"##,
+
+E0006: r##"
+The initializer of a static used a local variable. Statics are evaluated at
+compile time, so they can only refer to other statics and to literals.
+
+Erroneous code example:
+
+    fn limit(n: uint) -> uint {
+        static LIMIT: uint = n * 2;
+        LIMIT
+    }
+
+Corrected code example:
+
+    fn limit(n: uint) -> uint {
+        let limit = n * 2;
+        limit
+    }
+"##,
+
+E0007: r##"
+An `impl` or a bound named a trait which isn't in scope.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    impl Shape for Point {}
+
+Declare or import the trait first.
+
+Corrected code example:
+
+    trait Shape {}
+
+    struct Point { x: int, y: int }
+
+    impl Shape for Point {}
+"##,
+
+E0008: r##"
+A structure declared the same field twice.
+
+Erroneous code example:
+
+    struct Point { x: int, x: int }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+"##,
+
+E0009: r##"
+The patterns of a `match` arm joined with `|` don't all bind the same
+variables. Whichever of them matches, the arm's body must be able to use
+every variable it mentions, so each alternative has to bind all of them.
+
+Erroneous code example:
+
+    fn first(pair: (int, int)) -> int {
+        match pair {
+            (x, 0) | (0, y) => x,
+            _ => 0,
+        }
+    }
+
+Corrected code example:
+
+    fn first(pair: (int, int)) -> int {
+        match pair {
+            (x, 0) | (0, x) => x,
+            _ => 0,
+        }
+    }
+"##,
+
+E0010: r##"
+A variable is bound by value in one of the patterns of a `match` arm joined
+with `|`, and by reference in another. It must be bound the same way in all
+of them, so that it has the same type whichever pattern matched.
+
+Erroneous code example:
+
+    enum Name { First(~str), Last(~str) }
+
+    fn len(n: &Name) -> uint {
+        match *n {
+            First(ref s) | Last(s) => s.len(),
+        }
+    }
+
+Corrected code example:
+
+    enum Name { First(~str), Last(~str) }
+
+    fn len(n: &Name) -> uint {
+        match *n {
+            First(ref s) | Last(ref s) => s.len(),
+        }
+    }
+"##,
+
+E0011: r##"
+Type or lifetime parameters were given to something which doesn't take any,
+such as a primitive type, a module in a path, or a non-generic item.
+
+Erroneous code example:
+
+    fn count(x: int<u8>) -> int { x }
+
+Corrected code example:
+
+    fn count(x: int) -> int { x }
+"##,
+
+E0012: r##"
+A type was used which isn't declared or imported in this scope.
+
+Erroneous code example:
+
+    fn origin() -> Point { Point { x: 0, y: 0 } }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn origin() -> Point { Point { x: 0, y: 0 } }
+"##,
+
+E0013: r##"
+A `let` or function argument declared a variable with the name of an enum
+variant or a unit-like structure which is in scope. In such a pattern the
+name would refer to the variant rather than declare a variable, which would
+make the pattern refutable.
+
+Erroneous code example:
+
+    enum Color { Red, Green }
+
+    fn main() {
+        let Red = 0xff0000;
+    }
+
+Corrected code example:
+
+    enum Color { Red, Green }
+
+    fn main() {
+        let red = 0xff0000;
+    }
+"##,
+
+E0014: r##"
+A `let` or function argument declared a variable with the name of a static
+which is in scope. In a pattern that name refers to the static, and matching
+against a constant can fail, which isn't allowed in a `let`.
+
+Erroneous code example:
+
+    static LIMIT: int = 10;
+
+    fn main() {
+        let LIMIT = 20;
+    }
+
+Corrected code example:
+
+    static LIMIT: int = 10;
+
+    fn main() {
+        let limit = 20;
+    }
+"##,
+
+E0015: r##"
+A pattern bound the same variable twice.
+
+Erroneous code example:
+
+    fn sum(pair: (int, int)) -> int {
+        let (x, x) = pair;
+        x + x
+    }
+
+Corrected code example:
+
+    fn sum(pair: (int, int)) -> int {
+        let (x, y) = pair;
+        x + y
+    }
+"##,
+
+E0016: r##"
+A path in a pattern doesn't name an enum variant, a structure or a static,
+which are the only things paths in patterns can match.
+
+Erroneous code example:
+
+    fn is_origin(x: int) -> bool {
+        match x {
+            is_origin => true,
+            _ => false,
+        }
+    }
+
+Corrected code example:
+
+    static ORIGIN: int = 0;
+
+    fn is_origin(x: int) -> bool {
+        match x {
+            ORIGIN => true,
+            _ => false,
+        }
+    }
+"##,
+
+E0017: r##"
+A structure expression or pattern used a name which isn't a structure or a
+struct-like enum variant.
+
+Erroneous code example:
+
+    type Point = (int, int);
+
+    fn origin() -> Point { Point { x: 0, y: 0 } }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn origin() -> Point { Point { x: 0, y: 0 } }
+"##,
+
+E0018: r##"
+A path went through a module which doesn't exist.
+
+Erroneous code example:
+
+    fn main() {
+        let x = net::connect();
+    }
+
+Corrected code example:
+
+    mod net {
+        pub fn connect() -> int { 0 }
+    }
+
+    fn main() {
+        let x = net::connect();
+    }
+"##,
+
+E0019: r##"
+A method was used as a value without being called. Methods can't be used as
+first-class values; wrap the call in a closure instead.
+
+Erroneous code example:
+
+    struct Counter { n: int }
+
+    impl Counter {
+        fn get(&self) -> int { self.n }
+    }
+
+    fn main() {
+        let f = Counter::get;
+    }
+
+Corrected code example:
+
+    struct Counter { n: int }
+
+    impl Counter {
+        fn get(&self) -> int { self.n }
+    }
+
+    fn main() {
+        let c = Counter { n: 0 };
+        let f = || c.get();
+    }
+"##,
+
+E0020: r##"
+A structure with named fields was called like a function. Only tuple-like
+structures are built with the call syntax.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn origin() -> Point { Point(0, 0) }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn origin() -> Point { Point { x: 0, y: 0 } }
+"##,
+
+E0021: r##"
+An expression used a name which isn't declared in this scope. This is often
+a misspelling, or a function which wasn't imported.
+
+Erroneous code example:
+
+    fn main() {
+        let count = 1;
+        let next = cuont + 1;
+    }
+
+Corrected code example:
+
+    fn main() {
+        let count = 1;
+        let next = count + 1;
+    }
+"##,
+
+E0022: r##"
+A `break` or `loop` referred to a label which isn't declared by any of the
+enclosing loops.
+
+Erroneous code example:
+
+    fn main() {
+        'outer: loop {
+            loop { break 'outr; }
+        }
+    }
+
+Corrected code example:
+
+    fn main() {
+        'outer: loop {
+            loop { break 'outer; }
+        }
+    }
+"##,
+
+E0023: r##"
+A `ref` binding was used with a name which refers to a static, an enum
+variant or a structure. Such a name matches a value rather than binding one,
+so there is nothing to take a reference to.
+
+Erroneous code example:
+
+    static ZERO: int = 0;
+
+    fn is_zero(x: int) -> bool {
+        match x {
+            ref ZERO => true,
+            _ => false,
+        }
+    }
+
+Corrected code example:
+
+    static ZERO: int = 0;
+
+    fn is_zero(x: int) -> bool {
+        match x {
+            ZERO => true,
+            _ => false,
+        }
+    }
+"##,
+
+// Type checking
+
+E0024: r##"
+A reference type is missing its lifetime in a place where it can't be
+inferred, such as the field of a structure. Declare a lifetime parameter
+and name it.
+
+Erroneous code example:
+
+    struct Parser { input: &str }
+
+Corrected code example:
+
+    struct Parser<'a> { input: &'a str }
+"##,
+
+E0025: r##"
+A type was given a different number of lifetime parameters than it declares.
+
+Erroneous code example:
+
+    struct Parser<'a> { input: &'a str }
+
+    fn rest<'a, 'b>(p: Parser<'a, 'b>) -> &'a str { p.input }
+
+Corrected code example:
+
+    struct Parser<'a> { input: &'a str }
+
+    fn rest<'a>(p: Parser<'a>) -> &'a str { p.input }
+"##,
+
+E0026: r##"
+A type parameter which has a default was given explicitly. Defaults for type
+parameters are experimental and must be enabled with a feature attribute.
+
+Erroneous code example:
+
+    struct Table<K, V = ()> { keys: Vec<K>, values: Vec<V> }
+
+    fn lookup(t: &Table<int, ~str>) {}
+
+Corrected code example:
+
+    #![feature(default_type_params)]
+
+    struct Table<K, V = ()> { keys: Vec<K>, values: Vec<V> }
+
+    fn lookup(t: &Table<int, ~str>) {}
+"##,
+
+E0027: r##"
+`str` or `[T]` was used on its own as a type. Their size isn't known, so
+they can only be used behind a pointer, as `&str`, `~str`, `&[T]` or `~[T]`.
+
+Erroneous code example:
+
+    fn greet(name: str) {}
+
+Corrected code example:
+
+    fn greet(name: &str) {}
+"##,
+
+E0028: r##"
+A managed vector, string or closure was used. Only managed boxes are
+supported; use `Vec<T>` or `~str` inside the box instead.
+
+Erroneous code example:
+
+    fn names() -> @[~str] { @[] }
+
+Corrected code example:
+
+    fn names() -> @Vec<~str> { @Vec::new() }
+"##,
+
+E0029: r##"
+A trait was used where a type is expected. A value of some type implementing
+a trait is used through a pointer to a trait object, such as `~Trait` or
+`&Trait`.
+
+Erroneous code example:
+
+    trait Shape { fn area(&self) -> f64; }
+
+    fn total(s: Shape) -> f64 { s.area() }
+
+Corrected code example:
+
+    trait Shape { fn area(&self) -> f64; }
+
+    fn total(s: &Shape) -> f64 { s.area() }
+"##,
+
+E0030: r##"
+A function with a variadic argument list (`...`) used a calling convention
+other than C's, the only one which supports them.
+
+Erroneous code example:
+
+    extern "stdcall" {
+        fn printf(format: *u8, ...) -> int;
+    }
+
+Corrected code example:
+
+    extern "C" {
+        fn printf(format: *u8, ...) -> int;
+    }
+"##,
+
+E0031: r##"
+Kind bounds such as `:Send` were given on a type which isn't a trait object.
+Such bounds only restrict what a trait object may contain; for other types
+they are known from the type itself.
+
+Erroneous code example:
+
+    fn spawn_with(v: Vec<int>:Send) {}
+
+Corrected code example:
+
+    fn spawn_with(v: Vec<int>) {}
+"##,
+
+E0032: r##"
+An expression doesn't have the type its context requires, for example the
+type of a variable, the argument of a function, or the other branch of an
+`if`. Integers and floats are never converted implicitly; use `as` to
+convert between numeric types.
+
+Erroneous code example:
+
+    fn main() {
+        let count: int = "three";
+    }
+
+Corrected code example:
+
+    fn main() {
+        let count: int = 3;
+    }
+"##,
+
+E0033: r##"
+The `main` function, or the function marked `#[start]`, declared type
+parameters. The runtime calls these functions directly, so they can't be
+generic.
+
+Erroneous code example:
+
+    fn main<T>() {}
+
+Corrected code example:
+
+    fn main() {}
+"##,
+
+E0034: r##"
+A reference is required to live longer than the data it points to. This
+happens, for example, when a function returns a reference with a longer
+lifetime than the one it was given, or when a reference into a local
+variable is stored somewhere that outlives the variable.
+
+Erroneous code example:
+
+    fn first<'a>(v: &'a [int]) -> &'static int { &v[0] }
+
+Corrected code example:
+
+    fn first<'a>(v: &'a [int]) -> &'a int { &v[0] }
+"##,
+
+E0035: r##"
+The compiler couldn't find a lifetime which satisfies all the requirements
+on a reference at once: it must live at least as long as one region but no
+longer than another which is shorter. The notes following the error point at
+the two requirements. Usually a signature needs to say how the lifetimes of
+its arguments and its result are related.
+
+Erroneous code example:
+
+    fn longest<'a, 'b>(x: &'a str, y: &'b str) -> &'a str {
+        if x.len() > y.len() { x } else { y }
+    }
+
+Corrected code example:
+
+    fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+        if x.len() > y.len() { x } else { y }
+    }
+"##,
+
+E0036: r##"
+An inherent `impl` (one without a trait) was written for a type defined in
+another crate, or for a type which isn't a structure, enum or trait object.
+Methods can only be added directly to the crate's own nominal types; to add
+methods to other types, define a trait and implement it for them.
+
+Erroneous code example:
+
+    impl Vec<int> {
+        fn total(&self) -> int { self.iter().fold(0, |a, &b| a + b) }
+    }
+
+Corrected code example:
+
+    trait Total { fn total(&self) -> int; }
+
+    impl Total for Vec<int> {
+        fn total(&self) -> int { self.iter().fold(0, |a, &b| a + b) }
+    }
+"##,
+
+E0037: r##"
+A trait defined in another crate was implemented for a type which is also
+defined in another crate. At least one of the two must be defined in the
+crate containing the `impl`, so that two crates can't provide conflicting
+implementations. Wrap the type in a new structure to implement the trait.
+
+Erroneous code example:
+
+    impl Iterator<char> for ~str {
+        fn next(&mut self) -> Option<char> { None }
+    }
+
+Corrected code example:
+
+    struct Chars { s: ~str }
+
+    impl Iterator<char> for Chars {
+        fn next(&mut self) -> Option<char> { None }
+    }
+"##,
+
+E0038: r##"
+A trait was implemented twice for the same type, so it isn't known which
+implementation to use.
+
+Erroneous code example:
+
+    trait Describe { fn describe(&self) -> ~str; }
+
+    impl Describe for int { fn describe(&self) -> ~str { ~"int" } }
+    impl<T> Describe for T { fn describe(&self) -> ~str { ~"T" } }
+
+Corrected code example:
+
+    trait Describe { fn describe(&self) -> ~str; }
+
+    impl Describe for int { fn describe(&self) -> ~str { ~"int" } }
+    impl Describe for uint { fn describe(&self) -> ~str { ~"uint" } }
+"##,
+
+E0039: r##"
+The `Drop` trait was implemented for something other than a structure.
+
+Erroneous code example:
+
+    enum Handle { Open(int), Closed }
+
+    impl Drop for Handle { fn drop(&mut self) {} }
+
+Wrap the enum in a structure and implement `Drop` for the structure.
+
+Corrected code example:
+
+    enum State { Open(int), Closed }
+    struct Handle { state: State }
+
+    impl Drop for Handle { fn drop(&mut self) {} }
+"##,
+
+E0040: r##"
+A method call could refer to methods of several traits in scope which are
+all implemented for the receiver.
+
+Erroneous code example:
+
+    trait Area { fn size(&self) -> int; }
+    trait Volume { fn size(&self) -> int; }
+
+    impl Area for int { fn size(&self) -> int { *self } }
+    impl Volume for int { fn size(&self) -> int { *self } }
+
+    fn main() { 3.size(); }
+
+Rename one of the methods, or only import one of the traits.
+
+Corrected code example:
+
+    trait Area { fn area(&self) -> int; }
+    trait Volume { fn volume(&self) -> int; }
+
+    impl Area for int { fn area(&self) -> int { *self } }
+    impl Volume for int { fn volume(&self) -> int { *self } }
+
+    fn main() { 3.area(); }
+"##,
+
+E0041: r##"
+A value was cast to a trait object from a pointer of the wrong kind. Only a
+`~` pointer can be cast to a `~Trait` and only a `&` pointer to a `&Trait`,
+and a `&Trait` can't be mutable unless the pointer is.
+
+Erroneous code example:
+
+    trait Shape {}
+    impl Shape for int {}
+
+    fn main() {
+        let x = 5;
+        let s = &x as ~Shape;
+    }
+
+Corrected code example:
+
+    trait Shape {}
+    impl Shape for int {}
+
+    fn main() {
+        let s = ~5 as ~Shape;
+    }
+"##,
+
+E0042: r##"
+The type of an expression or a variable couldn't be inferred, because
+nothing constrains it. Add a type annotation.
+
+Erroneous code example:
+
+    fn main() {
+        let names = Vec::new();
+    }
+
+Corrected code example:
+
+    fn main() {
+        let names: Vec<~str> = Vec::new();
+    }
+"##,
+
+E0043: r##"
+A bare function which is only known by its value, for example a variable of
+type `fn()`, was used where a closure is expected. Only functions named
+directly can be turned into closures.
+
+Erroneous code example:
+
+    fn hello() {}
+
+    fn main() {
+        let f: fn() = hello;
+        let g: || = f;
+    }
+
+Corrected code example:
+
+    fn hello() {}
+
+    fn main() {
+        let f: fn() = hello;
+        let g: || = || f();
+    }
+"##,
+
+E0044: r##"
+A pattern for an enum variant or tuple structure has a different number of
+fields than the variant or structure.
+
+Erroneous code example:
+
+    enum Shape { Rect(int, int) }
+
+    fn width(s: Shape) -> int {
+        match s { Rect(w) => w }
+    }
+
+Corrected code example:
+
+    enum Shape { Rect(int, int) }
+
+    fn width(s: Shape) -> int {
+        match s { Rect(w, _) => w }
+    }
+"##,
+
+E0045: r##"
+A structure pattern mentioned the same field twice.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn get_x(p: Point) -> int {
+        let Point { x: a, x: b } = p;
+        a
+    }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn get_x(p: Point) -> int {
+        let Point { x: a, y: _ } = p;
+        a
+    }
+"##,
+
+E0046: r##"
+A structure pattern mentioned a field which the structure doesn't have.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn get_x(p: Point) -> int {
+        let Point { x: a, z: _ } = p;
+        a
+    }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn get_x(p: Point) -> int {
+        let Point { x: a, y: _ } = p;
+        a
+    }
+"##,
+
+E0047: r##"
+A structure pattern doesn't mention every field of the structure. List the
+missing fields, or end the pattern with `..` to ignore the remaining ones.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn get_x(p: Point) -> int {
+        let Point { x: a } = p;
+        a
+    }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn get_x(p: Point) -> int {
+        let Point { x: a, .. } = p;
+        a
+    }
+"##,
+
+E0048: r##"
+A range pattern is invalid: its bounds aren't numbers or characters, or its
+lower bound is greater than its upper one.
+
+Erroneous code example:
+
+    fn is_digit(c: char) -> bool {
+        match c {
+            '9' .. '0' => true,
+            _ => false,
+        }
+    }
+
+Corrected code example:
+
+    fn is_digit(c: char) -> bool {
+        match c {
+            '0' .. '9' => true,
+            _ => false,
+        }
+    }
+"##,
+
+E0049: r##"
+A function with a foreign calling convention, or a function declared in an
+`extern` block, has type parameters. Foreign code can't be generic, so it
+can't be called or defined generically either.
+
+Erroneous code example:
+
+    extern {
+        fn free<T>(p: *T);
+    }
+
+Corrected code example:
+
+    extern {
+        fn free(p: *u8);
+    }
+"##,
+
+E0050: r##"
+An implementation of a trait defined a method which the trait doesn't
+declare.
+
+Erroneous code example:
+
+    trait Shape { fn area(&self) -> f64; }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 { self.side * self.side }
+        fn side(&self) -> f64 { self.side }
+    }
+
+Put methods which aren't part of the trait in an inherent `impl`.
+
+Corrected code example:
+
+    trait Shape { fn area(&self) -> f64; }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 { self.side * self.side }
+    }
+
+    impl Square {
+        fn side(&self) -> f64 { self.side }
+    }
+"##,
+
+E0051: r##"
+An implementation of a trait doesn't define all of the trait's methods
+which have no default body.
+
+Erroneous code example:
+
+    trait Shape {
+        fn area(&self) -> f64;
+        fn perimeter(&self) -> f64;
+    }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 { self.side * self.side }
+    }
+
+Corrected code example:
+
+    trait Shape {
+        fn area(&self) -> f64;
+        fn perimeter(&self) -> f64;
+    }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 { self.side * self.side }
+        fn perimeter(&self) -> f64 { self.side * 4.0 }
+    }
+"##,
+
+E0052: r##"
+A method takes `self` in its implementation but not in the trait, or the
+other way around. The implementation must take `self` exactly when the trait
+declaration does.
+
+Erroneous code example:
+
+    trait Shape { fn unit() -> Self; }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn unit(&self) -> Square { Square { side: 1.0 } }
+    }
+
+Corrected code example:
+
+    trait Shape { fn unit() -> Self; }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn unit() -> Square { Square { side: 1.0 } }
+    }
+"##,
+
+E0053: r##"
+A method in an implementation of a trait has a different number of type
+parameters than its declaration in the trait.
+
+Erroneous code example:
+
+    trait Store { fn put<T>(&mut self, x: T); }
+
+    struct Null;
+
+    impl Store for Null {
+        fn put(&mut self, x: int) {}
+    }
+
+Corrected code example:
+
+    trait Store { fn put<T>(&mut self, x: T); }
+
+    struct Null;
+
+    impl Store for Null {
+        fn put<T>(&mut self, x: T) {}
+    }
+"##,
+
+E0054: r##"
+A method in an implementation of a trait has a different number of
+arguments than its declaration in the trait.
+
+Erroneous code example:
+
+    trait Shape { fn scale(&mut self, by: f64); }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn scale(&mut self) { self.side *= 2.0 }
+    }
+
+Corrected code example:
+
+    trait Shape { fn scale(&mut self, by: f64); }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn scale(&mut self, by: f64) { self.side *= by }
+    }
+"##,
+
+E0055: r##"
+A type parameter of a method in an implementation of a trait has different
+bounds than the same type parameter in the trait's declaration.
+
+Erroneous code example:
+
+    trait Store { fn put<T: Clone>(&mut self, x: T); }
+
+    struct Null;
+
+    impl Store for Null {
+        fn put<T: Clone + Send>(&mut self, x: T) {}
+    }
+
+Corrected code example:
+
+    trait Store { fn put<T: Clone>(&mut self, x: T); }
+
+    struct Null;
+
+    impl Store for Null {
+        fn put<T: Clone>(&mut self, x: T) {}
+    }
+"##,
+
+E0056: r##"
+A method in an implementation of a trait has a different type than the
+trait declares for it, in its arguments, its result or its `self`.
+
+Erroneous code example:
+
+    trait Shape { fn area(&self) -> f64; }
+
+    struct Square { side: int }
+
+    impl Shape for Square {
+        fn area(&self) -> int { self.side * self.side }
+    }
+
+Corrected code example:
+
+    trait Shape { fn area(&self) -> f64; }
+
+    struct Square { side: int }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 { (self.side * self.side) as f64 }
+    }
+"##,
+
+E0057: r##"
+Looking up a method or field dereferenced the receiver over and over without
+finding it, because its type dereferences to itself, directly or through
+other types.
+
+Erroneous code example:
+
+    struct Node;
+
+    impl Deref<Node> for Node {
+        fn deref<'a>(&'a self) -> &'a Node { self }
+    }
+
+    fn main() { Node.value; }
+
+Corrected code example:
+
+    struct Node { value: int }
+
+    fn main() { Node { value: 0 }.value; }
+"##,
+
+E0058: r##"
+A path gave a generic item or method more type parameters than it declares,
+or fewer than it requires.
+
+Erroneous code example:
+
+    fn convert<T>(x: int) -> Option<T> { None }
+
+    fn main() {
+        let x = convert::<int, uint>(5);
+    }
+
+Corrected code example:
+
+    fn convert<T>(x: int) -> Option<T> { None }
+
+    fn main() {
+        let x = convert::<int>(5);
+    }
+"##,
+
+E0059: r##"
+A function was called with a different number of arguments than it takes,
+or a variadic function with fewer than its fixed ones.
+
+Erroneous code example:
+
+    fn add(x: int, y: int) -> int { x + y }
+
+    fn main() { add(1); }
+
+Corrected code example:
+
+    fn add(x: int, y: int) -> int { x + y }
+
+    fn main() { add(1, 2); }
+"##,
+
+E0060: r##"
+A structure expression gave the same field twice.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn main() { let p = Point { x: 0, x: 1, y: 0 }; }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn main() { let p = Point { x: 0, y: 0 }; }
+"##,
+
+E0061: r##"
+A structure expression doesn't give every field of the structure. Give all
+of them, or take the remaining ones from another value of the structure with
+`..`.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn main() { let p = Point { x: 0 }; }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let p = Point { x: 0, y: 0 };
+        let q = Point { x: 1, ..p };
+    }
+"##,
+
+E0062: r##"
+`box(GC)` was used in a crate which defines the `managed_heap` lang item but
+not the `gc` one, the type of the boxes it allocates. This only happens in
+crates which provide their own lang items instead of using libstd's.
+
+Erroneous code example:
+
+    #![no_std]
+    #![feature(lang_items)]
+
+    #[lang="managed_heap"]
+    pub static GC: () = ();
+
+    fn main() { let x = box(GC) 5; }
+
+Corrected code example:
+
+    #![no_std]
+    #![feature(lang_items)]
+
+    #[lang="managed_heap"]
+    pub static GC: () = ();
+
+    #[lang="gc"]
+    pub struct Gc<T> { ptr: @T }
+
+    fn main() { let x = box(GC) 5; }
+"##,
+
+E0063: r##"
+A `box` expression named a place to allocate the value in which isn't
+supported. Only the exchange heap (the default, `box(HEAP)`) and the managed
+heap (`box(GC)`) can be used.
+
+Erroneous code example:
+
+    static ARENA: int = 0;
+
+    fn main() { let x = box(ARENA) 5; }
+
+Corrected code example:
+
+    fn main() { let x = box 5; }
+"##,
+
+E0064: r##"
+The left-hand side of an assignment isn't something that can be assigned
+to. Only variables, fields, indexing expressions and dereferences can be.
+
+Erroneous code example:
+
+    fn reset() -> int { 0 }
+
+    fn main() { reset() = 1; }
+
+Corrected code example:
+
+    fn main() {
+        let mut count = 0;
+        count = 1;
+    }
+"##,
+
+E0065: r##"
+A tuple-like structure with a single field was dereferenced with `*`, which
+no longer gives its field. Use a pattern to get at the field instead.
+
+Erroneous code example:
+
+    struct Meters(int);
+
+    fn value(m: Meters) -> int { *m }
+
+Corrected code example:
+
+    struct Meters(int);
+
+    fn value(m: Meters) -> int {
+        let Meters(v) = m;
+        v
+    }
+"##,
+
+E0066: r##"
+`return` without a value was used in a function which returns something.
+
+Erroneous code example:
+
+    fn parse(s: &str) -> int {
+        if s.is_empty() { return; }
+        1
+    }
+
+Corrected code example:
+
+    fn parse(s: &str) -> int {
+        if s.is_empty() { return 0; }
+        1
+    }
+"##,
+
+E0067: r##"
+A value was cast to `bool`. Casts to `bool` aren't supported; compare the
+value with zero instead.
+
+Erroneous code example:
+
+    fn is_set(flags: u8) -> bool { flags as bool }
+
+Corrected code example:
+
+    fn is_set(flags: u8) -> bool { flags != 0 }
+"##,
+
+E0068: r##"
+A structure or enum contains itself directly, so it would have an infinite
+size. Put the inner value behind a pointer, such as `~`, to give it a known
+size.
+
+Erroneous code example:
+
+    struct List { head: int, tail: Option<List> }
+
+Corrected code example:
+
+    struct List { head: int, tail: Option<~List> }
+"##,
+
+E0069: r##"
+A structure or enum always contains another value of its own type, so no
+value of it could ever be built. Make the inner value optional.
+
+Erroneous code example:
+
+    struct List { head: int, tail: ~List }
+
+Corrected code example:
+
+    struct List { head: int, tail: Option<~List> }
+"##,
+
+E0070: r##"
+A structure marked `#[simd]` isn't a valid SIMD vector: it must not be
+generic, must have at least one field, and all of its fields must be of the
+same machine type, such as `f32` or `u8`.
+
+Erroneous code example:
+
+    #![feature(simd)]
+
+    #[simd]
+    struct Pixel(u8, u8, u8, u16);
+
+Corrected code example:
+
+    #![feature(simd)]
+
+    #[simd]
+    struct Pixel(u8, u8, u8, u8);
+"##,
+
+E0071: r##"
+The discriminant of an enum variant isn't a constant integer expression.
+
+Erroneous code example:
+
+    fn code() -> int { 3 }
+
+    enum Status { Ok = 0, Failed = code() }
+
+Corrected code example:
+
+    static FAILED: int = 3;
+
+    enum Status { Ok = 0, Failed = FAILED }
+"##,
+
+E0072: r##"
+Two variants of an enum have the same discriminant. Variants without an
+explicit discriminant take the previous one plus one, which can also
+collide.
+
+Erroneous code example:
+
+    enum Status { Ok = 0, Failed = 1, Unknown = 0 }
+
+Corrected code example:
+
+    enum Status { Ok = 0, Failed = 1, Unknown = 2 }
+"##,
+
+E0073: r##"
+The discriminant of an enum variant doesn't fit in the integer type given
+with `#[repr]`.
+
+Erroneous code example:
+
+    #[repr(u8)]
+    enum Status { Ok = 0, Failed = 256 }
+
+Corrected code example:
+
+    #[repr(u16)]
+    enum Status { Ok = 0, Failed = 256 }
+"##,
+
+E0074: r##"
+An integer representation was given with `#[repr]` to an enum with only one
+variant, or none, which isn't supported.
+
+Erroneous code example:
+
+    #[repr(u8)]
+    enum Status { Ok }
+
+Corrected code example:
+
+    #[repr(u8)]
+    enum Status { Ok, Failed }
+"##,
+
+E0075: r##"
+A type alias declared a type parameter which the aliased type doesn't use.
+
+Erroneous code example:
+
+    type Index<T> = uint;
+
+Corrected code example:
+
+    type Index = uint;
+"##,
+
+E0076: r##"
+A function declared in an `extern "rust-intrinsic"` block isn't a known
+intrinsic. Intrinsics are provided by the compiler itself, so only its own
+are available.
+
+Erroneous code example:
+
+    extern "rust-intrinsic" {
+        fn frobnicate();
+    }
+
+Corrected code example:
+
+    extern "rust-intrinsic" {
+        fn abort() -> !;
+    }
+"##,
+
+E0077: r##"
+An intrinsic was declared with a different number of type parameters than
+the compiler expects for it.
+
+Erroneous code example:
+
+    extern "rust-intrinsic" {
+        fn size_of() -> uint;
+    }
+
+Corrected code example:
+
+    extern "rust-intrinsic" {
+        fn size_of<T>() -> uint;
+    }
+"##,
+
+E0078: r##"
+A method was called through a trait object which can't be called that way:
+one without a receiver, one taking `self` by value, a generic one, or one
+whose type mentions `Self`. The type of the value behind the object isn't
+known, so these methods can't be called through it.
+
+Erroneous code example:
+
+    trait Shape {
+        fn area(&self) -> f64;
+        fn scaled<T>(&self, by: T) -> f64;
+    }
+
+    fn scale(s: &Shape) -> f64 { s.scaled(2) }
+
+Corrected code example:
+
+    trait Shape {
+        fn area(&self) -> f64;
+        fn scaled(&self, by: f64) -> f64;
+    }
+
+    fn scale(s: &Shape) -> f64 { s.scaled(2.0) }
+"##,
+
+E0079: r##"
+A destructor was called explicitly. Destructors run when a value goes out of
+scope; call `drop` to destroy a value earlier.
+
+Erroneous code example:
+
+    struct File { fd: int }
+
+    impl Drop for File { fn drop(&mut self) {} }
+
+    fn main() {
+        let mut f = File { fd: 0 };
+        f.drop();
+    }
+
+Corrected code example:
+
+    struct File { fd: int }
+
+    impl Drop for File { fn drop(&mut self) {} }
+
+    fn main() {
+        let f = File { fd: 0 };
+        drop(f);
+    }
+"##,
+
+E0080: r##"
+The type placeholder `_` was used in the signature of an item. Types are
+only inferred inside function bodies, so signatures must be spelled out.
+
+Erroneous code example:
+
+    fn answer() -> _ { 42 }
+
+Corrected code example:
+
+    fn answer() -> int { 42 }
+"##,
+
+E0081: r##"
+A trait named the same supertrait twice.
+
+Erroneous code example:
+
+    trait Shape: Clone + Clone {}
+
+Corrected code example:
+
+    trait Shape: Clone {}
+"##,
+
+E0082: r##"
+An implementation of a trait defined the same method twice.
+
+Erroneous code example:
+
+    trait Shape { fn area(&self) -> f64; }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 { self.side * self.side }
+        fn area(&self) -> f64 { 0.0 }
+    }
+
+Corrected code example:
+
+    trait Shape { fn area(&self) -> f64; }
+
+    struct Square { side: f64 }
+
+    impl Shape for Square {
+        fn area(&self) -> f64 { self.side * self.side }
+    }
+"##,
+
+E0083: r##"
+The type parameters of a structure, enum or type alias were given trait
+bounds, which are only allowed on functions, traits and impls.
+
+Erroneous code example:
+
+    struct Wrapper<T: Clone> { value: T }
+
+Put the bounds on the functions and impls using the type instead.
+
+Corrected code example:
+
+    struct Wrapper<T> { value: T }
+
+    impl<T: Clone> Wrapper<T> {
+        fn get(&self) -> T { self.value.clone() }
+    }
+"##,
+
+E0084: r##"
+A builtin kind such as `Send`, `Share`, `Copy` or `Sized` was implemented
+explicitly. The compiler decides which types have these kinds from what they
+contain.
+
+Erroneous code example:
+
+    struct Handle { fd: int }
+
+    impl Send for Handle {}
+
+Corrected code example:
+
+    struct Handle { fd: int }
+"##,
+
+E0085: r##"
+A function declared in an `extern` block used a pattern for an argument.
+Foreign functions have no body to bind the pattern in, so only plain names
+are allowed.
+
+Erroneous code example:
+
+    extern {
+        fn move_to((x, y): (int, int));
+    }
+
+Corrected code example:
+
+    extern {
+        fn move_to(x: int, y: int);
+    }
+"##,
+
+// Borrow checking
+
+E0086: r##"
+A value was moved out of a place which doesn't own it, such as the contents
+of a `&` pointer, an element of a vector, or a static. Moving it would leave
+the place holding a value which has been moved away. Borrow the value or
+clone it instead.
+
+Erroneous code example:
+
+    fn first(v: &Vec<~str>) -> ~str {
+        let s = *v.get(0);
+        s
+    }
+
+Corrected code example:
+
+    fn first(v: &Vec<~str>) -> ~str {
+        let s = v.get(0).clone();
+        s
+    }
+"##,
+
+E0087: r##"
+A field was moved out of a value whose type implements `Drop`. Its
+destructor will still run on the whole value, so none of its fields may be
+moved out.
+
+Erroneous code example:
+
+    struct Message { body: ~str }
+
+    impl Drop for Message { fn drop(&mut self) {} }
+
+    fn body(m: Message) -> ~str { m.body }
+
+Corrected code example:
+
+    struct Message { body: ~str }
+
+    impl Drop for Message { fn drop(&mut self) {} }
+
+    fn body(m: Message) -> ~str { m.body.clone() }
+"##,
+
+E0088: r##"
+An immutable static whose type allows mutation through a shared reference,
+such as an atomic integer, was borrowed. Immutable statics may be placed in
+read-only memory, so they can't be mutated this way. Use a `static mut`
+instead.
+
+Erroneous code example:
+
+    use std::sync::atomics::{AtomicInt, INIT_ATOMIC_INT, SeqCst};
+
+    static COUNT: AtomicInt = INIT_ATOMIC_INT;
+
+    fn bump() { COUNT.fetch_add(1, SeqCst); }
+
+Corrected code example:
+
+    use std::sync::atomics::{AtomicInt, INIT_ATOMIC_INT, SeqCst};
+
+    static mut COUNT: AtomicInt = INIT_ATOMIC_INT;
+
+    fn bump() { unsafe { COUNT.fetch_add(1, SeqCst); } }
+"##,
+
+E0089: r##"
+An immutable variable or field was borrowed mutably, or assigned from a
+closure. Declare the variable `mut`, or borrow the value immutably.
+
+Erroneous code example:
+
+    fn main() {
+        let v = Vec::new();
+        let r = &mut v;
+        r.push(1);
+    }
+
+Corrected code example:
+
+    fn main() {
+        let mut v = Vec::new();
+        let r = &mut v;
+        r.push(1);
+    }
+"##,
+
+E0090: r##"
+A value inside a managed box was borrowed for longer than the compiler can
+keep the box alive. Borrow it for a shorter scope, or copy the value out of
+the box.
+
+Erroneous code example:
+
+    fn value(b: @int) -> &int { &*b }
+
+Corrected code example:
+
+    fn value(b: @int) -> int { *b }
+"##,
+
+E0091: r##"
+A value was borrowed for longer than it lives. The value is destroyed at the
+end of its scope, but the reference to it is still in use after that. Move
+the value to an outer scope, or return it instead of a reference to it.
+
+Erroneous code example:
+
+    fn main() {
+        let r;
+        {
+            let x = 5;
+            r = &x;
+        }
+        println!("{}", *r);
+    }
+
+Corrected code example:
+
+    fn main() {
+        let x = 5;
+        let r;
+        {
+            r = &x;
+        }
+        println!("{}", *r);
+    }
+"##,
+
+E0092: r##"
+The contents of a pointer were borrowed for longer than the pointer itself
+is guaranteed to point to them.
+
+Erroneous code example:
+
+    fn inner<'a, 'b>(x: &'a &'b int) -> &'b int { &**x }
+
+Corrected code example:
+
+    fn inner<'a, 'b>(x: &'a &'b int) -> &'b int { *x }
+"##,
+
+E0093: r##"
+A variable was used before anything was assigned to it on some path through
+the function.
+
+Erroneous code example:
+
+    fn main() {
+        let x: int;
+        if true { x = 1; }
+        println!("{}", x);
+    }
+
+Corrected code example:
+
+    fn main() {
+        let x: int;
+        if true { x = 1; } else { x = 0; }
+        println!("{}", x);
+    }
+"##,
+
+E0094: r##"
+A value was used after it was moved somewhere else. Values of types which
+aren't implicitly copyable, such as `~T` or `~str`, move when they are
+assigned or passed by value, and the old place can't be used afterwards.
+Clone the value or borrow it instead.
+
+Erroneous code example:
+
+    fn consume(s: ~str) {}
+
+    fn main() {
+        let s = ~"hello";
+        consume(s);
+        println!("{}", s);
+    }
+
+Corrected code example:
+
+    fn consume(s: ~str) {}
+
+    fn main() {
+        let s = ~"hello";
+        consume(s.clone());
+        println!("{}", s);
+    }
+"##,
+
+E0095: r##"
+An immutable local variable was assigned to more than once.
+
+Erroneous code example:
+
+    fn main() {
+        let x = 1;
+        x = 2;
+    }
+
+Corrected code example:
+
+    fn main() {
+        let mut x = 1;
+        x = 2;
+    }
+"##,
+
+E0096: r##"
+Something in an aliasable location, such as behind a `&` pointer, in a
+managed box or in a static, was mutated or borrowed mutably. Other pointers
+may see the same value, so it can't be changed through this one.
+
+Erroneous code example:
+
+    fn push(v: &Vec<int>) {
+        let r = &mut *v;
+        r.push(1);
+    }
+
+Corrected code example:
+
+    fn push(v: &mut Vec<int>) {
+        let r = &mut *v;
+        r.push(1);
+    }
+"##,
+
+E0097: r##"
+A value was borrowed while an earlier, conflicting borrow of it is still in
+use: a mutable borrow while any other borrow exists, or any borrow while a
+mutable one exists.
+
+Erroneous code example:
+
+    fn main() {
+        let mut v = vec!(1, 2, 3);
+        let first = v.get(0);
+        v.push(4);
+    }
+
+Corrected code example:
+
+    fn main() {
+        let mut v = vec!(1, 2, 3);
+        let first = *v.get(0);
+        v.push(4);
+    }
+"##,
+
+E0098: r##"
+Something immutable was assigned to, such as an immutable field, the
+contents of a `&` pointer, or an immutable static.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn reset(p: &Point) { p.x = 0; }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn reset(p: &mut Point) { p.x = 0; }
+"##,
+
+E0099: r##"
+A value was assigned to while it is borrowed. The borrow must end before the
+value can be changed.
+
+Erroneous code example:
+
+    fn main() {
+        let mut x = 5;
+        let r = &x;
+        x = 6;
+        println!("{}", *r);
+    }
+
+Corrected code example:
+
+    fn main() {
+        let mut x = 5;
+        {
+            let r = &x;
+            println!("{}", *r);
+        }
+        x = 6;
+    }
+"##,
+
+E0100: r##"
+A value was moved, possibly into a closure, while it is borrowed. The borrow
+must end before the value can be moved.
+
+Erroneous code example:
+
+    fn consume(s: ~str) {}
+
+    fn main() {
+        let s = ~"hello";
+        let r = &s;
+        consume(s);
+        println!("{}", *r);
+    }
+
+Corrected code example:
+
+    fn consume(s: ~str) {}
+
+    fn main() {
+        let s = ~"hello";
+        {
+            let r = &s;
+            println!("{}", *r);
+        }
+        consume(s);
+    }
+"##,
+
+// Associated types
+
+E0101: r##"
+The associated types of a trait can only be left out of a bound on the type
+parameter of a function or method, where they are inferred. Everywhere else,
+such as in the bounds of a type or impl and in trait objects, they must be
+given as trailing type parameters of the trait.
+
+Erroneous code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+        fn first(&self) -> Self::Item;
+    }
+
+    fn first_of(c: &Container) {}
+
+Corrected code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+        fn first(&self) -> Self::Item;
+    }
+
+    fn first_of(c: &Container<int>) {}
+"##,
+
+E0102: r##"
+An impl of a trait must bind every associated type of the trait which has no
+default.
+
+Erroneous code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+        fn first(&self) -> Self::Item;
+    }
+
+    impl Container for Vec<int> {
+        fn first(&self) -> int { *self.get(0) }
+    }
+
+Corrected code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+        fn first(&self) -> Self::Item;
+    }
+
+    impl Container for Vec<int> {
+        type Item = int;
+        fn first(&self) -> int { *self.get(0) }
+    }
+"##,
+
+E0103: r##"
+An impl bound an associated type which the trait does not declare.
+
+Erroneous code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+    }
+
+    impl Container for Vec<int> {
+        type Item = int;
+        type Index = uint;
+    }
+
+Corrected code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+    }
+
+    impl Container for Vec<int> {
+        type Item = int;
+    }
+"##,
+
+E0104: r##"
+An impl bound the same associated type more than once.
+
+Erroneous code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+    }
+
+    impl Container for Vec<int> {
+        type Item = int;
+        type Item = uint;
+    }
+
+Corrected code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+    }
+
+    impl Container for Vec<int> {
+        type Item = int;
+    }
+"##,
+
+E0105: r##"
+A path `T::Name` or `Self::Name` referred to an associated type which none of
+the traits bounding `T`, or the enclosing trait, declare. Within a list of type
+parameters, `T::Name` can also only be used after the bounds of `T`.
+
+Erroneous code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+        fn first(&self) -> Self::Item;
+    }
+
+    fn first_of<C: Container>(c: &C) -> C::Elem { c.first() }
+
+Corrected code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+        fn first(&self) -> Self::Item;
+    }
+
+    fn first_of<C: Container>(c: &C) -> C::Item { c.first() }
+"##,
+
+E0106: r##"
+A path `T::Name` referred to an associated type which several of the traits
+bounding `T` declare, so it is not clear which one is meant. Bound `T` by one
+of the traits and give the associated type of the other explicitly.
+
+Erroneous code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+        fn first(&self) -> Self::Item;
+    }
+
+    trait Stream {
+        type Item;
+        fn next(&mut self) -> Self::Item;
+    }
+
+    fn first_of<C: Container + Stream>(c: &C) -> C::Item { c.first() }
+
+Corrected code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+        fn first(&self) -> Self::Item;
+    }
+
+    trait Stream {
+        type Item;
+        fn next(&mut self) -> Self::Item;
+    }
+
+    fn first_of<I, C: Container + Stream<I>>(c: &C) -> C::Item { c.first() }
+"##,
+
+// Where clauses
+
+E0107: r##"
+A where clause on an impl or a trait can only bound a type parameter declared
+by the impl or trait, or an associated type of such a parameter, `T::Name`.
+Only the where clauses of functions and methods can bound other types, so the
+bound should be moved onto the methods which need it.
+
+Erroneous code example:
+
+    struct Wrapper<T> { v: Vec<T> }
+
+    impl<T> Wrapper<T> where Vec<T>: Show {
+        fn show(&self) { println!("{}", self.v); }
+    }
+
+Corrected code example:
+
+    struct Wrapper<T> { v: Vec<T> }
+
+    impl<T> Wrapper<T> {
+        fn show(&self) where Vec<T>: Show { println!("{}", self.v); }
+    }
+"##,
+
+E0108: r##"
+A where clause on an impl or a trait bounded `T::Name`, but the bounds of `T`
+already give that associated type, so there is no type parameter to bound.
+Associated types are only type parameters of their own in the bounds of
+functions and methods; elsewhere the bound on the associated type should be
+put on the type given for it instead.
+
+Erroneous code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+    }
+
+    struct Wrapper<C>;
+
+    impl<I, C: Container<I>> Wrapper<C> where C::Item: Clone {
+        fn new() -> Wrapper<C> { Wrapper }
+    }
+
+Corrected code example:
+
+    #![feature(associated_types)]
+
+    trait Container {
+        type Item;
+    }
+
+    struct Wrapper<C>;
+
+    impl<I: Clone, C: Container<I>> Wrapper<C> {
+        fn new() -> Wrapper<C> { Wrapper }
+    }
+"##,
+
+// Type checking, continued
+
+E0109: r##"
+A value was passed through the variadic part of an argument list as a type
+which C would promote to another one: `f32` is passed as a `c_double`, and
+`i8`, `u8`, `i16`, `u16` and `bool` as a `c_int` or `c_uint`. Rust doesn't
+promote them implicitly; cast the value instead.
+
+Erroneous code example:
+
+    extern {
+        fn printf(format: *u8, ...) -> i32;
+    }
+
+    fn print(x: f32) {
+        unsafe { printf("%f\n\0".as_ptr(), x); }
+    }
+
+Corrected code example:
+
+    extern {
+        fn printf(format: *u8, ...) -> i32;
+    }
+
+    fn print(x: f32) {
+        unsafe { printf("%f\n\0".as_ptr(), x as f64); }
+    }
+"##,
+
+E0110: r##"
+Something which isn't a function or closure was called.
+
+Erroneous code example:
+
+    fn main() {
+        let x = 5;
+        let y = x();
+    }
+
+Corrected code example:
+
+    fn main() {
+        let x = || 5;
+        let y = x();
+    }
+"##,
+
+E0111: r##"
+A method was called which the type of the receiver doesn't have, either
+directly or through one of the traits in scope. Check the spelling, and that
+the trait providing the method is imported.
+
+Erroneous code example:
+
+    fn main() {
+        let v = vec!(1, 2, 3);
+        v.lenght();
+    }
+
+Corrected code example:
+
+    fn main() {
+        let v = vec!(1, 2, 3);
+        v.len();
+    }
+"##,
+
+E0112: r##"
+A binary operator, or its assigning form such as `+=`, was used on a type
+which doesn't support it. Operators other than the builtin ones are provided
+by implementing the trait of the operator, such as `Add` for `+`; the
+assigning forms only work on the builtin numeric types.
+
+Erroneous code example:
+
+    struct Meters(f64);
+
+    fn total(a: Meters, b: Meters) -> Meters {
+        a + b
+    }
+
+Corrected code example:
+
+    struct Meters(f64);
+
+    impl Add<Meters, Meters> for Meters {
+        fn add(&self, other: &Meters) -> Meters {
+            let (&Meters(a), &Meters(b)) = (self, other);
+            Meters(a + b)
+        }
+    }
+
+    fn total(a: Meters, b: Meters) -> Meters {
+        a + b
+    }
+"##,
+
+E0113: r##"
+A unary operator was used on a type which doesn't support it. `!` works on
+integers and `bool`, and `-` on integers and floats; other types need to
+implement `Not` or `Neg`.
+
+Erroneous code example:
+
+    fn main() {
+        let s = ~"text";
+        let t = -s;
+    }
+
+Corrected code example:
+
+    fn main() {
+        let n = 5;
+        let m = -n;
+    }
+"##,
+
+E0114: r##"
+A field was accessed which the type of the value doesn't have. Check the
+spelling, and that the value is of the structure expected.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let p = Point { x: 1, y: 2 };
+        let z = p.z;
+    }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let p = Point { x: 1, y: 2 };
+        let y = p.y;
+    }
+"##,
+
+E0115: r##"
+A structure expression gave a field which the structure doesn't have.
+
+Erroneous code example:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let p = Point { x: 1, y: 2, z: 3 };
+    }
+
+Corrected code example:
+
+    struct Point { x: int, y: int }
+
+    fn main() {
+        let p = Point { x: 1, y: 2 };
+    }
+"##,
+
+E0116: r##"
+`*` was applied to a value which isn't a pointer and doesn't implement
+`Deref`.
+
+Erroneous code example:
+
+    fn main() {
+        let x = 5;
+        let y = *x;
+    }
+
+Corrected code example:
+
+    fn main() {
+        let x = &5;
+        let y = *x;
+    }
+"##,
+
+E0117: r##"
+A cast was written which `as` can't perform. `as` converts between numeric
+types, C-like enums and numbers, `u8` and `char`, and pointers, but not
+between other types, and `()` can't be cast to or from anything.
+
+Erroneous code example:
+
+    fn main() {
+        let c = 65u32 as char;
+    }
+
+Corrected code example:
+
+    fn main() {
+        let c = 65u8 as char;
+    }
+"##,
+
+E0118: r##"
+A value was indexed whose type can't be indexed. Indexing works on vectors
+and strings, and on types which implement `Index`.
+
+Erroneous code example:
+
+    fn main() {
+        let x = 5;
+        let y = x[0];
+    }
+
+Corrected code example:
+
+    fn main() {
+        let x = [5];
+        let y = x[0];
+    }
+"##,
+
+E0119: r##"
+A pattern matched the elements of a `~[T]` vector. Such patterns are no
+longer supported; match a slice of the vector instead.
+
+Erroneous code example:
+
+    fn first(v: ~[int]) -> int {
+        match v {
+            [x, ..] => x,
+            [] => 0,
+        }
+    }
+
+Corrected code example:
+
+    fn first(v: ~[int]) -> int {
+        match v.as_slice() {
+            [x, ..] => x,
+            [] => 0,
+        }
+    }
+"##,
+
+E0120: r##"
+A trait bound isn't met: a type parameter or a where clause requires a trait
+to be implemented for a type which doesn't implement it.
+
+Erroneous code example:
+
+    fn show<T: ToStr>(t: T) -> ~str {
+        t.to_str()
+    }
+
+    struct Opaque;
+
+    fn main() {
+        show(Opaque);
+    }
+
+Corrected code example:
+
+    fn show<T: ToStr>(t: T) -> ~str {
+        t.to_str()
+    }
+
+    fn main() {
+        show(5);
+    }
+"##,
+
+E0121: r##"
+A type was given more or fewer type arguments than it declares type
+parameters. Parameters with a default may be left out, but no more arguments
+than parameters may be given.
+
+Erroneous code example:
+
+    struct Pair<A, B> { a: A, b: B }
+
+    fn first(p: Pair<int>) -> int {
+        p.a
+    }
+
+Corrected code example:
+
+    struct Pair<A, B> { a: A, b: B }
+
+    fn first(p: Pair<int, ~str>) -> int {
+        p.a
+    }
+"##,
+
+E0122: r##"
+A type was defined in terms of itself without an enum or a struct in
+between, so it would be infinitely large.
+
+Erroneous code example:
+
+    type List = ~[List];
+
+Corrected code example:
+
+    struct List { elems: ~[List] }
+"##,
+
+E0123: r##"
+A path which names a module, a function or some other value was used where a
+type was expected.
+
+Erroneous code example:
+
+    mod shapes {
+        pub struct Circle;
+    }
+
+    fn draw(s: shapes) {}
+
+Corrected code example:
+
+    mod shapes {
+        pub struct Circle;
+    }
+
+    fn draw(s: shapes::Circle) {}
+"##,
+
+E0124: r##"
+The length of a fixed-length vector type isn't a constant integer
+expression.
+
+Erroneous code example:
+
+    fn zeroes(n: uint) {
+        let v: [int, ..n] = [0, ..n];
+    }
+
+Corrected code example:
+
+    static N: uint = 4;
+
+    fn zeroes() {
+        let v: [int, ..N] = [0, ..N];
+    }
+"##,
+
+E0125: r##"
+A trait which isn't built into the language was used as a bound on a
+closure or a trait object. Only `Send`, `Share`, `Copy` and `'static` may
+bound them.
+
+Erroneous code example:
+
+    fn call(f: proc:Clone()) {
+        f()
+    }
+
+Corrected code example:
+
+    fn call(f: proc:Send()) {
+        f()
+    }
+"##,
+
+E0126: r##"
+A type parameter with trait bounds was left for inference, but nothing
+determined its type, so the implementation of its bounds can't be chosen.
+
+Erroneous code example:
+
+    fn main() {
+        let s = format!("{:?}", None);
+    }
+
+Corrected code example:
+
+    fn main() {
+        let s = format!("{:?}", None::<int>);
+    }
+"##,
+
+E0127: r##"
+A path which doesn't name a trait was used where a trait was expected, such
+as in a trait implementation or as a supertrait.
+
+Erroneous code example:
+
+    struct Meters(f64);
+
+    impl Meters for f64 {}
+
+Corrected code example:
+
+    trait Meters {}
+
+    impl Meters for f64 {}
+"##
+
+)
+
+#[cfg(test)]
+mod test {
+    use super::DIAGNOSTICS;
+
+    // Every code the compiler reports is declared with an explanation, so
+    // what is left to check is that the explanations are all complete.
+    #[test]
+    fn every_code_is_explained() {
+        let mut last = "";
+        for &(code, text) in DIAGNOSTICS.iter() {
+            assert!(code.len() == 5 && code.starts_with("E") &&
+                    code.slice_from(1).chars().all(|c| c.is_digit()),
+                    "malformed error code {}", code);
+            assert!(code > last, "{} is out of order or repeated", code);
+            last = code;
+
+            let failing = text.find_str("Erroneous code example:\n\n    ");
+            let fixed = text.find_str("Corrected code example:\n\n    ");
+            match (failing, fixed) {
+                (Some(failing), Some(fixed)) => {
+                    assert!(failing > 0 && fixed > failing,
+                            "the explanation of {} is out of order", code)
+                }
+                _ => fail!("the explanation of {} needs a failing and a fixed example",
+                           code),
+            }
+        }
+    }
+}
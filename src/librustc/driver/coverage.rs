@@ -0,0 +1,196 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `rustc --coverage-report FORMAT foo.covmap`: render the counts collected
+//! by a program built with `-Z instrument-coverage` (see
+//! `middle::trans::coverage` for the files involved).
+//!
+//! Every record in the counts file, one per run of the program, is added up.
+//! The count of a line is that of the innermost region covering it; lines
+//! outside every region are not code as far as coverage is concerned.
+//! `lcov` writes a tracefile for lcov's `genhtml` and the like, and
+//! `annotate` prints each source file with the count of every line in the
+//! margin, `#####` marking lines which never ran.
+
+use driver::driver::early_error;
+use driver::session;
+
+use collections::TreeMap;
+use std::io;
+
+struct Region {
+    counter: uint,
+    file: ~str,
+    lo: (uint, uint),
+    hi: (uint, uint),
+}
+
+struct CoverageMap {
+    counters: uint,
+    counts: Path,
+    regions: Vec<Region>,
+}
+
+pub fn report(format: &str, map_path: &Path, output: session::ErrorOutputType) {
+    let map = match read_map(map_path) {
+        Ok(map) => map,
+        Err(msg) => early_error(output, format!("{}: {}", map_path.display(), msg)),
+    };
+    let counts = match read_counts(&map) {
+        Ok(counts) => counts,
+        Err(msg) => early_error(output, format!("{}: {}", map.counts.display(), msg)),
+    };
+    let lines = line_counts(&map, counts.as_slice());
+
+    let mut out = io::stdout();
+    let result = match format {
+        "lcov" => write_lcov(&mut out, &lines),
+        "annotate" => write_annotated(&mut out, &lines),
+        _ => early_error(output,
+                         format!("unknown coverage report format `{}`: \
+                                  expected `lcov` or `annotate`", format)),
+    };
+    match result {
+        Ok(()) => {}
+        Err(e) => early_error(output, format!("failed to write coverage report: {}", e)),
+    }
+}
+
+fn read_map(path: &Path) -> Result<CoverageMap, ~str> {
+    let src = match io::File::open(path).read_to_str() {
+        Ok(src) => src,
+        Err(e) => return Err(e.to_str()),
+    };
+    let mut map = CoverageMap {
+        counters: 0,
+        counts: Path::new(""),
+        regions: Vec::new(),
+    };
+    for (i, line) in src.lines().enumerate() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let num = |s: &str| match from_str::<uint>(s) {
+            Some(n) => Ok(n),
+            None => Err(format!("malformed line {}", i + 1)),
+        };
+        match fields.as_slice() {
+            [key, n] if key == "counters" => map.counters = try!(num(n)),
+            [key, path] if key == "counts" => map.counts = Path::new(path),
+            [counter, file, line, col, end_line, end_col, _function] => {
+                map.regions.push(Region {
+                    counter: try!(num(counter)),
+                    file: file.to_owned(),
+                    lo: (try!(num(line)), try!(num(col))),
+                    hi: (try!(num(end_line)), try!(num(end_col))),
+                });
+            }
+            _ => return Err(format!("malformed line {}", i + 1)),
+        }
+    }
+    if map.regions.iter().any(|r| r.counter >= map.counters) {
+        return Err(~"counter out of range")
+    }
+    Ok(map)
+}
+
+fn read_counts(map: &CoverageMap) -> Result<Vec<u64>, ~str> {
+    let data = match io::File::open(&map.counts).read_to_end() {
+        Ok(data) => data,
+        Err(e) => return Err(e.to_str()),
+    };
+    let record = map.counters * 8;
+    if data.len() == 0 {
+        return Err(~"no runs recorded")
+    }
+    // A run cut short while writing leaves a partial record behind.
+    if record == 0 || data.len() % record != 0 {
+        return Err(~"not recorded by this program, or truncated")
+    }
+
+    let mut counts = Vec::from_elem(map.counters, 0u64);
+    let mut reader = io::BufReader::new(data.as_slice());
+    for _ in range(0, data.len() / record) {
+        for count in counts.mut_iter() {
+            // The counts are written out as they are in memory.
+            let n = if cfg!(target_endian = "big") {
+                reader.read_be_u64()
+            } else {
+                reader.read_le_u64()
+            };
+            *count += n.unwrap();
+        }
+    }
+    Ok(counts)
+}
+
+// The count of every line of code, by file and line.
+fn line_counts(map: &CoverageMap, counts: &[u64]) -> TreeMap<~str, TreeMap<uint, u64>> {
+    // Going from the smallest region to the largest, the first region to
+    // reach a line is the innermost one covering it.
+    let mut regions: Vec<&Region> = map.regions.iter().collect();
+    regions.sort_by(|a, b| region_size(*a).cmp(&region_size(*b)));
+
+    let mut files = TreeMap::new();
+    for region in regions.iter() {
+        if !files.contains_key(&region.file) {
+            files.insert(region.file.clone(), TreeMap::new());
+        }
+        let lines = files.find_mut(&region.file).unwrap();
+        let ((lo, _), (hi, _)) = (region.lo, region.hi);
+        for line in range(lo, hi + 1) {
+            if !lines.contains_key(&line) {
+                lines.insert(line, counts[region.counter]);
+            }
+        }
+    }
+    files
+}
+
+fn region_size(r: &Region) -> (uint, uint) {
+    let ((lo_line, lo_col), (hi_line, hi_col)) = (r.lo, r.hi);
+    (hi_line - lo_line, if hi_line == lo_line { hi_col - lo_col } else { 0 })
+}
+
+fn write_lcov(w: &mut Writer,
+              files: &TreeMap<~str, TreeMap<uint, u64>>) -> io::IoResult<()> {
+    for (file, lines) in files.iter() {
+        try!(writeln!(w, "TN:"));
+        try!(writeln!(w, "SF:{}", *file));
+        for (line, count) in lines.iter() {
+            try!(writeln!(w, "DA:{},{}", *line, *count));
+        }
+        try!(writeln!(w, "LF:{}", lines.len()));
+        try!(writeln!(w, "LH:{}", lines.iter().filter(|&(_, &n)| n > 0).count()));
+        try!(writeln!(w, "end_of_record"));
+    }
+    Ok(())
+}
+
+fn write_annotated(w: &mut Writer,
+                   files: &TreeMap<~str, TreeMap<uint, u64>>) -> io::IoResult<()> {
+    for (file, lines) in files.iter() {
+        let src = match io::File::open(&Path::new(file.as_slice())).read_to_str() {
+            Ok(src) => src,
+            Err(e) => {
+                try!(writeln!(w, "{}: source not available: {}", *file, e));
+                continue
+            }
+        };
+        try!(writeln!(w, "{}:", *file));
+        for (i, text) in src.lines().enumerate() {
+            let count = match lines.find(&(i + 1)) {
+                Some(&0) => ~"#####",
+                Some(n) => n.to_str(),
+                None => ~"-",
+            };
+            try!(writeln!(w, "{:>9}:{:>5}:{}", count, i + 1, text));
+        }
+    }
+    Ok(())
+}
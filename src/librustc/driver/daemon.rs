@@ -0,0 +1,537 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `rustc --daemon SOCKET`: analyze a crate once, keep the results in memory
+//! and answer queries about it over a Unix socket.
+//!
+//! Clients send one request per line and get one line of JSON back for each:
+//!
+//! * `type-at FILE:LINE:COL` - `{"ty": ..., "span": ...}`, the type of the
+//!   innermost expression or pattern at the given position.
+//! * `definition FILE:LINE:COL` - `{"path": ..., "span": ...}`, where the
+//!   item, variable or method referred to at the given position is defined.
+//!   `span` is null for definitions in other crates, `path` for variables.
+//! * `references FILE:LINE:COL` - `{"spans": [...]}`, everything which
+//!   refers to the same definition, including the definition itself.
+//! * `diagnostics` - `{"diagnostics": [...]}`, everything reported while
+//!   compiling the crate, in the format of `--error-format=json`.
+//! * `reload` - analyze the crate again, picking up any edits.
+//! * `shutdown` - stop the daemon.
+//!
+//! Lines and columns start at 1, columns counting characters. Failed queries
+//! are answered with `{"error": ...}`. If the crate doesn't compile, only
+//! `diagnostics`, `reload` and `shutdown` are available.
+//!
+//! Nothing past analysis (`phase_3_run_analysis_passes`) is run.
+
+use back::link;
+use driver::driver;
+use driver::session;
+use metadata::creader::Loader;
+use middle::ty;
+use middle::typeck;
+use util::ppaux;
+
+use std::io;
+use std::io::{BufferedReader, Acceptor, Listener};
+use std::io::net::unix::{UnixListener, UnixAcceptor};
+use std::os;
+use std::task;
+use getopts;
+use serialize::json;
+use sync::{Arc, Mutex};
+use syntax::ast;
+use syntax::ast_map;
+use syntax::ast_util;
+use syntax::codemap::{BytePos, CodeMap, Pos, Span};
+use syntax::diagnostic;
+use syntax::diagnostic::{Diagnostic, DiagnosticSpan, Emitter, Level};
+use syntax::diagnostic::{RenderSpan, Suggestion};
+use syntax::visit;
+use syntax::visit::Visitor;
+
+enum Action {
+    Reply(~str),
+    Reload,
+    Shutdown,
+}
+
+#[deriving(Encodable)]
+struct TypeReply {
+    ty: ~str,
+    span: Option<DiagnosticSpan>,
+}
+
+#[deriving(Encodable)]
+struct DefinitionReply {
+    path: Option<~str>,
+    span: Option<DiagnosticSpan>,
+}
+
+#[deriving(Encodable)]
+struct ReferencesReply {
+    spans: Vec<DiagnosticSpan>,
+}
+
+#[deriving(Encodable)]
+struct DiagnosticsReply {
+    diagnostics: Vec<Diagnostic>,
+}
+
+#[deriving(Encodable)]
+struct ErrorReply {
+    error: ~str,
+}
+
+/// Run the daemon for the crate and options given by the command line
+/// `args`, listening on `socket`. Returns when a client asks for a shutdown.
+pub fn run(args: Vec<~str>, socket: Path) {
+    let error_format = driver::early_error_format(args.as_slice());
+    let mut acceptor = Some(bind(&socket, error_format));
+    loop {
+        let listening = match acceptor.take() {
+            Some(acceptor) => acceptor,
+            None => bind(&socket, error_format),
+        };
+        let diagnostics = Arc::new(Mutex::new(Vec::new()));
+
+        // The analysis runs on its own task so that a crate which fails to
+        // compile doesn't take the daemon down with it.
+        let mut task = task::task().named("rustc daemon");
+        task.opts.stack_size = Some(::STACK_SIZE);
+        let (args, collected) = (args.clone(), diagnostics.clone());
+        let result = task.try(proc() {
+            io::stdio::set_stderr(~io::util::NullWriter);
+            analyze_and_serve(args, listening, collected)
+        });
+
+        let next = match result {
+            Ok(next) => next,
+            // The acceptor went down with the task.
+            Err(..) => serve_diagnostics(bind(&socket, error_format), diagnostics),
+        };
+        match next {
+            Some(listening) => acceptor = Some(listening),
+            None => break,
+        }
+    }
+    let _ = io::fs::unlink(&socket);
+}
+
+fn bind(socket: &Path, error_format: session::ErrorOutputType) -> UnixAcceptor {
+    // Clean up after a daemon which didn't shut down properly.
+    if socket.exists() {
+        let _ = io::fs::unlink(socket);
+    }
+    match UnixListener::bind(socket).listen() {
+        Ok(acceptor) => acceptor,
+        Err(e) => {
+            driver::early_error(error_format,
+                                format!("failed to listen on `{}`: {}",
+                                        socket.display(), e))
+        }
+    }
+}
+
+/// Answer requests from one client after another until one of them asks
+/// for a reload or shutdown. Returns the acceptor to keep listening on after
+/// a reload.
+fn serve(mut acceptor: UnixAcceptor,
+         answer: |&str| -> Action) -> Option<UnixAcceptor> {
+    // `incoming` borrows the acceptor, so it can only be handed back once
+    // the loop is over.
+    let mut reload = false;
+    'clients: for stream in acceptor.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(..) => continue,
+        };
+        let mut reader = BufferedReader::new(stream.clone());
+        let mut writer = stream;
+        for line in reader.lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(..) => break,
+            };
+            let reply = match answer(line.trim()) {
+                Reply(reply) => reply,
+                Reload => {
+                    reload = true;
+                    break 'clients;
+                }
+                Shutdown => break 'clients,
+            };
+            if writer.write_line(reply).is_err() {
+                break
+            }
+        }
+    }
+    if reload { Some(acceptor) } else { None }
+}
+
+fn serve_diagnostics(acceptor: UnixAcceptor,
+                     diagnostics: Arc<Mutex<Vec<Diagnostic>>>)
+                     -> Option<UnixAcceptor> {
+    serve(acceptor, |request| {
+        match request {
+            "diagnostics" => Reply(diagnostics_reply(&diagnostics)),
+            "reload" => Reload,
+            "shutdown" => Shutdown,
+            _ => Reply(error_reply("the crate failed to compile; \
+                                    only `diagnostics` is available")),
+        }
+    })
+}
+
+fn analyze_and_serve(args: Vec<~str>, acceptor: UnixAcceptor,
+                     diagnostics: Arc<Mutex<Vec<Diagnostic>>>)
+                     -> Option<UnixAcceptor> {
+    let matches = match getopts::getopts(args.as_slice(),
+                                         driver::optgroups().as_slice()) {
+        Ok(m) => m,
+        Err(f) => driver::early_error(driver::early_error_format(args.as_slice()),
+                                      f.to_err_msg()),
+    };
+    let input_file = match matches.free.as_slice() {
+        [ref file] => Path::new(file.as_slice()),
+        _ => driver::early_error(driver::parse_error_format(&matches),
+                                 "--daemon requires a single input file"),
+    };
+
+    let sopts = driver::build_session_options(&matches);
+    let codemap = CodeMap::new();
+    let emitter = ~Collector { diagnostics: diagnostics.clone() };
+    let span_diagnostic = diagnostic::mk_span_handler(
+        diagnostic::mk_handler(emitter as ~Emitter:Send), codemap);
+    let sess = driver::build_session_(sopts, Some(input_file.clone()),
+                                      span_diagnostic);
+    let cfg = driver::build_configuration(&sess);
+    let filestem = input_file.filestem_str().unwrap().to_owned();
+    let input = driver::FileInput(input_file);
+
+    let krate = driver::phase_1_parse_input(&sess, cfg, &input);
+    let id = link::find_crate_id(krate.attrs.as_slice(), filestem);
+    let (krate, ast_map) = {
+        let loader = &mut Loader::new(&sess);
+        driver::phase_2_configure_and_expand(&sess, loader, krate, &id)
+    };
+    let analysis = driver::phase_3_run_analysis_passes(sess, &krate, ast_map);
+
+    let mut index = NodeIndex { nodes: Vec::new() };
+    visit::walk_crate(&mut index, &krate, ());
+    let query = Query {
+        tcx: &analysis.ty_cx,
+        method_map: &analysis.maps.method_map,
+        nodes: index.nodes,
+    };
+
+    serve(acceptor, |request| {
+        let (command, arg) = match request.find(' ') {
+            Some(i) => (request.slice_to(i), request.slice_from(i + 1).trim()),
+            None => (request, ""),
+        };
+        let reply = match command {
+            "diagnostics" => diagnostics_reply(&diagnostics),
+            "type-at" => query.with_position(arg, |found| query.type_at(found)),
+            "definition" => {
+                query.with_position(arg, |found| query.definition(found))
+            }
+            "references" => {
+                query.with_position(arg, |found| query.references(found))
+            }
+            "reload" => return Reload,
+            "shutdown" => return Shutdown,
+            _ => error_reply(format!("unknown request `{}`", command)),
+        };
+        Reply(reply)
+    })
+}
+
+fn diagnostics_reply(diagnostics: &Arc<Mutex<Vec<Diagnostic>>>) -> ~str {
+    let diagnostics = diagnostics.lock().clone();
+    json::Encoder::str_encode(&DiagnosticsReply { diagnostics: diagnostics })
+}
+
+fn error_reply(msg: &str) -> ~str {
+    json::Encoder::str_encode(&ErrorReply { error: msg.to_owned() })
+}
+
+/// Records diagnostics in the form `--error-format=json` prints them in.
+struct Collector {
+    diagnostics: Arc<Mutex<Vec<Diagnostic>>>,
+}
+
+impl Collector {
+    fn push(&mut self, diag: Diagnostic, lvl: Level) {
+        let mut diagnostics = self.diagnostics.lock();
+        let unattached = diag.attach_to(lvl, diagnostics.mut_last());
+        match unattached {
+            Some(diag) => diagnostics.push(diag),
+            None => {}
+        }
+    }
+}
+
+impl Emitter for Collector {
+    fn emit(&mut self,
+            cmsp: Option<(&CodeMap, Span)>,
+            msg: &str,
+            code: Option<&str>,
+            lvl: Level) {
+        self.push(Diagnostic::new(cmsp, msg, code, lvl), lvl);
+    }
+
+    fn custom_emit(&mut self, cm: &CodeMap,
+                   sp: RenderSpan, msg: &str, lvl: Level) {
+        self.emit(Some((cm, sp.span())), msg, None, lvl);
+    }
+
+    fn suggest(&mut self, cm: &CodeMap, sugg: &Suggestion) {
+        self.push(Diagnostic::from_suggestion(cm, sugg), diagnostic::Note);
+    }
+}
+
+enum NodeKind {
+    ExprNode,
+    // Whether the pattern introduces a new binding.
+    PatNode(bool),
+    TyNode,
+}
+
+struct Node {
+    id: ast::NodeId,
+    span: Span,
+    kind: NodeKind,
+}
+
+/// Every expression, pattern and type in the crate; positions are looked up
+/// in here.
+struct NodeIndex {
+    nodes: Vec<Node>,
+}
+
+impl Visitor<()> for NodeIndex {
+    fn visit_expr(&mut self, e: &ast::Expr, _: ()) {
+        self.nodes.push(Node { id: e.id, span: e.span, kind: ExprNode });
+        visit::walk_expr(self, e, ());
+    }
+
+    fn visit_pat(&mut self, p: &ast::Pat, _: ()) {
+        let binding = match p.node {
+            ast::PatIdent(..) => true,
+            _ => false,
+        };
+        self.nodes.push(Node { id: p.id, span: p.span, kind: PatNode(binding) });
+        visit::walk_pat(self, p, ());
+    }
+
+    fn visit_ty(&mut self, t: &ast::Ty, _: ()) {
+        // Resolve records what a path type refers to under the path's id.
+        let id = match t.node {
+            ast::TyPath(_, _, path_id) => path_id,
+            _ => t.id,
+        };
+        self.nodes.push(Node { id: id, span: t.span, kind: TyNode });
+        visit::walk_ty(self, t, ());
+    }
+}
+
+struct Query<'a> {
+    tcx: &'a ty::ctxt,
+    method_map: &'a typeck::MethodMap,
+    nodes: Vec<Node>,
+}
+
+impl<'a> Query<'a> {
+    fn cm(&self) -> &'a CodeMap {
+        self.tcx.sess.codemap()
+    }
+
+    fn span(&self, sp: Span) -> Option<DiagnosticSpan> {
+        DiagnosticSpan::from_span(self.cm(), sp)
+    }
+
+    /// Parse a `FILE:LINE:COL` position and run `f` with the nodes containing
+    /// it, innermost first.
+    fn with_position(&self, position: &str, f: |&[&Node]| -> ~str) -> ~str {
+        let pos = match self.parse_position(position) {
+            Some(pos) => pos,
+            None => return error_reply(format!("invalid position `{}`", position)),
+        };
+        let mut found: Vec<&Node> = self.nodes.iter().filter(|node| {
+            node.span.lo <= pos && pos < node.span.hi
+        }).collect();
+        found.sort_by(|a, b| {
+            let len = |node: & &Node| node.span.hi.to_uint() - node.span.lo.to_uint();
+            len(a).cmp(&len(b))
+        });
+        f(found.as_slice())
+    }
+
+    fn parse_position(&self, position: &str) -> Option<BytePos> {
+        let parts: Vec<&str> = position.rsplitn(':', 2).collect();
+        let (file, line, col) = match parts.as_slice() {
+            [col, line, file] => (file, from_str::<uint>(line), from_str::<uint>(col)),
+            _ => return None,
+        };
+        let (line, col) = match (line, col) {
+            (Some(line), Some(col)) if line > 0 && col > 0 => (line, col),
+            _ => return None,
+        };
+
+        let wanted = os::make_absolute(&Path::new(file));
+        let files = self.cm().files.borrow();
+        let fm = match files.iter().find(|fm| {
+            fm.name.as_slice() == file ||
+                os::make_absolute(&Path::new(fm.name.as_slice())) == wanted
+        }) {
+            Some(fm) => fm.clone(),
+            None => return None,
+        };
+
+        let start = match fm.lines.borrow().as_slice().get(line - 1) {
+            Some(start) => *start,
+            None => return None,
+        };
+        let offset = start.to_uint() - fm.start_pos.to_uint();
+        let text = fm.src.slice_from(offset);
+        let mut bytes = 0;
+        for (i, c) in text.chars().enumerate() {
+            if i + 1 == col {
+                return Some(BytePos::from_uint(start.to_uint() + bytes));
+            }
+            if c == '\n' {
+                break
+            }
+            bytes += c.len_utf8_bytes();
+        }
+        None
+    }
+
+    fn type_at(&self, found: &[&Node]) -> ~str {
+        for node in found.iter() {
+            match node.kind {
+                ExprNode | PatNode(_) => {}
+                TyNode => continue,
+            }
+            match ty::node_id_to_type_opt(self.tcx, node.id) {
+                Some(t) => {
+                    return json::Encoder::str_encode(&TypeReply {
+                        ty: ppaux::ty_to_str(self.tcx, t),
+                        span: self.span(node.span),
+                    });
+                }
+                None => {}
+            }
+        }
+        error_reply("no expression or pattern at this position")
+    }
+
+    /// What `node` refers to: the definition of a path, method or binding,
+    /// along with whether it's a local variable.
+    fn def_of(&self, node: &Node) -> Option<(ast::DefId, bool)> {
+        match node.kind {
+            ExprNode => {
+                let call = typeck::MethodCall::expr(node.id);
+                match self.method_map.borrow().find(&call) {
+                    Some(method) => {
+                        let did = match method.origin {
+                            typeck::MethodStatic(did) => did,
+                            typeck::MethodParam(ref p) => {
+                                ty::trait_method(self.tcx, p.trait_id,
+                                                 p.method_num).def_id
+                            }
+                            typeck::MethodObject(ref o) => {
+                                ty::trait_method(self.tcx, o.trait_id,
+                                                 o.method_num).def_id
+                            }
+                        };
+                        return Some((did, false));
+                    }
+                    None => {}
+                }
+            }
+            PatNode(_) | TyNode => {}
+        }
+        match self.tcx.def_map.borrow().find(&node.id) {
+            Some(&ast::DefPrimTy(..)) => None,
+            Some(&def) => {
+                let local = match def {
+                    ast::DefArg(..) | ast::DefLocal(..) | ast::DefBinding(..) |
+                    ast::DefUpvar(..) => true,
+                    _ => false,
+                };
+                Some((ast_util::def_id_of_def(def), local))
+            }
+            // A pattern binding a new variable refers to itself.
+            None => match node.kind {
+                PatNode(true) => Some((ast_util::local_def(node.id), true)),
+                _ => None,
+            },
+        }
+    }
+
+    fn def_span(&self, did: ast::DefId) -> Option<Span> {
+        if did.krate != ast::LOCAL_CRATE {
+            return None
+        }
+        match self.tcx.map.find(did.node) {
+            Some(ast_map::NodeItem(..)) | Some(ast_map::NodeForeignItem(..)) |
+            Some(ast_map::NodeTraitMethod(..)) | Some(ast_map::NodeMethod(..)) |
+            Some(ast_map::NodeVariant(..)) | Some(ast_map::NodeArg(..)) |
+            Some(ast_map::NodeLocal(..)) | Some(ast_map::NodeStructCtor(..)) => {
+                Some(self.tcx.map.span(did.node))
+            }
+            _ => None,
+        }
+    }
+
+    fn find_def(&self, found: &[&Node]) -> Option<(ast::DefId, bool)> {
+        found.iter().filter_map(|node| self.def_of(*node)).next()
+    }
+
+    fn definition(&self, found: &[&Node]) -> ~str {
+        match self.find_def(found) {
+            Some((did, local)) => {
+                let path = if local {
+                    None
+                } else {
+                    Some(ty::item_path_str(self.tcx, did))
+                };
+                json::Encoder::str_encode(&DefinitionReply {
+                    path: path,
+                    span: self.def_span(did).and_then(|sp| self.span(sp)),
+                })
+            }
+            None => error_reply("nothing is referred to at this position"),
+        }
+    }
+
+    fn references(&self, found: &[&Node]) -> ~str {
+        let did = match self.find_def(found) {
+            Some((did, _)) => did,
+            None => return error_reply("nothing is referred to at this position"),
+        };
+        let mut spans: Vec<Span> = self.nodes.iter().filter(|node| {
+            match self.def_of(*node) {
+                Some((d, _)) => d == did,
+                None => false,
+            }
+        }).map(|node| node.span).collect();
+        match self.def_span(did) {
+            Some(sp) if !spans.contains(&sp) => spans.push(sp),
+            _ => {}
+        }
+        spans.sort_by(|a, b| a.lo.to_uint().cmp(&b.lo.to_uint()));
+        json::Encoder::str_encode(&ReferencesReply {
+            spans: spans.iter().filter_map(|sp| self.span(*sp)).collect(),
+        })
+    }
+}
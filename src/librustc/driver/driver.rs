@@ -10,10 +10,10 @@
 
 
 use back::link;
-use back::{arm, x86, x86_64, mips};
+use back::{arm, x86, x86_64, mips, target, target_strs};
 use driver::session::{Aggressive, CrateTypeExecutable, CrateType,
                       FullDebugInfo, LimitedDebugInfo, NoDebugInfo};
-use driver::session::{Session, No, Less, Default};
+use driver::session::{Session, No, Less, Default, sess_os_to_meta_os};
 use driver::session;
 use front;
 use lib::llvm::llvm;
@@ -22,10 +22,12 @@ use metadata::common::LinkMeta;
 use metadata::{creader, filesearch};
 use metadata::cstore::CStore;
 use metadata::creader::Loader;
+use metadata::loader::meta_section_name;
 use metadata;
 use middle::{trans, freevars, kind, ty, typeck, lint, astencode, reachable};
 use middle;
 use util::common::time;
+use util::profile;
 use util::ppaux;
 use util::nodemap::{NodeMap, NodeSet};
 
@@ -37,6 +39,7 @@ use std::io::fs;
 use std::io::MemReader;
 use std::mem::drop;
 use std::os;
+use flate;
 use getopts::{optopt, optmulti, optflag, optflagopt};
 use getopts;
 use syntax::ast;
@@ -90,18 +93,22 @@ pub fn default_configuration(sess: &Session) ->
 
     // ARM is bi-endian, however using NDK seems to default
     // to little-endian unless a flag is provided.
-    let (end,arch,wordsz) = match sess.targ_cfg.arch {
-        abi::X86 =>    ("little", "x86",    "32"),
-        abi::X86_64 => ("little", "x86_64", "64"),
-        abi::Arm =>    ("little", "arm",    "32"),
-        abi::Mips =>   ("big",    "mips",   "32")
+    let (end,arch) = match sess.targ_cfg.arch {
+        abi::X86 =>    ("little", "x86"),
+        abi::X86_64 => ("little", "x86_64"),
+        abi::Arm =>    ("little", "arm"),
+        abi::Mips =>   ("big",    "mips")
     };
 
-    let fam = match sess.targ_cfg.os {
-        abi::OsWin32 => InternedString::new("windows"),
-        _ => InternedString::new("unix")
+    // A target description may give a 32-bit target an architecture which
+    // is otherwise 64-bit (and vice versa), so this follows the int types.
+    let wordsz = match sess.targ_cfg.uint_type {
+        ast::TyU64 => "64",
+        _ => "32"
     };
 
+    let fam = token::intern_and_get_ident(sess.targ_cfg.target_family.as_slice());
+
     let mk = attr::mk_name_value_item_str;
     return vec!(// Target bindings.
          attr::mk_word_item(fam.clone()),
@@ -211,8 +218,21 @@ pub fn phase_2_configure_and_expand(sess: &Session,
                                     -> (ast::Crate, syntax::ast_map::Map) {
     let time_passes = sess.time_passes();
 
+    sess.freestanding.set(session::freestanding(&sess.opts, &krate));
     sess.building_library.set(session::building_library(&sess.opts, &krate));
     *sess.crate_types.borrow_mut() = session::collect_crate_types(sess, krate.attrs.as_slice());
+    if sess.freestanding.get() {
+        for &ty in sess.crate_types.borrow().iter() {
+            let what = match ty {
+                session::CrateTypeExecutable => "an executable",
+                session::CrateTypeDylib => "a dynamic library",
+                session::CrateTypeRlib | session::CrateTypeStaticlib => continue,
+            };
+            sess.err(format!("a freestanding crate cannot be built as {}; \
+                              build an rlib, a static library or an object", what));
+        }
+        sess.abort_if_errors();
+    }
 
     time(time_passes, "gated feature checking", (), |_|
          front::feature_gate::check_crate(sess, &krate));
@@ -242,6 +262,9 @@ pub fn phase_2_configure_and_expand(sess: &Session,
                                           krate)
     });
 
+    // Expansion loaded the lint plugins, so the lints they define are known.
+    lint::check_plugin_lint_opts(sess);
+
     // strip again, in case expansion added anything with a #[cfg].
     krate = time(time_passes, "configuration 2", krate, |krate|
                  front::config::strip_unconfigured_items(krate));
@@ -315,6 +338,10 @@ pub fn phase_3_run_analysis_passes(sess: Session,
             syntax::ext::registrar::find_macro_registrar(
                 sess.diagnostic(), krate));
 
+    *sess.lint_registrar_fn.borrow_mut() =
+        time(time_passes, "looking for lint registrar", (), |_|
+            lint::find_lint_registrar(&sess, krate));
+
     let freevars = time(time_passes, "freevar finding", (), |_|
                         freevars::annotate_freevars(def_map, krate));
 
@@ -374,6 +401,11 @@ pub fn phase_3_run_analysis_passes(sess: Session,
     time(time_passes, "kind checking", (), |_|
          kind::check_crate(&ty_cx, method_map, krate));
 
+    if sess.freestanding.get() {
+        time(time_passes, "freestanding lang item checking", (), |_|
+             middle::lang_items::check_freestanding(&ty_cx, krate));
+    }
+
     let reachable_map =
         time(time_passes, "reachability checking", (), |_|
              reachable::find_reachable(&ty_cx, method_map, &exported_items));
@@ -389,6 +421,14 @@ pub fn phase_3_run_analysis_passes(sess: Session,
     time(time_passes, "lint checking", (), |_|
          lint::check_crate(&ty_cx, method_map, &exported_items, krate));
 
+    // Lints make most of the suggestions, so apply them before any lint
+    // errors stop the compilation.
+    if ty_cx.sess.opts.apply_suggestions {
+        time(time_passes, "applying suggestions", (), |_|
+             ty_cx.sess.apply_suggestions());
+    }
+    ty_cx.sess.abort_if_errors();
+
     CrateAnalysis {
         exp_map2: exp_map2,
         ty_cx: ty_cx,
@@ -411,6 +451,9 @@ pub struct CrateTranslation {
     pub link: LinkMeta,
     pub metadata: Vec<u8>,
     pub reachable: Vec<~str>,
+    /// The reachable symbols which C code can call by name, those of
+    /// `#[no_mangle]` and `#[export_name]` items.
+    pub c_symbols: Vec<~str>,
 }
 
 /// Run the translation phase to LLVM, after which the AST and analysis can
@@ -425,6 +468,61 @@ pub fn phase_4_translate_to_llvm(krate: ast::Crate,
          trans::base::trans_crate(krate, analysis.take_unwrap(), outputs))
 }
 
+/// Encode the crate's metadata straight from the analysis, for
+/// `--emit=metadata` on its own. No symbols are recorded, since they are only
+/// chosen during translation.
+pub fn phase_4_encode_metadata(krate: &ast::Crate,
+                               analysis: &CrateAnalysis,
+                               outputs: &OutputFilenames) -> Vec<u8> {
+    let tcx = &analysis.ty_cx;
+    if !tcx.sess.building_library.get() {
+        return Vec::new()
+    }
+
+    let item_symbols = RefCell::new(NodeMap::new());
+    let non_inlineable_statics = RefCell::new(NodeSet::new());
+    let link_meta = link::build_link_meta(krate, outputs.out_filestem);
+    let encode_inlined_item: metadata::encoder::EncodeInlinedItem =
+        |ecx, ebml_w, ii| astencode::encode_inlined_item(ecx, ebml_w, ii, &analysis.maps);
+    let parms = metadata::encoder::EncodeParams {
+        diag: tcx.sess.diagnostic(),
+        tcx: tcx,
+        reexports2: analysis.exp_map2,
+        item_symbols: &item_symbols,
+        non_inlineable_statics: &non_inlineable_statics,
+        link_meta: &link_meta,
+        cstore: &tcx.sess.cstore,
+        encode_inlined_item: encode_inlined_item,
+    };
+    time(tcx.sess.time_passes(), "metadata encoding", parms, |parms|
+         metadata::encoder::encode_metadata(parms, krate))
+}
+
+/// Write out the metadata requested with `--emit=metadata`, stamped and
+/// compressed the same way as in a dylib's metadata section.
+fn write_metadata(sess: &Session, id: &CrateId, metadata: &[u8],
+                  outputs: &OutputFilenames) {
+    if !sess.building_library.get() {
+        sess.warn("ignoring --emit=metadata, only libraries have metadata");
+        return
+    }
+    let path = link::metadata_filename(id, &outputs.path(link::OutputTypeMetadata));
+    let compressed = match flate::deflate_bytes(metadata) {
+        Some(compressed) => compressed,
+        None => sess.fatal("failed to compress metadata"),
+    };
+    let result = io::File::create(&path).and_then(|mut file| {
+        try!(file.write(metadata::encoder::metadata_encoding_version));
+        file.write(compressed.as_slice())
+    });
+    match result {
+        Ok(()) => {}
+        Err(e) => {
+            sess.fatal(format!("failed to write {}: {}", path.display(), e))
+        }
+    }
+}
+
 /// Run LLVM itself, producing a bitcode file, assembly file or object file
 /// as a side effect.
 pub fn phase_5_run_llvm_passes(sess: &Session,
@@ -514,6 +612,9 @@ fn write_out_deps(sess: &Session,
                     out_filenames.push(p);
                 }
             }
+            link::OutputTypeMetadata => {
+                out_filenames.push(link::metadata_filename(&id, &file));
+            }
             _ => { out_filenames.push(file); }
         }
     }
@@ -559,15 +660,31 @@ pub fn compile_input(sess: Session, cfg: ast::CrateConfig, input: &Input,
     // We need nested scopes here, because the intermediate results can keep
     // large chunks of memory alive and we want to free them as soon as
     // possible to keep the peak memory usage low
+    let mut self_profile = if sess.self_profile() {
+        Some(profile::start())
+    } else {
+        None
+    };
     let (outputs, trans, sess) = {
         let (outputs, expanded_crate, ast_map) = {
             let krate = phase_1_parse_input(&sess, cfg, input);
-            if stop_after_phase_1(&sess) { return; }
+            if stop_after_phase_1(&sess) {
+                // No plugin has been loaded, so every lint flag naming none
+                // of rustc's lints is a typo.
+                lint::check_plugin_lint_opts(&sess);
+                return;
+            }
             let outputs = build_output_filenames(input,
                                                  outdir,
                                                  output,
                                                  krate.attrs.as_slice(),
                                                  &sess);
+            match self_profile {
+                Some(ref mut profile) => {
+                    profile.report_to(outputs.with_extension("profile"))
+                }
+                None => {}
+            }
             let loader = &mut Loader::new(&sess);
             let id = link::find_crate_id(krate.attrs.as_slice(),
                                          outputs.out_filestem);
@@ -580,9 +697,25 @@ pub fn compile_input(sess: Session, cfg: ast::CrateConfig, input: &Input,
         if stop_after_phase_2(&sess) { return; }
 
         let analysis = phase_3_run_analysis_passes(sess, &expanded_crate, ast_map);
+        if analysis.ty_cx.sess.save_analysis() {
+            time(analysis.ty_cx.sess.time_passes(), "save analysis", (), |_|
+                 middle::save::process_crate(&expanded_crate, &analysis, &outputs));
+        }
         if stop_after_phase_3(&analysis.ty_cx.sess) { return; }
+        if analysis.ty_cx.sess.metadata_only() {
+            let id = link::find_crate_id(expanded_crate.attrs.as_slice(),
+                                         outputs.out_filestem);
+            let metadata = phase_4_encode_metadata(&expanded_crate, &analysis,
+                                                   &outputs);
+            write_metadata(&analysis.ty_cx.sess, &id, metadata.as_slice(), &outputs);
+            return;
+        }
         let (tcx, trans) = phase_4_translate_to_llvm(expanded_crate,
                                                      analysis, &outputs);
+        if tcx.sess.opts.output_types.contains(&link::OutputTypeMetadata) {
+            write_metadata(&tcx.sess, &trans.link.crateid,
+                           trans.metadata.as_slice(), &outputs);
+        }
 
         // Discard interned strings as they are no longer required.
         token::get_ident_interner().clear();
@@ -770,13 +903,19 @@ static architecture_abis : &'static [(&'static str, abi::Architecture)] = &'stat
     ("mips",   abi::Mips)];
 
 pub fn build_target_config(sopts: &session::Options) -> session::Config {
+    match sopts.target_spec {
+        Some(ref spec) => return target_config_from_spec(spec),
+        None => {}
+    }
+
     let os = match get_os(sopts.target_triple) {
       Some(os) => os,
-      None => early_error("unknown operating system")
+      None => early_error(sopts.error_format, "unknown operating system")
     };
     let arch = match get_arch(sopts.target_triple) {
       Some(arch) => arch,
-      None => early_error("unknown architecture: " + sopts.target_triple)
+      None => early_error(sopts.error_format,
+                          "unknown architecture: " + sopts.target_triple)
     };
     let (int_type, uint_type) = match arch {
       abi::X86 => (ast::TyI32, ast::TyU32),
@@ -797,6 +936,45 @@ pub fn build_target_config(sopts: &session::Options) -> session::Config {
         target_strs: target_strs,
         int_type: int_type,
         uint_type: uint_type,
+        target_family: default_target_family(os).to_owned(),
+        linker: None,
+        relocation_model: ~"pic",
+        has_runtime: true,
+    }
+}
+
+fn target_config_from_spec(spec: &target::TargetSpec) -> session::Config {
+    let (int_type, uint_type) = match spec.pointer_width {
+        32 => (ast::TyI32, ast::TyU32),
+        _ => (ast::TyI64, ast::TyU64),
+    };
+    let target_strs = target_strs::t {
+        module_asm: ~"",
+        meta_sect_name: meta_section_name(sess_os_to_meta_os(spec.os)).to_owned(),
+        data_layout: spec.data_layout.clone(),
+        target_triple: spec.llvm_target.clone(),
+        cc_args: spec.cc_args.clone(),
+    };
+    session::Config {
+        os: spec.os,
+        arch: spec.arch,
+        target_strs: target_strs,
+        int_type: int_type,
+        uint_type: uint_type,
+        target_family: match spec.target_family {
+            Some(ref family) => family.clone(),
+            None => default_target_family(spec.os).to_owned(),
+        },
+        linker: spec.linker.clone(),
+        relocation_model: spec.relocation_model.clone().unwrap_or(~"pic"),
+        has_runtime: spec.has_runtime,
+    }
+}
+
+fn default_target_family(os: abi::Os) -> &'static str {
+    match os {
+        abi::OsWin32 => "windows",
+        _ => "unix"
     }
 }
 
@@ -812,7 +990,44 @@ pub fn host_triple() -> ~str {
     (env!("CFG_COMPILER_HOST_TRIPLE")).to_owned()
 }
 
+/// Reads `--error-format`, so that errors in the remaining options can be
+/// reported in the requested format.
+pub fn parse_error_format(matches: &getopts::Matches) -> session::ErrorOutputType {
+    match matches.opt_str("error-format").as_ref().map(|s| s.as_slice()) {
+        None |
+        Some("human") => session::ErrorOutputHuman,
+        Some("json") => session::ErrorOutputJson,
+        Some(arg) => {
+            early_error(session::ErrorOutputHuman,
+                        format!("argument for --error-format must be human \
+                                 or json (instead was `{}`)", arg));
+        }
+    }
+}
+
+/// Looks for `--error-format` in the raw command line `args`, so that errors
+/// in parsing the arguments themselves can be reported in the requested
+/// format. Unrecognized formats are left for `parse_error_format` to reject.
+pub fn early_error_format(args: &[~str]) -> session::ErrorOutputType {
+    for (i, arg) in args.iter().enumerate() {
+        let format = if arg.as_slice() == "--error-format" {
+            if i + 1 < args.len() { args[i + 1].as_slice() } else { "" }
+        } else if arg.starts_with("--error-format=") {
+            arg.slice_from("--error-format=".len())
+        } else {
+            continue
+        };
+        return if format == "json" {
+            session::ErrorOutputJson
+        } else {
+            session::ErrorOutputHuman
+        };
+    }
+    session::ErrorOutputHuman
+}
+
 pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
+    let error_format = parse_error_format(matches);
     let mut crate_types: Vec<CrateType> = Vec::new();
     let unparsed_crate_types = matches.opt_strs("crate-type");
     for unparsed_crate_type in unparsed_crate_types.iter() {
@@ -823,7 +1038,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
                 "staticlib" => session::CrateTypeStaticlib,
                 "dylib"     => session::CrateTypeDylib,
                 "bin"       => session::CrateTypeExecutable,
-                _ => early_error(format!("unknown crate type: `{}`", part))
+                _ => early_error(error_format, format!("unknown crate type: `{}`", part))
             };
             crate_types.push(new_part)
         }
@@ -836,6 +1051,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
     let lint_levels = [lint::allow, lint::warn,
                        lint::deny, lint::forbid];
     let mut lint_opts = Vec::new();
+    let mut plugin_lint_opts = Vec::new();
     let lint_dict = lint::get_lint_dict();
     for level in lint_levels.iter() {
         let level_name = lint::level_to_str(*level);
@@ -847,10 +1063,10 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
         for lint_name in flags.iter() {
             let lint_name = lint_name.replace("-", "_");
             match lint_dict.find_equiv(&lint_name) {
-              None => {
-                early_error(format!("unknown {} flag: {}",
-                                    level_name, lint_name));
-              }
+              // This may be a lint defined by a plugin, which can't be
+              // checked until the crate has been expanded; see
+              // `lint::check_plugin_lint_opts`.
+              None => plugin_lint_opts.push((lint_name.clone(), *level)),
               Some(lint) => {
                 lint_opts.push((lint.lint, *level));
               }
@@ -859,16 +1075,25 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
     }
 
     let mut debugging_opts = 0;
+    let mut incremental = None;
     let debug_flags = matches.opt_strs("Z");
     let debug_map = session::debugging_opts_map();
     for debug_flag in debug_flags.iter() {
+        if debug_flag.starts_with("incremental=") {
+            let dir = debug_flag.slice_from("incremental=".len());
+            if dir.is_empty() {
+                early_error(error_format, "-Z incremental requires a directory")
+            }
+            incremental = Some(Path::new(dir));
+            continue
+        }
         let mut this_bit = 0;
         for tuple in debug_map.iter() {
             let (name, bit) = match *tuple { (ref a, _, b) => (a, b) };
             if *name == *debug_flag { this_bit = bit; break; }
         }
         if this_bit == 0 {
-            early_error(format!("unknown debug flag: {}", *debug_flag))
+            early_error(error_format, format!("unknown debug flag: {}", *debug_flag))
         }
         debugging_opts |= this_bit;
     }
@@ -887,8 +1112,11 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
                     "ir"   => link::OutputTypeLlvmAssembly,
                     "bc"   => link::OutputTypeBitcode,
                     "obj"  => link::OutputTypeObject,
+                    "metadata" => link::OutputTypeMetadata,
+                    "header" => link::OutputTypeCHeader,
                     "link" => link::OutputTypeExe,
-                    _ => early_error(format!("unknown emission type: `{}`", part))
+                    _ => early_error(error_format,
+                                     format!("unknown emission type: `{}`", part))
                 };
                 output_types.push(output_type)
             }
@@ -902,12 +1130,24 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
 
     let sysroot_opt = matches.opt_str("sysroot").map(|m| Path::new(m));
     let target = matches.opt_str("target").unwrap_or(host_triple());
+    let target_spec = if target::is_spec_path(target) {
+        match target::TargetSpec::load(&Path::new(target.as_slice())) {
+            Ok(spec) => Some(spec),
+            Err(msg) => early_error(error_format, msg),
+        }
+    } else {
+        None
+    };
+    let target = match target_spec {
+        Some(ref spec) => spec.llvm_target.clone(),
+        None => target,
+    };
     let opt_level = {
         if (debugging_opts & session::NO_OPT) != 0 {
             No
         } else if matches.opt_present("O") {
             if matches.opt_present("opt-level") {
-                early_error("-O and --opt-level both provided");
+                early_error(error_format, "-O and --opt-level both provided");
             }
             Default
         } else if matches.opt_present("opt-level") {
@@ -918,8 +1158,9 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
                 Some("2") => Default,
                 Some("3") => Aggressive,
                 Some(arg) => {
-                    early_error(format!("optimization level needs to be between 0-3 \
-                                        (instead was `{}`)", arg));
+                    early_error(error_format,
+                                format!("optimization level needs to be between 0-3 \
+                                         (instead was `{}`)", arg));
                 }
             }
         } else {
@@ -929,7 +1170,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
     let gc = debugging_opts & session::GC != 0;
     let debuginfo = if matches.opt_present("g") {
         if matches.opt_present("debuginfo") {
-            early_error("-g and --debuginfo both provided");
+            early_error(error_format, "-g and --debuginfo both provided");
         }
         FullDebugInfo
     } else if matches.opt_present("debuginfo") {
@@ -939,8 +1180,9 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
             None      |
             Some("2") => FullDebugInfo,
             Some(arg) => {
-                early_error(format!("optimization level needs to be between 0-3 \
-                                    (instead was `{}`)", arg));
+                early_error(error_format,
+                            format!("optimization level needs to be between 0-3 \
+                                     (instead was `{}`)", arg));
             }
         }
     } else {
@@ -959,7 +1201,36 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
     let print_metas = (matches.opt_present("crate-id"),
                        matches.opt_present("crate-name"),
                        matches.opt_present("crate-file-name"));
-    let cg = build_codegen_options(matches);
+    let cg = build_codegen_options(matches, error_format);
+
+    let apply_suggestions = matches.opt_present("apply-suggestions");
+
+    let lint_policy = matches.opt_str("lint-config").map(|path| {
+        let path = Path::new(path);
+        let src = match io::File::open(&path).read_to_str() {
+            Ok(src) => src,
+            Err(e) => {
+                early_error(error_format,
+                            format!("couldn't read lint policy `{}`: {}",
+                                    path.display(), e));
+            }
+        };
+        match lint::LintPolicy::parse(&path, src) {
+            Ok(policy) => policy,
+            Err(msg) => early_error(error_format, msg),
+        }
+    });
+
+    let remap_path_prefix = matches.opt_strs("remap-path-prefix").move_iter().map(|arg| {
+        match arg.find('=') {
+            Some(i) => (arg.slice_to(i).to_owned(), arg.slice_from(i + 1).to_owned()),
+            None => {
+                early_error(error_format,
+                            format!("argument for --remap-path-prefix must be \
+                                     of the form FROM=TO (instead was `{}`)", arg));
+            }
+        }
+    }).collect();
 
     session::Options {
         crate_types: crate_types,
@@ -967,10 +1238,13 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
         optimize: opt_level,
         debuginfo: debuginfo,
         lint_opts: lint_opts,
+        plugin_lint_opts: plugin_lint_opts,
+        lint_policy: lint_policy,
         output_types: output_types,
         addl_lib_search_paths: RefCell::new(addl_lib_search_paths),
         maybe_sysroot: sysroot_opt,
         target_triple: target,
+        target_spec: target_spec,
         cfg: cfg,
         test: test,
         parse_only: parse_only,
@@ -980,10 +1254,17 @@ pub fn build_session_options(matches: &getopts::Matches) -> session::Options {
         write_dependency_info: write_dependency_info,
         print_metas: print_metas,
         cg: cg,
+        error_format: error_format,
+        apply_suggestions: apply_suggestions,
+        incremental: incremental,
+        freestanding: matches.opt_present("freestanding"),
+        deterministic: matches.opt_present("deterministic"),
+        remap_path_prefix: remap_path_prefix,
     }
 }
 
-pub fn build_codegen_options(matches: &getopts::Matches)
+pub fn build_codegen_options(matches: &getopts::Matches,
+                             error_format: session::ErrorOutputType)
         -> session::CodegenOptions
 {
     let mut cg = session::basic_codegen_options();
@@ -997,9 +1278,11 @@ pub fn build_codegen_options(matches: &getopts::Matches)
             if option_to_lookup.as_slice() != candidate { continue }
             if !setter(&mut cg, value) {
                 match value {
-                    Some(..) => early_error(format!("codegen option `{}` takes \
+                    Some(..) => early_error(error_format,
+                                            format!("codegen option `{}` takes \
                                                      no value", key)),
-                    None => early_error(format!("codegen option `{0}` requires \
+                    None => early_error(error_format,
+                                        format!("codegen option `{0}` requires \
                                                  a value (-C {0}=<value>)",
                                                 key))
                 }
@@ -1008,18 +1291,22 @@ pub fn build_codegen_options(matches: &getopts::Matches)
             break;
         }
         if !found {
-            early_error(format!("unknown codegen option: `{}`", key));
+            early_error(error_format, format!("unknown codegen option: `{}`", key));
         }
     }
+    if cg.codegen_units == 0 {
+        early_error(error_format, "codegen option `codegen-units` must be at least 1");
+    }
     return cg;
 }
 
 pub fn build_session(sopts: session::Options,
                      local_crate_source_file: Option<Path>)
                      -> Session {
-    let codemap = codemap::CodeMap::new();
+    let path_mapping = codemap::FilePathMapping::new(sopts.remap_path_prefix.clone());
+    let codemap = codemap::CodeMap::with_path_mapping(path_mapping);
     let diagnostic_handler =
-        diagnostic::default_handler();
+        diagnostic::mk_handler(stderr_emitter(sopts.error_format));
     let span_diagnostic_handler =
         diagnostic::mk_span_handler(diagnostic_handler, codemap);
 
@@ -1055,8 +1342,12 @@ pub fn build_session_(sopts: session::Options,
         entry_fn: RefCell::new(None),
         entry_type: Cell::new(None),
         macro_registrar_fn: RefCell::new(None),
+        lint_registrar_fn: RefCell::new(None),
+        lint_registry: RefCell::new(lint::LintRegistry::new()),
+        plugin_libs: RefCell::new(Vec::new()),
         default_sysroot: default_sysroot,
         building_library: Cell::new(false),
+        freestanding: Cell::new(false),
         local_crate_source_file: local_crate_source_file,
         working_dir: os::getcwd(),
         lints: RefCell::new(NodeMap::new()),
@@ -1091,7 +1382,7 @@ pub fn optgroups() -> Vec<getopts::OptGroup> {
   optmulti("", "crate-type", "Comma separated list of types of crates for the compiler to emit",
            "[bin|lib|rlib|dylib|staticlib]"),
   optmulti("", "emit", "Comma separated list of types of output for the compiler to emit",
-           "[asm|bc|ir|obj|link]"),
+           "[asm|bc|ir|obj|metadata|header|link]"),
   optflag("", "crate-id", "Output the crate id and exit"),
   optflag("", "crate-name", "Output the crate name and exit"),
   optflag("", "crate-file-name", "Output the file(s) that would be written if compilation \
@@ -1119,13 +1410,30 @@ pub fn optgroups() -> Vec<getopts::OptGroup> {
   optflagopt("", "dep-info", "Output dependency info to <filename> after compiling", "FILENAME"),
   optopt("", "sysroot", "Override the system root", "PATH"),
   optflag("", "test", "Build a test harness"),
+  optflag("", "freestanding", "Build a static library or object without the standard \
+          library or any runtime, as with #![freestanding]"),
+  optflag("", "deterministic", "Make the output depend on nothing but the \
+                                 input, such as when archives were made"),
+  optmulti("", "remap-path-prefix", "Replace the prefix FROM of source paths \
+                                     with TO in debuginfo and file!()", "FROM=TO"),
+  optopt("", "error-format", "How errors and other messages are produced", "human|json"),
+  optflag("", "apply-suggestions", "Rewrite the source files in place with the \
+                                    compiler's suggested fixes"),
+  optopt("", "explain", "Print an extended explanation of the error code CODE", "CODE"),
+  optopt("", "daemon", "Analyze the crate and answer queries about it on a Unix \
+                        socket instead of compiling it", "SOCKET"),
+  optopt("", "coverage-report", "Render the counts collected by a program built \
+                                 with -Z instrument-coverage, given its .covmap \
+                                 file", "lcov|annotate"),
   optopt("", "target", "Target triple cpu-manufacturer-kernel[-os]
                         to compile for (see chapter 3.4 of http://www.sourceware.org/autobook/
-                        for details)", "TRIPLE"),
+                        for details), or the path of a JSON file describing
+                        the target", "TRIPLE"),
   optmulti("W", "warn", "Set lint warnings", "OPT"),
   optmulti("A", "allow", "Set lint allowed", "OPT"),
   optmulti("D", "deny", "Set lint denied", "OPT"),
   optmulti("F", "forbid", "Set lint forbidden", "OPT"),
+  optopt("", "lint-config", "Read lint levels from a policy file", "PATH"),
   optmulti("C", "codegen", "Set a codegen option", "OPT[=VALUE]"),
   optmulti("Z", "", "Set internal debugging options", "FLAG"),
   optflag( "v", "version", "Print version info and exit"))
@@ -1153,6 +1461,8 @@ impl OutputFilenames {
             link::OutputTypeAssembly => base.with_extension("s"),
             link::OutputTypeLlvmAssembly => base.with_extension("ll"),
             link::OutputTypeObject => base.with_extension("o"),
+            link::OutputTypeMetadata => base.with_extension("rmeta"),
+            link::OutputTypeCHeader => base.with_extension("h"),
             link::OutputTypeExe => base,
         }
     }
@@ -1214,9 +1524,20 @@ pub fn build_output_filenames(input: &Input,
     }
 }
 
-pub fn early_error(msg: &str) -> ! {
-    let mut emitter = diagnostic::EmitterWriter::stderr();
-    emitter.emit(None, msg, diagnostic::Fatal);
+fn stderr_emitter(output: session::ErrorOutputType) -> ~Emitter:Send {
+    match output {
+        session::ErrorOutputHuman => {
+            ~diagnostic::EmitterWriter::stderr() as ~Emitter:Send
+        }
+        session::ErrorOutputJson => {
+            ~diagnostic::JsonEmitter::stderr() as ~Emitter:Send
+        }
+    }
+}
+
+pub fn early_error(output: session::ErrorOutputType, msg: &str) -> ! {
+    let mut emitter = stderr_emitter(output);
+    emitter.emit(None, msg, None, diagnostic::Fatal);
     fail!(diagnostic::FatalError);
 }
 
@@ -10,5 +10,7 @@
 
 pub use syntax::diagnostic;
 
+pub mod coverage;
+pub mod daemon;
 pub mod driver;
 pub mod session;
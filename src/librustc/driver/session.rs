@@ -21,14 +21,19 @@ use util::nodemap::NodeMap;
 use syntax::attr::AttrMetaMethods;
 use syntax::ast::NodeId;
 use syntax::ast::{IntTy, UintTy};
-use syntax::codemap::Span;
+use syntax::codemap::{Pos, Span};
 use syntax::diagnostic;
 use syntax::parse::ParseSess;
 use syntax::{abi, ast, codemap};
 use syntax;
 
 use std::cell::{Cell, RefCell};
-use collections::HashSet;
+use std::io;
+use std::mem::replace;
+use std::str;
+use std::strbuf::StrBuf;
+use std::unstable::dynamic_lib::DynamicLibrary;
+use collections::{HashMap, HashSet};
 
 pub struct Config {
     pub os: abi::Os,
@@ -36,6 +41,10 @@ pub struct Config {
     pub target_strs: target_strs::t,
     pub int_type: IntTy,
     pub uint_type: UintTy,
+    pub target_family: ~str,
+    pub linker: Option<~str>,
+    pub relocation_model: ~str,
+    pub has_runtime: bool,
 }
 
 macro_rules! debugging_opts(
@@ -69,7 +78,12 @@ debugging_opts!(
         PRINT_LLVM_PASSES,
         LTO,
         AST_JSON,
-        AST_JSON_NOEXPAND
+        AST_JSON_NOEXPAND,
+        SAVE_ANALYSIS,
+        SELF_PROFILE,
+        PRINT_MONO_ITEMS,
+        INSTRUMENT_COVERAGE,
+        THIN_LTO
     ]
     0
 )
@@ -102,7 +116,17 @@ pub fn debugging_opts_map() -> Vec<(&'static str, &'static str, u64)> {
       PRINT_LLVM_PASSES),
      ("lto", "Perform LLVM link-time optimizations", LTO),
      ("ast-json", "Print the AST as JSON and halt", AST_JSON),
-     ("ast-json-noexpand", "Print the pre-expansion AST as JSON and halt", AST_JSON_NOEXPAND))
+     ("ast-json-noexpand", "Print the pre-expansion AST as JSON and halt", AST_JSON_NOEXPAND),
+     ("save-analysis", "Write out resolved names and types for code indexers",
+      SAVE_ANALYSIS),
+     ("self-profile", "Report the time and memory spent per pass and per item",
+      SELF_PROFILE),
+     ("print-mono-items", "Print every monomorphization and the instructions \
+                           it generated", PRINT_MONO_ITEMS),
+     ("instrument-coverage", "Count how often each block of code runs; see \
+                              --coverage-report", INSTRUMENT_COVERAGE),
+     ("thin-lto", "Import the small and hot functions of upstream crates \
+                   instead of performing full LTO", THIN_LTO))
 }
 
 #[deriving(Clone, Eq)]
@@ -120,6 +144,12 @@ pub enum DebugInfoLevel {
     FullDebugInfo,
 }
 
+#[deriving(Clone, Eq)]
+pub enum ErrorOutputType {
+    ErrorOutputHuman,
+    ErrorOutputJson,
+}
+
 #[deriving(Clone)]
 pub struct Options {
     // The crate config requested for the session, which may be combined
@@ -130,6 +160,10 @@ pub struct Options {
     pub optimize: OptLevel,
     pub debuginfo: DebugInfoLevel,
     pub lint_opts: Vec<(lint::Lint, lint::level)> ,
+    // Levels given for lints which aren't built in, which are looked up
+    // once lint plugins have been loaded.
+    pub plugin_lint_opts: Vec<(~str, lint::level)>,
+    pub lint_policy: Option<lint::LintPolicy>,
     pub output_types: Vec<back::link::OutputType> ,
     // This was mutable for rustpkg, which updates search paths based on the
     // parsed code. It remains mutable in case its replacements wants to use
@@ -137,6 +171,9 @@ pub struct Options {
     pub addl_lib_search_paths: RefCell<HashSet<Path>>,
     pub maybe_sysroot: Option<Path>,
     pub target_triple: ~str,
+    /// The target description `--target` named instead of a triple, if any.
+    /// `target_triple` is then its LLVM target.
+    pub target_spec: Option<back::target::TargetSpec>,
     // User-specified cfg meta items. The compiler itself will add additional
     // items to the crate config, and during parsing the entire crate config
     // will be added to the crate AST node.  This should not be used for
@@ -152,6 +189,19 @@ pub struct Options {
     /// Crate id-related things to maybe print. It's (crate_id, crate_name, crate_file_name).
     pub print_metas: (bool, bool, bool),
     pub cg: CodegenOptions,
+    /// How diagnostics are rendered: as text for humans or as JSON for tools.
+    pub error_format: ErrorOutputType,
+    /// Whether to rewrite the source files with the suggested fixes.
+    pub apply_suggestions: bool,
+    /// Directory holding the incremental compilation cache, if any.
+    pub incremental: Option<Path>,
+    /// Whether `--freestanding` was given; see `Session::freestanding`.
+    pub freestanding: bool,
+    /// Whether `--deterministic` was given, so that the same input gives
+    /// the same output byte for byte.
+    pub deterministic: bool,
+    /// The `--remap-path-prefix` mappings, as (from, to) pairs.
+    pub remap_path_prefix: Vec<(~str, ~str)>,
 }
 
 // The type of entry function, so
@@ -182,13 +232,22 @@ pub struct Session {
     pub entry_fn: RefCell<Option<(NodeId, codemap::Span)>>,
     pub entry_type: Cell<Option<EntryFnType>>,
     pub macro_registrar_fn: RefCell<Option<ast::DefId>>,
+    pub lint_registrar_fn: RefCell<Option<ast::DefId>>,
+    pub lint_registry: RefCell<lint::LintRegistry>,
+    // The dylibs of the lint plugins, which must outlive the passes they
+    // registered and so come after `lint_registry`.
+    pub plugin_libs: RefCell<Vec<DynamicLibrary>>,
     pub default_sysroot: Option<Path>,
     pub building_library: Cell<bool>,
+    // Whether the crate is built without the standard library or any
+    // runtime, by `--freestanding` or `#![freestanding]`.
+    pub freestanding: Cell<bool>,
     // The name of the root source file of the crate, in the local file system. The path is always
     // expected to be absolute. `None` means that there is no source file.
     pub local_crate_source_file: Option<Path>,
     pub working_dir: Path,
-    pub lints: RefCell<NodeMap<Vec<(lint::Lint, codemap::Span, ~str)>>>,
+    pub lints: RefCell<NodeMap<Vec<(lint::Lint, codemap::Span, ~str,
+                                    Option<diagnostic::Suggestion>)>>>,
     pub node_id: Cell<ast::NodeId>,
     pub crate_types: RefCell<Vec<CrateType>>,
     pub features: front::feature_gate::Features,
@@ -202,12 +261,18 @@ impl Session {
     pub fn span_fatal(&self, sp: Span, msg: &str) -> ! {
         self.diagnostic().span_fatal(sp, msg)
     }
+    pub fn span_fatal_with_code(&self, sp: Span, msg: &str, code: &str) -> ! {
+        self.diagnostic().span_fatal_with_code(sp, msg, code)
+    }
     pub fn fatal(&self, msg: &str) -> ! {
         self.diagnostic().handler().fatal(msg)
     }
     pub fn span_err(&self, sp: Span, msg: &str) {
         self.diagnostic().span_err(sp, msg)
     }
+    pub fn span_err_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.diagnostic().span_err_with_code(sp, msg, code)
+    }
     pub fn err(&self, msg: &str) {
         self.diagnostic().handler().err(msg)
     }
@@ -223,6 +288,9 @@ impl Session {
     pub fn span_warn(&self, sp: Span, msg: &str) {
         self.diagnostic().span_warn(sp, msg)
     }
+    pub fn span_warn_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.diagnostic().span_warn_with_code(sp, msg, code)
+    }
     pub fn warn(&self, msg: &str) {
         self.diagnostic().handler().warn(msg)
     }
@@ -235,6 +303,12 @@ impl Session {
     pub fn fileline_note(&self, sp: Span, msg: &str) {
         self.diagnostic().fileline_note(sp, msg)
     }
+    pub fn span_suggestion(&self, sp: Span, msg: &str, replacement: ~str) {
+        self.diagnostic().span_suggestion(sp, msg, replacement)
+    }
+    pub fn suggest(&self, sugg: diagnostic::Suggestion) {
+        self.diagnostic().suggest(sugg)
+    }
     pub fn note(&self, msg: &str) {
         self.diagnostic().handler().note(msg)
     }
@@ -255,12 +329,67 @@ impl Session {
                     id: ast::NodeId,
                     sp: Span,
                     msg: ~str) {
+        self.add_lint_with_suggestion(lint, id, sp, msg, None)
+    }
+    pub fn add_lint_with_suggestion(&self,
+                                    lint: lint::Lint,
+                                    id: ast::NodeId,
+                                    sp: Span,
+                                    msg: ~str,
+                                    sugg: Option<diagnostic::Suggestion>) {
         let mut lints = self.lints.borrow_mut();
         match lints.find_mut(&id) {
-            Some(arr) => { arr.push((lint, sp, msg)); return; }
+            Some(arr) => { arr.push((lint, sp, msg, sugg)); return; }
             None => {}
         }
-        lints.insert(id, vec!((lint, sp, msg)));
+        lints.insert(id, vec!((lint, sp, msg, sugg)));
+    }
+    /// Rewrite the crate's source files in place, applying every
+    /// machine-applicable suggestion reported so far. Suggestions which
+    /// overlap an earlier one in the same file are dropped.
+    pub fn apply_suggestions(&self) {
+        let suggestions = replace(&mut *self.diagnostic().suggestions.borrow_mut(),
+                                  Vec::new());
+        let mut edits: HashMap<~str, Vec<(uint, uint, ~str)>> = HashMap::new();
+        for sugg in suggestions.move_iter().filter(|s| s.machine_applicable) {
+            let lo = self.codemap().lookup_byte_offset(sugg.span.lo);
+            let hi = self.codemap().lookup_byte_offset(sugg.span.hi);
+            if lo.fm.start_pos != hi.fm.start_pos || !lo.fm.is_real_file() {
+                continue
+            }
+            edits.find_or_insert(lo.fm.name.clone(), Vec::new())
+                 .push((lo.pos.to_uint(), hi.pos.to_uint(), sugg.replacement));
+        }
+
+        for (name, edits) in edits.move_iter() {
+            let path = Path::new(name.as_slice());
+            let result = io::File::open(&path).read_to_end().and_then(|bytes| {
+                let src = match str::from_utf8(bytes.as_slice()) {
+                    Some(src) => src,
+                    None => {
+                        self.warn(format!("not applying suggestions to {}: \
+                                           it is not valid UTF-8", path.display()));
+                        return Ok(())
+                    }
+                };
+                // The codemap strips the byte order mark, so offsets are
+                // relative to whatever follows it.
+                let (bom, src) = if src.starts_with("\ufeff") {
+                    ("\ufeff", src.slice_from(3))
+                } else {
+                    ("", src)
+                };
+                let fixed = splice_suggestions(src, edits);
+                io::File::create(&path).write_str(bom.to_owned() + fixed)
+            });
+            match result {
+                Ok(()) => {}
+                Err(e) => {
+                    self.err(format!("failed to apply suggestions to {}: {}",
+                                     path.display(), e));
+                }
+            }
+        }
     }
     pub fn next_node_id(&self) -> ast::NodeId {
         self.reserve_node_ids(1)
@@ -311,12 +440,39 @@ impl Session {
     pub fn lto(&self) -> bool {
         self.debugging_opt(LTO)
     }
+    pub fn thin_lto(&self) -> bool {
+        self.debugging_opt(THIN_LTO) && !self.lto()
+    }
     pub fn no_landing_pads(&self) -> bool {
-        self.debugging_opt(NO_LANDING_PADS)
+        // Nothing unwinds without a runtime.
+        self.debugging_opt(NO_LANDING_PADS) || self.freestanding.get()
+    }
+    /// Whether the runtime libraries are linked in, which they are unless
+    /// the crate is freestanding or the target has no runtime.
+    pub fn has_runtime(&self) -> bool {
+        self.targ_cfg.has_runtime && !self.freestanding.get()
     }
     pub fn show_span(&self) -> bool {
         self.debugging_opt(SHOW_SPAN)
     }
+    pub fn save_analysis(&self) -> bool {
+        self.debugging_opt(SAVE_ANALYSIS)
+    }
+    pub fn self_profile(&self) -> bool {
+        self.debugging_opt(SELF_PROFILE)
+    }
+    pub fn print_mono_items(&self) -> bool {
+        self.debugging_opt(PRINT_MONO_ITEMS)
+    }
+    pub fn instrument_coverage(&self) -> bool {
+        self.debugging_opt(INSTRUMENT_COVERAGE)
+    }
+    /// Whether `--emit=metadata` is the only output asked for, in which case
+    /// the crate is never translated.
+    pub fn metadata_only(&self) -> bool {
+        !self.opts.output_types.is_empty() &&
+            self.opts.output_types.iter().all(|t| *t == back::link::OutputTypeMetadata)
+    }
     pub fn filesearch<'a>(&'a self) -> filesearch::FileSearch<'a> {
         let sysroot = match self.opts.maybe_sysroot {
             Some(ref sysroot) => sysroot,
@@ -338,10 +494,13 @@ pub fn basic_options() -> Options {
         optimize: No,
         debuginfo: NoDebugInfo,
         lint_opts: Vec::new(),
+        plugin_lint_opts: Vec::new(),
+        lint_policy: None,
         output_types: Vec::new(),
         addl_lib_search_paths: RefCell::new(HashSet::new()),
         maybe_sysroot: None,
         target_triple: host_triple(),
+        target_spec: None,
         cfg: Vec::new(),
         test: false,
         parse_only: false,
@@ -351,6 +510,12 @@ pub fn basic_options() -> Options {
         write_dependency_info: (false, None),
         print_metas: (false, false, false),
         cg: basic_codegen_options(),
+        error_format: ErrorOutputHuman,
+        apply_suggestions: false,
+        incremental: None,
+        freestanding: false,
+        deterministic: false,
+        remap_path_prefix: Vec::new(),
     }
 }
 
@@ -409,6 +574,13 @@ macro_rules! cgoptions(
             }
         }
 
+        fn parse_uint(slot: &mut uint, v: Option<&str>) -> bool {
+            match v.and_then(from_str) {
+                Some(n) => { *slot = n; true },
+                None => false,
+            }
+        }
+
         fn parse_list(slot: &mut Vec<~str>, v: Option<&str>)
                       -> bool {
             match v {
@@ -458,8 +630,15 @@ cgoptions!(
         "prefer dynamic linking to static linking"),
     no_integrated_as: bool = (false, parse_bool,
         "use an external assembler rather than LLVM's integrated one"),
-    relocation_model: ~str = (~"pic", parse_string,
+    relocation_model: Option<~str> = (None, parse_opt_string,
          "choose the relocation model to use (llc -relocation-model for details)"),
+    codegen_units: uint = (1, parse_uint,
+        "divide the crate into N units to optimize and emit in parallel"),
+    profile_generate: Option<~str> = (None, parse_opt_string,
+        "count how often each block of code runs, appending the counts to \
+         the given profile whenever the program exits"),
+    profile_use: Option<~str> = (None, parse_opt_string,
+        "optimize with the profile recorded by a build with profile-generate"),
 )
 
 // Seems out of place, but it uses session, so I'm putting it here
@@ -467,8 +646,15 @@ pub fn expect<T:Clone>(sess: &Session, opt: Option<T>, msg: || -> ~str) -> T {
     diagnostic::expect(sess.diagnostic(), opt, msg)
 }
 
+pub fn freestanding(options: &Options, krate: &ast::Crate) -> bool {
+    options.freestanding ||
+        syntax::attr::contains_name(krate.attrs.as_slice(), "freestanding")
+}
+
 pub fn building_library(options: &Options, krate: &ast::Crate) -> bool {
     if options.test { return false }
+    // Freestanding crates are always libraries of some sort.
+    if freestanding(options, krate) { return true }
     for output in options.crate_types.iter() {
         match *output {
             CrateTypeExecutable => {}
@@ -530,7 +716,11 @@ pub fn collect_crate_types(session: &Session,
     });
     base.extend(iter);
     if base.len() == 0 {
-        base.push(CrateTypeExecutable);
+        if session.freestanding.get() {
+            base.push(CrateTypeStaticlib);
+        } else {
+            base.push(CrateTypeExecutable);
+        }
     }
     base.as_mut_slice().sort();
     base.dedup();
@@ -548,3 +738,65 @@ pub fn sess_os_to_meta_os(os: abi::Os) -> metadata::loader::Os {
         abi::OsFreebsd => loader::OsFreebsd
     }
 }
+
+fn splice_suggestions(src: &str, mut edits: Vec<(uint, uint, ~str)>) -> ~str {
+    edits.as_mut_slice().sort_by(|&(a, _, _), &(b, _, _)| a.cmp(&b));
+    let mut out = StrBuf::new();
+    let mut pos = 0;
+    for &(lo, hi, ref replacement) in edits.iter() {
+        if hi > src.len() { continue }
+        // A deletion that leaves its line blank takes the line with it.
+        let (lo, hi) = if replacement.is_empty() {
+            blank_line_extent(src, lo, hi)
+        } else {
+            (lo, hi)
+        };
+        if lo < pos { continue }
+        out.push_str(src.slice(pos, lo));
+        out.push_str(*replacement);
+        pos = hi;
+    }
+    out.push_str(src.slice_from(pos));
+    out.into_owned()
+}
+
+// The whole line around `lo..hi`, newline included, if nothing but
+// whitespace is left on it once `lo..hi` is removed.
+fn blank_line_extent(src: &str, lo: uint, hi: uint) -> (uint, uint) {
+    let start = src.slice_to(lo).rfind('\n').map_or(0, |i| i + 1);
+    let end = src.slice_from(hi).find('\n').map_or(src.len(), |i| hi + i + 1);
+    let blank = |s: &str| s.chars().all(|c| c.is_whitespace());
+    if blank(src.slice(start, lo)) && blank(src.slice(hi, end)) {
+        (start, end)
+    } else {
+        (lo, hi)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use driver::session::splice_suggestions;
+
+    #[test]
+    fn test_splice_suggestions() {
+        let src = "let mut x = (1);";
+        let edits = vec!((12, 15, ~"1"), (4, 9, ~"x"));
+        assert_eq!(splice_suggestions(src, edits), ~"let x = 1;");
+    }
+
+    // Overlapping suggestions can't both be applied, so the later one is
+    // dropped.
+    #[test]
+    fn test_splice_suggestions_overlapping() {
+        let src = "foo(bar)";
+        let edits = vec!((4, 7, ~"baz"), (0, 8, ~"qux"));
+        assert_eq!(splice_suggestions(src, edits), ~"qux");
+    }
+
+    #[test]
+    fn test_splice_suggestions_removed_line() {
+        let src = "use a;\n    use b; use c;\nfn f() {}\n";
+        let edits = vec!((0, 6, ~""), (11, 17, ~""));
+        assert_eq!(splice_suggestions(src, edits), ~"     use c;\nfn f() {}\n");
+    }
+}
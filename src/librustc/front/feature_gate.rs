@@ -47,6 +47,7 @@ static KNOWN_FEATURES: &'static [(&'static str, Status)] = &[
     ("link_args", Active),
     ("phase", Active),
     ("macro_registrar", Active),
+    ("lint_registrar", Active),
     ("log_syntax", Active),
     ("trace_macros", Active),
     ("concat_idents", Active),
@@ -55,6 +56,8 @@ static KNOWN_FEATURES: &'static [(&'static str, Status)] = &[
     ("default_type_params", Active),
     ("quote", Active),
     ("linkage", Active),
+    ("const_fn", Active),
+    ("associated_types", Active),
 
     // These are used to test this portion of the compiler, they don't actually
     // mean anything
@@ -188,6 +191,30 @@ impl<'a> Visitor<()> for Context<'a> {
                                       "cross-crate macro exports are \
                                        experimental and possibly buggy");
                 }
+                if attr::contains_name(i.attrs.as_slice(), "lint_registrar") {
+                    self.gate_feature("lint_registrar", i.span,
+                                      "lint plugins are experimental and \
+                                       their interface may change");
+                }
+                if attr::contains_name(i.attrs.as_slice(), "const_fn") {
+                    self.gate_feature("const_fn", i.span,
+                                      "constant functions are experimental and \
+                                       only support a subset of the language");
+                }
+            }
+
+            ast::ItemTrait(ref generics, _, _) => {
+                if generics.ty_params.iter().any(|param| param.associated) {
+                    self.gate_feature("associated_types", i.span,
+                                      "associated types are experimental");
+                }
+            }
+
+            ast::ItemImpl(_, Some(ref trait_ref), _, _) => {
+                if !trait_ref.bindings.is_empty() {
+                    self.gate_feature("associated_types", trait_ref.bindings.get(0).span,
+                                      "associated types are experimental");
+                }
             }
 
             ast::ItemStruct(..) => {
@@ -286,6 +313,10 @@ impl<'a> Visitor<()> for Context<'a> {
 
     fn visit_generics(&mut self, generics: &ast::Generics, _: ()) {
         for type_parameter in generics.ty_params.iter() {
+            if type_parameter.associated {
+                // Gated on the trait declaring it.
+                continue;
+            }
             match type_parameter.default {
                 Some(ty) => {
                     self.gate_feature("default_type_params", ty.span,
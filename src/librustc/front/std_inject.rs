@@ -26,7 +26,7 @@ pub static VERSION: &'static str = "0.11-pre";
 
 pub fn maybe_inject_crates_ref(sess: &Session, krate: ast::Crate)
                                -> ast::Crate {
-    if use_std(&krate) {
+    if use_std(sess, &krate) {
         inject_crates_ref(sess, krate)
     } else {
         krate
@@ -34,15 +34,18 @@ pub fn maybe_inject_crates_ref(sess: &Session, krate: ast::Crate)
 }
 
 pub fn maybe_inject_prelude(sess: &Session, krate: ast::Crate) -> ast::Crate {
-    if use_std(&krate) {
+    if use_std(sess, &krate) {
         inject_prelude(sess, krate)
     } else {
         krate
     }
 }
 
-fn use_std(krate: &ast::Crate) -> bool {
-    !attr::contains_name(krate.attrs.as_slice(), "no_std")
+fn use_std(sess: &Session, krate: &ast::Crate) -> bool {
+    // A freestanding crate has neither the standard library nor its prelude,
+    // though it may still link to other crates explicitly.
+    !sess.freestanding.get() &&
+        !attr::contains_name(krate.attrs.as_slice(), "no_std")
 }
 
 fn use_start(krate: &ast::Crate) -> bool {
@@ -93,6 +93,7 @@ pub mod middle {
     pub mod graph;
     pub mod cfg;
     pub mod dead;
+    pub mod save;
 }
 
 pub mod front {
@@ -108,11 +109,13 @@ pub mod back {
     pub mod abi;
     pub mod archive;
     pub mod arm;
+    pub mod incremental;
     pub mod link;
     pub mod lto;
     pub mod mips;
     pub mod rpath;
     pub mod svh;
+    pub mod target;
     pub mod target_strs;
     pub mod x86;
     pub mod x86_64;
@@ -120,6 +123,8 @@ pub mod back {
 
 pub mod metadata;
 
+pub mod diagnostics;
+
 pub mod driver;
 
 pub mod util {
@@ -128,6 +133,7 @@ pub mod util {
     pub mod sha2;
     pub mod nodemap;
     pub mod fs;
+    pub mod profile;
 }
 
 pub mod lib {
@@ -164,6 +170,8 @@ Available lint options:
     -A <foo>           Allow <foo>
     -D <foo>           Deny <foo>
     -F <foo>           Forbid <foo> (deny, and deny all overrides)
+    --lint-config <f>  Read levels from the policy file <f>, one
+                       `<level> <lint> [<module>]` per line
 ");
 
     let lint_dict = lint::get_lint_dict();
@@ -204,6 +212,8 @@ pub fn describe_debug_flags() {
             }
         }
     }
+    println!("    -Z {:>20s} -- {}", "incremental=DIR",
+             "reuse unchanged functions translated by the previous build");
 }
 
 pub fn describe_codegen_flags() {
@@ -232,7 +242,7 @@ pub fn run_compiler(args: &[~str]) {
         &match getopts::getopts(args, d::optgroups().as_slice()) {
           Ok(m) => m,
           Err(f) => {
-            d::early_error(f.to_err_msg());
+            d::early_error(d::early_error_format(args), f.to_err_msg());
           }
         };
 
@@ -240,6 +250,7 @@ pub fn run_compiler(args: &[~str]) {
         usage(binary);
         return;
     }
+    let error_format = d::parse_error_format(matches);
 
     let lint_flags = matches.opt_strs("W").move_iter().collect::<Vec<_>>().append(
                                     matches.opt_strs("warn").as_slice());
@@ -269,8 +280,39 @@ pub fn run_compiler(args: &[~str]) {
         version(binary);
         return;
     }
+    match matches.opt_str("explain") {
+        Some(code) => {
+            match diagnostics::explain(code.as_slice()) {
+                Some(text) => println!("{}", text),
+                None => d::early_error(error_format,
+                                       format!("no extended information for {}", code)),
+            }
+            return;
+        }
+        None => {}
+    }
+    match matches.opt_str("daemon") {
+        Some(socket) => {
+            driver::daemon::run(args.move_iter().collect(), Path::new(socket));
+            return;
+        }
+        None => {}
+    }
+    match matches.opt_str("coverage-report") {
+        Some(format) => {
+            if matches.free.len() != 1 {
+                d::early_error(error_format,
+                               "--coverage-report expects the path of one .covmap file");
+            }
+            driver::coverage::report(format, &Path::new(matches.free.get(0).as_slice()),
+                                     error_format);
+            return;
+        }
+        None => {}
+    }
+
     let (input, input_file_path) = match matches.free.len() {
-      0u => d::early_error("no input filename given"),
+      0u => d::early_error(error_format, "no input filename given"),
       1u => {
         let ifile = matches.free.get(0).as_slice();
         if ifile == "-" {
@@ -281,7 +323,7 @@ pub fn run_compiler(args: &[~str]) {
             (d::FileInput(Path::new(ifile)), Some(Path::new(ifile)))
         }
       }
-      _ => d::early_error("multiple input filenames provided")
+      _ => d::early_error(error_format, "multiple input filenames provided")
     };
 
     let sopts = d::build_session_options(matches);
@@ -307,7 +349,7 @@ pub fn run_compiler(args: &[~str]) {
                 d::list_metadata(&sess, &(*ifile), &mut stdout).unwrap();
             }
             d::StrInput(_) => {
-                d::early_error("can not list metadata for stdin");
+                d::early_error(error_format, "can not list metadata for stdin");
             }
         }
         return;
@@ -360,20 +402,20 @@ fn parse_crate_attrs(sess: &session::Session, input: &d::Input) ->
     result.move_iter().collect()
 }
 
+// FIXME: This is a hack for newsched since it doesn't support split stacks.
+// rustc needs a lot of stack! When optimizations are disabled, it needs
+// even *more* stack than usual as well.
+#[cfg(rtopt)]
+static STACK_SIZE: uint = 6000000;  // 6MB
+#[cfg(not(rtopt))]
+static STACK_SIZE: uint = 20000000; // 20MB
+
 /// Run a procedure which will detect failures in the compiler and print nicer
 /// error messages rather than just failing the test.
 ///
 /// The diagnostic emitter yielded to the procedure should be used for reporting
 /// errors of the compiler.
 pub fn monitor(f: proc():Send) {
-    // FIXME: This is a hack for newsched since it doesn't support split stacks.
-    // rustc needs a lot of stack! When optimizations are disabled, it needs
-    // even *more* stack than usual as well.
-    #[cfg(rtopt)]
-    static STACK_SIZE: uint = 6000000;  // 6MB
-    #[cfg(not(rtopt))]
-    static STACK_SIZE: uint = 20000000; // 20MB
-
     let mut task_builder = task::task().named("rustc");
 
     // FIXME: Hacks on hacks. If the env is trying to override the stack size
@@ -402,6 +444,7 @@ pub fn monitor(f: proc():Send) {
                     emitter.emit(
                         None,
                         "unexpected failure",
+                        None,
                         diagnostic::Bug);
                 }
 
@@ -411,13 +454,14 @@ pub fn monitor(f: proc():Send) {
                     ~"run with `RUST_BACKTRACE=1` for a backtrace",
                 ];
                 for note in xs.iter() {
-                    emitter.emit(None, *note, diagnostic::Note)
+                    emitter.emit(None, *note, None, diagnostic::Note)
                 }
 
                 match r.read_to_str() {
                     Ok(s) => println!("{}", s),
                     Err(e) => emitter.emit(None,
                                            format!("failed to read internal stderr: {}", e),
+                                           None,
                                            diagnostic::Error),
                 }
             }
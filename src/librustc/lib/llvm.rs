@@ -198,7 +198,7 @@ pub enum AsmDialect {
     AD_Intel = 1
 }
 
-#[deriving(Eq)]
+#[deriving(Eq, Clone)]
 #[repr(C)]
 pub enum CodeGenOptLevel {
     CodeGenLevelNone = 0,
@@ -207,6 +207,7 @@ pub enum CodeGenOptLevel {
     CodeGenLevelAggressive = 3,
 }
 
+#[deriving(Clone)]
 #[repr(C)]
 pub enum RelocMode {
     RelocDefault = 0,
@@ -1770,6 +1771,20 @@ pub mod llvm {
         pub fn LLVMRustLinkInExternalBitcode(M: ModuleRef,
                                              bc: *c_char,
                                              len: size_t) -> bool;
+        pub fn LLVMRustLinkInCachedBitcode(M: ModuleRef,
+                                           bc: *c_char,
+                                           len: size_t,
+                                           syms: **c_char,
+                                           nsyms: size_t) -> bool;
+        pub fn LLVMRustImportFunctions(M: ModuleRef,
+                                       bc: *c_char,
+                                       len: size_t,
+                                       limit: c_uint) -> c_int;
+        pub fn LLVMRustPrepareCodegenUnits(M: ModuleRef, Prefix: *c_char);
+        pub fn LLVMRustLoadCodegenUnit(C: ContextRef,
+                                       Path: *c_char,
+                                       Units: c_uint,
+                                       Unit: c_uint) -> ModuleRef;
         pub fn LLVMRustRunRestrictionPass(M: ModuleRef,
                                           syms: **c_char,
                                           len: size_t);
@@ -199,6 +199,13 @@ pub static tag_macro_registrar_fn: uint = 0x63;
 pub static tag_exported_macros: uint = 0x64;
 pub static tag_macro_def: uint = 0x65;
 
+pub static tag_lint_registrar_fn: uint = 0x66;
+
+pub static tag_macro_def_name: uint = 0x67;
+pub static tag_macro_def_body: uint = 0x68;
+
+pub static tag_lto_includes_deps: uint = 0x69;
+
 #[deriving(Clone, Show)]
 pub struct LinkMeta {
     pub crateid: CrateId,
@@ -23,6 +23,7 @@ use metadata::decoder;
 use metadata::loader;
 use metadata::loader::Os;
 use metadata::loader::CratePaths;
+use middle::lint;
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -404,6 +405,14 @@ impl<'a> CrateLoader for Loader<'a> {
         let cstore = &self.env.sess.cstore;
         let registrar = csearch::get_macro_registrar_fn(cstore, cnum)
                             .map(|did| csearch::get_symbol(cstore, did));
+        let lint_registrar = csearch::get_lint_registrar_fn(cstore, cnum)
+                                 .map(|did| csearch::get_symbol(cstore, did));
+        match (&library.dylib, lint_registrar) {
+            (&Some(ref dylib), Some(symbol)) => {
+                lint::load_plugin(self.env.sess, dylib, symbol, krate.span);
+            }
+            _ => {}
+        }
         MacroCrate {
             lib: library.dylib,
             macros: macros.move_iter().collect(),
@@ -23,6 +23,7 @@ use std::rc::Rc;
 use syntax::ast;
 use syntax::ast_map;
 use syntax::diagnostic::expect;
+use syntax::ext::base::ExportedMacro;
 use syntax::parse::token;
 
 pub struct StaticMethodInfo {
@@ -284,9 +285,34 @@ pub fn get_macro_registrar_fn(cstore: &cstore::CStore,
     decoder::get_macro_registrar_fn(cdata)
 }
 
+pub fn get_lint_registrar_fn(cstore: &cstore::CStore,
+                             crate_num: ast::CrateNum)
+                             -> Option<ast::DefId> {
+    let cdata = cstore.get_crate_data(crate_num);
+    decoder::get_lint_registrar_fn(cdata)
+}
+
+/// The symbols of the crate's items, which a dylib built with LTO that links
+/// the crate in must keep exporting.
+pub fn get_item_symbols(cstore: &cstore::CStore,
+                        crate_num: ast::CrateNum)
+                        -> Vec<~str> {
+    let cdata = cstore.get_crate_data(crate_num);
+    decoder::get_item_symbols(cdata)
+}
+
+/// Whether the crate is a dylib built with LTO, which contains all of its
+/// upstream crates.
+pub fn get_lto_includes_deps(cstore: &cstore::CStore,
+                             crate_num: ast::CrateNum)
+                             -> bool {
+    let cdata = cstore.get_crate_data(crate_num);
+    decoder::get_lto_includes_deps(cdata)
+}
+
 pub fn get_exported_macros(cstore: &cstore::CStore,
                            crate_num: ast::CrateNum)
-                           -> Vec<~str> {
+                           -> Vec<ExportedMacro> {
     let cdata = cstore.get_crate_data(crate_num);
     decoder::get_exported_macros(cdata)
 }
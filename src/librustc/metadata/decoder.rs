@@ -44,6 +44,7 @@ use syntax::print::pprust;
 use syntax::ast;
 use syntax::codemap;
 use syntax::crateid::CrateId;
+use syntax::ext::base::ExportedMacro;
 
 pub type Cmd = @crate_metadata;
 
@@ -1240,12 +1241,41 @@ pub fn get_macro_registrar_fn(cdata: Cmd) -> Option<ast::DefId> {
         .map(|doc| item_def_id(doc, cdata))
 }
 
-pub fn get_exported_macros(cdata: Cmd) -> Vec<~str> {
+pub fn get_lint_registrar_fn(cdata: Cmd) -> Option<ast::DefId> {
+    reader::maybe_get_doc(reader::Doc(cdata.data()), tag_lint_registrar_fn)
+        .map(|doc| item_def_id(doc, cdata))
+}
+
+pub fn get_item_symbols(cdata: Cmd) -> Vec<~str> {
+    let items = reader::get_doc(reader::Doc(cdata.data()), tag_items);
+    let items_data = reader::get_doc(items, tag_items_data);
+    let mut symbols = Vec::new();
+    reader::tagged_docs(items_data, tag_items_data_item, |item| {
+        match reader::maybe_get_doc(item, tag_items_data_item_symbol) {
+            Some(symbol) => symbols.push(symbol.as_str()),
+            None => {}
+        }
+        true
+    });
+    symbols
+}
+
+pub fn get_lto_includes_deps(cdata: Cmd) -> bool {
+    reader::maybe_get_doc(reader::Doc(cdata.data()), tag_lto_includes_deps).is_some()
+}
+
+pub fn get_exported_macros(cdata: Cmd) -> Vec<ExportedMacro> {
     let macros = reader::get_doc(reader::Doc(cdata.data()),
                                  tag_exported_macros);
     let mut result = Vec::new();
     reader::tagged_docs(macros, tag_macro_def, |macro_doc| {
-        result.push(macro_doc.as_str());
+        let name = reader::get_doc(macro_doc, tag_macro_def_name).as_str();
+        let body_doc = reader::get_doc(macro_doc, tag_macro_def_body);
+        let mut decoder = reader::Decoder(body_doc);
+        result.push(ExportedMacro {
+            name: token::str_to_ident(name),
+            tts: Decodable::decode(&mut decoder).unwrap(),
+        });
         true
     });
     result
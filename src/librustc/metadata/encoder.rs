@@ -14,6 +14,7 @@
 #![allow(non_camel_case_types)]
 
 use back::svh::Svh;
+use driver::session;
 use metadata::common::*;
 use metadata::cstore;
 use metadata::decoder;
@@ -86,6 +87,7 @@ pub struct Stats {
     lang_item_bytes: Cell<u64>,
     native_lib_bytes: Cell<u64>,
     macro_registrar_fn_bytes: Cell<u64>,
+    lint_registrar_fn_bytes: Cell<u64>,
     macro_defs_bytes: Cell<u64>,
     impl_bytes: Cell<u64>,
     misc_bytes: Cell<u64>,
@@ -259,18 +261,21 @@ fn encode_method_fty(ecx: &EncodeContext,
 fn encode_symbol(ecx: &EncodeContext,
                  ebml_w: &mut Encoder,
                  id: NodeId) {
-    ebml_w.start_tag(tag_items_data_item_symbol);
     match ecx.item_symbols.borrow().find(&id) {
         Some(x) => {
             debug!("encode_symbol(id={:?}, str={})", id, *x);
+            ebml_w.start_tag(tag_items_data_item_symbol);
             ebml_w.writer.write(x.as_bytes());
+            ebml_w.end_tag();
         }
+        // Symbols are chosen during translation, so metadata written without
+        // it has none. Crates checked against such metadata never need them.
+        None if ecx.tcx.sess.metadata_only() => {}
         None => {
             ecx.diag.handler().bug(
                 format!("encode_symbol: id not found {}", id));
         }
     }
-    ebml_w.end_tag();
 }
 
 fn encode_disr_val(_: &EncodeContext,
@@ -404,7 +409,7 @@ fn encode_reexported_static_base_methods(ecx: &EncodeContext,
                                          -> bool {
     match ecx.tcx.inherent_impls.borrow().find(&exp.def_id) {
         Some(implementations) => {
-            for &base_impl in implementations.borrow().iter() {
+            for &base_impl in sorted_impls(implementations.borrow().as_slice()).iter() {
                 for &m in base_impl.methods.iter() {
                     if m.explicit_self == ast::SelfStatic {
                         encode_reexported_static_method(ebml_w, exp, m.def_id, m.ident);
@@ -524,7 +529,13 @@ fn encode_reexports(ecx: &EncodeContext,
     match ecx.reexports2.borrow().find(&id) {
         Some(ref exports) => {
             debug!("(encoding info for module) found reexports for {}", id);
-            for exp in exports.iter() {
+            // Resolve finds the exports in no particular order. A name can
+            // be exported twice, once per namespace.
+            let mut exports: Vec<&middle::resolve::Export2> = exports.iter().collect();
+            exports.sort_by(|a, b| {
+                (a.name.as_slice(), a.def_id).cmp(&(b.name.as_slice(), b.def_id))
+            });
+            for &exp in exports.iter() {
                 debug!("(encoding info for module) reexport '{}' ({}/{}) for \
                         {}",
                        exp.name,
@@ -824,6 +835,14 @@ fn should_inline(attrs: &[Attribute]) -> bool {
     }
 }
 
+// The implementations are collected in no particular order, so they are
+// sorted to make the metadata the same from one build to the next.
+fn sorted_impls(implementations: &[@ty::Impl]) -> Vec<@ty::Impl> {
+    let mut implementations = Vec::from_slice(implementations);
+    implementations.sort_by(|a, b| a.did.cmp(&b.did));
+    implementations
+}
+
 // Encodes the inherent implementations of a structure, enumeration, or trait.
 fn encode_inherent_implementations(ecx: &EncodeContext,
                                    ebml_w: &mut Encoder,
@@ -831,7 +850,7 @@ fn encode_inherent_implementations(ecx: &EncodeContext,
     match ecx.tcx.inherent_impls.borrow().find(&def_id) {
         None => {}
         Some(&implementations) => {
-            for implementation in implementations.borrow().iter() {
+            for implementation in sorted_impls(implementations.borrow().as_slice()).iter() {
                 ebml_w.start_tag(tag_items_data_item_inherent_impl);
                 encode_def_id(ebml_w, implementation.did);
                 ebml_w.end_tag();
@@ -847,7 +866,7 @@ fn encode_extension_implementations(ecx: &EncodeContext,
     match ecx.tcx.trait_impls.borrow().find(&trait_def_id) {
         None => {}
         Some(&implementations) => {
-            for implementation in implementations.borrow().iter() {
+            for implementation in sorted_impls(implementations.borrow().as_slice()).iter() {
                 ebml_w.start_tag(tag_items_data_item_extension_impl);
                 encode_def_id(ebml_w, implementation.did);
                 ebml_w.end_tag();
@@ -909,7 +928,9 @@ fn encode_info_for_item(ecx: &EncodeContext,
         encode_name(ebml_w, item.ident.name);
         encode_path(ebml_w, path);
         encode_attributes(ebml_w, item.attrs.as_slice());
-        if tps_len > 0u || should_inline(item.attrs.as_slice()) {
+        // Constant functions are evaluated from their AST by other crates.
+        if tps_len > 0u || should_inline(item.attrs.as_slice()) ||
+                attr::contains_name(item.attrs.as_slice(), "const_fn") {
             (ecx.encode_inlined_item)(ecx, ebml_w, IIItemRef(item));
         } else {
             encode_symbol(ecx, ebml_w, item.id);
@@ -1575,19 +1596,42 @@ fn encode_macro_registrar_fn(ecx: &EncodeContext, ebml_w: &mut Encoder) {
     }
 }
 
+fn encode_lint_registrar_fn(ecx: &EncodeContext, ebml_w: &mut Encoder) {
+    match *ecx.tcx.sess.lint_registrar_fn.borrow() {
+        Some(did) => {
+            ebml_w.start_tag(tag_lint_registrar_fn);
+            encode_def_id(ebml_w, did);
+            ebml_w.end_tag();
+        }
+        None => {}
+    }
+}
+
+// A dylib built with LTO has the code of all of its upstream crates linked
+// into it, which crates linking to it must not link in a second time.
+fn encode_lto_includes_deps(ecx: &EncodeContext, ebml_w: &mut Encoder) {
+    let sess = &ecx.tcx.sess;
+    if sess.lto() && sess.crate_types.borrow().contains(&session::CrateTypeDylib) {
+        ebml_w.start_tag(tag_lto_includes_deps);
+        ebml_w.end_tag();
+    }
+}
+
 struct MacroDefVisitor<'a, 'b> {
-    ecx: &'a EncodeContext<'a>,
     ebml_w: &'a mut Encoder<'b>
 }
 
 impl<'a, 'b> Visitor<()> for MacroDefVisitor<'a, 'b> {
     fn visit_item(&mut self, item: &Item, _: ()) {
         match item.node {
-            ItemMac(..) => {
-                let def = self.ecx.tcx.sess.codemap().span_to_snippet(item.span)
-                    .expect("Unable to find source for macro");
+            ItemMac(ref mac) => {
+                let MacInvocTT(_, ref tts, _) = mac.node;
                 self.ebml_w.start_tag(tag_macro_def);
-                self.ebml_w.wr_str(def);
+                self.ebml_w.wr_tagged_str(tag_macro_def_name,
+                                          token::get_ident(item.ident).get());
+                self.ebml_w.start_tag(tag_macro_def_body);
+                tts.encode(self.ebml_w);
+                self.ebml_w.end_tag();
                 self.ebml_w.end_tag();
             }
             _ => {}
@@ -1596,13 +1640,10 @@ impl<'a, 'b> Visitor<()> for MacroDefVisitor<'a, 'b> {
     }
 }
 
-fn encode_macro_defs(ecx: &EncodeContext,
-                     krate: &Crate,
-                     ebml_w: &mut Encoder) {
+fn encode_macro_defs(krate: &Crate, ebml_w: &mut Encoder) {
     ebml_w.start_tag(tag_exported_macros);
     {
         let mut visitor = MacroDefVisitor {
-            ecx: ecx,
             ebml_w: ebml_w,
         };
         visit::walk_crate(&mut visitor, krate, ());
@@ -1735,6 +1776,7 @@ fn encode_metadata_inner(wr: &mut MemWriter, parms: EncodeParams, krate: &Crate)
         lang_item_bytes: Cell::new(0),
         native_lib_bytes: Cell::new(0),
         macro_registrar_fn_bytes: Cell::new(0),
+        lint_registrar_fn_bytes: Cell::new(0),
         macro_defs_bytes: Cell::new(0),
         impl_bytes: Cell::new(0),
         misc_bytes: Cell::new(0),
@@ -1798,9 +1840,16 @@ fn encode_metadata_inner(wr: &mut MemWriter, parms: EncodeParams, krate: &Crate)
     encode_macro_registrar_fn(&ecx, &mut ebml_w);
     ecx.stats.macro_registrar_fn_bytes.set(ebml_w.writer.tell().unwrap() - i);
 
+    // Encode the lint registrar function
+    i = ebml_w.writer.tell().unwrap();
+    encode_lint_registrar_fn(&ecx, &mut ebml_w);
+    ecx.stats.lint_registrar_fn_bytes.set(ebml_w.writer.tell().unwrap() - i);
+
+    encode_lto_includes_deps(&ecx, &mut ebml_w);
+
     // Encode macro definitions
     i = ebml_w.writer.tell().unwrap();
-    encode_macro_defs(&ecx, krate, &mut ebml_w);
+    encode_macro_defs(krate, &mut ebml_w);
     ecx.stats.macro_defs_bytes.set(ebml_w.writer.tell().unwrap() - i);
 
     // Encode the def IDs of impls, for coherence checking.
@@ -1841,6 +1890,7 @@ fn encode_metadata_inner(wr: &mut MemWriter, parms: EncodeParams, krate: &Crate)
         println!("      lang item bytes: {}", ecx.stats.lang_item_bytes.get());
         println!("         native bytes: {}", ecx.stats.native_lib_bytes.get());
         println!("macro registrar bytes: {}", ecx.stats.macro_registrar_fn_bytes.get());
+        println!(" lint registrar bytes: {}", ecx.stats.lint_registrar_fn_bytes.get());
         println!("      macro def bytes: {}", ecx.stats.macro_defs_bytes.get());
         println!("           impl bytes: {}", ecx.stats.impl_bytes.get());
         println!("           misc bytes: {}", ecx.stats.misc_bytes.get());
@@ -156,6 +156,10 @@ impl<'a> Context<'a> {
         let dylib_prefix = format!("{}{}-", dyprefix, self.crate_id.name);
         let rlib_prefix = format!("lib{}-", self.crate_id.name);
 
+        // Crates which are only checked need nothing but metadata from their
+        // dependencies, so those can come from `--emit=metadata` as well.
+        let accept_rmeta = self.sess.opts.no_trans || self.sess.metadata_only();
+
         let mut candidates = HashMap::new();
 
         // First, find all possible candidate rlibs and dylibs purely based on
@@ -182,9 +186,9 @@ impl<'a> Context<'a> {
                     Some(hash) => {
                         info!("rlib accepted, hash: {}", hash);
                         let slot = candidates.find_or_insert_with(hash, |_| {
-                            (HashSet::new(), HashSet::new())
+                            (HashSet::new(), HashSet::new(), HashSet::new())
                         });
-                        let (ref mut rlibs, _) = *slot;
+                        let (ref mut rlibs, _, _) = *slot;
                         rlibs.insert(realpath(path));
                         FileMatches
                     }
@@ -199,9 +203,9 @@ impl<'a> Context<'a> {
                     Some(hash) => {
                         info!("dylib accepted, hash: {}", hash);
                         let slot = candidates.find_or_insert_with(hash, |_| {
-                            (HashSet::new(), HashSet::new())
+                            (HashSet::new(), HashSet::new(), HashSet::new())
                         });
-                        let (_, ref mut dylibs) = *slot;
+                        let (_, ref mut dylibs, _) = *slot;
                         dylibs.insert(realpath(path));
                         FileMatches
                     }
@@ -210,6 +214,24 @@ impl<'a> Context<'a> {
                         FileDoesntMatch
                     }
                 }
+            } else if accept_rmeta && file.starts_with(rlib_prefix) &&
+                      file.ends_with(".rmeta") {
+                info!("rmeta candidate: {}", path.display());
+                match self.try_match(file, rlib_prefix, ".rmeta") {
+                    Some(hash) => {
+                        info!("rmeta accepted, hash: {}", hash);
+                        let slot = candidates.find_or_insert_with(hash, |_| {
+                            (HashSet::new(), HashSet::new(), HashSet::new())
+                        });
+                        let (_, _, ref mut rmetas) = *slot;
+                        rmetas.insert(realpath(path));
+                        FileMatches
+                    }
+                    None => {
+                        info!("rmeta rejected");
+                        FileDoesntMatch
+                    }
+                }
             } else {
                 FileDoesntMatch
             }
@@ -223,9 +245,14 @@ impl<'a> Context<'a> {
         // A Library candidate is created if the metadata for the set of
         // libraries corresponds to the crate id and hash criteria that this
         // serach is being performed for.
+        //
+        // Metadata-only files are read first: when a build checks a crate
+        // as soon as its dependencies' metadata is written, an rlib next to
+        // it may well be left over from an earlier build.
         let mut libraries = Vec::new();
-        for (_hash, (rlibs, dylibs)) in candidates.move_iter() {
+        for (_hash, (rlibs, dylibs, rmetas)) in candidates.move_iter() {
             let mut metadata = None;
+            self.extract_one(rmetas, "rmeta", &mut metadata);
             let rlib = self.extract_one(rlibs, "rlib", &mut metadata);
             let dylib = self.extract_one(dylibs, "dylib", &mut metadata);
             match metadata {
@@ -455,6 +482,25 @@ fn get_metadata_section_imp(os: Os, filename: &Path) -> Result<MetadataBlob, ~st
     if !filename.exists() {
         return Err(format!("no such file: '{}'", filename.display()));
     }
+    if filename.filename_str().unwrap().ends_with(".rmeta") {
+        // Written by `--emit=metadata` in the same format as the metadata
+        // section of a dylib.
+        let data = match io::File::open(filename).read_to_end() {
+            Ok(data) => data,
+            Err(e) => return Err(format!("failed to read metadata: '{}': {}",
+                                         filename.display(), e)),
+        };
+        let vlen = encoder::metadata_encoding_version.len();
+        if !data.as_slice().starts_with(encoder::metadata_encoding_version) {
+            return Err(format!("incompatible metadata version found: '{}'",
+                               filename.display()));
+        }
+        return match flate::inflate_bytes(data.slice_from(vlen)) {
+            Some(inflated) => Ok(MetadataVec(inflated)),
+            None => Err(format!("failed to decompress metadata for: '{}'",
+                                filename.display())),
+        }
+    }
     if filename.filename_str().unwrap().ends_with(".rlib") {
         // Use ArchiveRO for speed here, it's backed by LLVM and uses mmap
         // internally to read the file. We also avoid even using a memcpy by
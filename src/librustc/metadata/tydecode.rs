@@ -562,8 +562,10 @@ fn parse_type_param_def(st: &mut PState, conv: conv_did) -> ty::TypeParameterDef
     ty::TypeParameterDef {
         ident: parse_ident(st, ':'),
         def_id: parse_def(st, NominalType, |x,y| conv(x,y)),
+        associated: next(st) == 'A',
         bounds: @parse_bounds(st, |x,y| conv(x,y)),
-        default: parse_opt(st, |st| parse_ty(st, |x,y| conv(x,y)))
+        default: parse_opt(st, |st| parse_ty(st, |x,y| conv(x,y))),
+        where_ty: parse_opt(st, |st| parse_ty(st, |x,y| conv(x,y)))
     }
 }
 
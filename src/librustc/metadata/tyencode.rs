@@ -407,7 +407,9 @@ fn enc_bounds(w: &mut MemWriter, cx: &ctxt, bs: &ty::ParamBounds) {
 }
 
 pub fn enc_type_param_def(w: &mut MemWriter, cx: &ctxt, v: &ty::TypeParameterDef) {
-    mywrite!(w, "{}:{}|", token::get_ident(v.ident), (cx.ds)(v.def_id));
+    mywrite!(w, "{}:{}|{}", token::get_ident(v.ident), (cx.ds)(v.def_id),
+             if v.associated { 'A' } else { 'N' });
     enc_bounds(w, cx, v.bounds);
     enc_opt(w, v.default, |w, t| enc_ty(w, cx, t));
+    enc_opt(w, v.where_ty, |w, t| enc_ty(w, cx, t));
 }
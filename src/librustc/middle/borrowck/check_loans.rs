@@ -18,6 +18,7 @@
 // 4. moves do not affect things loaned out in any way
 
 
+use diagnostics;
 use mc = middle::mem_categorization;
 use middle::borrowck::*;
 use middle::moves;
@@ -248,40 +249,44 @@ impl<'a> CheckLoanCtxt<'a> {
 
             match (new_loan.kind, old_loan.kind) {
                 (ty::MutBorrow, ty::MutBorrow) => {
-                    self.bccx.span_err(
+                    self.bccx.span_err_with_code(
                         new_loan.span,
                         format!("cannot borrow `{}` as mutable \
                                 more than once at a time",
-                                self.bccx.loan_path_to_str(new_loan.loan_path)));
+                                self.bccx.loan_path_to_str(new_loan.loan_path)),
+                        diagnostics::E0097);
                 }
 
                 (ty::UniqueImmBorrow, _) => {
-                    self.bccx.span_err(
+                    self.bccx.span_err_with_code(
                         new_loan.span,
                         format!("closure requires unique access to `{}` \
                                 but {} is already borrowed",
                                 self.bccx.loan_path_to_str(new_loan.loan_path),
-                                old_pronoun));
+                                old_pronoun),
+                        diagnostics::E0097);
                 }
 
                 (_, ty::UniqueImmBorrow) => {
-                    self.bccx.span_err(
+                    self.bccx.span_err_with_code(
                         new_loan.span,
                         format!("cannot borrow `{}` as {} because \
                                 previous closure requires unique access",
                                 self.bccx.loan_path_to_str(new_loan.loan_path),
-                                new_loan.kind.to_user_str()));
+                                new_loan.kind.to_user_str()),
+                        diagnostics::E0097);
                 }
 
                 (_, _) => {
-                    self.bccx.span_err(
+                    self.bccx.span_err_with_code(
                         new_loan.span,
                         format!("cannot borrow `{}` as {} because \
                                 {} is also borrowed as {}",
                                 self.bccx.loan_path_to_str(new_loan.loan_path),
                                 new_loan.kind.to_user_str(),
                                 old_pronoun,
-                                old_loan.kind.to_user_str()));
+                                old_loan.kind.to_user_str()),
+                        diagnostics::E0097);
                 }
             }
 
@@ -417,19 +422,21 @@ impl<'a> CheckLoanCtxt<'a> {
         // Otherwise, just a plain error.
         match opt_loan_path(cmt) {
             Some(lp) => {
-                self.bccx.span_err(
+                self.bccx.span_err_with_code(
                     expr.span,
                     format!("cannot assign to {} {} `{}`",
                             cmt.mutbl.to_user_str(),
                             self.bccx.cmt_to_str(cmt),
-                            self.bccx.loan_path_to_str(lp)));
+                            self.bccx.loan_path_to_str(lp)),
+                    diagnostics::E0098);
             }
             None => {
-                self.bccx.span_err(
+                self.bccx.span_err_with_code(
                     expr.span,
                     format!("cannot assign to {} {}",
                             cmt.mutbl.to_user_str(),
-                            self.bccx.cmt_to_str(cmt)));
+                            self.bccx.cmt_to_str(cmt)),
+                    diagnostics::E0098);
             }
         }
         return;
@@ -663,10 +670,11 @@ impl<'a> CheckLoanCtxt<'a> {
                                    expr: &ast::Expr,
                                    loan_path: &LoanPath,
                                    loan: &Loan) {
-        self.bccx.span_err(
+        self.bccx.span_err_with_code(
             expr.span,
             format!("cannot assign to `{}` because it is borrowed",
-                 self.bccx.loan_path_to_str(loan_path)));
+                 self.bccx.loan_path_to_str(loan_path)),
+            diagnostics::E0099);
         self.bccx.span_note(
             loan.span,
             format!("borrow of `{}` occurs here",
@@ -692,11 +700,12 @@ impl<'a> CheckLoanCtxt<'a> {
             match self.analyze_move_out_from(id, move_path) {
                 MoveOk => {}
                 MoveWhileBorrowed(loan_path, loan_span) => {
-                    self.bccx.span_err(
+                    self.bccx.span_err_with_code(
                         span,
                         format!("cannot move out of `{}` \
                                 because it is borrowed",
-                             self.bccx.loan_path_to_str(move_path)));
+                             self.bccx.loan_path_to_str(move_path)),
+                        diagnostics::E0100);
                     self.bccx.span_note(
                         loan_span,
                         format!("borrow of `{}` occurs here",
@@ -732,11 +741,12 @@ impl<'a> CheckLoanCtxt<'a> {
             match move_err {
                 MoveOk => {}
                 MoveWhileBorrowed(loan_path, loan_span) => {
-                    this.bccx.span_err(
+                    this.bccx.span_err_with_code(
                         cap_var.span,
                         format!("cannot move `{}` into closure \
                                 because it is borrowed",
-                                this.bccx.loan_path_to_str(move_path)));
+                                this.bccx.loan_path_to_str(move_path)),
+                        diagnostics::E0100);
                     this.bccx.span_note(
                         loan_span,
                         format!("borrow of `{}` occurs here",
@@ -12,6 +12,7 @@
  * Computes moves.
  */
 
+use diagnostics;
 use mc = middle::mem_categorization;
 use middle::borrowck::*;
 use middle::borrowck::move_data::*;
@@ -119,10 +120,11 @@ fn check_is_legal_to_move_from(bccx: &BorrowckCtxt,
         mc::cat_deref(_, _, mc::UnsafePtr(..)) |
         mc::cat_upvar(..) | mc::cat_static_item |
         mc::cat_copied_upvar(mc::CopiedUpvar { onceness: ast::Many, .. }) => {
-            bccx.span_err(
+            bccx.span_err_with_code(
                 cmt0.span,
                 format!("cannot move out of {}",
-                        bccx.cmt_to_str(cmt)));
+                        bccx.cmt_to_str(cmt)),
+                diagnostics::E0086);
             false
         }
 
@@ -144,11 +146,12 @@ fn check_is_legal_to_move_from(bccx: &BorrowckCtxt,
             match ty::get(b.ty).sty {
                 ty::ty_struct(did, _) | ty::ty_enum(did, _) => {
                     if ty::has_dtor(bccx.tcx, did) {
-                        bccx.span_err(
+                        bccx.span_err_with_code(
                             cmt0.span,
                             format!("cannot move out of type `{}`, \
                                   which defines the `Drop` trait",
-                                 b.ty.user_string(bccx.tcx)));
+                                 b.ty.user_string(bccx.tcx)),
+                            diagnostics::E0087);
                         false
                     } else {
                         check_is_legal_to_move_from(bccx, cmt0, b)
@@ -16,6 +16,7 @@
 // their associated scopes.  In phase two, checking loans, we will then make
 // sure that all of these loans are honored.
 
+use diagnostics;
 use middle::borrowck::*;
 use middle::borrowck::move_data::MoveData;
 use mc = middle::mem_categorization;
@@ -325,9 +326,11 @@ fn check_aliasability(bccx: &BorrowckCtxt,
                     // In such cases, we cannot permit it to be borrowed, because the
                     // static item resides in immutable memory and mutating it would
                     // cause segfaults.
-                    bccx.tcx.sess.span_err(borrow_span,
-                                           format!("borrow of immutable static items with \
-                                                    unsafe interior is not allowed"));
+                    bccx.tcx.sess.span_err_with_code(
+                        borrow_span,
+                        format!("borrow of immutable static items with \
+                                 unsafe interior is not allowed"),
+                        diagnostics::E0088);
                     Err(())
                 }
                 mc::InteriorSafe => {
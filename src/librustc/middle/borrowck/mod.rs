@@ -12,6 +12,7 @@
 
 #![allow(non_camel_case_types)]
 
+use diagnostics;
 use mc = middle::mem_categorization;
 use middle::ty;
 use middle::typeck;
@@ -530,9 +531,10 @@ impl<'a> BorrowckCtxt<'a> {
     }
 
     pub fn report(&self, err: BckError) {
-        self.span_err(
+        self.span_err_with_code(
             err.span,
-            self.bckerr_to_str(err));
+            self.bckerr_to_str(err),
+            self.bckerr_code(err));
         self.note_and_explain_bckerr(err);
     }
 
@@ -549,20 +551,22 @@ impl<'a> BorrowckCtxt<'a> {
 
         match move.kind {
             move_data::Declared => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     use_span,
                     format!("{} of possibly uninitialized variable: `{}`",
                          verb,
-                         self.loan_path_to_str(lp)));
+                         self.loan_path_to_str(lp)),
+                    diagnostics::E0093);
             }
             _ => {
                 let partially = if lp == moved_lp {""} else {"partially "};
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     use_span,
                     format!("{} of {}moved value: `{}`",
                          verb,
                          partially,
-                         self.loan_path_to_str(lp)));
+                         self.loan_path_to_str(lp)),
+                    diagnostics::E0094);
             }
         }
 
@@ -635,17 +639,18 @@ impl<'a> BorrowckCtxt<'a> {
                                                 lp: &LoanPath,
                                                 assign:
                                                 &move_data::Assignment) {
-        self.tcx.sess.span_err(
+        self.tcx.sess.span_err_with_code(
             span,
             format!("re-assignment of immutable variable `{}`",
-                 self.loan_path_to_str(lp)));
+                 self.loan_path_to_str(lp)),
+            diagnostics::E0095);
         self.tcx.sess.span_note(
             assign.span,
             format!("prior assignment occurs here"));
     }
 
-    pub fn span_err(&self, s: Span, m: &str) {
-        self.tcx.sess.span_err(s, m);
+    pub fn span_err_with_code(&self, s: Span, m: &str, code: &str) {
+        self.tcx.sess.span_err_with_code(s, m, code);
     }
 
     pub fn span_note(&self, s: Span, m: &str) {
@@ -701,6 +706,15 @@ impl<'a> BorrowckCtxt<'a> {
         }
     }
 
+    pub fn bckerr_code(&self, err: BckError) -> &'static str {
+        match err.code {
+            err_mutbl => diagnostics::E0089,
+            err_out_of_root_scope(..) => diagnostics::E0090,
+            err_out_of_scope(..) => diagnostics::E0091,
+            err_borrowed_pointer_too_short(..) => diagnostics::E0092,
+        }
+    }
+
     pub fn report_aliasability_violation(&self,
                                          span: Span,
                                          kind: AliasableViolationKind,
@@ -728,25 +742,29 @@ impl<'a> BorrowckCtxt<'a> {
 
         match cause {
             mc::AliasableOther => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    format!("{} in an aliasable location", prefix));
+                    format!("{} in an aliasable location", prefix),
+                    diagnostics::E0096);
             }
             mc::AliasableStatic(..) |
             mc::AliasableStaticMut(..) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    format!("{} in a static location", prefix));
+                    format!("{} in a static location", prefix),
+                    diagnostics::E0096);
             }
             mc::AliasableManaged => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    format!("{} in a `@` pointer", prefix));
+                    format!("{} in a `@` pointer", prefix),
+                    diagnostics::E0096);
             }
             mc::AliasableBorrowed => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    format!("{} in a `&` reference", prefix));
+                    format!("{} in a `&` reference", prefix),
+                    diagnostics::E0096);
             }
         }
     }
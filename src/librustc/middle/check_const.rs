@@ -10,6 +10,7 @@
 
 
 use driver::session::Session;
+use middle::const_eval;
 use middle::resolve;
 use middle::ty;
 use middle::typeck;
@@ -56,6 +57,7 @@ fn check_item(v: &mut CheckCrateVisitor, it: &Item, _is_const: bool) {
         ItemStatic(_, _, ex) => {
             v.visit_expr(ex, true);
             check_item_recursion(&v.tcx.sess, &v.tcx.map, v.def_map, it);
+            check_const_fn_calls(v.tcx, ex);
         }
         ItemEnum(ref enum_definition, _) => {
             for var in (*enum_definition).variants.iter() {
@@ -145,13 +147,15 @@ fn check_expr(v: &mut CheckCrateVisitor, e: &Expr, is_const: bool) {
             }
           }
           ExprCall(callee, _) => {
-            match v.def_map.borrow().find(&callee.id) {
-                Some(&DefStruct(..)) => {}    // OK.
-                Some(&DefVariant(..)) => {}    // OK.
+            let def = v.def_map.borrow().find_copy(&callee.id);
+            match def {
+                Some(DefStruct(..)) => {}    // OK.
+                Some(DefVariant(..)) => {}    // OK.
+                Some(DefFn(did, _)) if const_eval::is_const_fn(v.tcx, did) => {}
                 _ => {
                     v.tcx.sess.span_err(e.span,
                         "function calls in constants are limited to \
-                         struct and enum constructors");
+                         struct and enum constructors and `#[const_fn]` functions");
                 }
             }
           }
@@ -184,6 +188,44 @@ fn check_expr(v: &mut CheckCrateVisitor, e: &Expr, is_const: bool) {
     visit::walk_expr(v, e, is_const);
 }
 
+struct ConstFnCallVisitor<'a> {
+    tcx: &'a ty::ctxt,
+}
+
+// Evaluate the calls to `#[const_fn]` functions in the initializer of a
+// static, so that those which can't be evaluated are all reported, whether
+// or not the crate is translated.
+fn check_const_fn_calls(tcx: &ty::ctxt, e: &Expr) {
+    let mut visitor = ConstFnCallVisitor { tcx: tcx };
+    visitor.visit_expr(e, ());
+}
+
+impl<'a> Visitor<()> for ConstFnCallVisitor<'a> {
+    fn visit_expr(&mut self, e: &Expr, _: ()) {
+        match e.node {
+            ExprCall(callee, _) => {
+                let def = self.tcx.def_map.borrow().find_copy(&callee.id);
+                match def {
+                    Some(DefFn(did, _)) if const_eval::is_const_fn(self.tcx, did) => {
+                        match const_eval::eval_const_expr_partial(self.tcx, e) {
+                            Ok(_) => {}
+                            Err(err) => {
+                                self.tcx.sess.span_err(e.span, err.msg);
+                                const_eval::note_const_eval_err(self.tcx, &err);
+                            }
+                        }
+                        // The arguments have been evaluated with the call.
+                        return
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        visit::walk_expr(self, e, ());
+    }
+}
+
 struct CheckItemRecursionVisitor<'a> {
     root_it: &'a Item,
     sess: &'a Session,
@@ -22,9 +22,10 @@ use util::ppaux::ty_to_str;
 use std::cmp;
 use std::iter;
 use syntax::ast::*;
-use syntax::ast_util::{unguarded_pat, walk_pat};
-use syntax::codemap::{DUMMY_SP, Span};
+use syntax::ast_util::{ident_to_path, unguarded_pat, walk_pat};
+use syntax::codemap::{dummy_spanned, DUMMY_SP, Span};
 use syntax::parse::token;
+use syntax::print::pprust;
 use syntax::visit;
 use syntax::visit::{Visitor, FnKind};
 
@@ -145,6 +146,7 @@ fn check_arms(cx: &MatchCheckCtxt, arms: &[Arm]) {
             match is_useful(cx, &seen, v.as_slice()) {
               not_useful => {
                 cx.tcx.sess.span_err(pat.span, "unreachable pattern");
+                note_subsuming_pats(cx, &seen, v.as_slice());
               }
               _ => ()
             }
@@ -160,60 +162,53 @@ fn raw_pat(p: @Pat) -> @Pat {
     }
 }
 
+// Points out the earlier patterns which make `v` unreachable. All of `seen`
+// together subsumes `v`, so this drops every row which isn't needed for that
+// to keep holding.
+fn note_subsuming_pats(cx: &MatchCheckCtxt, seen: &matrix, v: &[@Pat]) {
+    let mut rows = seen.clone();
+    let mut i = 0;
+    while i < rows.len() {
+        let row = rows.remove(i).unwrap();
+        match is_useful(cx, &rows, v) {
+          not_useful => {}
+          useful(_) => {
+            rows.insert(i, row);
+            i += 1;
+          }
+        }
+    }
+    let msg = if rows.len() == 1 {
+        "this pattern already matches everything the unreachable one does"
+    } else {
+        "together with the other noted patterns, this pattern matches everything \
+         the unreachable one does"
+    };
+    for row in rows.iter() {
+        cx.tcx.sess.span_note(row.get(0).span, msg);
+    }
+}
+
 fn check_exhaustive(cx: &MatchCheckCtxt, sp: Span, pats: Vec<@Pat> ) {
     assert!((!pats.is_empty()));
-    let ext = match is_useful(cx, &pats.iter().map(|p| vec!(*p)).collect(), [wild()]) {
+    match is_useful(cx, &pats.iter().map(|p| vec!(*p)).collect(), [wild()]) {
         not_useful => {
             // This is good, wildcard pattern isn't reachable
-            return;
         }
-        useful_ => None,
-        useful(ty, ref ctor) => {
-            match ty::get(ty).sty {
-                ty::ty_bool => {
-                    match *ctor {
-                        val(const_bool(true)) => Some(~"true"),
-                        val(const_bool(false)) => Some(~"false"),
-                        _ => None
-                    }
-                }
-                ty::ty_enum(id, _) => {
-                    let vid = match *ctor {
-                        variant(id) => id,
-                        _ => fail!("check_exhaustive: non-variant ctor"),
-                    };
-                    let variants = ty::enum_variants(cx.tcx, id);
-
-                    match variants.iter().find(|v| v.id == vid) {
-                        Some(v) => Some(token::get_ident(v.name).get().to_str()),
-                        None => {
-                            fail!("check_exhaustive: bad variant in ctor")
-                        }
-                    }
-                }
-                ty::ty_vec(..) => {
-                    match *ctor {
-                        vec(n) => Some(format!("vectors of length {}", n)),
-                        _ => None
-                    }
-                }
-                _ => None
-            }
+        useful(witness) => {
+            cx.tcx.sess.span_err(sp, format!("non-exhaustive patterns: `{}` not covered",
+                                             pprust::pat_to_str(&**witness.get(0))));
         }
-    };
-    let msg = ~"non-exhaustive patterns" + match ext {
-        Some(ref s) => format!(": {} not covered",  *s),
-        None => ~""
-    };
-    cx.tcx.sess.span_err(sp, msg);
+    }
 }
 
 type matrix = Vec<Vec<@Pat> > ;
 
+// When `v` is useful, the witness holds one pattern for each of its columns,
+// which together match some input that `v` matches but no row of `m` does.
 #[deriving(Clone)]
 enum useful {
-    useful(ty::t, ctor),
-    useful_,
+    useful(Vec<@Pat>),
     not_useful,
 }
 
@@ -241,7 +236,7 @@ enum ctor {
 // So it assumes that v is non-empty.
 fn is_useful(cx: &MatchCheckCtxt, m: &matrix, v: &[@Pat]) -> useful {
     if m.len() == 0u {
-        return useful_;
+        return useful(Vec::from_elem(v.len(), wild()));
     }
     if m.get(0).len() == 0u {
         return not_useful
@@ -311,8 +306,19 @@ fn is_useful(cx: &MatchCheckCtxt, m: &matrix, v: &[@Pat]) -> useful {
                                 default(cx, r.as_slice())
                             }).collect::<matrix>(),
                             v.tail()) {
-              useful_ => useful(left_ty, (*ctor).clone()),
-              ref u => (*u).clone(),
+              useful(witness) => {
+                // Nothing in this column constrains a single constructor, so
+                // any value of the type will do.
+                let head = match *ctor {
+                  single => wild(),
+                  _ => {
+                    let arity = ctor_arity(cx, ctor, left_ty);
+                    construct_witness(cx, ctor, Vec::from_elem(arity, wild()), left_ty)
+                  }
+                };
+                useful(vec!(head).append(witness.as_slice()))
+              }
+              not_useful => not_useful,
             }
           }
         }
@@ -337,8 +343,12 @@ fn is_useful_specialized(cx: &MatchCheckCtxt,
     let could_be_useful = is_useful(
         cx, &ms, specialize(cx, v, &ctor, arity, lty).unwrap().as_slice());
     match could_be_useful {
-      useful_ => useful(lty, ctor),
-      ref u => (*u).clone(),
+      useful(witness) => {
+        let args = Vec::from_slice(witness.slice_to(arity));
+        let head = construct_witness(cx, &ctor, args, lty);
+        useful(vec!(head).append(witness.slice_from(arity)))
+      }
+      not_useful => not_useful,
     }
 }
 
@@ -547,6 +557,70 @@ fn wild_multi() -> @Pat {
     @Pat {id: 0, node: PatWildMulti, span: DUMMY_SP}
 }
 
+// Builds the pattern for a value of type `left_ty` made with `ctor`, the
+// inverse of `specialize`: `pats` are the patterns for its fields.
+fn construct_witness(cx: &MatchCheckCtxt,
+                     ctor: &ctor,
+                     pats: Vec<@Pat>,
+                     left_ty: ty::t)
+                  -> @Pat {
+    match *ctor {
+      val(const_bool(b)) => {
+        let lit = @Expr {
+            id: 0,
+            node: ExprLit(@dummy_spanned(LitBool(b))),
+            span: DUMMY_SP
+        };
+        return @Pat {id: 0, node: PatLit(lit), span: DUMMY_SP};
+      }
+      val(_) | range(..) => return wild(),
+      _ => ()
+    }
+    let node = match ty::get(left_ty).sty {
+      ty::ty_tup(_) => PatTup(pats),
+      ty::ty_uniq(_) => PatUniq(*pats.get(0)),
+      ty::ty_rptr(..) => PatRegion(*pats.get(0)),
+      ty::ty_vec(..) => PatVec(pats, None, Vec::new()),
+      ty::ty_enum(eid, _) => {
+        let vid = match *ctor {
+          variant(id) => id,
+          _ => return wild()
+        };
+        let variants = ty::enum_variants(cx.tcx, eid);
+        let v = match variants.iter().find(|v| v.id == vid) {
+          Some(v) => v,
+          None => fail!("construct_witness: bad variant in ctor")
+        };
+        let path = ident_to_path(DUMMY_SP, v.name);
+        match v.arg_names {
+          Some(ref names) => {
+            let fields = names.iter().zip(pats.move_iter()).map(|(name, pat)| {
+                FieldPat { ident: *name, pat: pat }
+            }).collect();
+            PatStruct(path, fields, false)
+          }
+          None => PatEnum(path, Some(pats))
+        }
+      }
+      ty::ty_struct(cid, _) => {
+        let name = ty::with_path(cx.tcx, cid, |mut path| path.last()).unwrap().name();
+        let path = ident_to_path(DUMMY_SP, Ident::new(name));
+        let class_fields = ty::lookup_struct_fields(cx.tcx, cid);
+        if class_fields.iter().any(|f| f.name == token::special_idents::unnamed_field.name) ||
+           class_fields.is_empty() {
+            PatEnum(path, Some(pats))
+        } else {
+            let fields = class_fields.iter().zip(pats.move_iter()).map(|(f, pat)| {
+                FieldPat { ident: Ident::new(f.name), pat: pat }
+            }).collect();
+            PatStruct(path, fields, false)
+        }
+      }
+      _ => PatWild
+    };
+    @Pat {id: 0, node: node, span: DUMMY_SP}
+}
+
 fn specialize(cx: &MatchCheckCtxt,
                   r: &[@Pat],
                   ctor_id: &ctor,
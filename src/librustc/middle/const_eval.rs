@@ -18,6 +18,8 @@ use middle::typeck::astconv;
 use util::nodemap::{DefIdMap, FnvHashMap, NodeMap};
 
 use syntax::ast::*;
+use syntax::attr;
+use syntax::codemap::Span;
 use syntax::parse::token::InternedString;
 use syntax::visit::Visitor;
 use syntax::visit;
@@ -305,127 +307,44 @@ pub enum const_val {
     const_bool(bool)
 }
 
+/// Why a constant expression couldn't be evaluated. If the evaluation got
+/// into a constant function, `span` is where in it the evaluation stopped.
+pub struct ConstEvalErr {
+    pub msg: ~str,
+    pub span: Option<Span>,
+}
+
+/// Points at where the evaluation behind `err` stopped, after the error
+/// about the constant expression itself has been reported.
+pub fn note_const_eval_err(tcx: &ty::ctxt, err: &ConstEvalErr) {
+    match err.span {
+        Some(sp) => tcx.sess.span_note(sp, "the constant evaluation stopped here"),
+        None => {}
+    }
+}
+
 pub fn eval_const_expr(tcx: &ty::ctxt, e: &Expr) -> const_val {
     match eval_const_expr_partial(tcx, e) {
         Ok(r) => r,
-        Err(s) => tcx.sess.span_fatal(e.span, s)
+        Err(err) => {
+            tcx.sess.span_err(e.span, err.msg);
+            note_const_eval_err(tcx, &err);
+            tcx.sess.abort_if_errors();
+            unreachable!()
+        }
     }
 }
 
 pub fn eval_const_expr_partial<T: ty::ExprTyProvider>(tcx: &T, e: &Expr)
-                            -> Result<const_val, ~str> {
-    fn fromb(b: bool) -> Result<const_val, ~str> { Ok(const_int(b as i64)) }
-    match e.node {
-      ExprUnary(UnNeg, inner) => {
-        match eval_const_expr_partial(tcx, inner) {
-          Ok(const_float(f)) => Ok(const_float(-f)),
-          Ok(const_int(i)) => Ok(const_int(-i)),
-          Ok(const_uint(i)) => Ok(const_uint(-i)),
-          Ok(const_str(_)) => Err(~"negate on string"),
-          Ok(const_bool(_)) => Err(~"negate on boolean"),
-          ref err => ((*err).clone())
-        }
-      }
-      ExprUnary(UnNot, inner) => {
-        match eval_const_expr_partial(tcx, inner) {
-          Ok(const_int(i)) => Ok(const_int(!i)),
-          Ok(const_uint(i)) => Ok(const_uint(!i)),
-          Ok(const_bool(b)) => Ok(const_bool(!b)),
-          _ => Err(~"not on float or string")
-        }
+                            -> Result<const_val, ConstEvalErr> {
+    let r = match e.node {
+      ExprUnary(op, inner) if op == UnNeg || op == UnNot => {
+        eval_unop(op, try!(eval_const_expr_partial(tcx, inner)))
       }
       ExprBinary(op, a, b) => {
-        match (eval_const_expr_partial(tcx, a),
-               eval_const_expr_partial(tcx, b)) {
-          (Ok(const_float(a)), Ok(const_float(b))) => {
-            match op {
-              BiAdd => Ok(const_float(a + b)),
-              BiSub => Ok(const_float(a - b)),
-              BiMul => Ok(const_float(a * b)),
-              BiDiv => Ok(const_float(a / b)),
-              BiRem => Ok(const_float(a % b)),
-              BiEq => fromb(a == b),
-              BiLt => fromb(a < b),
-              BiLe => fromb(a <= b),
-              BiNe => fromb(a != b),
-              BiGe => fromb(a >= b),
-              BiGt => fromb(a > b),
-              _ => Err(~"can't do this op on floats")
-            }
-          }
-          (Ok(const_int(a)), Ok(const_int(b))) => {
-            match op {
-              BiAdd => Ok(const_int(a + b)),
-              BiSub => Ok(const_int(a - b)),
-              BiMul => Ok(const_int(a * b)),
-              BiDiv if b == 0 => Err(~"attempted to divide by zero"),
-              BiDiv => Ok(const_int(a / b)),
-              BiRem if b == 0 => Err(~"attempted remainder with a divisor of zero"),
-              BiRem => Ok(const_int(a % b)),
-              BiAnd | BiBitAnd => Ok(const_int(a & b)),
-              BiOr | BiBitOr => Ok(const_int(a | b)),
-              BiBitXor => Ok(const_int(a ^ b)),
-              BiShl => Ok(const_int(a << b)),
-              BiShr => Ok(const_int(a >> b)),
-              BiEq => fromb(a == b),
-              BiLt => fromb(a < b),
-              BiLe => fromb(a <= b),
-              BiNe => fromb(a != b),
-              BiGe => fromb(a >= b),
-              BiGt => fromb(a > b)
-            }
-          }
-          (Ok(const_uint(a)), Ok(const_uint(b))) => {
-            match op {
-              BiAdd => Ok(const_uint(a + b)),
-              BiSub => Ok(const_uint(a - b)),
-              BiMul => Ok(const_uint(a * b)),
-              BiDiv if b == 0 => Err(~"attempted to divide by zero"),
-              BiDiv => Ok(const_uint(a / b)),
-              BiRem if b == 0 => Err(~"attempted remainder with a divisor of zero"),
-              BiRem => Ok(const_uint(a % b)),
-              BiAnd | BiBitAnd => Ok(const_uint(a & b)),
-              BiOr | BiBitOr => Ok(const_uint(a | b)),
-              BiBitXor => Ok(const_uint(a ^ b)),
-              BiShl => Ok(const_uint(a << b)),
-              BiShr => Ok(const_uint(a >> b)),
-              BiEq => fromb(a == b),
-              BiLt => fromb(a < b),
-              BiLe => fromb(a <= b),
-              BiNe => fromb(a != b),
-              BiGe => fromb(a >= b),
-              BiGt => fromb(a > b),
-            }
-          }
-          // shifts can have any integral type as their rhs
-          (Ok(const_int(a)), Ok(const_uint(b))) => {
-            match op {
-              BiShl => Ok(const_int(a << b)),
-              BiShr => Ok(const_int(a >> b)),
-              _ => Err(~"can't do this op on an int and uint")
-            }
-          }
-          (Ok(const_uint(a)), Ok(const_int(b))) => {
-            match op {
-              BiShl => Ok(const_uint(a << b)),
-              BiShr => Ok(const_uint(a >> b)),
-              _ => Err(~"can't do this op on a uint and int")
-            }
-          }
-          (Ok(const_bool(a)), Ok(const_bool(b))) => {
-            Ok(const_bool(match op {
-              BiAnd => a && b,
-              BiOr => a || b,
-              BiBitXor => a ^ b,
-              BiBitAnd => a & b,
-              BiBitOr => a | b,
-              BiEq => a == b,
-              BiNe => a != b,
-              _ => return Err(~"can't do this op on bools")
-             }))
-          }
-          _ => Err(~"bad operands for binary")
-        }
+        eval_binop(op,
+                   try!(eval_const_expr_partial(tcx, a)),
+                   try!(eval_const_expr_partial(tcx, b)))
       }
       ExprCast(base, target_ty) => {
         // This tends to get called w/o the type actually having been
@@ -438,51 +357,695 @@ pub fn eval_const_expr_partial<T: ty::ExprTyProvider>(tcx: &T, e: &Expr)
                     format!("target type not found for const cast")
                 ));
 
-        let base = eval_const_expr_partial(tcx, base);
-        match base {
-            Err(_) => base,
-            Ok(val) => {
-                match ty::get(ety).sty {
-                    ty::ty_float(_) => {
-                        match val {
-                            const_uint(u) => Ok(const_float(u as f64)),
-                            const_int(i) => Ok(const_float(i as f64)),
-                            const_float(f) => Ok(const_float(f)),
-                            _ => Err(~"can't cast float to str"),
-                        }
-                    }
-                    ty::ty_uint(_) => {
-                        match val {
-                            const_uint(u) => Ok(const_uint(u)),
-                            const_int(i) => Ok(const_uint(i as u64)),
-                            const_float(f) => Ok(const_uint(f as u64)),
-                            _ => Err(~"can't cast str to uint"),
-                        }
-                    }
-                    ty::ty_int(_) | ty::ty_bool => {
-                        match val {
-                            const_uint(u) => Ok(const_int(u as i64)),
-                            const_int(i) => Ok(const_int(i)),
-                            const_float(f) => Ok(const_int(f as i64)),
-                            _ => Err(~"can't cast str to int"),
-                        }
-                    }
-                    _ => Err(~"can't cast this type")
-                }
-            }
-        }
+        cast_const(tcx.ty_ctxt(), try!(eval_const_expr_partial(tcx, base)), ety)
       }
       ExprPath(_) => {
           match lookup_const(tcx.ty_ctxt(), e) {
-              Some(actual_e) => eval_const_expr_partial(tcx.ty_ctxt(), actual_e),
+              Some(actual_e) => return eval_const_expr_partial(tcx.ty_ctxt(), actual_e),
               None => Err(~"non-constant path in constant expr")
           }
       }
+      ExprCall(..) => {
+        let mut cx = ConstFnEval::new(tcx.ty_ctxt());
+        return cx.value(e)
+      }
       ExprLit(lit) => Ok(lit_to_const(lit)),
       // If we have a vstore, just keep going; it has to be a string
-      ExprVstore(e, _) => eval_const_expr_partial(tcx, e),
-      ExprParen(e)     => eval_const_expr_partial(tcx, e),
+      ExprVstore(e, _) => return eval_const_expr_partial(tcx, e),
+      ExprParen(e)     => return eval_const_expr_partial(tcx, e),
       _ => Err(~"unsupported constant expr")
+    };
+    r.map_err(|msg| ConstEvalErr { msg: msg, span: None })
+}
+
+fn eval_unop(op: UnOp, v: const_val) -> Result<const_val, ~str> {
+    match op {
+      UnNeg => {
+        match v {
+          const_float(f) => Ok(const_float(-f)),
+          const_int(i) => Ok(const_int(-i)),
+          const_uint(i) => Ok(const_uint(-i)),
+          const_str(_) | const_binary(_) => Err(~"negate on string"),
+          const_bool(_) => Err(~"negate on boolean"),
+        }
+      }
+      UnNot => {
+        match v {
+          const_int(i) => Ok(const_int(!i)),
+          const_uint(i) => Ok(const_uint(!i)),
+          const_bool(b) => Ok(const_bool(!b)),
+          _ => Err(~"not on float or string")
+        }
+      }
+      _ => Err(~"unsupported unary operator in constant expr")
+    }
+}
+
+fn eval_binop(op: BinOp, a: const_val, b: const_val) -> Result<const_val, ~str> {
+    fn fromb(b: bool) -> Result<const_val, ~str> { Ok(const_int(b as i64)) }
+    // Unsuffixed integer literals evaluate to signed values even where they
+    // were inferred to be unsigned, so the operands of anything but a shift
+    // are both taken to be unsigned if either of them is.
+    let (a, b) = match (a, b) {
+        (const_int(a), const_uint(b)) if op != BiShl && op != BiShr => {
+            (const_uint(a as u64), const_uint(b))
+        }
+        (const_uint(a), const_int(b)) if op != BiShl && op != BiShr => {
+            (const_uint(a), const_uint(b as u64))
+        }
+        operands => operands
+    };
+    match (a, b) {
+      (const_float(a), const_float(b)) => {
+        match op {
+          BiAdd => Ok(const_float(a + b)),
+          BiSub => Ok(const_float(a - b)),
+          BiMul => Ok(const_float(a * b)),
+          BiDiv => Ok(const_float(a / b)),
+          BiRem => Ok(const_float(a % b)),
+          BiEq => fromb(a == b),
+          BiLt => fromb(a < b),
+          BiLe => fromb(a <= b),
+          BiNe => fromb(a != b),
+          BiGe => fromb(a >= b),
+          BiGt => fromb(a > b),
+          _ => Err(~"can't do this op on floats")
+        }
+      }
+      (const_int(a), const_int(b)) => {
+        match op {
+          BiAdd => Ok(const_int(a + b)),
+          BiSub => Ok(const_int(a - b)),
+          BiMul => Ok(const_int(a * b)),
+          BiDiv if b == 0 => Err(~"attempted to divide by zero"),
+          BiDiv => Ok(const_int(a / b)),
+          BiRem if b == 0 => Err(~"attempted remainder with a divisor of zero"),
+          BiRem => Ok(const_int(a % b)),
+          BiAnd | BiBitAnd => Ok(const_int(a & b)),
+          BiOr | BiBitOr => Ok(const_int(a | b)),
+          BiBitXor => Ok(const_int(a ^ b)),
+          BiShl => Ok(const_int(a << b)),
+          BiShr => Ok(const_int(a >> b)),
+          BiEq => fromb(a == b),
+          BiLt => fromb(a < b),
+          BiLe => fromb(a <= b),
+          BiNe => fromb(a != b),
+          BiGe => fromb(a >= b),
+          BiGt => fromb(a > b)
+        }
+      }
+      (const_uint(a), const_uint(b)) => {
+        match op {
+          BiAdd => Ok(const_uint(a + b)),
+          BiSub => Ok(const_uint(a - b)),
+          BiMul => Ok(const_uint(a * b)),
+          BiDiv if b == 0 => Err(~"attempted to divide by zero"),
+          BiDiv => Ok(const_uint(a / b)),
+          BiRem if b == 0 => Err(~"attempted remainder with a divisor of zero"),
+          BiRem => Ok(const_uint(a % b)),
+          BiAnd | BiBitAnd => Ok(const_uint(a & b)),
+          BiOr | BiBitOr => Ok(const_uint(a | b)),
+          BiBitXor => Ok(const_uint(a ^ b)),
+          BiShl => Ok(const_uint(a << b)),
+          BiShr => Ok(const_uint(a >> b)),
+          BiEq => fromb(a == b),
+          BiLt => fromb(a < b),
+          BiLe => fromb(a <= b),
+          BiNe => fromb(a != b),
+          BiGe => fromb(a >= b),
+          BiGt => fromb(a > b),
+        }
+      }
+      // shifts can have any integral type as their rhs
+      (const_int(a), const_uint(b)) => {
+        match op {
+          BiShl => Ok(const_int(a << b)),
+          BiShr => Ok(const_int(a >> b)),
+          _ => Err(~"can't do this op on an int and uint")
+        }
+      }
+      (const_uint(a), const_int(b)) => {
+        match op {
+          BiShl => Ok(const_uint(a << b)),
+          BiShr => Ok(const_uint(a >> b)),
+          _ => Err(~"can't do this op on a uint and int")
+        }
+      }
+      (const_bool(a), const_bool(b)) => {
+        Ok(const_bool(match op {
+          BiAnd => a && b,
+          BiOr => a || b,
+          BiBitXor => a ^ b,
+          BiBitAnd => a & b,
+          BiBitOr => a | b,
+          BiEq => a == b,
+          BiNe => a != b,
+          _ => return Err(~"can't do this op on bools")
+         }))
+      }
+      (const_str(a), const_str(b)) => {
+        match op {
+          BiEq => fromb(a == b),
+          BiLt => fromb(a < b),
+          BiLe => fromb(a <= b),
+          BiNe => fromb(a != b),
+          BiGe => fromb(a >= b),
+          BiGt => fromb(a > b),
+          _ => Err(~"can't do this op on strings")
+        }
+      }
+      _ => Err(~"bad operands for binary")
+    }
+}
+
+fn cast_const(tcx: &ty::ctxt, val: const_val, ety: ty::t) -> Result<const_val, ~str> {
+    let v = match ty::get(ety).sty {
+        ty::ty_float(_) => {
+            match val {
+                const_uint(u) => const_float(u as f64),
+                const_int(i) => const_float(i as f64),
+                const_float(f) => const_float(f),
+                _ => return Err(~"can't cast float to str"),
+            }
+        }
+        ty::ty_uint(_) => {
+            match val {
+                const_uint(u) => const_uint(u),
+                const_int(i) => const_uint(i as u64),
+                const_float(f) => const_uint(f as u64),
+                _ => return Err(~"can't cast str to uint"),
+            }
+        }
+        ty::ty_int(_) | ty::ty_bool => {
+            match val {
+                const_uint(u) => const_int(u as i64),
+                const_int(i) => const_int(i),
+                const_float(f) => const_int(f as i64),
+                _ => return Err(~"can't cast str to int"),
+            }
+        }
+        _ => return Err(~"can't cast this type")
+    };
+    Ok(wrap_int(tcx, v, ety))
+}
+
+/// Truncates an integer to the width of the type `t`, sign extending it if
+/// the type is signed, as arithmetic on values of that type does at run
+/// time. Other values are returned as they are.
+fn wrap_int(tcx: &ty::ctxt, v: const_val, t: ty::t) -> const_val {
+    let (bits, signed) = match ty::get(t).sty {
+        ty::ty_int(ity) => {
+            let ity = if ity == TyI { tcx.sess.targ_cfg.int_type } else { ity };
+            (match ity { TyI8 => 8, TyI16 => 16, TyI32 => 32, _ => 64 }, true)
+        }
+        ty::ty_uint(uty) => {
+            let uty = if uty == TyU { tcx.sess.targ_cfg.uint_type } else { uty };
+            (match uty { TyU8 => 8, TyU16 => 16, TyU32 => 32, _ => 64 }, false)
+        }
+        _ => return v
+    };
+    let n = match v {
+        const_int(i) => i as u64,
+        const_uint(u) => u,
+        v => return v
+    };
+    let n = if bits == 64 {
+        n
+    } else {
+        let mask = (1u64 << bits) - 1;
+        let n = n & mask;
+        if signed && (n >> (bits - 1)) == 1 { n | !mask } else { n }
+    };
+    if signed { const_int(n as i64) } else { const_uint(n) }
+}
+
+// Calls to functions marked `#[const_fn]` are evaluated by interpreting
+// their bodies. Only the following is supported in them: literals, integer,
+// float, boolean and string operations, casts to primitive types, local
+// variables, assignments, `if`, `match` on literal and range patterns,
+// `while`, `loop`, `break`, `continue`, `return`, and references to statics
+// and other constant functions. Anything else is reported with its location
+// when the evaluation reaches it, as is a call which doesn't finish within
+// `MAX_CONST_FN_STEPS` steps.
+//
+// Integers are wrapped to the width of their type as far as it can be told
+// from the source: declared types, literal suffixes, casts and the return
+// types of constant functions. Types from type checking aren't used, as
+// constants are also evaluated before it, for instance in vector lengths.
+
+static MAX_CONST_FN_STEPS: uint = 1000000;
+static MAX_CONST_FN_DEPTH: uint = 128;
+
+/// Finds the definition of a function marked `#[const_fn]`.
+pub fn lookup_const_fn(tcx: &ty::ctxt, def_id: ast::DefId) -> Option<@Item> {
+    fn const_fn(item: @Item) -> Option<@Item> {
+        match item.node {
+            ItemFn(_, _, _, ref generics, _) if !generics.is_type_parameterized() &&
+                    attr::contains_name(item.attrs.as_slice(), "const_fn") => Some(item),
+            _ => None
+        }
+    }
+
+    if ast_util::is_local(def_id) {
+        match tcx.map.find(def_id.node) {
+            Some(ast_map::NodeItem(it)) => const_fn(it),
+            _ => None
+        }
+    } else {
+        match tcx.extern_const_fns.borrow().find(&def_id) {
+            Some(&it) => return it,
+            None => {}
+        }
+        let maps = astencode::Maps {
+            root_map: @RefCell::new(HashMap::new()),
+            method_map: @RefCell::new(FnvHashMap::new()),
+            vtable_map: @RefCell::new(FnvHashMap::new()),
+            capture_map: RefCell::new(NodeMap::new())
+        };
+        let it = match csearch::maybe_get_item_ast(tcx, def_id,
+            |a, b, c, d| astencode::decode_inlined_item(a, b, &maps, c, d)) {
+            csearch::found(ast::IIItem(item)) => const_fn(item),
+            _ => None
+        };
+        tcx.extern_const_fns.borrow_mut().insert(def_id, it);
+        it
+    }
+}
+
+pub fn is_const_fn(tcx: &ty::ctxt, def_id: ast::DefId) -> bool {
+    lookup_const_fn(tcx, def_id).is_some()
+}
+
+// How evaluating an expression in a constant function finished.
+enum Flow {
+    Value(const_val),
+    Break,
+    Continue,
+    Return(const_val),
+}
+
+// Gives an error from evaluating an operation the location of the operation.
+fn at<T>(sp: Span, r: Result<T, ~str>) -> Result<T, ConstEvalErr> {
+    r.map_err(|msg| ConstEvalErr { msg: msg, span: Some(sp) })
+}
+
+// Evaluates an expression to a value, passing any other kind of control
+// flow on to the caller.
+macro_rules! value(
+    ($e:expr) => (
+        match try!($e) {
+            Value(v) => v,
+            flow => return Ok(flow)
+        }
+    )
+)
+
+struct ConstFnEval<'a> {
+    tcx: &'a ty::ctxt,
+    steps: uint,
+    // The local variables of each function being called.
+    frames: Vec<NodeMap<const_val>>,
+    // The integer types of the local variables, where they are known.
+    local_tys: NodeMap<ty::t>,
+}
+
+impl<'a> ConstFnEval<'a> {
+    fn new(tcx: &'a ty::ctxt) -> ConstFnEval<'a> {
+        ConstFnEval {
+            tcx: tcx,
+            steps: 0,
+            frames: Vec::new(),
+            local_tys: NodeMap::new(),
+        }
+    }
+
+    fn unsupported<T>(&self, sp: Span, what: &str) -> Result<T, ConstEvalErr> {
+        at(sp, Err(format!("{} not supported in constant functions", what)))
+    }
+
+    fn value(&mut self, e: &Expr) -> Result<const_val, ConstEvalErr> {
+        match try!(self.expr(e)) {
+            Value(v) => Ok(v),
+            _ => self.unsupported(e.span, "`break`, `continue` and `return` are")
+        }
+    }
+
+    fn truth(&self, sp: Span, v: const_val) -> Result<bool, ConstEvalErr> {
+        match v {
+            const_bool(b) => Ok(b),
+            // Comparisons evaluate to integers.
+            const_int(i) => Ok(i != 0),
+            _ => at(sp, Err(~"expected a boolean"))
+        }
+    }
+
+    // Converts a value to the type it is declared with, for the types whose
+    // values don't record it.
+    fn coerce(&self, v: const_val, ty: &Ty) -> Result<const_val, ConstEvalErr> {
+        match astconv::ast_ty_to_prim_ty(self.tcx, ty) {
+            Some(t) => match ty::get(t).sty {
+                ty::ty_int(_) | ty::ty_uint(_) | ty::ty_float(_) => {
+                    at(ty.span, cast_const(self.tcx, v, t))
+                }
+                ty::ty_bool => Ok(match v {
+                    const_int(i) => const_bool(i != 0),
+                    v => v
+                }),
+                _ => Ok(v)
+            },
+            None => Ok(v)
+        }
+    }
+
+    // The integer type of `e`, if it can be told from the source.
+    fn int_ty(&self, e: &Expr) -> Option<ty::t> {
+        match e.node {
+            ExprLit(lit) => match lit.node {
+                LitInt(_, t) => Some(ty::mk_mach_int(t)),
+                LitUint(_, t) => Some(ty::mk_mach_uint(t)),
+                _ => None
+            },
+            ExprParen(e) | ExprUnary(_, e) => self.int_ty(e),
+            ExprBinary(op, a, b) => match op {
+                BiEq | BiNe | BiLt | BiLe | BiGt | BiGe | BiAnd | BiOr => None,
+                BiShl | BiShr => self.int_ty(a),
+                _ => self.int_ty(a).or_else(|| self.int_ty(b))
+            },
+            ExprCast(_, t) => astconv::ast_ty_to_prim_ty(self.tcx, t),
+            ExprPath(_) => match self.tcx.def_map.borrow().find_copy(&e.id) {
+                Some(DefArg(id, _)) | Some(DefLocal(id, _)) | Some(DefBinding(id, _)) => {
+                    self.local_tys.find_copy(&id)
+                }
+                _ => None
+            },
+            ExprCall(callee, _) => match self.tcx.def_map.borrow().find_copy(&callee.id) {
+                Some(DefFn(def_id, _)) => match lookup_const_fn(self.tcx, def_id) {
+                    Some(item) => match item.node {
+                        ItemFn(decl, _, _, _, _) => {
+                            astconv::ast_ty_to_prim_ty(self.tcx, decl.output)
+                        }
+                        _ => None
+                    },
+                    None => None
+                },
+                _ => None
+            },
+            _ => None
+        }
+    }
+
+    // Wraps an integer to the width of the type of `e`, which the result of
+    // an arithmetic operation is given whether or not it is declared.
+    fn wrap(&self, e: &Expr, v: const_val) -> const_val {
+        match self.int_ty(e) {
+            Some(t) => wrap_int(self.tcx, v, t),
+            None => v
+        }
+    }
+
+    // Binds the variables of an irrefutable pattern, declared with the
+    // integer type `t` if it is known.
+    fn bind(&mut self, p: &Pat, t: Option<ty::t>, v: const_val) -> Result<(), ConstEvalErr> {
+        match (&p.node, t) {
+            (&PatIdent(..), Some(t)) => { self.local_tys.insert(p.id, t); }
+            _ => {}
+        }
+        if !try!(self.pat(p, v)) {
+            return self.unsupported(p.span, "refutable patterns are")
+        }
+        Ok(())
+    }
+
+    fn call(&mut self, e: &Expr, callee: &Expr, args: Vec<const_val>)
+            -> Result<const_val, ConstEvalErr> {
+        let def_id = match self.tcx.def_map.borrow().find_copy(&callee.id) {
+            Some(DefFn(def_id, _)) => def_id,
+            _ => return self.unsupported(callee.span, "calls to anything but functions are")
+        };
+        let item = match lookup_const_fn(self.tcx, def_id) {
+            Some(item) => item,
+            None => {
+                return at(e.span, Err(format!("`{}` is not a constant function",
+                                              ty::item_path_str(self.tcx, def_id))))
+            }
+        };
+        let (decl, body) = match item.node {
+            ItemFn(decl, _, _, _, body) => (decl, body),
+            _ => fail!("lookup_const_fn returned a non-function")
+        };
+        if self.frames.len() == MAX_CONST_FN_DEPTH {
+            return at(e.span, Err(format!("constant function calls nested more than {} deep",
+                                          MAX_CONST_FN_DEPTH)))
+        }
+
+        self.frames.push(NodeMap::new());
+        for (input, arg) in decl.inputs.iter().zip(args.move_iter()) {
+            let arg = try!(self.coerce(arg, input.ty));
+            let t = astconv::ast_ty_to_prim_ty(self.tcx, input.ty);
+            try!(self.bind(input.pat, t, arg));
+        }
+        let v = match try!(self.block(body)) {
+            Value(v) | Return(v) => v,
+            // Reported by check_loop, which doesn't stop the compilation.
+            Break | Continue => {
+                return self.unsupported(body.span, "`break` and `continue` outside of loops are")
+            }
+        };
+        self.frames.pop();
+        self.coerce(v, decl.output)
+    }
+
+    fn block(&mut self, b: &Block) -> Result<Flow, ConstEvalErr> {
+        for stmt in b.stmts.iter() {
+            match stmt.node {
+                StmtDecl(decl, _) => match decl.node {
+                    DeclLocal(local) => match local.init {
+                        Some(init) => {
+                            let v = value!(self.expr(init));
+                            let v = try!(self.coerce(v, local.ty));
+                            let t = astconv::ast_ty_to_prim_ty(self.tcx, local.ty)
+                                           .or_else(|| self.int_ty(init));
+                            try!(self.bind(local.pat, t, v));
+                        }
+                        None => {}
+                    },
+                    // Nested items can only be used through paths, which
+                    // are handled when they're reached.
+                    DeclItem(_) => {}
+                },
+                StmtExpr(e, _) | StmtSemi(e, _) => { value!(self.expr(e)); }
+                StmtMac(..) => return self.unsupported(stmt.span, "unexpanded macros are")
+            }
+        }
+        match b.expr {
+            Some(e) => self.expr(e),
+            None => Ok(Value(const_int(0)))
+        }
+    }
+
+    fn expr(&mut self, e: &Expr) -> Result<Flow, ConstEvalErr> {
+        self.steps += 1;
+        if self.steps > MAX_CONST_FN_STEPS {
+            return at(e.span, Err(format!("constant evaluation took more than {} steps",
+                                          MAX_CONST_FN_STEPS)))
+        }
+
+        let v = match e.node {
+            ExprLit(lit) => lit_to_const(lit),
+            ExprVstore(e, _) | ExprParen(e) => return self.expr(e),
+            ExprPath(_) => try!(self.path(e)),
+            ExprUnary(op, inner) if op == UnNeg || op == UnNot => {
+                let v = value!(self.expr(inner));
+                self.wrap(e, try!(at(e.span, eval_unop(op, v))))
+            }
+            ExprBinary(op, a, b) if op == BiAnd || op == BiOr => {
+                let a = value!(self.expr(a));
+                if try!(self.truth(e.span, a)) == (op == BiOr) {
+                    const_bool(op == BiOr)
+                } else {
+                    let b = value!(self.expr(b));
+                    const_bool(try!(self.truth(e.span, b)))
+                }
+            }
+            ExprBinary(op, a, b) => {
+                let a = value!(self.expr(a));
+                let b = value!(self.expr(b));
+                self.wrap(e, try!(at(e.span, eval_binop(op, a, b))))
+            }
+            ExprCast(base, target_ty) => {
+                let base = value!(self.expr(base));
+                match astconv::ast_ty_to_prim_ty(self.tcx, target_ty) {
+                    Some(ety) => try!(at(e.span, cast_const(self.tcx, base, ety))),
+                    None => return self.unsupported(e.span, "casts to non-primitive types are")
+                }
+            }
+            ExprCall(callee, ref args) => {
+                let mut vals = Vec::new();
+                for arg in args.iter() {
+                    vals.push(value!(self.expr(*arg)));
+                }
+                try!(self.call(e, callee, vals))
+            }
+            ExprBlock(b) => return self.block(b),
+            ExprIf(cond, then, els) => {
+                let c = value!(self.expr(cond));
+                if try!(self.truth(cond.span, c)) {
+                    return self.block(then)
+                }
+                match els {
+                    Some(els) => return self.expr(els),
+                    None => const_int(0)
+                }
+            }
+            ExprWhile(cond, body) => {
+                loop {
+                    let c = value!(self.expr(cond));
+                    if !try!(self.truth(cond.span, c)) {
+                        break
+                    }
+                    match try!(self.block(body)) {
+                        Value(_) | Continue => {}
+                        Break => break,
+                        ret => return Ok(ret)
+                    }
+                }
+                const_int(0)
+            }
+            ExprLoop(body, None) => {
+                loop {
+                    match try!(self.block(body)) {
+                        Value(_) | Continue => {}
+                        Break => break,
+                        ret => return Ok(ret)
+                    }
+                }
+                const_int(0)
+            }
+            ExprLoop(_, Some(_)) | ExprBreak(Some(_)) | ExprAgain(Some(_)) => {
+                return self.unsupported(e.span, "labeled loops are")
+            }
+            ExprBreak(None) => return Ok(Break),
+            ExprAgain(None) => return Ok(Continue),
+            ExprRet(v) => {
+                let v = match v {
+                    Some(v) => value!(self.expr(v)),
+                    None => const_int(0)
+                };
+                return Ok(Return(v))
+            }
+            ExprAssign(lhs, rhs) => {
+                let v = value!(self.expr(rhs));
+                try!(self.assign(lhs, v));
+                const_int(0)
+            }
+            ExprAssignOp(op, lhs, rhs) => {
+                let a = value!(self.expr(lhs));
+                let b = value!(self.expr(rhs));
+                let v = self.wrap(lhs, try!(at(e.span, eval_binop(op, a, b))));
+                try!(self.assign(lhs, v));
+                const_int(0)
+            }
+            ExprMatch(scrut, ref arms) => {
+                let v = value!(self.expr(scrut));
+                for arm in arms.iter() {
+                    for pat in arm.pats.iter() {
+                        if !try!(self.pat(*pat, v.clone())) {
+                            continue
+                        }
+                        match arm.guard {
+                            Some(guard) => {
+                                let g = value!(self.expr(guard));
+                                if !try!(self.truth(guard.span, g)) {
+                                    continue
+                                }
+                            }
+                            None => {}
+                        }
+                        return self.expr(arm.body)
+                    }
+                }
+                return at(e.span, Err(~"no arm of the match matched"))
+            }
+            ExprForLoop(..) => return self.unsupported(e.span, "`for` loops are"),
+            ExprMethodCall(..) => return self.unsupported(e.span, "method calls are"),
+            _ => return self.unsupported(e.span, "this expression is")
+        };
+        Ok(Value(v))
+    }
+
+    fn path(&mut self, e: &Expr) -> Result<const_val, ConstEvalErr> {
+        let def = self.tcx.def_map.borrow().find_copy(&e.id);
+        match def {
+            Some(DefArg(id, _)) | Some(DefLocal(id, _)) | Some(DefBinding(id, _)) => {
+                match self.frames.last().and_then(|locals| locals.find_copy(&id)) {
+                    Some(v) => Ok(v),
+                    None => self.unsupported(e.span, "uninitialized variables are")
+                }
+            }
+            _ => match lookup_const(self.tcx, e) {
+                Some(actual_e) => self.value(actual_e),
+                None => self.unsupported(e.span, "this path is")
+            }
+        }
+    }
+
+    fn assign(&mut self, lhs: &Expr, v: const_val) -> Result<(), ConstEvalErr> {
+        let def = self.tcx.def_map.borrow().find_copy(&lhs.id);
+        match def {
+            Some(DefArg(id, _)) | Some(DefLocal(id, _)) | Some(DefBinding(id, _)) => {
+                let v = match self.local_tys.find_copy(&id) {
+                    Some(t) => wrap_int(self.tcx, v, t),
+                    None => v
+                };
+                self.frames.mut_last().unwrap().insert(id, v);
+                Ok(())
+            }
+            _ => self.unsupported(lhs.span, "assignments to anything but local variables are")
+        }
+    }
+
+    // Matches a value against a pattern, binding the variables in it.
+    fn pat(&mut self, p: &Pat, v: const_val) -> Result<bool, ConstEvalErr> {
+        let def = self.tcx.def_map.borrow().find_copy(&p.id);
+        match p.node {
+            PatWild | PatWildMulti => Ok(true),
+            PatIdent(_, _, sub) => {
+                match def {
+                    Some(DefStatic(did, false)) => {
+                        let c = match lookup_const_by_id(self.tcx, did) {
+                            Some(c) => try!(self.value(c)),
+                            None => return self.unsupported(p.span, "this pattern is")
+                        };
+                        return self.truth(p.span, try!(at(p.span, eval_binop(BiEq, v, c))))
+                    }
+                    Some(DefStatic(..)) | Some(DefVariant(..)) => {
+                        return self.unsupported(p.span, "this pattern is")
+                    }
+                    _ => {}
+                }
+                match sub {
+                    Some(sub) if !try!(self.pat(sub, v.clone())) => return Ok(false),
+                    _ => {}
+                }
+                if self.frames.is_empty() {
+                    return self.unsupported(p.span, "bindings outside of functions are")
+                }
+                self.frames.mut_last().unwrap().insert(p.id, v);
+                Ok(true)
+            }
+            PatLit(lit) => {
+                let lit = try!(self.value(lit));
+                self.truth(p.span, try!(at(p.span, eval_binop(BiEq, v, lit))))
+            }
+            PatRange(lo, hi) => {
+                let lo = try!(self.value(lo));
+                let hi = try!(self.value(hi));
+                let above = try!(at(p.span, eval_binop(BiLe, lo, v.clone())));
+                let below = try!(at(p.span, eval_binop(BiLe, v, hi)));
+                Ok(try!(self.truth(p.span, above)) && try!(self.truth(p.span, below)))
+            }
+            _ => self.unsupported(p.span, "this pattern is")
+        }
     }
 }
 
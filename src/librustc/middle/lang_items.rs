@@ -24,8 +24,10 @@ use driver::session::Session;
 use metadata::csearch::each_lang_item;
 use middle::ty;
 use syntax::ast;
+use syntax::ast_util;
 use syntax::ast_util::local_def;
 use syntax::attr::AttrMetaMethods;
+use syntax::codemap::Span;
 use syntax::parse::token::InternedString;
 use syntax::visit::Visitor;
 use syntax::visit;
@@ -203,6 +205,130 @@ pub fn collect_language_items(krate: &ast::Crate,
     @items
 }
 
+struct FreestandingChecker<'a> {
+    tcx: &'a ty::ctxt,
+    // Lang items already reported as missing.
+    reported: Vec<uint>,
+}
+
+impl<'a> FreestandingChecker<'a> {
+    fn require(&mut self, it: LangItem, sp: Span, what: &str) {
+        let index = it as uint;
+        if self.tcx.lang_items.items.get(index).is_some() ||
+           self.reported.contains(&index) {
+            return
+        }
+        self.reported.push(index);
+        self.tcx.sess.span_err(sp, format!("{} requires the `{}` lang item, which \
+                                            this freestanding crate does not define",
+                                           what, LanguageItems::item_name(index)));
+    }
+
+    fn require_owned(&mut self, sp: Span, what: &str) {
+        self.require(ExchangeMallocFnLangItem, sp, what);
+        self.require(ExchangeFreeFnLangItem, sp, what);
+    }
+
+    fn require_managed(&mut self, sp: Span, what: &str) {
+        self.require(MallocFnLangItem, sp, what);
+        self.require(FreeFnLangItem, sp, what);
+    }
+
+    fn is_indexed_sequence(&self, base: &ast::Expr) -> bool {
+        let mut t = ty::expr_ty(self.tcx, base);
+        loop {
+            if ty::index(t).is_some() { return true }
+            match ty::deref(t, true) {
+                Some(mt) => t = mt.ty,
+                None => return false,
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<()> for FreestandingChecker<'a> {
+    fn visit_expr(&mut self, e: &ast::Expr, _: ()) {
+        match e.node {
+            ast::ExprVstore(inner, ast::ExprVstoreUniq) => {
+                match inner.node {
+                    ast::ExprLit(lit) if ast_util::lit_is_str(lit) => {
+                        self.require(StrDupUniqFnLangItem, e.span, "this string");
+                        self.require(ExchangeFreeFnLangItem, e.span, "this string");
+                    }
+                    _ => self.require_owned(e.span, "this vector"),
+                }
+            }
+            ast::ExprBox(..) | ast::ExprUnary(ast::UnUniq, _) |
+            ast::ExprUnary(ast::UnBox, _) => {
+                match ty::get(ty::expr_ty(self.tcx, e)).sty {
+                    ty::ty_box(..) => self.require_managed(e.span, "this allocation"),
+                    _ => self.require_owned(e.span, "this allocation"),
+                }
+            }
+            ast::ExprProc(..) => {
+                self.require(ClosureExchangeMallocFnLangItem, e.span, "this closure");
+                self.require(ExchangeFreeFnLangItem, e.span, "this closure");
+            }
+            ast::ExprIndex(base, _) if self.is_indexed_sequence(base) => {
+                self.require(FailBoundsCheckFnLangItem, e.span,
+                             "bounds checking this index");
+            }
+            ast::ExprBinary(op, _, rhs) | ast::ExprAssignOp(op, _, rhs)
+                    if (op == ast::BiDiv || op == ast::BiRem) &&
+                       ty::type_is_integral(ty::expr_ty(self.tcx, rhs)) => {
+                self.require(FailFnLangItem, e.span, "checking this division");
+            }
+            _ => {}
+        }
+        visit::walk_expr(self, e, ());
+    }
+
+    fn visit_pat(&mut self, p: &ast::Pat, _: ()) {
+        match p.node {
+            ast::PatLit(lit) => {
+                match lit.node {
+                    ast::ExprLit(l) if ast_util::lit_is_str(l) => {
+                        match ty::get(ty::pat_ty(self.tcx, p)).sty {
+                            ty::ty_str(ty::VstoreUniq) => {
+                                self.require(UniqStrEqFnLangItem, p.span,
+                                             "matching this string");
+                            }
+                            _ => self.require(StrEqFnLangItem, p.span,
+                                              "matching this string"),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            ast::PatIdent(ast::BindByValue(_), _, _) => {
+                // Whatever a binding owns is freed when it goes out of scope.
+                let contents = ty::type_contents(self.tcx, ty::pat_ty(self.tcx, p));
+                if contents.owns_owned() {
+                    self.require(ExchangeFreeFnLangItem, p.span, "dropping this value");
+                }
+                if contents.owns_managed() {
+                    self.require(FreeFnLangItem, p.span, "dropping this value");
+                }
+            }
+            _ => {}
+        }
+        visit::walk_pat(self, p, ());
+    }
+}
+
+/// Report every lang item which a freestanding crate uses but neither
+/// defines nor gets from a crate it links to, at the first place it is
+/// needed. Without the standard library these would otherwise only be found
+/// one at a time, during translation.
+pub fn check_freestanding(tcx: &ty::ctxt, krate: &ast::Crate) {
+    let mut checker = FreestandingChecker {
+        tcx: tcx,
+        reported: Vec::new(),
+    };
+    visit::walk_crate(&mut checker, krate, ());
+    tcx.sess.abort_if_errors();
+}
+
 // End of the macro
     }
 }
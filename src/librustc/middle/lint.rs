@@ -32,6 +32,12 @@
 //! on the session at the appropriate time, or write a few linting functions and
 //! modify the Context visitor appropriately. If you're adding lints from the
 //! Context itself, span_lint should be used instead of add_lint.
+//!
+//! Lints can also be defined outside of the compiler, by a crate with a
+//! `#[lint_registrar]` function which is loaded with
+//! `#[phase(syntax)] extern crate`. The registrar is given a `LintRegistry`
+//! to which it adds its lints and the `LintPass`es checking them; these lints
+//! are then controlled by the same flags and attributes as built-in ones.
 
 #![allow(non_camel_case_types)]
 
@@ -53,24 +59,26 @@ use std::i16;
 use std::i32;
 use std::i64;
 use std::i8;
+use std::os;
 use std::to_str::ToStr;
 use std::u16;
 use std::u32;
 use std::u64;
 use std::u8;
-use collections::SmallIntMap;
+use std::unstable::dynamic_lib::DynamicLibrary;
 use syntax::abi;
 use syntax::ast_map;
 use syntax::ast_util::IdVisitingOperation;
 use syntax::attr::{AttrMetaMethods, AttributeMethods};
 use syntax::attr;
 use syntax::codemap::Span;
+use syntax::diagnostic::Suggestion;
 use syntax::parse::token::InternedString;
 use syntax::parse::token;
 use syntax::visit::Visitor;
 use syntax::{ast, ast_util, visit};
 
-#[deriving(Clone, Eq, Ord, TotalEq, TotalOrd)]
+#[deriving(Clone, Eq, Ord, TotalEq, TotalOrd, Hash)]
 pub enum Lint {
     CTypes,
     UnusedImports,
@@ -118,6 +126,9 @@ pub enum Lint {
     Warnings,
 
     RawPointerDeriving,
+
+    // The nth lint added to the session's `LintRegistry` by a plugin.
+    PluginLint(uint),
 }
 
 pub fn level_to_str(lv: level) -> &'static str {
@@ -147,7 +158,76 @@ pub type LintDict = HashMap<&'static str, LintSpec>;
 enum LintSource {
     Node(Span),
     Default,
-    CommandLine
+    CommandLine,
+    PolicyFile
+}
+
+/// Lint levels read from a `--lint-config` file, so that a policy can be
+/// shared by all the crates of a project. Each line of the file is either
+/// blank, a `#` comment, or
+///
+///     <level> <lint> [<module>]
+///
+/// e.g. `deny unused-imports` or `allow dead-code foo::tests`. A line without
+/// a module sets the level for the whole crate, and one with a module (a path
+/// from the crate root) sets it within that module.
+///
+/// Levels are decided in this order, later ones overriding earlier ones:
+/// lint defaults, crate-wide levels from the file, `-A/-W/-D/-F` flags,
+/// per-module levels from the file, and lint attributes in the source. A
+/// per-module level from the file is only applied if the level in effect
+/// still comes from a default or from the file itself, so the command line
+/// and attributes always win over it; `forbid` holds wherever it came from.
+#[deriving(Clone)]
+pub struct LintPolicy {
+    pub path: Path,
+    pub entries: Vec<LintPolicyEntry>,
+}
+
+#[deriving(Clone)]
+pub struct LintPolicyEntry {
+    pub line: uint,
+    pub level: level,
+    pub lint: ~str,
+    pub module: Option<~str>,
+}
+
+impl LintPolicy {
+    /// Parse the policy file at `path`, whose contents are `src`.
+    pub fn parse(path: &Path, src: &str) -> Result<LintPolicy, ~str> {
+        let mut entries = Vec::new();
+        for (i, line) in src.lines().enumerate() {
+            let line = match line.find('#') {
+                Some(pos) => line.slice_to(pos),
+                None => line,
+            };
+            let words: Vec<&str> = line.words().collect();
+            if words.is_empty() {
+                continue
+            }
+            let err = |msg: &str| format!("{}:{}: {}", path.display(), i + 1, msg);
+            if words.len() > 3 {
+                return Err(err("expected `<level> <lint> [<module>]`"));
+            }
+            let level = match *words.get(0) {
+                "allow" => allow,
+                "warn" => warn,
+                "deny" => deny,
+                "forbid" => forbid,
+                s => return Err(err(format!("unknown lint level `{}`", s))),
+            };
+            if words.len() < 2 {
+                return Err(err("expected a lint name after the level"));
+            }
+            entries.push(LintPolicyEntry {
+                line: i + 1,
+                level: level,
+                lint: words.get(1).replace("-", "_"),
+                module: words.as_slice().get(2).map(|m| m.to_owned()),
+            });
+        }
+        Ok(LintPolicy { path: path.clone(), entries: entries })
+    }
 }
 
 static lint_table: &'static [(&'static str, LintSpec)] = &[
@@ -430,11 +510,144 @@ pub fn get_lint_dict() -> LintDict {
     return map;
 }
 
-struct Context<'a> {
+/// A check defined by a lint plugin, run on the crate along with the
+/// built-in ones. Problems are reported through `Context::span_lint`, which
+/// takes the lint levels in effect into account.
+pub trait LintPass {
+    fn check_item(&mut self, _cx: &Context, _it: &ast::Item) {}
+    fn check_expr(&mut self, _cx: &Context, _e: &ast::Expr) {}
+    fn check_pat(&mut self, _cx: &Context, _p: &ast::Pat) {}
+    fn check_stmt(&mut self, _cx: &Context, _s: &ast::Stmt) {}
+}
+
+/// The lints and passes defined by lint plugins.
+pub struct LintRegistry {
+    lints: Vec<(&'static str, LintSpec)>,
+    passes: Vec<~LintPass>,
+}
+
+/// The type of a `#[lint_registrar]` function.
+pub type LintRegistrarFun = extern "Rust" fn(&mut LintRegistry);
+
+impl LintRegistry {
+    pub fn new() -> LintRegistry {
+        LintRegistry {
+            lints: Vec::new(),
+            passes: Vec::new(),
+        }
+    }
+
+    /// Define a lint, returning the `Lint` to report it with. As with
+    /// built-in lints, `name` should be in snake case.
+    pub fn register_lint(&mut self, name: &'static str, default: level,
+                         desc: &'static str) -> Lint {
+        let lint = PluginLint(self.lints.len());
+        self.lints.push((name, LintSpec {
+            lint: lint,
+            desc: desc,
+            default: default,
+        }));
+        lint
+    }
+
+    pub fn register_pass(&mut self, pass: ~LintPass) {
+        self.passes.push(pass);
+    }
+}
+
+/// Load the lint plugin with the registrar `symbol` from the dylib at
+/// `path`, adding its lints and passes to the session.
+pub fn load_plugin(sess: &session::Session, path: &Path, symbol: &str,
+                   span: Span) {
+    // Make sure the path contains a / or the linker will search for it.
+    let path = os::make_absolute(path);
+    let lib = match DynamicLibrary::open(Some(&path)) {
+        Ok(lib) => lib,
+        Err(err) => sess.span_fatal(span, err)
+    };
+
+    unsafe {
+        let registrar: LintRegistrarFun = match lib.symbol(symbol) {
+            Ok(registrar) => registrar,
+            Err(err) => sess.span_fatal(span, err)
+        };
+        let mut registry = sess.lint_registry.borrow_mut();
+        let first = registry.lints.len();
+        registrar(&mut *registry);
+
+        let builtin = get_lint_dict();
+        for (i, &(name, _)) in registry.lints.iter().enumerate().skip(first) {
+            if builtin.contains_key(&name) ||
+               registry.lints.slice_to(i).iter().any(|&(n, _)| n == name) {
+                sess.span_err(span, format!("lint `{}` is already defined", name));
+            }
+        }
+    }
+
+    // The passes the library registered are only run once the crate is type
+    // checked, so it stays loaded as long as the session.
+    sess.plugin_libs.borrow_mut().push(lib);
+}
+
+/// Reports the `-A`/`-W`/`-D`/`-F` flags naming a lint which neither rustc nor
+/// any of the plugins loaded so far defines.
+pub fn check_plugin_lint_opts(sess: &session::Session) {
+    let registry = sess.lint_registry.borrow();
+    for &(ref name, level) in sess.opts.plugin_lint_opts.iter() {
+        if !registry.lints.iter().any(|&(n, _)| n == name.as_slice()) {
+            sess.err(format!("unknown {} flag: {}", level_to_str(level), *name));
+        }
+    }
+    sess.abort_if_errors();
+}
+
+struct LintRegistrarContext {
+    registrars: Vec<(ast::NodeId, Span)>,
+}
+
+impl Visitor<()> for LintRegistrarContext {
+    fn visit_item(&mut self, item: &ast::Item, _: ()) {
+        match item.node {
+            ast::ItemFn(..) => {
+                if attr::contains_name(item.attrs.as_slice(), "lint_registrar") {
+                    self.registrars.push((item.id, item.span));
+                }
+            }
+            _ => {}
+        }
+
+        visit::walk_item(self, item, ());
+    }
+}
+
+/// Find the `#[lint_registrar]` function of the crate, if it has one.
+pub fn find_lint_registrar(sess: &session::Session,
+                           krate: &ast::Crate) -> Option<ast::DefId> {
+    let mut ctx = LintRegistrarContext { registrars: Vec::new() };
+    visit::walk_crate(&mut ctx, krate, ());
+
+    match ctx.registrars.len() {
+        0 => None,
+        1 => {
+            let (node_id, _) = ctx.registrars.pop().unwrap();
+            Some(ast_util::local_def(node_id))
+        }
+        _ => {
+            sess.err("multiple lint registration functions found");
+            for &(_, span) in ctx.registrars.iter() {
+                sess.span_note(span, "one is here");
+            }
+            sess.abort_if_errors();
+            unreachable!();
+        }
+    }
+}
+
+pub struct Context<'a> {
     // All known lint modes (string versions)
     dict: @LintDict,
     // Current levels of each lint warning
-    cur: SmallIntMap<(level, LintSource)>,
+    cur: HashMap<Lint, (level, LintSource)>,
     // context we're checking in (used to access fields like sess)
     tcx: &'a ty::ctxt,
     // maps from an expression id that corresponds to a method call to the
@@ -459,25 +672,24 @@ struct Context<'a> {
 
 impl<'a> Context<'a> {
     fn get_level(&self, lint: Lint) -> level {
-        match self.cur.find(&(lint as uint)) {
+        match self.cur.find(&lint) {
           Some(&(lvl, _)) => lvl,
           None => allow
         }
     }
 
     fn get_source(&self, lint: Lint) -> LintSource {
-        match self.cur.find(&(lint as uint)) {
+        match self.cur.find(&lint) {
           Some(&(_, src)) => src,
           None => Default
         }
     }
 
     fn set_level(&mut self, lint: Lint, level: level, src: LintSource) {
-        if level == allow {
-            self.cur.remove(&(lint as uint));
-        } else {
-            self.cur.insert(lint as uint, (level, src));
-        }
+        // Allowed lints are kept too, so that the policy file can tell a
+        // lint allowed by the command line or an attribute from one which
+        // is merely off by default.
+        self.cur.insert(lint, (level, src));
     }
 
     fn lint_to_str(&self, lint: Lint) -> &'static str {
@@ -489,8 +701,26 @@ impl<'a> Context<'a> {
         fail!("unregistered lint {:?}", lint);
     }
 
-    fn span_lint(&self, lint: Lint, span: Span, msg: &str) {
-        let (level, src) = match self.cur.find(&(lint as uint)) {
+    /// Report `msg` at `span` if `lint` is enabled at this point.
+    pub fn span_lint(&self, lint: Lint, span: Span, msg: &str) {
+        self.span_lint_with_suggestion(lint, span, msg, None)
+    }
+
+    /// The type context of the crate being checked.
+    pub fn tcx(&self) -> &'a ty::ctxt {
+        self.tcx
+    }
+
+    // Run `f` on each pass registered by a lint plugin.
+    fn with_plugin_passes(&self, f: |&mut LintPass|) {
+        for pass in self.tcx.sess.lint_registry.borrow_mut().passes.mut_iter() {
+            f(&mut **pass);
+        }
+    }
+
+    fn span_lint_with_suggestion(&self, lint: Lint, span: Span, msg: &str,
+                                 sugg: Option<Suggestion>) {
+        let (level, src) = match self.cur.find(&lint) {
             None => { return }
             Some(&(warn, src)) => (self.get_level(Warnings), src),
             Some(&pair) => pair,
@@ -510,22 +740,76 @@ impl<'a> Context<'a> {
                         allow => fail!()
                     }, self.lint_to_str(lint).replace("_", "-"))
             },
+            PolicyFile => {
+                let policy = self.tcx.sess.opts.lint_policy.get_ref();
+                format!("{} [{} {} in {}]", msg, level_to_str(level),
+                        self.lint_to_str(lint).replace("_", "-"),
+                        policy.path.display())
+            },
             Node(src) => {
                 note = Some(src);
                 msg.to_str()
             }
         };
         match level {
-            warn =>          { self.tcx.sess.span_warn(span, msg); }
-            deny | forbid => { self.tcx.sess.span_err(span, msg);  }
+            warn => {
+                self.tcx.sess.span_warn_with_code(span, msg, self.lint_to_str(lint));
+            }
+            deny | forbid => {
+                self.tcx.sess.span_err_with_code(span, msg, self.lint_to_str(lint));
+            }
             allow => fail!(),
         }
 
+        for sugg in sugg.move_iter() {
+            self.tcx.sess.suggest(sugg);
+        }
+
         for &span in note.iter() {
             self.tcx.sess.span_note(span, "lint level defined here");
         }
     }
 
+    /**
+     * Apply the levels which the lint policy file sets within the module
+     * `it`, if it is one, call the provided function, then reset the lints
+     * in effect to their previous state.
+     */
+    fn with_module_policy(&mut self, it: &ast::Item, f: |&mut Context|) {
+        let tcx = self.tcx;
+        let mut pushed = 0u;
+        match (&tcx.sess.opts.lint_policy, &it.node) {
+            (&Some(ref policy), &ast::ItemMod(..)) => {
+                let path = tcx.map.path_to_str(it.id);
+                for entry in policy.entries.iter() {
+                    if entry.module.as_ref() != Some(&path) {
+                        continue
+                    }
+                    let lint = match self.dict.find_equiv(&entry.lint) {
+                        Some(spec) => spec.lint,
+                        // Reported by `check_crate`.
+                        None => continue,
+                    };
+                    let now = self.get_level(lint);
+                    let src = self.get_source(lint);
+                    if now != forbid && (src == Default || src == PolicyFile) {
+                        self.lint_stack.push((lint, now, src));
+                        pushed += 1;
+                        self.set_level(lint, entry.level, PolicyFile);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        f(self);
+
+        for _ in range(0, pushed) {
+            let (lint, lvl, src) = self.lint_stack.pop().unwrap();
+            self.set_level(lint, lvl, src);
+        }
+    }
+
     /**
      * Merge the lints specified by any lint attributes into the
      * current lint context, call the provided function, then reset the
@@ -1002,6 +1286,7 @@ fn check_raw_ptr_deriving(cx: &Context, item: &ast::Item) {
 
 static crate_attrs: &'static [&'static str] = &[
     "crate_type", "feature", "no_start", "no_main", "no_std", "crate_id",
+    "freestanding",
     "desc", "comment", "license", "copyright", // not used in rustc now
 ];
 
@@ -1024,14 +1309,15 @@ static other_attrs: &'static [&'static str] = &[
     "cfg", "doc", "export_name", "link_section",
     "no_mangle", "static_assert", "unsafe_no_drop_flag", "packed",
     "simd", "repr", "deriving", "unsafe_destructor", "link", "phase",
-    "macro_export", "must_use", "automatically_derived",
+    "macro_export", "must_use", "automatically_derived", "macro_import",
 
     //mod-level
     "path", "link_name", "link_args", "macro_escape", "no_implicit_prelude",
 
     // fn-level
     "test", "bench", "should_fail", "ignore", "inline", "lang", "main", "start",
-    "no_split_stack", "cold", "macro_registrar", "linkage",
+    "no_split_stack", "cold", "macro_registrar", "lint_registrar", "linkage",
+    "const_fn",
 
     // internal attribute: bypass privacy inside items
     "!resolve_unexported",
@@ -1278,9 +1564,18 @@ fn check_struct_uppercase_variable(cx: &Context, s: &ast::StructDef) {
 
 fn check_unnecessary_parens_core(cx: &Context, value: &ast::Expr, msg: &str) {
     match value.node {
-        ast::ExprParen(_) => {
-            cx.span_lint(UnnecessaryParens, value.span,
-                         format!("unnecessary parentheses around {}", msg))
+        ast::ExprParen(inner) => {
+            let sugg = cx.tcx.sess.codemap().span_to_snippet(inner.span).map(|s| {
+                Suggestion {
+                    span: value.span,
+                    msg: ~"remove these parentheses",
+                    replacement: s,
+                    machine_applicable: true,
+                }
+            });
+            cx.span_lint_with_suggestion(UnnecessaryParens, value.span,
+                                         format!("unnecessary parentheses around {}", msg),
+                                         sugg)
         }
         _ => {}
     }
@@ -1354,8 +1649,22 @@ fn check_unused_mut_pat(cx: &Context, p: &ast::Pat) {
 
             if !initial_underscore &&
                !cx.tcx.used_mut_nodes.borrow().contains(&p.id) {
-                cx.span_lint(UnusedMut, p.span,
-                             "variable does not need to be mutable");
+                let snippet = cx.tcx.sess.codemap().span_to_snippet(p.span);
+                let sugg = snippet.and_then(|s| {
+                    if s.starts_with("mut") {
+                        Some(Suggestion {
+                            span: p.span,
+                            msg: ~"remove `mut`",
+                            replacement: s.slice_from(3).trim_left().to_owned(),
+                            machine_applicable: true,
+                        })
+                    } else {
+                        None
+                    }
+                });
+                cx.span_lint_with_suggestion(UnusedMut, p.span,
+                                             "variable does not need to be mutable",
+                                             sugg);
             }
         }
         _ => ()
@@ -1619,18 +1928,21 @@ fn check_stability(cx: &Context, e: &ast::Expr) {
 
 impl<'a> Visitor<()> for Context<'a> {
     fn visit_item(&mut self, it: &ast::Item, _: ()) {
-        self.with_lint_attrs(it.attrs.as_slice(), |cx| {
-            check_item_ctypes(cx, it);
-            check_item_non_camel_case_types(cx, it);
-            check_item_non_uppercase_statics(cx, it);
-            check_heap_item(cx, it);
-            check_missing_doc_item(cx, it);
-            check_attrs_usage(cx, it.attrs.as_slice());
-            check_raw_ptr_deriving(cx, it);
-
-            cx.visit_ids(|v| v.visit_item(it, ()));
-
-            visit::walk_item(cx, it, ());
+        self.with_module_policy(it, |cx| {
+            cx.with_lint_attrs(it.attrs.as_slice(), |cx| {
+                check_item_ctypes(cx, it);
+                check_item_non_camel_case_types(cx, it);
+                check_item_non_uppercase_statics(cx, it);
+                check_heap_item(cx, it);
+                check_missing_doc_item(cx, it);
+                check_attrs_usage(cx, it.attrs.as_slice());
+                check_raw_ptr_deriving(cx, it);
+                cx.with_plugin_passes(|pass| pass.check_item(cx, it));
+
+                cx.visit_ids(|v| v.visit_item(it, ()));
+
+                visit::walk_item(cx, it, ());
+            })
         })
     }
 
@@ -1652,6 +1964,7 @@ impl<'a> Visitor<()> for Context<'a> {
         check_pat_non_uppercase_statics(self, p);
         check_pat_uppercase_variable(self, p);
         check_unused_mut_pat(self, p);
+        self.with_plugin_passes(|pass| pass.check_pat(self, p));
 
         visit::walk_pat(self, p, ());
     }
@@ -1681,6 +1994,7 @@ impl<'a> Visitor<()> for Context<'a> {
         check_type_limits(self, e);
         check_unused_casts(self, e);
         check_deprecated_owned_vector(self, e);
+        self.with_plugin_passes(|pass| pass.check_expr(self, e));
 
         visit::walk_expr(self, e, ());
     }
@@ -1689,6 +2003,7 @@ impl<'a> Visitor<()> for Context<'a> {
         check_path_statement(self, s);
         check_unused_result(self, s);
         check_unnecessary_parens_stmt(self, s);
+        self.with_plugin_passes(|pass| pass.check_stmt(self, s));
 
         visit::walk_stmt(self, s, ());
     }
@@ -1766,8 +2081,8 @@ impl<'a> IdVisitingOperation for Context<'a> {
         match self.tcx.sess.lints.borrow_mut().pop(&id) {
             None => {}
             Some(l) => {
-                for (lint, span, msg) in l.move_iter() {
-                    self.span_lint(lint, span, msg)
+                for (lint, span, msg, sugg) in l.move_iter() {
+                    self.span_lint_with_suggestion(lint, span, msg, sugg)
                 }
             }
         }
@@ -1778,9 +2093,17 @@ pub fn check_crate(tcx: &ty::ctxt,
                    method_map: typeck::MethodMap,
                    exported_items: &privacy::ExportedItems,
                    krate: &ast::Crate) {
+    let mut dict = get_lint_dict();
+    for &(name, spec) in tcx.sess.lint_registry.borrow().lints.iter() {
+        // Redefinitions were reported when the plugin was loaded, and the
+        // first definition stands.
+        if !dict.contains_key(&name) {
+            dict.insert(name, spec);
+        }
+    }
     let mut cx = Context {
-        dict: @get_lint_dict(),
-        cur: SmallIntMap::new(),
+        dict: @dict,
+        cur: HashMap::new(),
         tcx: tcx,
         method_map: method_map,
         exported_items: exported_items,
@@ -1790,14 +2113,40 @@ pub fn check_crate(tcx: &ty::ctxt,
         negated_expr_id: -1
     };
 
-    // Install default lint levels, followed by the command line levels, and
-    // then actually visit the whole crate.
+    // Install default lint levels, followed by the crate-wide levels from the
+    // policy file and the command line levels, and then actually visit the
+    // whole crate.
     for (_, spec) in cx.dict.iter() {
         cx.set_level(spec.lint, spec.default, Default);
     }
+    match tcx.sess.opts.lint_policy {
+        Some(ref policy) => {
+            for entry in policy.entries.iter() {
+                match cx.dict.find_equiv(&entry.lint) {
+                    Some(spec) if entry.module.is_none() => {
+                        cx.set_level(spec.lint, entry.level, PolicyFile);
+                    }
+                    Some(_) => {}
+                    None => {
+                        tcx.sess.err(format!("{}:{}: unknown lint `{}`",
+                                             policy.path.display(), entry.line,
+                                             entry.lint));
+                    }
+                }
+            }
+        }
+        None => {}
+    }
     for &(lint, level) in tcx.sess.opts.lint_opts.iter() {
         cx.set_level(lint, level, CommandLine);
     }
+    for &(ref name, level) in tcx.sess.opts.plugin_lint_opts.iter() {
+        match cx.dict.find_equiv(name) {
+            Some(spec) => cx.set_level(spec.lint, level, CommandLine),
+            // Reported by `check_plugin_lint_opts`.
+            None => {}
+        }
+    }
     cx.with_lint_attrs(krate.attrs.as_slice(), |cx| {
         cx.visit_id(ast::CRATE_NODE_ID);
         cx.visit_ids(|v| {
@@ -1820,11 +2169,9 @@ pub fn check_crate(tcx: &ty::ctxt,
     // If we missed any lints added to the session, then there's a bug somewhere
     // in the iteration code.
     for (id, v) in tcx.sess.lints.borrow().iter() {
-        for &(lint, span, ref msg) in v.iter() {
+        for &(lint, span, ref msg, _) in v.iter() {
             tcx.sess.span_bug(span, format!("unprocessed lint {:?} at {}: {}",
                                             lint, tcx.map.node_to_str(*id), *msg))
         }
     }
-
-    tcx.sess.abort_if_errors();
 }
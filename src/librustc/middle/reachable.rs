@@ -29,9 +29,10 @@ use syntax::visit::Visitor;
 use syntax::visit;
 
 // Returns true if the given set of attributes contains the `#[inline]`
-// attribute.
+// attribute, or the `#[const_fn]` one since the bodies of constant functions
+// are inlined into other crates as well.
 fn attributes_specify_inlining(attrs: &[ast::Attribute]) -> bool {
-    attr::contains_name(attrs, "inline")
+    attr::contains_name(attrs, "inline") || attr::contains_name(attrs, "const_fn")
 }
 
 // Returns true if the given set of generics implies that the item it's
@@ -27,6 +27,7 @@ use syntax::parse::token::special_idents;
 use syntax::parse::token;
 use syntax::print::pprust::path_to_str;
 use syntax::codemap::{Span, DUMMY_SP, Pos};
+use syntax::diagnostic::Suggestion;
 use syntax::owned_slice::OwnedSlice;
 use syntax::visit;
 use syntax::visit::Visitor;
@@ -1094,7 +1095,8 @@ impl<'a> Resolver<'a> {
                     self.resolve_error(sp,
                         format!("duplicate definition of {} `{}`",
                              namespace_error_to_str(duplicate_type),
-                             token::get_ident(name)));
+                             token::get_ident(name)),
+                                       diagnostics::E0001);
                     {
                         let r = child.span_for_namespace(ns);
                         for sp in r.iter() {
@@ -2086,7 +2088,7 @@ impl<'a> Resolver<'a> {
                                        import_directive.module_path
                                                        .as_slice(),
                                        *import_directive.subclass));
-                    self.resolve_error(import_directive.span, msg);
+                    self.resolve_error(import_directive.span, msg, diagnostics::E0002);
                 }
                 Indeterminate => {
                     // Bail out. We'll come around next time.
@@ -2462,7 +2464,7 @@ impl<'a> Resolver<'a> {
                                `{}` in `{}`",
                               token::get_ident(source),
                               self.module_to_str(containing_module));
-            self.resolve_error(directive.span, msg);
+            self.resolve_error(directive.span, msg, diagnostics::E0002);
             return Failed;
         }
         let value_used_public = value_used_reexport || value_used_public;
@@ -2698,11 +2700,13 @@ impl<'a> Resolver<'a> {
                                               format!("unresolved import. maybe \
                                                     a missing `extern crate \
                                                     {}`?",
-                                                    segment_name));
+                                                    segment_name),
+                                           diagnostics::E0002);
                         return Failed;
                     }
                     self.resolve_error(span, format!("unresolved import: could not find `{}` in \
-                                                     `{}`.", segment_name, module_name));
+                                                     `{}`.", segment_name, module_name),
+                                       diagnostics::E0002);
                     return Failed;
                 }
                 Indeterminate => {
@@ -2720,7 +2724,8 @@ impl<'a> Resolver<'a> {
                                 None => {
                                     // Not a module.
                                     self.resolve_error(span, format!("not a module `{}`",
-                                                                 token::get_ident(name)));
+                                                                 token::get_ident(name)),
+                                                       diagnostics::E0003);
                                     return Failed;
                                 }
                                 Some(module_def) => {
@@ -2734,7 +2739,8 @@ impl<'a> Resolver<'a> {
                                             self.resolve_error(
                                                 span,
                                                 "cannot import from a trait \
-                                                 or type implementation");
+                                                 or type implementation",
+                                                diagnostics::E0003);
                                             return Failed;
                                         }
                                         (_, _) => {
@@ -2763,7 +2769,8 @@ impl<'a> Resolver<'a> {
                             // There are no type bindings at all.
                             self.resolve_error(span,
                                                   format!("not a module `{}`",
-                                                       token::get_ident(name)));
+                                                       token::get_ident(name)),
+                                               diagnostics::E0003);
                             return Failed;
                         }
                     }
@@ -2813,7 +2820,8 @@ impl<'a> Resolver<'a> {
                                                          // idx +- 1 to account for the colons
                                                          // on either side
                                                          mpath.slice_from(idx + 1),
-                                                         mpath.slice_to(idx - 1)));
+                                                         mpath.slice_to(idx - 1)),
+                                           diagnostics::E0002);
                     },
                     None => (),
                 };
@@ -2845,7 +2853,7 @@ impl<'a> Resolver<'a> {
                             module_path[0]);
                         match result {
                             Failed => {
-                                self.resolve_error(span, "unresolved name");
+                                self.resolve_error(span, "unresolved name", diagnostics::E0002);
                                 return Failed;
                             }
                             Indeterminate => {
@@ -3244,11 +3252,15 @@ impl<'a> Resolver<'a> {
                          .unwrap();
             if sn.contains("::") {
                 self.resolve_error(imports.get(index).span,
-                                   "unresolved import");
+                                   "unresolved import",
+                                   diagnostics::E0002);
             } else {
                 let err = format!("unresolved import (maybe you meant `{}::*`?)",
                                sn.slice(0, sn.len()));
-                self.resolve_error(imports.get(index).span, err);
+                self.resolve_error(imports.get(index).span, err, diagnostics::E0002);
+                self.resolve_suggestion(imports.get(index).span,
+                                        "import everything from the module",
+                                        sn + "::*");
             }
         }
 
@@ -3506,14 +3518,16 @@ impl<'a> Resolver<'a> {
                         self.resolve_error(
                             span,
                             "can't capture dynamic environment in a fn item; \
-                            use the || { ... } closure form instead");
+                            use the || { ... } closure form instead",
+                            diagnostics::E0004);
                     } else {
                         // This was an attempt to use a type parameter outside
                         // its scope.
 
                         self.resolve_error(span,
                                               "attempt to use a type \
-                                              argument out of scope");
+                                              argument out of scope",
+                                           diagnostics::E0005);
                     }
 
                     return None;
@@ -3529,14 +3543,16 @@ impl<'a> Resolver<'a> {
                         self.resolve_error(
                             span,
                             "can't capture dynamic environment in a fn item; \
-                            use the || { ... } closure form instead");
+                            use the || { ... } closure form instead",
+                            diagnostics::E0004);
                     } else {
                         // This was an attempt to use a type parameter outside
                         // its scope.
 
                         self.resolve_error(span,
                                               "attempt to use a type \
-                                              argument out of scope");
+                                              argument out of scope",
+                                           diagnostics::E0005);
                     }
 
                     return None;
@@ -3546,12 +3562,14 @@ impl<'a> Resolver<'a> {
                         // see #9186
                         self.resolve_error(span,
                                               "cannot use an outer type \
-                                               parameter in this context");
+                                               parameter in this context",
+                                           diagnostics::E0005);
                     } else {
                         // Still doesn't deal with upvars
                         self.resolve_error(span,
                                               "attempt to use a non-constant \
-                                               value in a constant");
+                                               value in a constant",
+                                           diagnostics::E0006);
                     }
 
                 }
@@ -3667,6 +3685,7 @@ impl<'a> Resolver<'a> {
                     for trt in traits.iter() {
                         this.resolve_trait_reference(item.id, trt, TraitDerivation);
                     }
+                    this.resolve_where_clause(generics, true);
 
                     for method in (*methods).iter() {
                         // Create a new rib for the method-specific type
@@ -3687,6 +3706,7 @@ impl<'a> Resolver<'a> {
                                 // parameters.
                                 this.resolve_type_parameters(
                                     &ty_m.generics.ty_params);
+                                this.resolve_where_clause(&ty_m.generics, false);
 
                                 for argument in ty_m.decl.inputs.iter() {
                                     this.resolve_type(argument.ty);
@@ -3781,13 +3801,17 @@ impl<'a> Resolver<'a> {
                     let ident = type_parameter.ident;
                     debug!("with_type_parameter_rib: {} {}", node_id,
                            type_parameter.id);
-                    let def_like = DlDef(DefTyParam
-                        (local_def(type_parameter.id),
-                         index + initial_index));
                     // Associate this type parameter with
                     // the item that bound it
                     self.record_def(type_parameter.id,
                                     (DefTyParamBinder(node_id), LastMod(AllPublic)));
+                    // Associated types are only reachable as `Self::Name`.
+                    if type_parameter.associated {
+                        continue;
+                    }
+                    let def_like = DlDef(DefTyParam
+                        (local_def(type_parameter.id),
+                         index + initial_index));
                     // plain insert (no renaming)
                     function_type_rib.bindings.borrow_mut()
                                      .insert(ident.name, def_like);
@@ -3843,6 +3867,7 @@ impl<'a> Resolver<'a> {
                 }
                 HasTypeParameters(ref generics, _, _, _) => {
                     this.resolve_type_parameters(&generics.ty_params);
+                    this.resolve_where_clause(*generics, false);
                 }
             }
 
@@ -3890,6 +3915,48 @@ impl<'a> Resolver<'a> {
         }
     }
 
+    fn resolve_where_clause(&mut self, generics: &Generics, own_params_only: bool) {
+        /*!
+         * Resolves the where clause of `generics`. Functions and methods
+         * can bound any type; with `own_params_only`, for impls and traits,
+         * only the type parameters of the item and their associated types
+         * can be bounded.
+         */
+
+        for predicate in generics.where_predicates.iter() {
+            self.resolve_type(predicate.ty);
+            for bound in predicate.bounds.iter() {
+                self.resolve_type_parameter_bound(predicate.ty.id, bound);
+            }
+
+            if !own_params_only {
+                continue;
+            }
+            let valid = match predicate.ty.node {
+                TyPath(ref path, None, path_id) if !path.global &&
+                        path.segments.len() <= 2 &&
+                        path.segments.iter().all(|s| s.lifetimes.is_empty() &&
+                                                     s.types.is_empty()) => {
+                    match self.def_map.borrow().find_copy(&path_id) {
+                        Some(DefTyParam(did, _)) => {
+                            generics.ty_params.iter().any(|p| local_def(p.id) == did)
+                        }
+                        // The path didn't resolve, which has been reported.
+                        None => true,
+                        Some(_) => false
+                    }
+                }
+                _ => false
+            };
+            if !valid {
+                self.resolve_error(predicate.ty.span,
+                                   "where clauses on impls and traits can only bound \
+                                    their own type parameters, or their associated types",
+                                   diagnostics::E0107);
+            }
+        }
+    }
+
     fn resolve_type_parameter_bound(&mut self,
                                         id: NodeId,
                                         type_parameter_bound: &TyParamBound) {
@@ -3915,7 +3982,7 @@ impl<'a> Resolver<'a> {
                 };
 
                 let msg = format!("attempt to {} a nonexistent trait `{}`", usage_str, path_str);
-                self.resolve_error(trait_reference.path.span, msg);
+                self.resolve_error(trait_reference.path.span, msg, diagnostics::E0007);
             }
             Some(def) => {
                 debug!("(resolving trait) found trait def: {:?}", def);
@@ -3936,7 +4003,8 @@ impl<'a> Resolver<'a> {
                         Some(&prev_field) => {
                             let ident_str = token::get_ident(ident);
                             self.resolve_error(field.span,
-                                format!("field `{}` is already declared", ident_str));
+                                format!("field `{}` is already declared", ident_str),
+                                               diagnostics::E0008);
                             self.session.span_note(prev_field.span,
                                 "previously declared here");
                         },
@@ -3996,6 +4064,7 @@ impl<'a> Resolver<'a> {
                                      |this| {
             // Resolve the type parameters.
             this.resolve_type_parameters(&generics.ty_params);
+            this.resolve_where_clause(generics, true);
 
             // Resolve the trait reference, if necessary.
             let original_trait_refs;
@@ -4003,6 +4072,9 @@ impl<'a> Resolver<'a> {
                 &Some(ref trait_reference) => {
                     this.resolve_trait_reference(id, trait_reference,
                         TraitImplementation);
+                    for binding in trait_reference.bindings.iter() {
+                        this.resolve_type(binding.ty);
+                    }
 
                     // Record the current set of trait references.
                     let mut new_trait_refs = Vec::new();
@@ -4111,7 +4183,8 @@ impl<'a> Resolver<'a> {
                         format!("variable `{}` from pattern \\#1 is \
                                   not bound in pattern \\#{}",
                                 token::get_name(key),
-                                i + 1));
+                                i + 1),
+                        diagnostics::E0009);
                   }
                   Some(binding_i) => {
                     if binding_0.binding_mode != binding_i.binding_mode {
@@ -4120,7 +4193,8 @@ impl<'a> Resolver<'a> {
                             format!("variable `{}` is bound with different \
                                       mode in pattern \\#{} than in pattern \\#1",
                                     token::get_name(key),
-                                    i + 1));
+                                    i + 1),
+                            diagnostics::E0010);
                     }
                   }
                 }
@@ -4133,7 +4207,8 @@ impl<'a> Resolver<'a> {
                         format!("variable `{}` from pattern \\#{} is \
                                   not bound in pattern \\#1",
                                 token::get_name(key),
-                                i + 1));
+                                i + 1),
+                        diagnostics::E0009);
                 }
             }
         }
@@ -4212,17 +4287,19 @@ impl<'a> Resolver<'a> {
                             if path.segments
                                    .iter()
                                    .any(|s| !s.lifetimes.is_empty()) {
-                                self.session.span_err(path.span,
-                                                      "lifetime parameters \
-                                                       are not allowed on \
-                                                       this type")
+                                self.session.span_err_with_code(path.span,
+                                                                "lifetime parameters \
+                                                                 are not allowed on \
+                                                                 this type",
+                                                                diagnostics::E0011)
                             } else if path.segments
                                           .iter()
                                           .any(|s| s.types.len() > 0) {
-                                self.session.span_err(path.span,
-                                                      "type parameters are \
-                                                       not allowed on this \
-                                                       type")
+                                self.session.span_err_with_code(path.span,
+                                                                "type parameters are \
+                                                                 not allowed on this \
+                                                                 type",
+                                                                diagnostics::E0011)
                             }
                         }
                         None => {
@@ -4231,6 +4308,19 @@ impl<'a> Resolver<'a> {
                     }
                 }
 
+                // `T::Name` and `Self::Name` refer to an associated type of
+                // the bounds of `T` or of the enclosing trait. Typeck works
+                // out which one, so record the definition of `T` or `Self`.
+                if result_def.is_none() && !path.global && path.segments.len() == 2 {
+                    let ident = path.segments.get(0).identifier;
+                    match self.resolve_identifier_in_local_ribs(ident, TypeNS, path.span) {
+                        Some(def @ DefTyParam(..)) | Some(def @ DefSelfTy(..)) => {
+                            result_def = Some((def, LastMod(AllPublic)));
+                        }
+                        _ => {}
+                    }
+                }
+
                 match result_def {
                     None => {
                         match self.resolve_path(ty.id, path, TypeNS, true) {
@@ -4263,7 +4353,7 @@ impl<'a> Resolver<'a> {
                     None => {
                         let msg = format!("use of undeclared type name `{}`",
                                           self.path_idents_to_str(path));
-                        self.resolve_error(ty.span, msg);
+                        self.resolve_error(ty.span, msg, diagnostics::E0012);
                     }
                 }
 
@@ -4333,7 +4423,8 @@ impl<'a> Resolver<'a> {
                                                         shadows an enum \
                                                         variant or unit-like \
                                                         struct in scope",
-                                                        token::get_name(renamed)));
+                                                        token::get_name(renamed)),
+                                               diagnostics::E0013);
                         }
                         FoundConst(def, lp) if mode == RefutableMode => {
                             debug!("(resolving pattern) resolving `{}` to \
@@ -4349,7 +4440,8 @@ impl<'a> Resolver<'a> {
                         FoundConst(..) => {
                             self.resolve_error(pattern.span,
                                                   "only irrefutable patterns \
-                                                   allowed here");
+                                                   allowed here",
+                                               diagnostics::E0014);
                         }
                         BareIdentifierPatternUnresolved => {
                             debug!("(resolving pattern) binding `{}`",
@@ -4404,7 +4496,8 @@ impl<'a> Resolver<'a> {
                                      self.resolve_error(pattern.span,
                                        format!("identifier `{}` is bound more \
                                              than once in the same pattern",
-                                            path_to_str(path)));
+                                            path_to_str(path)),
+                                                        diagnostics::E0015);
                                   }
                                   // Not bound in the same pattern: do nothing
                                 }
@@ -4450,11 +4543,13 @@ impl<'a> Resolver<'a> {
                                 path.span,
                                 format!("`{}` is not an enum variant or constant",
                                      token::get_ident(
-                                         path.segments.last().unwrap().identifier)))
+                                         path.segments.last().unwrap().identifier)),
+                                diagnostics::E0016)
                         }
                         None => {
                             self.resolve_error(path.span,
-                                                  "unresolved enum variant");
+                                                  "unresolved enum variant",
+                                               diagnostics::E0016);
                         }
                     }
 
@@ -4480,14 +4575,16 @@ impl<'a> Resolver<'a> {
                                 format!("`{}` is not an enum variant, struct or const",
                                     token::get_ident(path.segments
                                                          .last().unwrap()
-                                                         .identifier)));
+                                                         .identifier)),
+                                               diagnostics::E0016);
                         }
                         None => {
                             self.resolve_error(path.span,
                                 format!("unresolved enum variant, struct or const `{}`",
                                     token::get_ident(path.segments
                                                          .last().unwrap()
-                                                         .identifier)));
+                                                         .identifier)),
+                                               diagnostics::E0016);
                         }
                     }
 
@@ -4528,7 +4625,7 @@ impl<'a> Resolver<'a> {
                                     def: {:?}", result);
                             let msg = format!("`{}` does not name a structure",
                                               self.path_idents_to_str(path));
-                            self.resolve_error(path.span, msg);
+                            self.resolve_error(path.span, msg, diagnostics::E0017);
                         }
                     }
                 }
@@ -4747,7 +4844,7 @@ impl<'a> Resolver<'a> {
             Failed => {
                 let msg = format!("use of undeclared module `{}`",
                                   self.idents_to_str(module_path_idents.as_slice()));
-                self.resolve_error(path.span, msg);
+                self.resolve_error(path.span, msg, diagnostics::E0018);
                 return None;
             }
 
@@ -4817,7 +4914,7 @@ impl<'a> Resolver<'a> {
             Failed => {
                 let msg = format!("use of undeclared module `::{}`",
                                   self.idents_to_str(module_path_idents.as_slice()));
-                self.resolve_error(path.span, msg);
+                self.resolve_error(path.span, msg, diagnostics::E0018);
                 return None;
             }
 
@@ -4925,9 +5022,15 @@ impl<'a> Resolver<'a> {
         rs
     }
 
-    fn resolve_error(&mut self, span: Span, s: &str) {
+    fn resolve_error(&mut self, span: Span, s: &str, code: &'static str) {
         if self.emit_errors {
-            self.session.span_err(span, s);
+            self.session.span_err_with_code(span, s, code);
+        }
+    }
+
+    fn resolve_suggestion(&mut self, span: Span, msg: &str, replacement: ~str) {
+        if self.emit_errors {
+            self.session.span_suggestion(span, msg, replacement);
         }
     }
 
@@ -4998,7 +5101,8 @@ impl<'a> Resolver<'a> {
                             (DefMethod(..), _) => {
                                 self.resolve_error(expr.span,
                                                       "first-class methods \
-                                                       are not supported");
+                                                       are not supported",
+                                                   diagnostics::E0019);
                                 self.session.span_note(expr.span,
                                                        "call the method \
                                                         using the `.` \
@@ -5023,7 +5127,8 @@ impl<'a> Resolver<'a> {
                                         format!("`{}` is a structure name, but \
                                                  this expression \
                                                  uses it like a function name",
-                                                wrong_name));
+                                                wrong_name),
+                                                   diagnostics::E0020);
 
                                 self.session.span_note(expr.span,
                                     format!("Did you mean to write: \
@@ -5039,12 +5144,17 @@ impl<'a> Resolver<'a> {
                                        self.resolve_error(expr.span,
                                            format!("unresolved name `{}`. \
                                                     Did you mean `{}`?",
-                                                    wrong_name, m));
+                                                    wrong_name, m),
+                                                          diagnostics::E0021);
+                                       self.resolve_suggestion(expr.span,
+                                           format!("replace with `{}`", m),
+                                           m);
                                    }
                                    None => {
                                        self.resolve_error(expr.span,
                                             format!("unresolved name `{}`.",
-                                                    wrong_name));
+                                                    wrong_name),
+                                                          diagnostics::E0021);
                                    }
                                }
                         }
@@ -5078,7 +5188,7 @@ impl<'a> Resolver<'a> {
                                 def: {:?}", result);
                         let msg = format!("`{}` does not name a structure",
                                           self.path_idents_to_str(path));
-                        self.resolve_error(path.span, msg);
+                        self.resolve_error(path.span, msg, diagnostics::E0017);
                     }
                 }
 
@@ -5110,7 +5220,8 @@ impl<'a> Resolver<'a> {
                     None =>
                         self.resolve_error(expr.span,
                                               format!("use of undeclared label `{}`",
-                                                   token::get_ident(label))),
+                                                   token::get_ident(label)),
+                                           diagnostics::E0022),
                     Some(DlDef(def @ DefLabel(_))) => {
                         // Since this def is a label, it is never read.
                         self.record_def(expr.id, (def, LastMod(AllPublic)))
@@ -5263,7 +5374,8 @@ impl<'a> Resolver<'a> {
                 self.resolve_error(
                     pat.span,
                     format!("cannot use `ref` binding mode with {}",
-                         descr));
+                         descr),
+                    diagnostics::E0023);
             }
         }
     }
@@ -5292,18 +5404,34 @@ impl<'a> Resolver<'a> {
         match vi.node {
             ViewItemExternCrate(..) => {} // ignore
             ViewItemUse(ref path) => {
+                // When the directive only imports a single name, the fix for
+                // an unused import is to delete the whole `use` item.
+                let removal = if path.len() == 1 {
+                    Some(Suggestion {
+                        span: vi.span,
+                        msg: ~"remove this `use` directive",
+                        replacement: ~"",
+                        machine_applicable: true,
+                    })
+                } else {
+                    None
+                };
                 for p in path.iter() {
                     match p.node {
-                        ViewPathSimple(_, _, id) => self.finalize_import(id, p.span),
+                        ViewPathSimple(_, _, id) => {
+                            self.finalize_import(id, p.span, removal.clone())
+                        }
                         ViewPathList(_, ref list, _) => {
                             for i in list.iter() {
-                                self.finalize_import(i.node.id, i.span);
+                                self.finalize_import(i.node.id, i.span, None);
                             }
                         },
                         ViewPathGlob(_, id) => {
                             if !self.used_imports.contains(&(id, TypeNS)) &&
                                !self.used_imports.contains(&(id, ValueNS)) {
-                                self.session.add_lint(UnusedImports, id, p.span, ~"unused import");
+                                self.session.add_lint_with_suggestion(UnusedImports, id, p.span,
+                                                                      ~"unused import",
+                                                                      removal.clone());
                             }
                         },
                     }
@@ -5319,12 +5447,13 @@ impl<'a> Resolver<'a> {
     // which are used. In particular, this means that if an import could name either a
     // public or private item, we will check the correct thing, dependent on how the import
     // is used.
-    fn finalize_import(&mut self, id: NodeId, span: Span) {
+    fn finalize_import(&mut self, id: NodeId, span: Span, removal: Option<Suggestion>) {
         debug!("finalizing import uses for {}", self.session.codemap().span_to_snippet(span));
 
         if !self.used_imports.contains(&(id, TypeNS)) &&
            !self.used_imports.contains(&(id, ValueNS)) {
-            self.session.add_lint(UnusedImports, id, span, ~"unused import");
+            self.session.add_lint_with_suggestion(UnusedImports, id, span,
+                                                  ~"unused import", removal);
         }
 
         let (v_priv, t_priv) = match self.last_private.find(&id) {
@@ -0,0 +1,413 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Dumps the compiler's view of every name in a crate for code indexers
+//! (`-Z save-analysis`).
+//!
+//! The output is a CSV file, `save-analysis/<crate>.csv` in the output
+//! directory, with a record per definition and per reference:
+//!
+//!     record,kind,name,qualname,file,line,col,byte_start,byte_end,crate,def_crate,def_id,type
+//!
+//! `record` is `def` or `ref`. A reference's `def_crate` and `def_id` are
+//! those of the definition it resolves to, in this crate or another; `crate`
+//! is always the name of the crate the definition lives in. Lines and columns
+//! start at 1. For definitions, the span is narrowed down to the name when it
+//! can be found in the item's source; for field and method references, to
+//! the name after the `.`.
+
+use back::link;
+use driver::driver::{CrateAnalysis, OutputFilenames};
+use metadata::csearch;
+use middle::ty;
+use middle::typeck;
+use util::ppaux;
+
+use std::io;
+use std::io::fs;
+use std::strbuf::StrBuf;
+use syntax::ast;
+use syntax::ast_map;
+use syntax::ast_util;
+use syntax::codemap::{Pos, Span, DUMMY_SP};
+use syntax::parse::token;
+use syntax::visit;
+use syntax::visit::Visitor;
+
+/// Write out the analysis of `krate` next to the other outputs.
+pub fn process_crate(krate: &ast::Crate,
+                     analysis: &CrateAnalysis,
+                     outputs: &OutputFilenames) {
+    let tcx = &analysis.ty_cx;
+    let crate_name = link::find_crate_id(krate.attrs.as_slice(),
+                                         outputs.out_filestem).name;
+
+    let dir = outputs.out_directory.join("save-analysis");
+    let path = dir.join(format!("{}.csv", crate_name));
+    let file = fs::mkdir_recursive(&dir, io::UserRWX).and_then(|()| {
+        io::File::create(&path)
+    });
+    let file = match file {
+        Ok(file) => file,
+        Err(e) => {
+            tcx.sess.err(format!("could not open `{}`: {}", path.display(), e));
+            return
+        }
+    };
+
+    let mut visitor = DumpVisitor {
+        tcx: tcx,
+        method_map: &analysis.maps.method_map,
+        crate_name: crate_name,
+        out: io::BufferedWriter::new(file),
+        result: Ok(()),
+    };
+    visitor.write_line(["record", "kind", "name", "qualname", "file", "line",
+                        "col", "byte_start", "byte_end", "crate", "def_crate",
+                        "def_id", "type"]);
+    visit::walk_crate(&mut visitor, krate, ());
+    let result = match visitor.result {
+        Ok(()) => visitor.out.flush(),
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => {}
+        Err(e) => {
+            tcx.sess.err(format!("could not write `{}`: {}", path.display(), e));
+        }
+    }
+}
+
+struct DumpVisitor<'a> {
+    tcx: &'a ty::ctxt,
+    method_map: &'a typeck::MethodMap,
+    crate_name: ~str,
+    out: io::BufferedWriter<io::File>,
+    // The first write error, if any; everything after it is dropped.
+    result: io::IoResult<()>,
+}
+
+fn def_kind(def: ast::Def) -> Option<&'static str> {
+    match def {
+        ast::DefFn(..) => Some("function"),
+        ast::DefStaticMethod(..) | ast::DefMethod(..) => Some("method"),
+        ast::DefStatic(..) => Some("static"),
+        ast::DefMod(..) | ast::DefForeignMod(..) => Some("mod"),
+        ast::DefTy(..) => Some("type"),
+        ast::DefStruct(..) => Some("struct"),
+        ast::DefVariant(..) => Some("variant"),
+        ast::DefTrait(..) => Some("trait"),
+        ast::DefTyParam(..) => Some("type_param"),
+        ast::DefArg(..) | ast::DefLocal(..) | ast::DefBinding(..) |
+        ast::DefUpvar(..) => Some("variable"),
+        ast::DefUse(..) => Some("use"),
+        ast::DefPrimTy(..) | ast::DefSelfTy(..) | ast::DefRegion(..) |
+        ast::DefTyParamBinder(..) | ast::DefLabel(..) => None,
+    }
+}
+
+impl<'a> DumpVisitor<'a> {
+    fn write_line(&mut self, fields: &[&str]) {
+        if self.result.is_err() {
+            return
+        }
+        let mut line = StrBuf::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                line.push_char(',');
+            }
+            if field.contains_char(',') || field.contains_char('"') ||
+               field.contains_char('\n') {
+                line.push_char('"');
+                line.push_str(field.replace("\"", "\"\""));
+                line.push_char('"');
+            } else {
+                line.push_str(*field);
+            }
+        }
+        line.push_char('\n');
+        self.result = self.out.write_str(line.as_slice());
+    }
+
+    fn crate_of(&self, did: ast::DefId) -> ~str {
+        if did.krate == ast::LOCAL_CRATE {
+            self.crate_name.clone()
+        } else {
+            self.tcx.sess.cstore.get_crate_data(did.krate).name.clone()
+        }
+    }
+
+    // The fully qualified path of a definition; external ones are looked up
+    // in their crate's metadata.
+    fn qualname(&self, did: ast::DefId) -> ~str {
+        if did.krate == ast::LOCAL_CRATE {
+            match self.tcx.map.find(did.node) {
+                Some(ast_map::NodeItem(..)) | Some(ast_map::NodeForeignItem(..)) |
+                Some(ast_map::NodeMethod(..)) | Some(ast_map::NodeTraitMethod(..)) |
+                Some(ast_map::NodeVariant(..)) => self.tcx.map.path_to_str(did.node),
+                _ => ~"",
+            }
+        } else {
+            let path = csearch::get_item_path(self.tcx, did);
+            ast_map::path_to_str(path.move_iter())
+        }
+    }
+
+    fn type_of(&self, id: ast::NodeId) -> ~str {
+        match ty::node_id_to_type_opt(self.tcx, id) {
+            Some(t) => ppaux::ty_to_str(self.tcx, t),
+            None => ~"",
+        }
+    }
+
+    // The part of `span` covering `name`, found by looking for the first
+    // occurrence of it in the source as a whole word.
+    fn name_span(&self, span: Span, name: &str) -> Span {
+        let snippet = match self.tcx.sess.codemap().span_to_snippet(span) {
+            Some(snippet) => snippet,
+            None => return span,
+        };
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+        let mut start = 0;
+        loop {
+            let rest = snippet.slice_from(start);
+            let i = match rest.find_str(name) {
+                Some(i) => start + i,
+                None => return span,
+            };
+            let end = i + name.len();
+            let before = i == 0 || !is_ident(snippet.char_at_reverse(i));
+            let after = end == snippet.len() || !is_ident(snippet.char_at(end));
+            if before && after {
+                let lo = span.lo + Pos::from_uint(i);
+                return Span { lo: lo, hi: lo + Pos::from_uint(name.len()),
+                              expn_info: span.expn_info };
+            }
+            start = end;
+        }
+    }
+
+    // The part of the span of `ex`, a field access or method call on
+    // `base`, covering the field or method name.
+    fn member_span(&self, ex: &ast::Expr, base: &ast::Expr, name: &str) -> Span {
+        if base.span.hi < ex.span.lo || base.span.hi > ex.span.hi {
+            return ex.span
+        }
+        let span = Span { lo: base.span.hi, hi: ex.span.hi, expn_info: ex.span.expn_info };
+        self.name_span(span, name)
+    }
+
+    fn record(&mut self, record: &str, kind: &str, name: &str, span: Span,
+              did: ast::DefId, ty: &str) {
+        if span == DUMMY_SP {
+            return
+        }
+        let cm = self.tcx.sess.codemap();
+        let lo = cm.lookup_char_pos(span.lo);
+        let hi = cm.lookup_char_pos(span.hi);
+        let file = lo.file.name.clone();
+        let line = lo.line.to_str();
+        let col = (lo.col.to_uint() + 1).to_str();
+        let byte_start = (span.lo.to_uint() - lo.file.start_pos.to_uint()).to_str();
+        let byte_end = (span.hi.to_uint() - hi.file.start_pos.to_uint()).to_str();
+        let krate = self.crate_of(did);
+        let qualname = self.qualname(did);
+        let def_crate = did.krate.to_str();
+        let def_id = did.node.to_str();
+        self.write_line([record, kind, name, qualname.as_slice(),
+                         file.as_slice(), line.as_slice(), col.as_slice(),
+                         byte_start.as_slice(), byte_end.as_slice(),
+                         krate.as_slice(), def_crate.as_slice(),
+                         def_id.as_slice(), ty]);
+    }
+
+    fn def(&mut self, kind: &str, ident: ast::Ident, span: Span,
+           id: ast::NodeId) {
+        let name = token::get_ident(ident);
+        let span = self.name_span(span, name.get());
+        let ty = self.type_of(id);
+        self.record("def", kind, name.get(), span, ast_util::local_def(id),
+                    ty.as_slice());
+    }
+
+    fn reference(&mut self, name: &str, span: Span, def: ast::Def,
+                 id: ast::NodeId) {
+        let kind = match def_kind(def) {
+            Some(kind) => kind,
+            None => return,
+        };
+        let ty = self.type_of(id);
+        self.record("ref", kind, name, span, ast_util::def_id_of_def(def),
+                    ty.as_slice());
+    }
+
+    fn path_reference(&mut self, path: &ast::Path, id: ast::NodeId,
+                      type_id: ast::NodeId) {
+        let def = match self.tcx.def_map.borrow().find(&id) {
+            Some(&def) => def,
+            None => return,
+        };
+        let name = match path.segments.last() {
+            Some(segment) => token::get_ident(segment.identifier),
+            None => return,
+        };
+        self.reference(name.get(), path.span, def, type_id);
+    }
+
+    fn method_call(&mut self, ex: &ast::Expr, receiver: &ast::Expr,
+                   ident: ast::Ident) {
+        let call = typeck::MethodCall::expr(ex.id);
+        let did = match self.method_map.borrow().find(&call) {
+            Some(method) => match method.origin {
+                typeck::MethodStatic(did) => did,
+                typeck::MethodParam(ref p) => {
+                    ty::trait_method(self.tcx, p.trait_id, p.method_num).def_id
+                }
+                typeck::MethodObject(ref o) => {
+                    ty::trait_method(self.tcx, o.trait_id, o.method_num).def_id
+                }
+            },
+            None => return,
+        };
+        let name = token::get_ident(ident);
+        let span = self.member_span(ex, receiver, name.get());
+        let ty = self.type_of(ex.id);
+        self.record("ref", "method", name.get(), span, did, ty.as_slice());
+    }
+
+    fn field_access(&mut self, ex: &ast::Expr, base: &ast::Expr,
+                    ident: ast::Ident) {
+        let mut t = match ty::expr_ty_opt(self.tcx, base) {
+            Some(t) => t,
+            None => return,
+        };
+        loop {
+            match ty::deref(t, false) {
+                Some(mt) => t = mt.ty,
+                None => break,
+            }
+        }
+        let did = match ty::get(t).sty {
+            ty::ty_struct(did, _) => did,
+            _ => return,
+        };
+        let field = ty::lookup_struct_fields(self.tcx, did).move_iter()
+                       .find(|f| f.name == ident.name);
+        match field {
+            Some(field) => {
+                let name = token::get_ident(ident);
+                let span = self.member_span(ex, base, name.get());
+                let ty = self.type_of(ex.id);
+                self.record("ref", "field", name.get(), span, field.id,
+                            ty.as_slice());
+            }
+            None => {}
+        }
+    }
+}
+
+impl<'a> Visitor<()> for DumpVisitor<'a> {
+    fn visit_item(&mut self, item: &ast::Item, _: ()) {
+        match item.node {
+            ast::ItemFn(..) => self.def("function", item.ident, item.span, item.id),
+            ast::ItemStatic(..) => self.def("static", item.ident, item.span, item.id),
+            ast::ItemMod(..) | ast::ItemForeignMod(..) => {
+                self.def("mod", item.ident, item.span, item.id)
+            }
+            ast::ItemTy(..) => self.def("type", item.ident, item.span, item.id),
+            ast::ItemStruct(def, _) => {
+                self.def("struct", item.ident, item.span, item.id);
+                for field in def.fields.iter() {
+                    match field.node.kind {
+                        ast::NamedField(ident, _) => {
+                            self.def("field", ident, field.span, field.node.id)
+                        }
+                        ast::UnnamedField(..) => {}
+                    }
+                }
+            }
+            ast::ItemEnum(ref def, _) => {
+                self.def("enum", item.ident, item.span, item.id);
+                for variant in def.variants.iter() {
+                    self.def("variant", variant.node.name, variant.span,
+                             variant.node.id);
+                }
+            }
+            ast::ItemTrait(_, _, ref methods) => {
+                self.def("trait", item.ident, item.span, item.id);
+                for method in methods.iter() {
+                    match *method {
+                        ast::Required(ref m) => {
+                            self.def("method", m.ident, m.span, m.id)
+                        }
+                        ast::Provided(m) => self.def("method", m.ident, m.span, m.id),
+                    }
+                }
+            }
+            ast::ItemImpl(_, _, _, ref methods) => {
+                for m in methods.iter() {
+                    self.def("method", m.ident, m.span, m.id);
+                }
+            }
+            ast::ItemMac(..) => {}
+        }
+        visit::walk_item(self, item, ());
+    }
+
+    fn visit_expr(&mut self, ex: &ast::Expr, _: ()) {
+        match ex.node {
+            ast::ExprPath(ref path) | ast::ExprStruct(ref path, _, _) => {
+                self.path_reference(path, ex.id, ex.id)
+            }
+            ast::ExprMethodCall(ident, _, ref args) => {
+                self.method_call(ex, *args.get(0), ident)
+            }
+            ast::ExprField(base, ident, _) => self.field_access(ex, base, ident),
+            _ => {}
+        }
+        visit::walk_expr(self, ex, ());
+    }
+
+    fn visit_ty(&mut self, t: &ast::Ty, _: ()) {
+        match t.node {
+            ast::TyPath(ref path, _, path_id) => {
+                self.path_reference(path, path_id, t.id)
+            }
+            _ => {}
+        }
+        visit::walk_ty(self, t, ());
+    }
+
+    fn visit_pat(&mut self, p: &ast::Pat, _: ()) {
+        match p.node {
+            ast::PatIdent(_, ref path, _) => {
+                // Resolve records a binding as a definition of itself;
+                // anything else is a variant, struct or static being
+                // matched against.
+                let def = self.tcx.def_map.borrow().find_copy(&p.id);
+                match def {
+                    Some(ast::DefLocal(id, _)) |
+                    Some(ast::DefBinding(id, _)) |
+                    Some(ast::DefArg(id, _)) if id == p.id => {
+                        let ident = path.segments.last().unwrap().identifier;
+                        self.def("variable", ident, p.span, p.id);
+                    }
+                    Some(_) => self.path_reference(path, p.id, p.id),
+                    None => {}
+                }
+            }
+            ast::PatEnum(ref path, _) | ast::PatStruct(ref path, _, _) => {
+                self.path_reference(path, p.id, p.id);
+            }
+            _ => {}
+        }
+        visit::walk_pat(self, p, ());
+    }
+}
@@ -242,7 +242,9 @@ impl Subst for ty::TypeParameterDef {
             ident: self.ident,
             def_id: self.def_id,
             bounds: self.bounds.subst_spanned(tcx, substs, span),
-            default: self.default.map(|x| x.subst_spanned(tcx, substs, span))
+            default: self.default.map(|x| x.subst_spanned(tcx, substs, span)),
+            associated: self.associated,
+            where_ty: self.where_ty.map(|x| x.subst_spanned(tcx, substs, span))
         }
     }
 }
@@ -25,6 +25,7 @@
 
 #![allow(non_camel_case_types)]
 
+use back::incremental::IncrementalCache;
 use back::link::{mangle_exported_name};
 use back::{link, abi};
 use driver::session;
@@ -42,11 +43,15 @@ use middle::trans::adt;
 use middle::trans::build::*;
 use middle::trans::builder::{Builder, noname};
 use middle::trans::callee;
+use middle::trans::cheader;
 use middle::trans::cleanup;
 use middle::trans::cleanup::CleanupMethods;
 use middle::trans::common::*;
 use middle::trans::consts;
 use middle::trans::controlflow;
+use middle::trans::coverage;
+use middle::trans::coverage::CoverageMap;
+use middle::trans::pgo;
 use middle::trans::datum;
 // use middle::trans::datum::{Datum, Lvalue, Rvalue, ByRef, ByValue};
 use middle::trans::debuginfo;
@@ -69,6 +74,7 @@ use util::common::indenter;
 use util::ppaux::{Repr, ty_to_str};
 use util::sha2::Sha256;
 use util::nodemap::NodeMap;
+use util::profile;
 
 use arena::TypedArena;
 use libc::c_uint;
@@ -81,6 +87,7 @@ use syntax::attr::AttrMetaMethods;
 use syntax::attr;
 use syntax::codemap::Span;
 use syntax::parse::token::InternedString;
+use syntax::print::pprust;
 use syntax::visit::Visitor;
 use syntax::visit;
 use syntax::{ast, ast_util, ast_map};
@@ -1327,6 +1334,7 @@ pub fn trans_closure(ccx: &CrateContext,
 
     let _icx = push_ctxt("trans_closure");
     set_uwtable(llfndecl);
+    pgo::set_fn_hotness(ccx, llfndecl, id);
 
     debug!("trans_closure(..., param_substs={})",
            param_substs.repr(ccx.tcx()));
@@ -1422,6 +1430,12 @@ pub fn trans_fn(ccx: &CrateContext,
                 id: ast::NodeId,
                 attrs: &[ast::Attribute]) {
     let _s = StatRecorder::new(ccx, ccx.tcx.map.path_to_str(id));
+    // Monomorphizations are profiled by `monomorphic_fn`, with their
+    // type parameters.
+    let _activity = match param_substs {
+        None => Some(profile::activity("trans", || ccx.tcx.map.path_to_str(id))),
+        Some(_) => None,
+    };
     debug!("trans_fn(param_substs={})", param_substs.repr(ccx.tcx()));
     let _icx = push_ctxt("trans_fn");
     let output_type = ty::ty_fn_ret(ty::node_id_to_type(ccx.tcx(), id));
@@ -1551,6 +1565,26 @@ impl<'a> Visitor<()> for TransItemVisitor<'a> {
     }
 }
 
+/// Whether the previous build's translation of the function `id` can be
+/// taken from the incremental cache, in which case it is only declared here.
+pub fn reuse_cached_fn(ccx: &CrateContext, id: ast::NodeId,
+                       source: || -> ~str) -> bool {
+    match ccx.incremental {
+        Some(ref cache) => {
+            let sym = ccx.item_symbols.borrow().get(&id).clone();
+            cache.try_reuse(sym, source())
+        }
+        None => false,
+    }
+}
+
+// Items which aren't reachable from other crates are given internal linkage,
+// except in incremental mode, where a function reused from the previous build
+// has to be able to refer to anything in the crate.
+fn should_internalize(ccx: &CrateContext, id: ast::NodeId) -> bool {
+    !ccx.reachable.contains(&id) && ccx.incremental.is_none()
+}
+
 pub fn trans_item(ccx: &CrateContext, item: &ast::Item) {
     let _icx = push_ctxt("trans_item");
     match item.node {
@@ -1561,13 +1595,15 @@ pub fn trans_item(ccx: &CrateContext, item: &ast::Item) {
                 ccx, decl, body, item.attrs.as_slice(), llfndecl, item.id);
         } else if !generics.is_type_parameterized() {
             let llfn = get_item_val(ccx, item.id);
-            trans_fn(ccx,
-                     decl,
-                     body,
-                     llfn,
-                     None,
-                     item.id,
-                     item.attrs.as_slice());
+            if !reuse_cached_fn(ccx, item.id, || pprust::item_to_str(item)) {
+                trans_fn(ccx,
+                         decl,
+                         body,
+                         llfn,
+                         None,
+                         item.id,
+                         item.attrs.as_slice());
+            }
         } else {
             // Be sure to travel more than just one layer deep to catch nested
             // items in blocks and such.
@@ -1657,7 +1693,7 @@ fn finish_register_fn(ccx: &CrateContext, sp: Span, sym: ~str, node_id: ast::Nod
                       llfn: ValueRef) {
     ccx.item_symbols.borrow_mut().insert(node_id, sym);
 
-    if !ccx.reachable.contains(&node_id) {
+    if should_internalize(ccx, node_id) {
         lib::llvm::SetLinkage(llfn, lib::llvm::InternalLinkage);
     }
 
@@ -2018,7 +2054,7 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
     // foreign items (extern fns and extern statics) don't have internal
     // linkage b/c that doesn't quite make sense. Otherwise items can
     // have internal linkage if they're not reachable.
-    if !foreign && !ccx.reachable.contains(&id) {
+    if !foreign && should_internalize(ccx, id) {
         lib::llvm::SetLinkage(val, lib::llvm::InternalLinkage);
     }
 
@@ -2131,14 +2167,63 @@ pub fn trans_crate(krate: ast::Crate,
     // 1. http://llvm.org/bugs/show_bug.cgi?id=11479
     let llmod_id = link_meta.crateid.name + ".rs";
 
+    let incremental = match tcx.sess.opts.incremental {
+        Some(ref dir) if tcx.sess.opts.debuginfo != NoDebugInfo => {
+            tcx.sess.warn(format!("ignoring incremental compilation cache `{}`: \
+                                   not supported together with debuginfo",
+                                  dir.display()));
+            None
+        }
+        Some(ref dir) if tcx.sess.instrument_coverage() ||
+                         tcx.sess.opts.cg.profile_generate.is_some() ||
+                         tcx.sess.opts.cg.profile_use.is_some() => {
+            tcx.sess.warn(format!("ignoring incremental compilation cache `{}`: \
+                                   not supported together with coverage \
+                                   instrumentation or profiles", dir.display()));
+            None
+        }
+        Some(ref dir) => {
+            Some(IncrementalCache::load(&tcx.sess, dir, link_meta.crateid.name,
+                                        &krate))
+        }
+        None => None,
+    };
+
+    let coverage = if tcx.sess.instrument_coverage() ||
+                      tcx.sess.opts.cg.profile_generate.is_some() {
+        Some(CoverageMap::new(&tcx, maps.method_map, &krate))
+    } else {
+        None
+    };
+    let profile = match tcx.sess.opts.cg.profile_use {
+        Some(ref path) => {
+            let blocks = CoverageMap::new(&tcx, maps.method_map, &krate);
+            pgo::Profile::load(&tcx.sess, blocks, &Path::new(path.as_slice()))
+        }
+        None => None,
+    };
+
     let ccx = CrateContext::new(llmod_id, tcx, exp_map2, maps,
-                                Sha256::new(), link_meta, reachable);
+                                Sha256::new(), link_meta, reachable,
+                                incremental, coverage, profile);
     {
         let _icx = push_ctxt("text");
         trans_mod(&ccx, &krate.module);
     }
 
+    match ccx.incremental {
+        Some(ref cache) => {
+            cache.link_cached(ccx.sess(), ccx.llmod);
+            cache.save(ccx.sess(), ccx.llmod);
+        }
+        None => {}
+    }
+
     glue::emit_tydescs(&ccx);
+    coverage::finish(&ccx, output);
+    if ccx.sess().opts.output_types.contains(&link::OutputTypeCHeader) {
+        cheader::write(&ccx, output);
+    }
     if ccx.sess().opts.debuginfo != NoDebugInfo {
         debuginfo::finalize(&ccx);
     }
@@ -2173,14 +2258,32 @@ pub fn trans_crate(krate: ast::Crate,
             println!("{:7u} {}", *v, *k);
         }
     }
+    if ccx.sess().print_mono_items() {
+        monomorphize::print_mono_items(&ccx);
+    }
 
     let llcx = ccx.llcx;
     let link_meta = ccx.link_meta.clone();
     let llmod = ccx.llmod;
 
-    let mut reachable: Vec<~str> = ccx.reachable.iter().filter_map(|id| {
-        ccx.item_symbols.borrow().find(id).map(|s| s.to_owned())
-    }).collect();
+    let mut reachable = Vec::new();
+    let mut c_symbols = Vec::new();
+    for id in ccx.reachable.iter() {
+        let sym = match ccx.item_symbols.borrow().find(id) {
+            Some(sym) => sym.to_owned(),
+            None => continue,
+        };
+        let unmangled = ccx.tcx.map.with_attrs(*id, |attrs| {
+            attrs.map_or(false, |attrs| {
+                attr::contains_name(attrs, "no_mangle") ||
+                    attr::contains_name(attrs, "export_name")
+            })
+        });
+        if unmangled {
+            c_symbols.push(sym.clone());
+        }
+        reachable.push(sym);
+    }
 
     // Make sure that some other crucial symbols are not eliminated from the
     // module. This includes the main function, the crate map (used for debug
@@ -2188,10 +2291,14 @@ pub fn trans_crate(krate: ast::Crate,
     // symbol. This symbol is required for use by the libmorestack library that
     // we link in, so we must ensure that this symbol is not internalized (if
     // defined in the crate).
-    reachable.push(~"main");
-    reachable.push(~"rust_stack_exhausted");
-    reachable.push(~"rust_eh_personality"); // referenced from .eh_frame section on some platforms
-    reachable.push(~"rust_eh_personality_catch"); // referenced from rt/rust_try.ll
+    let crucial = ["main",
+                   "rust_stack_exhausted",
+                   "rust_eh_personality", // referenced from .eh_frame section on some platforms
+                   "rust_eh_personality_catch"]; // referenced from rt/rust_try.ll
+    for sym in crucial.iter() {
+        reachable.push(sym.to_owned());
+        c_symbols.push(sym.to_owned());
+    }
 
     let metadata_module = ccx.metadata_llmod;
 
@@ -2202,5 +2309,6 @@ pub fn trans_crate(krate: ast::Crate,
         metadata_module: metadata_module,
         metadata: metadata,
         reachable: reachable,
+        c_symbols: c_symbols,
     })
 }
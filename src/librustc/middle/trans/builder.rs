@@ -51,6 +51,12 @@ impl<'a> Builder<'a> {
                                                 .n_llvm_insns
                                                 .get() + 1);
         }
+        if self.ccx.sess().print_mono_items() {
+            match self.ccx.stats.mono_stack.borrow_mut().mut_last() {
+                Some(item) => item.insns += 1,
+                None => {}
+            }
+        }
         if self.ccx.sess().count_llvm_insns() {
             base::with_insn_ctxt(|v| {
                 let mut h = self.ccx.stats.llvm_insns.borrow_mut();
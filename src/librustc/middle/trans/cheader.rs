@@ -0,0 +1,407 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! C header generation (`--emit=header`).
+//!
+//! The header declares everything C code can link against: the reachable
+//! `extern "C"` functions and statics which are `#[no_mangle]` or have an
+//! `#[export_name]`, along with the `#[repr(C)]` structs and the C-like
+//! enums with a representation hint which are reachable or which those
+//! declarations use. Structs which are only used through pointers are
+//! declared without a definition, so that C can handle them opaquely
+//! whatever their representation. Types in an exported signature which
+//! have no C representation are errors, as is any argument the target's C
+//! ABI would drop.
+//!
+//! The output only depends on the crate's interface, in source order, so
+//! that it can be checked in and diffed.
+
+use back::link;
+use driver::driver::OutputFilenames;
+use middle::trans::cabi;
+use middle::trans::context::CrateContext;
+use middle::trans::machine;
+use middle::trans::type_of;
+use middle::ty;
+use util::nodemap::DefIdSet;
+use util::ppaux::ty_to_str;
+
+use std::io;
+use std::strbuf::StrBuf;
+use syntax::abi;
+use syntax::ast;
+use syntax::ast_map;
+use syntax::ast_util;
+use syntax::attr;
+use syntax::parse::token;
+
+struct HeaderContext<'a> {
+    ccx: &'a CrateContext,
+    /// Every struct and enum which has been looked at.
+    seen: DefIdSet,
+    /// The definitions of the structs and enums the header needs, each after
+    /// the ones its fields contain by value.
+    types: Vec<~str>,
+    /// Forward declarations of the structs, which pointers may refer to
+    /// before their definition.
+    forward: Vec<~str>,
+    decls: Vec<~str>,
+}
+
+pub fn write(ccx: &CrateContext, outputs: &OutputFilenames) {
+    let mut hcx = HeaderContext {
+        ccx: ccx,
+        seen: DefIdSet::new(),
+        types: Vec::new(),
+        forward: Vec::new(),
+        decls: Vec::new(),
+    };
+    let mut reachable: Vec<ast::NodeId> = ccx.reachable.iter().map(|id| *id).collect();
+    reachable.as_mut_slice().sort();
+    for &id in reachable.iter() {
+        match ccx.tcx.map.find(id) {
+            Some(ast_map::NodeItem(item)) => hcx.item(item),
+            _ => {}
+        }
+    }
+
+    let path = outputs.path(link::OutputTypeCHeader);
+    let name = ccx.link_meta.crateid.name.as_slice();
+    let result = io::File::create(&path).and_then(|mut file| {
+        hcx.write(&mut file, name)
+    });
+    match result {
+        Ok(()) => {}
+        Err(e) => {
+            ccx.sess().err(format!("failed to write C header `{}`: {}",
+                                   path.display(), e));
+        }
+    }
+}
+
+fn is_c_abi(abi: abi::Abi) -> bool {
+    match abi {
+        abi::C | abi::Cdecl | abi::System => true,
+        _ => false,
+    }
+}
+
+fn is_unmangled(item: &ast::Item) -> bool {
+    attr::contains_name(item.attrs.as_slice(), "no_mangle") ||
+        attr::contains_name(item.attrs.as_slice(), "export_name")
+}
+
+fn item_name(tcx: &ty::ctxt, did: ast::DefId) -> ~str {
+    ty::with_path(tcx, did, |mut path| path.last().unwrap().to_str())
+}
+
+fn int_name(ity: attr::IntType) -> &'static str {
+    match ity {
+        attr::SignedInt(ast::TyI) => "intptr_t",
+        attr::SignedInt(ast::TyI8) => "int8_t",
+        attr::SignedInt(ast::TyI16) => "int16_t",
+        attr::SignedInt(ast::TyI32) => "int32_t",
+        attr::SignedInt(ast::TyI64) => "int64_t",
+        attr::UnsignedInt(ast::TyU) => "uintptr_t",
+        attr::UnsignedInt(ast::TyU8) => "uint8_t",
+        attr::UnsignedInt(ast::TyU16) => "uint16_t",
+        attr::UnsignedInt(ast::TyU32) => "uint32_t",
+        attr::UnsignedInt(ast::TyU64) => "uint64_t",
+    }
+}
+
+/// Declarators which bind looser than the one being wrapped around them
+/// need parentheses: `(*p)[4]` is a pointer to an array.
+fn parenthesize(decl: ~str) -> ~str {
+    if decl.starts_with("*") { format!("({})", decl) } else { decl }
+}
+
+impl<'a> HeaderContext<'a> {
+    fn tcx(&self) -> &'a ty::ctxt {
+        self.ccx.tcx()
+    }
+
+    fn item(&mut self, item: &ast::Item) {
+        let result = match item.node {
+            ast::ItemFn(decl, _, abi, _, _) if is_c_abi(abi) && is_unmangled(item) => {
+                self.function(item, decl)
+            }
+            ast::ItemStatic(_, mutbl, _) if is_unmangled(item) => {
+                let t = ty::node_id_to_type(self.tcx(), item.id);
+                let name = self.symbol(item.id);
+                self.declare(t, mutbl == ast::MutImmutable, name).map(|decl| {
+                    self.decls.push(format!("extern {};", decl));
+                })
+            }
+            // Other types are only declared when an exported signature
+            // needs them.
+            ast::ItemStruct(..) | ast::ItemEnum(..) => {
+                let did = ast_util::local_def(item.id);
+                let tpt = ty::lookup_item_type(self.tcx(), did);
+                let c_like = match item.node {
+                    ast::ItemEnum(..) => {
+                        ty::enum_variants(self.tcx(), did).iter().all(|v| v.args.is_empty())
+                    }
+                    _ => true,
+                };
+                if c_like && !tpt.generics.has_type_params() &&
+                        ty::lookup_repr_hint(self.tcx(), did) != attr::ReprAny {
+                    self.declare(tpt.ty, false, ~"").map(|_| ())
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        };
+        match result {
+            Ok(()) => {}
+            Err(msg) => self.ccx.sess().span_err(item.span, msg),
+        }
+    }
+
+    fn symbol(&self, id: ast::NodeId) -> ~str {
+        self.ccx.item_symbols.borrow().get_copy(&id)
+    }
+
+    fn function(&mut self, item: &ast::Item, decl: &ast::FnDecl) -> Result<(), ~str> {
+        let sig = match ty::get(ty::node_id_to_type(self.tcx(), item.id)).sty {
+            ty::ty_bare_fn(ref f) => f.sig.clone(),
+            _ => self.ccx.sess().span_bug(item.span, "exported fn without a fn type"),
+        };
+
+        // Whatever the C ABI of the target passes nothing for can't be
+        // written down in C either; that's what zero-sized types come to.
+        let llargs: Vec<_> = sig.inputs.iter().map(|&t| {
+            type_of::type_of(self.ccx, t)
+        }).collect();
+        let llret = type_of::type_of(self.ccx, sig.output);
+        let ret_def = !ty::type_is_nil(sig.output) && !ty::type_is_bot(sig.output);
+        let fn_ty = cabi::compute_abi_info(self.ccx, llargs.as_slice(), llret, ret_def);
+        for (i, arg) in fn_ty.arg_tys.iter().enumerate() {
+            if arg.is_ignore() || machine::llsize_of_alloc(self.ccx, arg.ty) == 0 {
+                return Err(format!("`{}` is zero-sized and has no C representation",
+                                   ty_to_str(self.tcx(), *sig.inputs.get(i))));
+            }
+        }
+
+        let mut params = Vec::new();
+        for (input, &t) in decl.inputs.iter().zip(sig.inputs.iter()) {
+            let name = match input.pat.node {
+                ast::PatIdent(_, ref path, None) if path.segments.len() == 1 => {
+                    token::get_ident(path.segments.get(0).identifier).get().to_owned()
+                }
+                _ => ~"",
+            };
+            params.push(try!(self.param(t, name)));
+        }
+        if params.is_empty() {
+            params.push(~"void");
+        }
+        let declarator = format!("{}({})", self.symbol(item.id), params.connect(", "));
+        let decl = if ty::type_is_bot(sig.output) {
+            format!("void {}", declarator)
+        } else {
+            try!(self.param(sig.output, declarator))
+        };
+        self.decls.push(decl + ";");
+        Ok(())
+    }
+
+    /// Declares a parameter or return value. C passes arrays as pointers, so
+    /// unlike fields they can't be arrays.
+    fn param(&mut self, t: ty::t, decl: ~str) -> Result<~str, ~str> {
+        match ty::get(t).sty {
+            ty::ty_vec(_, ty::VstoreFixed(_)) => {
+                Err(format!("`{}` is passed by value, which C arrays can't be",
+                            ty_to_str(self.tcx(), t)))
+            }
+            _ => self.declare(t, false, decl),
+        }
+    }
+
+    /// The C declaration of `decl` with type `t`, `const` if `is_const`.
+    fn declare(&mut self, t: ty::t, is_const: bool, decl: ~str) -> Result<~str, ~str> {
+        let base = match ty::get(t).sty {
+            ty::ty_nil => ~"void",
+            ty::ty_bool => ~"bool",
+            ty::ty_char => ~"uint32_t",
+            ty::ty_int(ity) => int_name(attr::SignedInt(ity)).to_owned(),
+            ty::ty_uint(uty) => int_name(attr::UnsignedInt(uty)).to_owned(),
+            ty::ty_float(ast::TyF32) => ~"float",
+            ty::ty_float(ast::TyF64) => ~"double",
+            ty::ty_ptr(mt) | ty::ty_rptr(_, mt) => {
+                let inner = if is_const {
+                    format!("*const {}", decl)
+                } else {
+                    format!("*{}", decl)
+                };
+                let inner = inner.trim_right().to_owned();
+                let const_pointee = mt.mutbl == ast::MutImmutable;
+                match ty::get(mt.ty).sty {
+                    // C only needs the definition of a struct to get at its
+                    // fields, so it can be left out when only the pointer is
+                    // passed around.
+                    ty::ty_struct(did, ref substs) if substs.tps.is_empty() &&
+                            ty::lookup_repr_hint(self.tcx(), did) != attr::ReprExtern => {
+                        let base = self.add_opaque_struct(did);
+                        let base = if const_pointee { format!("const {}", base) } else { base };
+                        return Ok(format!("{} {}", base, inner))
+                    }
+                    _ => return self.declare(mt.ty, const_pointee, inner),
+                }
+            }
+            ty::ty_vec(elem, ty::VstoreFixed(n)) => {
+                return self.declare(elem, is_const, format!("{}[{}]", parenthesize(decl), n))
+            }
+            ty::ty_bare_fn(ref f) if is_c_abi(f.abi) && !f.sig.variadic => {
+                let mut params = Vec::new();
+                for &input in f.sig.inputs.iter() {
+                    params.push(try!(self.param(input, ~"")));
+                }
+                if params.is_empty() {
+                    params.push(~"void");
+                }
+                let ptr = if is_const { format!("*const {}", decl) } else { format!("*{}", decl) };
+                let inner = format!("({})({})", ptr.trim_right(), params.connect(", "));
+                return self.param(f.sig.output, inner)
+            }
+            ty::ty_struct(did, ref substs) if substs.tps.is_empty() => {
+                try!(self.add_struct(t, did));
+                format!("struct {}", item_name(self.tcx(), did))
+            }
+            ty::ty_enum(did, ref substs) if substs.tps.is_empty() => {
+                // `libc::c_void` is an enum only so that Rust can't make one.
+                if item_name(self.tcx(), did).as_slice() == "c_void" {
+                    ~"void"
+                } else {
+                    try!(self.add_enum(t, did))
+                }
+            }
+            _ => {
+                return Err(format!("`{}` has no C representation",
+                                   ty_to_str(self.tcx(), t)))
+            }
+        };
+        let base = if is_const { format!("const {}", base) } else { base };
+        Ok(if decl.is_empty() { base } else { format!("{} {}", base, decl) })
+    }
+
+    fn add_struct(&mut self, t: ty::t, did: ast::DefId) -> Result<(), ~str> {
+        // Checked before anything else, as the struct may have been seen
+        // behind a pointer already.
+        let tcx = self.tcx();
+        if ty::lookup_repr_hint(tcx, did) != attr::ReprExtern {
+            return Err(format!("`{}` has no C representation, it needs `#[repr(C)]`",
+                               ty_to_str(tcx, t)))
+        }
+        if !self.seen.insert(did) {
+            return Ok(())
+        }
+        let name = item_name(tcx, did);
+        self.forward.push(format!("struct {};", name));
+        // Structs of other crates belong in their own headers.
+        if did.krate != ast::LOCAL_CRATE {
+            return Ok(())
+        }
+        if machine::llsize_of_alloc(self.ccx, type_of::type_of(self.ccx, t)) == 0 {
+            return Err(format!("`{}` is zero-sized and has no C representation",
+                               ty_to_str(tcx, t)))
+        }
+
+        let mut def = StrBuf::from_owned_str(format!("struct {} \\{\n", name));
+        let fields = ty::lookup_struct_fields(tcx, did);
+        for (i, field) in fields.iter().enumerate() {
+            let field_name = if field.name == token::special_idents::unnamed_field.name {
+                format!("_{}", i)
+            } else {
+                token::get_name(field.name).get().to_owned()
+            };
+            let field_ty = ty::lookup_field_type(tcx, did, field.id, &ty::substs::empty());
+            def.push_str(format!("    {};\n", try!(self.declare(field_ty, false, field_name))));
+        }
+        def.push_str("};");
+        self.types.push(def.into_owned());
+        Ok(())
+    }
+
+    /// Forward declares a struct which C only sees through pointers, and
+    /// returns its C type.
+    fn add_opaque_struct(&mut self, did: ast::DefId) -> ~str {
+        let name = item_name(self.tcx(), did);
+        if self.seen.insert(did) {
+            self.forward.push(format!("struct {};", name));
+        }
+        format!("struct {}", name)
+    }
+
+    /// Declares a C-like enum and returns the type its values have in C.
+    fn add_enum(&mut self, t: ty::t, did: ast::DefId) -> Result<~str, ~str> {
+        let tcx = self.tcx();
+        let name = item_name(tcx, did);
+        let ity = match ty::lookup_repr_hint(tcx, did) {
+            attr::ReprExtern => None,
+            attr::ReprInt(_, ity) => Some(ity),
+            attr::ReprAny => {
+                return Err(format!("`{}` has no C representation, it needs \
+                                    `#[repr(C)]` or an integer representation",
+                                   ty_to_str(tcx, t)))
+            }
+        };
+        let c_ty = match ity {
+            Some(ity) => int_name(ity).to_owned(),
+            None => format!("enum {}", name),
+        };
+        if !self.seen.insert(did) || did.krate != ast::LOCAL_CRATE {
+            return Ok(c_ty)
+        }
+
+        let mut def = StrBuf::from_owned_str(format!("enum {} \\{\n", name));
+        for variant in ty::enum_variants(tcx, did).iter() {
+            if !variant.args.is_empty() {
+                return Err(format!("`{}` carries data and has no C representation",
+                                   ty_to_str(tcx, t)))
+            }
+            let disr = match ity {
+                Some(ity) if ity.is_signed() => (variant.disr_val as i64).to_str(),
+                _ => variant.disr_val.to_str(),
+            };
+            def.push_str(format!("    {}_{} = {},\n", name,
+                                 token::get_ident(variant.name), disr));
+        }
+        def.push_str("};");
+        self.types.push(def.into_owned());
+        Ok(c_ty)
+    }
+
+    fn write(&self, out: &mut Writer, crate_name: &str) -> io::IoResult<()> {
+        let guard: ~str = crate_name.chars().map(|c| {
+            if c.is_alphanumeric() { c.to_uppercase() } else { '_' }
+        }).collect();
+        try!(write!(out, "/* Generated by rustc from crate `{}`. Do not edit. */\n\n",
+                    crate_name));
+        try!(write!(out, "\\#ifndef {0}_H\n\\#define {0}_H\n\n", guard));
+        try!(out.write_str("#include <stdbool.h>\n#include <stdint.h>\n\n"));
+        try!(out.write_str("#ifdef __cplusplus\nextern \"C\" {\n#endif\n"));
+        // Declarations are grouped together, definitions each stand apart.
+        let mut blocks = Vec::new();
+        if !self.forward.is_empty() {
+            blocks.push(self.forward.connect("\n"));
+        }
+        blocks.push_all(self.types.as_slice());
+        if !self.decls.is_empty() {
+            blocks.push(self.decls.connect("\n"));
+        }
+        for block in blocks.iter() {
+            try!(write!(out, "\n{}\n", *block));
+        }
+        try!(out.write_str("\n#ifdef __cplusplus\n}\n#endif\n\n"));
+        write!(out, "\\#endif /* {}_H */\n", guard)
+    }
+}
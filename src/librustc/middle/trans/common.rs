@@ -167,6 +167,20 @@ pub struct Stats {
     pub llvm_insns: RefCell<HashMap<~str, uint>>,
     // (ident, time-in-ms, llvm-instructions)
     pub fn_stats: RefCell<Vec<(~str, uint, uint)> >,
+    // Monomorphizations being translated, innermost last, and those done.
+    pub mono_stack: RefCell<Vec<MonoItem>>,
+    pub mono_items: RefCell<Vec<MonoItem>>,
+}
+
+/// An instantiation of a generic function, for `-Z print-mono-items`.
+pub struct MonoItem {
+    pub generic: ~str,
+    pub instance: ~str,
+    // The function which first needed this instance.
+    pub caller: ~str,
+    // Instructions emitted while this was the innermost monomorphization
+    // being translated.
+    pub insns: uint,
 }
 
 pub struct BuilderRef_res {
@@ -81,6 +81,22 @@ pub fn const_lit(cx: &CrateContext, e: &ast::Expr, lit: ast::Lit)
     }
 }
 
+// The call has been checked to be to a `#[const_fn]` function, so its value
+// is computed by const_eval.
+fn const_fn_call(cx: &CrateContext, e: &ast::Expr) -> ValueRef {
+    let ety = ty::expr_ty(cx.tcx(), e);
+    let llty = type_of::type_of(cx, ety);
+    match (const_eval::eval_const_expr(cx.tcx(), e), &ty::get(ety).sty) {
+        (const_eval::const_int(i), &ty::ty_bool) => C_bool(cx, i != 0),
+        (const_eval::const_bool(b), _) => C_bool(cx, b),
+        (const_eval::const_int(i), _) => C_integral(llty, i as u64, true),
+        (const_eval::const_uint(u), _) => C_integral(llty, u, false),
+        (const_eval::const_float(f), _) => C_floating(f.to_str().as_slice(), llty),
+        (const_eval::const_str(s), _) => C_str_slice(cx, s),
+        (const_eval::const_binary(data), _) => C_binary_slice(cx, data.as_slice()),
+    }
+}
+
 pub fn const_ptrcast(cx: &CrateContext, a: ValueRef, t: Type) -> ValueRef {
     unsafe {
         let b = llvm::LLVMConstPointerCast(a, t.ptr_to().to_ref());
@@ -649,6 +665,7 @@ fn const_expr_unadjusted(cx: &CrateContext, e: &ast::Expr,
                       (adt::trans_const(cx, repr, 0, arg_vals.as_slice()),
                        inlineable)
                   }
+                  Some(ast::DefFn(..)) => (const_fn_call(cx, e), true),
                   Some(ast::DefVariant(enum_did, variant_did, _)) => {
                       let ety = ty::expr_ty(cx.tcx(), e);
                       let repr = adt::represent_type(cx, ety);
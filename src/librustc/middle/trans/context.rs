@@ -9,6 +9,9 @@
 // except according to those terms.
 
 
+use back::incremental::IncrementalCache;
+use middle::trans::coverage::CoverageMap;
+use middle::trans::pgo::Profile;
 use driver::session::NoDebugInfo;
 use driver::session::Session;
 use lib::llvm::{ContextRef, ModuleRef, ValueRef};
@@ -106,6 +109,13 @@ pub struct CrateContext {
     /// is not emitted by LLVM's GC pass when no functions use GC.
     pub uses_gc: bool,
     pub dbg_cx: Option<debuginfo::CrateDebugContext>,
+    /// Set when compiling with `-Z incremental`.
+    pub incremental: Option<IncrementalCache>,
+    /// Set when compiling with `-Z instrument-coverage` or
+    /// `-C profile-generate`.
+    pub coverage: Option<CoverageMap>,
+    /// Set when compiling with `-C profile-use`.
+    pub profile: Option<Profile>,
 
     intrinsics: RefCell<HashMap<&'static str, ValueRef>>,
 }
@@ -117,7 +127,10 @@ impl CrateContext {
                maps: astencode::Maps,
                symbol_hasher: Sha256,
                link_meta: LinkMeta,
-               reachable: NodeSet)
+               reachable: NodeSet,
+               incremental: Option<IncrementalCache>,
+               coverage: Option<CoverageMap>,
+               profile: Option<Profile>)
                -> CrateContext {
         unsafe {
             let llcx = llvm::LLVMContextCreate();
@@ -191,12 +204,17 @@ impl CrateContext {
                     n_llvm_insns: Cell::new(0u),
                     llvm_insns: RefCell::new(HashMap::new()),
                     fn_stats: RefCell::new(Vec::new()),
+                    mono_stack: RefCell::new(Vec::new()),
+                    mono_items: RefCell::new(Vec::new()),
                 },
                 int_type: Type::from_ref(ptr::null()),
                 opaque_vec_type: Type::from_ref(ptr::null()),
                 builder: BuilderRef_res(llvm::LLVMCreateBuilderInContext(llcx)),
                 uses_gc: false,
                 dbg_cx: dbg_cx,
+                incremental: incremental,
+                coverage: coverage,
+                profile: profile,
                 intrinsics: RefCell::new(HashMap::new()),
             };
 
@@ -19,6 +19,7 @@ use middle::trans::debuginfo;
 use middle::trans::cleanup;
 use middle::trans::cleanup::CleanupMethods;
 use middle::trans::expr;
+use middle::trans::pgo;
 use util::ppaux::Repr;
 
 use middle::trans::type_::Type;
@@ -166,12 +167,14 @@ pub fn trans_if<'a>(bcx: &'a Block<'a>,
             next_bcx = bcx.fcx.join_blocks(if_id,
                                            [then_bcx_out, else_bcx_out]);
             CondBr(bcx, cond_val, then_bcx_in.llbb, else_bcx_in.llbb);
+            pgo::weigh_if(bcx, cond, true);
         }
 
         None => {
             next_bcx = bcx.fcx.new_id_block("next-block", if_id);
             Br(then_bcx_out, next_bcx.llbb);
             CondBr(bcx, cond_val, then_bcx_in.llbb, next_bcx.llbb);
+            pgo::weigh_if(bcx, cond, false);
         }
     }
 
@@ -216,6 +219,7 @@ pub fn trans_while<'a>(bcx: &'a Block<'a>,
     let Result {bcx: cond_bcx_out, val: cond_val} =
         expr::trans(cond_bcx_in, cond).to_llbool();
     CondBr(cond_bcx_out, cond_val, body_bcx_in.llbb, cleanup_llbb);
+    pgo::weigh_while(cond_bcx_out, cond);
 
     // loop body:
     let body_bcx_out = trans_block(body_bcx_in, body, expr::Ignore);
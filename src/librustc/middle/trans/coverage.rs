@@ -0,0 +1,437 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Code coverage instrumentation (`-Z instrument-coverage`), which also
+//! records the profiles of `-C profile-generate` (see `middle::trans::pgo`).
+//!
+//! The control-flow graph of every function body is split into basic
+//! blocks: maximal chains of nodes which are entered only at the top and
+//! left only at the bottom. Each block gets a counter, bumped when trans
+//! starts on the first expression of the block, and a region, the union of
+//! the spans of its nodes, leaving out the nodes where branches meet again
+//! (an `if` or `match` as a whole, say), whose spans cover the branches too.
+//!
+//! The counters live in a global array, which a function registered with
+//! `atexit` appends to `<output>.covcounts`, or to the profile named by
+//! `-C profile-generate`, when the program exits. Each run appends one record
+//! of native-endian 64-bit counts. With `-Z instrument-coverage`, the regions
+//! are written to `<output>.covmap` as tab-separated lines:
+//!
+//!     counters  <number of counters>
+//!     counts    <absolute path of the counts file>
+//!     <counter> <file> <line> <col> <end line> <end col> <function>
+//!
+//! from which `rustc --coverage-report` renders the counts.
+
+use driver::session::Session;
+use driver::driver::OutputFilenames;
+use lib::llvm::{llvm, ValueRef};
+use lib;
+use middle::cfg;
+use middle::graph;
+use middle::trans::base;
+use middle::trans::build::{Add, GEPi, Load, Store};
+use middle::trans::builder::noname;
+use middle::trans::common::*;
+use middle::trans::context::CrateContext;
+use middle::trans::type_::Type;
+use middle::ty;
+use middle::typeck;
+use util::nodemap::NodeMap;
+
+use std::cell::Cell;
+use std::io;
+use std::os;
+use libc::c_uint;
+use syntax::ast;
+use syntax::ast_map;
+use syntax::ast_util;
+use syntax::codemap::Span;
+use syntax::parse::token;
+use syntax::visit;
+use syntax::visit::Visitor;
+
+struct Region {
+    span: Span,
+    function: ~str,
+}
+
+pub struct CoverageMap {
+    // The counter to bump when trans starts on each of these expressions.
+    counters: NodeMap<uint>,
+    // The region of each counter, by index.
+    regions: Vec<Region>,
+    // The counter of the block each node of a control-flow graph is in.
+    blocks: NodeMap<uint>,
+    // The counters of the blocks a branching node may go to, in the order
+    // of the edges of the graph.
+    branches: NodeMap<Vec<Option<uint>>>,
+    // The counter of the first block of each function.
+    entries: NodeMap<uint>,
+    llcounters: Cell<Option<ValueRef>>,
+}
+
+struct RegionCollector<'a> {
+    tcx: &'a ty::ctxt,
+    method_map: typeck::MethodMap,
+    map: CoverageMap,
+}
+
+impl<'a> Visitor<()> for RegionCollector<'a> {
+    fn visit_fn(&mut self, fk: &visit::FnKind, fd: &ast::FnDecl,
+                b: &ast::Block, s: Span, id: ast::NodeId, _: ()) {
+        let function = match *fk {
+            visit::FkFnBlock(..) => {
+                let parent = self.tcx.map.get_parent(id);
+                format!("{} (closure)", self.tcx.map.path_to_str(parent))
+            }
+            _ => self.tcx.map.path_to_str(id),
+        };
+        let cfg = cfg::CFG::new(self.tcx, self.method_map, b);
+        self.add_blocks(&cfg, id, function);
+        visit::walk_fn(self, fk, fd, b, s, id, ());
+    }
+}
+
+impl<'a> RegionCollector<'a> {
+    fn add_blocks(&mut self, cfg: &cfg::CFG, fn_id: ast::NodeId, function: ~str) {
+        let graph = &cfg.graph;
+        let n = graph.all_nodes().len();
+        let mut preds = Vec::from_elem(n, Vec::new());
+        let mut succs = Vec::from_elem(n, Vec::new());
+        for edge in graph.all_edges().iter() {
+            preds.get_mut(index(edge.target())).push(index(edge.source()));
+            succs.get_mut(index(edge.source())).push(index(edge.target()));
+        }
+
+        // A node starts a block unless its only predecessor goes nowhere
+        // else.
+        let starts_block = |i: uint| {
+            i == index(cfg.entry) || match preds.get(i).as_slice() {
+                [pred] => succs.get(pred).len() != 1,
+                _ => true,
+            }
+        };
+
+        let mut block_of = Vec::from_elem(n, None);
+        for start in range(0, n) {
+            if !starts_block(start) {
+                continue
+            }
+
+            let mut chain = vec!(start);
+            loop {
+                let last = *chain.last().unwrap();
+                if succs.get(last).len() != 1 { break }
+                let next = *succs.get(last).get(0);
+                if starts_block(next) { break }
+                chain.push(next);
+            }
+
+            // The counter goes on the outermost of the first expressions
+            // of the block, which is where its translation starts.
+            let mut region: Option<Span> = None;
+            let mut first: Option<(ast::NodeId, Span)> = None;
+            for &node in chain.iter() {
+                let id = graph.node_data(graph::NodeIndex(node)).id;
+                if id == 0 {
+                    continue
+                }
+                let span = match self.tcx.map.find(id) {
+                    Some(ast_map::NodeExpr(e)) if joins_branches(e) => continue,
+                    Some(ast_map::NodeExpr(e)) => {
+                        let outer = match first {
+                            None => true,
+                            Some((_, sp)) => e.span.lo < sp.lo ||
+                                (e.span.lo == sp.lo && e.span.hi >= sp.hi),
+                        };
+                        if outer {
+                            first = Some((id, e.span));
+                        }
+                        e.span
+                    }
+                    Some(_) => self.tcx.map.span(id),
+                    None => continue,
+                };
+                region = Some(match region {
+                    None => span,
+                    Some(r) => Span {
+                        lo: if span.lo < r.lo { span.lo } else { r.lo },
+                        hi: if span.hi > r.hi { span.hi } else { r.hi },
+                        expn_info: None,
+                    },
+                });
+            }
+
+            match (first, region) {
+                (Some((id, _)), Some(span)) => {
+                    let counter = self.map.regions.len();
+                    self.map.counters.insert(id, counter);
+                    self.map.regions.push(Region {
+                        span: span,
+                        function: function.clone(),
+                    });
+                    for &node in chain.iter() {
+                        *block_of.get_mut(node) = Some(counter);
+                        let id = graph.node_data(graph::NodeIndex(node)).id;
+                        if id != 0 {
+                            self.map.blocks.insert(id, counter);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for node in range(0, n) {
+            let id = graph.node_data(graph::NodeIndex(node)).id;
+            if id != 0 && succs.get(node).len() > 1 {
+                let targets = succs.get(node).iter().map(|&s| *block_of.get(s)).collect();
+                self.map.branches.insert(id, targets);
+            }
+        }
+        match *block_of.get(index(cfg.entry)) {
+            Some(counter) => { self.map.entries.insert(fn_id, counter); }
+            None => {}
+        }
+    }
+}
+
+// Whether the node of `e` is where its branches meet again, in which case
+// its span covers the branches as well as what comes after it.
+fn joins_branches(e: &ast::Expr) -> bool {
+    match e.node {
+        ast::ExprIf(..) | ast::ExprMatch(..) |
+        ast::ExprWhile(..) | ast::ExprLoop(..) => true,
+        ast::ExprBinary(op, _, _) => ast_util::lazy_binop(op),
+        _ => false,
+    }
+}
+
+fn index(idx: graph::NodeIndex) -> uint {
+    let graph::NodeIndex(i) = idx;
+    i
+}
+
+impl CoverageMap {
+    /// Find the blocks of every function in the crate.
+    pub fn new(tcx: &ty::ctxt, method_map: typeck::MethodMap,
+               krate: &ast::Crate) -> CoverageMap {
+        let mut collector = RegionCollector {
+            tcx: tcx,
+            method_map: method_map,
+            map: CoverageMap {
+                counters: NodeMap::new(),
+                regions: Vec::new(),
+                blocks: NodeMap::new(),
+                branches: NodeMap::new(),
+                entries: NodeMap::new(),
+                llcounters: Cell::new(None),
+            },
+        };
+        visit::walk_crate(&mut collector, krate, ());
+        collector.map
+    }
+
+    pub fn num_counters(&self) -> uint {
+        self.regions.len()
+    }
+
+    /// The counter of the block the control-flow graph node `id` is in.
+    pub fn block_counter(&self, id: ast::NodeId) -> Option<uint> {
+        self.blocks.find(&id).map(|&c| c)
+    }
+
+    /// The counters of the blocks the branching node `id` may go to.
+    pub fn branch_counters<'a>(&'a self, id: ast::NodeId) -> Option<&'a [Option<uint>]> {
+        self.branches.find(&id).map(|targets| targets.as_slice())
+    }
+
+    /// The counter of the first block of the function `id`.
+    pub fn entry_counter(&self, id: ast::NodeId) -> Option<uint> {
+        self.entries.find(&id).map(|&c| c)
+    }
+
+    /// The counters of the first blocks of all functions.
+    pub fn entry_counters(&self) -> Vec<uint> {
+        self.entries.values().map(|&c| c).collect()
+    }
+
+    fn llcounters(&self, ccx: &CrateContext) -> ValueRef {
+        match self.llcounters.get() {
+            Some(v) => return v,
+            None => {}
+        }
+        let ty = Type::array(&Type::i64(ccx), self.regions.len() as u64);
+        let g = unsafe {
+            let g = "_rust_coverage_counters".with_c_str(|buf| {
+                llvm::LLVMAddGlobal(ccx.llmod, ty.to_ref(), buf)
+            });
+            llvm::LLVMSetInitializer(g, C_null(ty));
+            lib::llvm::SetLinkage(g, lib::llvm::InternalLinkage);
+            g
+        };
+        self.llcounters.set(Some(g));
+        g
+    }
+}
+
+/// Bump the counter of the block which starts with `expr`, if any.
+pub fn count_expr(bcx: &Block, expr: &ast::Expr) {
+    let ccx = bcx.ccx();
+    let map = match ccx.coverage {
+        Some(ref map) => map,
+        None => return,
+    };
+    let counter = match map.counters.find(&expr.id) {
+        Some(&counter) => counter,
+        None => return,
+    };
+    let slot = GEPi(bcx, map.llcounters(ccx), [0u, counter]);
+    let count = Load(bcx, slot);
+    Store(bcx, Add(bcx, count, C_i64(ccx, 1)), slot);
+}
+
+/// Emit the code writing out the counts when the program exits, and write
+/// the map of counters to regions next to the output when instrumenting for
+/// coverage.
+pub fn finish(ccx: &CrateContext, outputs: &OutputFilenames) {
+    let map = match ccx.coverage {
+        Some(ref map) => map,
+        None => return,
+    };
+    if map.regions.len() == 0 {
+        return
+    }
+
+    let counts_path = match ccx.sess().opts.cg.profile_generate {
+        Some(ref path) => os::make_absolute(&Path::new(path.as_slice())),
+        None => os::make_absolute(&outputs.with_extension("covcounts")),
+    };
+    // The path is written into both the program and the map as text.
+    match counts_path.as_str() {
+        Some(path) => emit_dump(ccx, map, path),
+        None => {
+            ccx.sess().err(format!("the coverage counts path `{}` is not valid UTF-8",
+                                   counts_path.display()));
+            return
+        }
+    }
+    if !ccx.sess().instrument_coverage() {
+        return
+    }
+
+    let map_path = outputs.with_extension("covmap");
+    let result = io::File::create(&map_path).and_then(|mut file| {
+        write_map(&mut file, ccx.sess(), map, &counts_path)
+    });
+    match result {
+        Ok(()) => {}
+        Err(e) => {
+            ccx.sess().err(format!("failed to write coverage map `{}`: {}",
+                                   map_path.display(), e));
+        }
+    }
+}
+
+fn write_map(w: &mut Writer, sess: &Session, map: &CoverageMap,
+             counts_path: &Path) -> io::IoResult<()> {
+    try!(writeln!(w, "counters\t{}", map.regions.len()));
+    try!(writeln!(w, "counts\t{}", counts_path.display()));
+    for (counter, region) in map.regions.iter().enumerate() {
+        let lo = sess.codemap().lookup_char_pos(region.span.lo);
+        let hi = sess.codemap().lookup_char_pos(region.span.hi);
+        try!(writeln!(w, "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                      counter, lo.file.name, lo.line, lo.col.to_uint() + 1,
+                      hi.line, hi.col.to_uint() + 1, region.function));
+    }
+    Ok(())
+}
+
+// Roughly:
+//
+//     static void dump(void) {
+//         FILE *f = fopen("<counts>", "ab");
+//         if (f) { fwrite(counters, 8, N, f); fclose(f); }
+//     }
+//     __attribute__((constructor)) static void init(void) { atexit(dump); }
+fn emit_dump(ccx: &CrateContext, map: &CoverageMap, counts_path: &str) {
+    let i8p = Type::i8p(ccx);
+    let void_fn = Type::func([], &Type::void(ccx));
+    let fopen = declare_libc_fn(ccx, "fopen", Type::func([i8p, i8p], &i8p));
+    let fwrite = declare_libc_fn(ccx, "fwrite",
+                                 Type::func([i8p, ccx.int_type, ccx.int_type, i8p],
+                                            &ccx.int_type));
+    let fclose = declare_libc_fn(ccx, "fclose", Type::func([i8p], &Type::i32(ccx)));
+    let atexit = declare_libc_fn(ccx, "atexit",
+                                 Type::func([void_fn.ptr_to()], &Type::i32(ccx)));
+
+    let cstr = |s: &str| unsafe {
+        let s = C_cstr(ccx, token::intern_and_get_ident(s), true);
+        llvm::LLVMConstPointerCast(s, i8p.to_ref())
+    };
+
+    unsafe {
+        let bld = ccx.builder.b;
+
+        let dump = define_internal_fn(ccx, "coverage_dump", void_fn);
+        let entry = append_block(ccx, dump, "entry");
+        let write = append_block(ccx, dump, "write");
+        let done = append_block(ccx, dump, "done");
+        llvm::LLVMPositionBuilderAtEnd(bld, entry);
+        let file = call(bld, fopen, [cstr(counts_path), cstr("ab")]);
+        let opened = llvm::LLVMBuildIsNotNull(bld, file, noname());
+        llvm::LLVMBuildCondBr(bld, opened, write, done);
+        llvm::LLVMPositionBuilderAtEnd(bld, write);
+        let counters = llvm::LLVMConstPointerCast(map.llcounters(ccx), i8p.to_ref());
+        call(bld, fwrite, [counters, C_uint(ccx, 8),
+                           C_uint(ccx, map.regions.len()), file]);
+        call(bld, fclose, [file]);
+        llvm::LLVMBuildBr(bld, done);
+        llvm::LLVMPositionBuilderAtEnd(bld, done);
+        llvm::LLVMBuildRetVoid(bld);
+
+        let init = define_internal_fn(ccx, "coverage_init", void_fn);
+        llvm::LLVMPositionBuilderAtEnd(bld, append_block(ccx, init, "entry"));
+        call(bld, atexit, [dump]);
+        llvm::LLVMBuildRetVoid(bld);
+
+        // Run `init` before `main`.
+        let ctor_ty = Type::struct_(ccx, [Type::i32(ccx), void_fn.ptr_to()], false);
+        let ctors = C_array(ctor_ty, [C_struct(ccx, [C_i32(ccx, 65535), init], false)]);
+        let g = "llvm.global_ctors".with_c_str(|buf| {
+            llvm::LLVMAddGlobal(ccx.llmod, val_ty(ctors).to_ref(), buf)
+        });
+        llvm::LLVMSetInitializer(g, ctors);
+        lib::llvm::SetLinkage(g, lib::llvm::AppendingLinkage);
+    }
+
+    fn declare_libc_fn(ccx: &CrateContext, name: &str, ty: Type) -> ValueRef {
+        base::get_extern_fn(&mut *ccx.externs.borrow_mut(), ccx.llmod, name,
+                            lib::llvm::CCallConv, ty, ty::mk_nil())
+    }
+
+    fn define_internal_fn(ccx: &CrateContext, name: &str, ty: Type) -> ValueRef {
+        let name = format!("_rust_{}", name);
+        let llfn = base::decl_cdecl_fn(ccx.llmod, name, ty, ty::mk_nil());
+        lib::llvm::SetLinkage(llfn, lib::llvm::InternalLinkage);
+        llfn
+    }
+
+    unsafe fn append_block(ccx: &CrateContext, llfn: ValueRef,
+                           name: &str) -> lib::llvm::BasicBlockRef {
+        name.with_c_str(|buf| llvm::LLVMAppendBasicBlockInContext(ccx.llcx, llfn, buf))
+    }
+
+    unsafe fn call(bld: lib::llvm::BuilderRef, llfn: ValueRef,
+                   args: &[ValueRef]) -> ValueRef {
+        llvm::LLVMBuildCall(bld, llfn, args.as_ptr(), args.len() as c_uint, noname())
+    }
+}
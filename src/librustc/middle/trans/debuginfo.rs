@@ -153,7 +153,6 @@ use std::strbuf::StrBuf;
 use std::sync::atomics;
 use syntax::codemap::{Span, Pos};
 use syntax::{abi, ast, codemap, ast_util, ast_map};
-use syntax::owned_slice::OwnedSlice;
 use syntax::parse::token;
 use syntax::parse::token::special_idents;
 
@@ -614,7 +613,7 @@ pub fn create_function_debug_context(cx: &CrateContext,
         return FunctionWithoutDebugInfo;
     }
 
-    let empty_generics = ast::Generics { lifetimes: Vec::new(), ty_params: OwnedSlice::empty() };
+    let empty_generics = ast_util::empty_generics();
 
     let fnitem = cx.tcx.map.get(fn_ast_id);
 
@@ -945,10 +944,17 @@ fn create_DIArray(builder: DIBuilderRef, arr: &[DIDescriptor]) -> DIArray {
 
 fn compile_unit_metadata(cx: &CrateContext) {
     let work_dir = &cx.sess().working_dir;
+    let mapping = &cx.sess().codemap().path_mapping;
     let compile_unit_name = match cx.sess().local_crate_source_file {
         None => fallback_path(cx),
         Some(ref abs_path) => {
-            if abs_path.is_relative() {
+            // A remapped path is as relative to anything as it is going to
+            // get.
+            let path = abs_path.as_str().unwrap_or("");
+            let mapped = mapping.map_prefix(path);
+            if mapped.as_slice() != path {
+                mapped.to_c_str()
+            } else if abs_path.is_relative() {
                 cx.sess().warn("debuginfo: Invalid path to crate's local root source file!");
                 fallback_path(cx)
             } else {
@@ -975,9 +981,11 @@ fn compile_unit_metadata(cx: &CrateContext) {
 
     debug!("compile_unit_metadata: {:?}", compile_unit_name);
     let producer = format!("rustc version {}", env!("CFG_VERSION"));
+    // FIXME (#9639): This needs to handle non-utf8 paths
+    let work_dir = mapping.map_prefix(work_dir.as_str().unwrap());
 
     compile_unit_name.with_ref(|compile_unit_name| {
-        work_dir.as_vec().with_c_str(|work_dir| {
+        work_dir.with_c_str(|work_dir| {
             producer.with_c_str(|producer| {
                 "".with_c_str(|flags| {
                     "".with_c_str(|split_name| {
@@ -1096,12 +1104,14 @@ fn file_metadata(cx: &CrateContext, full_path: &str) -> DIFile {
     debug!("file_metadata: {}", full_path);
 
     // FIXME (#9639): This needs to handle non-utf8 paths
-    let work_dir = cx.sess().working_dir.as_str().unwrap();
+    let mapping = &cx.sess().codemap().path_mapping;
+    let work_dir = mapping.map_prefix(cx.sess().working_dir.as_str().unwrap());
+    let mapped_path = mapping.map_prefix(full_path);
     let file_name =
-        if full_path.starts_with(work_dir) {
-            full_path.slice(work_dir.len() + 1u, full_path.len())
+        if mapped_path.starts_with(work_dir.as_slice()) {
+            mapped_path.slice(work_dir.len() + 1u, mapped_path.len())
         } else {
-            full_path
+            mapped_path.as_slice()
         };
 
     let file_metadata =
@@ -50,6 +50,7 @@ use middle::trans::cleanup::CleanupMethods;
 use middle::trans::closure;
 use middle::trans::common::*;
 use middle::trans::consts;
+use middle::trans::coverage;
 use middle::trans::controlflow;
 use middle::trans::datum::*;
 use middle::trans::debuginfo;
@@ -116,6 +117,7 @@ pub fn trans_into<'a>(bcx: &'a Block<'a>,
 
     debug!("trans_into() expr={}", expr.repr(bcx.tcx()));
     debuginfo::set_source_location(bcx.fcx, expr.id, expr.span);
+    coverage::count_expr(bcx, expr);
 
     bcx.fcx.push_ast_cleanup_scope(expr.id);
 
@@ -150,6 +152,7 @@ pub fn trans<'a>(bcx: &'a Block<'a>,
     let mut bcx = bcx;
     let fcx = bcx.fcx;
 
+    coverage::count_expr(bcx, expr);
     fcx.push_ast_cleanup_scope(expr.id);
     let datum = unpack_datum!(bcx, trans_unadjusted(bcx, expr));
     let datum = unpack_datum!(bcx, apply_adjustments(bcx, expr, datum));
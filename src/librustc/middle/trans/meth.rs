@@ -36,6 +36,7 @@ use util::ppaux::Repr;
 use std::c_str::ToCStr;
 use syntax::abi::Rust;
 use syntax::parse::token;
+use syntax::print::pprust;
 use syntax::{ast, ast_map, visit};
 
 /**
@@ -66,8 +67,11 @@ pub fn trans_impl(ccx: &CrateContext,
     for method in methods.iter() {
         if method.generics.ty_params.len() == 0u {
             let llfn = get_item_val(ccx, method.id);
-            trans_fn(ccx, method.decl, method.body,
-                     llfn, None, method.id, []);
+            let source = || pprust::to_str(|s| s.print_method(*method));
+            if !reuse_cached_fn(ccx, method.id, source) {
+                trans_fn(ccx, method.decl, method.body,
+                         llfn, None, method.id, []);
+            }
         } else {
             let mut v = TransItemVisitor{ ccx: ccx };
             visit::walk_method_helper(&mut v, *method, ());
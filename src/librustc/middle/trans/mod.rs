@@ -46,3 +46,6 @@ pub mod value;
 pub mod basic_block;
 pub mod llrepr;
 pub mod cleanup;
+pub mod coverage;
+pub mod pgo;
+pub mod cheader;
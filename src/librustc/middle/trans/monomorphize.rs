@@ -22,7 +22,10 @@ use middle::trans::intrinsic;
 use middle::ty;
 use middle::typeck;
 use util::ppaux::Repr;
+use util::ppaux;
+use util::profile;
 
+use collections::HashMap;
 use syntax::abi;
 use syntax::ast;
 use syntax::ast_map;
@@ -84,6 +87,10 @@ pub fn monomorphic_fn(ccx: &CrateContext,
         None => ()
     }
 
+    let _activity = profile::activity("trans", || {
+        instance_name(ccx, fn_id, &*psubsts)
+    });
+
     let tpt = ty::lookup_item_type(ccx.tcx(), fn_id);
     let llitem_ty = tpt.ty;
 
@@ -190,6 +197,22 @@ pub fn monomorphic_fn(ccx: &CrateContext,
         lldecl
     };
 
+    if ccx.sess().print_mono_items() {
+        let caller = match ccx.stats.mono_stack.borrow().last() {
+            Some(item) => item.instance.clone(),
+            None => match ref_id {
+                Some(id) => ccx.tcx.map.path_to_str(ccx.tcx.map.get_parent(id)),
+                None => ~"-",
+            },
+        };
+        ccx.stats.mono_stack.borrow_mut().push(MonoItem {
+            generic: ty::item_path_str(ccx.tcx(), fn_id),
+            instance: instance_name(ccx, fn_id, &*psubsts),
+            caller: caller,
+            insns: 0,
+        });
+    }
+
     let lldecl = match map_node {
         ast_map::NodeItem(i) => {
             match *i {
@@ -281,12 +304,53 @@ pub fn monomorphic_fn(ccx: &CrateContext,
         }
     };
 
+    if ccx.sess().print_mono_items() {
+        let item = ccx.stats.mono_stack.borrow_mut().pop().unwrap();
+        ccx.stats.mono_items.borrow_mut().push(item);
+    }
+
     ccx.monomorphizing.borrow_mut().insert(fn_id, depth);
 
     debug!("leaving monomorphic fn {}", ty::item_path_str(ccx.tcx(), fn_id));
     (lldecl, must_cast)
 }
 
+// The path of `fn_id` followed by the types it is instantiated with.
+fn instance_name(ccx: &CrateContext, fn_id: ast::DefId,
+                 psubsts: &param_substs) -> ~str {
+    let tys: Vec<~str> = psubsts.tys.iter().map(|&t| {
+        ppaux::ty_to_str(ccx.tcx(), t)
+    }).collect();
+    format!("{}::<{}>", ty::item_path_str(ccx.tcx(), fn_id), tys.connect(", "))
+}
+
+/// Print the instantiations made by `monomorphic_fn`, grouped by the
+/// generic function they instantiate, most instructions first.
+pub fn print_mono_items(ccx: &CrateContext) {
+    let items = ccx.stats.mono_items.borrow();
+    let mut by_generic: HashMap<&str, Vec<&MonoItem>> = HashMap::new();
+    for item in items.iter() {
+        by_generic.find_or_insert(item.generic.as_slice(), Vec::new()).push(item);
+    }
+    let mut groups: Vec<(uint, &str, Vec<&MonoItem>)> =
+        by_generic.move_iter().map(|(generic, mut instances)| {
+            instances.sort_by(|a, b| b.insns.cmp(&a.insns));
+            let total = instances.iter().fold(0, |n, item| n + item.insns);
+            (total, generic, instances)
+        }).collect();
+    groups.sort_by(|&(a, _, _), &(b, _, _)| b.cmp(&a));
+
+    println!("--- mono items ---");
+    for &(total, generic, ref instances) in groups.iter() {
+        println!("{:7u} insns, {} instantiations: {}",
+                 total, instances.len(), generic);
+        for item in instances.iter() {
+            println!("    {:7u} insns: {} (requested by {})",
+                     item.insns, item.instance, item.caller);
+        }
+    }
+}
+
 pub fn make_mono_id(ccx: &CrateContext,
                     item: ast::DefId,
                     substs: &param_substs) -> mono_id {
@@ -0,0 +1,180 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Profile-guided optimization.
+//!
+//! A program built with `-C profile-generate=PATH` counts how often each
+//! block of code runs, with the counters of `middle::trans::coverage`, and
+//! appends the counts to `PATH` every time it exits. Building the same
+//! source again with `-C profile-use=PATH` finds the same blocks, adds up
+//! the runs in the profile, and hands what they say to LLVM:
+//!
+//! * the conditional branches of `if` and `while` are weighted by how often
+//!   each way was taken, which guides block placement and the like;
+//! * functions which never ran are marked cold, and those entered at least
+//!   a hundredth as often as the most frequently entered one are hinted for
+//!   inlining.
+//!
+//! All instantiations of a generic function share its counters. A profile
+//! whose size doesn't fit the blocks of the crate was recorded from other
+//! source and is ignored; one recorded from other source with as many
+//! blocks can't be told apart and will simply make for poor guesses.
+
+use driver::session::Session;
+use lib::llvm::{llvm, ValueRef};
+use lib;
+use middle::trans::base;
+use middle::trans::common::*;
+use middle::trans::context::CrateContext;
+use middle::trans::coverage::CoverageMap;
+
+use std::io;
+use std::num::Saturating;
+use std::u32;
+use libc::c_uint;
+use syntax::ast;
+
+pub struct Profile {
+    blocks: CoverageMap,
+    // The count of each counter over all runs.
+    counts: Vec<u64>,
+    // Functions entered at least this often are hot.
+    hot_entries: u64,
+}
+
+impl Profile {
+    /// Read the profile at `path`, recorded by a build of the crate whose
+    /// blocks are `blocks`. Problems are reported as warnings, since the
+    /// crate builds just as well without a profile.
+    pub fn load(sess: &Session, blocks: CoverageMap, path: &Path) -> Option<Profile> {
+        let data = match io::File::open(path).read_to_end() {
+            Ok(data) => data,
+            Err(e) => {
+                sess.warn(format!("ignoring profile `{}`: {}", path.display(), e));
+                return None
+            }
+        };
+        let n = blocks.num_counters();
+        let record = n * 8;
+        if record == 0 || data.len() == 0 || data.len() % record != 0 {
+            sess.warn(format!("ignoring profile `{}`: it was not recorded by \
+                               this crate, or the source has changed since",
+                              path.display()));
+            return None
+        }
+
+        let mut counts = Vec::from_elem(n, 0u64);
+        let mut reader = io::BufReader::new(data.as_slice());
+        for _ in range(0, data.len() / record) {
+            for count in counts.mut_iter() {
+                // The counts are written out as they are in memory.
+                let n = if cfg!(target_endian = "big") {
+                    reader.read_be_u64()
+                } else {
+                    reader.read_le_u64()
+                };
+                *count += n.unwrap();
+            }
+        }
+
+        let max_entries = blocks.entry_counters().iter().map(|&c| *counts.get(c))
+                                .max().unwrap_or(0);
+        Some(Profile {
+            blocks: blocks,
+            counts: counts,
+            hot_entries: if max_entries == 0 { 1 } else { (max_entries + 99) / 100 },
+        })
+    }
+
+    fn count(&self, counter: uint) -> u64 {
+        *self.counts.get(counter)
+    }
+}
+
+/// Mark the function `id` cold or hot according to how often it was
+/// entered.
+pub fn set_fn_hotness(ccx: &CrateContext, llfn: ValueRef, id: ast::NodeId) {
+    let profile = match ccx.profile {
+        Some(ref profile) => profile,
+        None => return,
+    };
+    match profile.blocks.entry_counter(id).map(|c| profile.count(c)) {
+        Some(0) => unsafe { llvm::LLVMAddColdAttribute(llfn) },
+        Some(n) if n >= profile.hot_entries => base::set_inline_hint(llfn),
+        _ => {}
+    }
+}
+
+/// Weigh the conditional branch ending `bcx` on the condition of an `if`.
+pub fn weigh_if(bcx: &Block, cond: &ast::Expr, has_else: bool) {
+    let weights = bcx.ccx().profile.as_ref().and_then(|profile| {
+        let (then, other) = match profile.blocks.branch_counters(cond.id) {
+            Some([Some(then), other]) => (profile.count(then), other),
+            _ => return None,
+        };
+        // Without an `else`, the other way out is to what follows the `if`,
+        // which is also where the `then` block goes, so it is the count of
+        // the condition which tells the two apart.
+        let other = if has_else {
+            other.map(|c| profile.count(c))
+        } else {
+            profile.blocks.block_counter(cond.id).map(|c| {
+                profile.count(c).saturating_sub(then)
+            })
+        };
+        other.map(|other| (then, other))
+    });
+    set_branch_weights(bcx, weights);
+}
+
+/// Weigh the conditional branch ending `bcx` on the condition of a `while`.
+pub fn weigh_while(bcx: &Block, cond: &ast::Expr) {
+    let weights = bcx.ccx().profile.as_ref().and_then(|profile| {
+        // The loop is left from the condition to the `while` itself before
+        // it goes into the body.
+        let body = match profile.blocks.branch_counters(cond.id) {
+            Some([_, Some(body)]) => profile.count(body),
+            _ => return None,
+        };
+        profile.blocks.block_counter(cond.id).map(|c| {
+            (body, profile.count(c).saturating_sub(body))
+        })
+    });
+    set_branch_weights(bcx, weights);
+}
+
+fn set_branch_weights(bcx: &Block, weights: Option<(u64, u64)>) {
+    let (taken, not_taken) = match weights {
+        Some(weights) => weights,
+        None => return,
+    };
+    if bcx.unreachable.get() {
+        return
+    }
+
+    // Weights are 32-bit, so large counts are scaled down together.
+    let scale = (if taken > not_taken { taken } else { not_taken }) /
+                (u32::MAX as u64) + 1;
+    let ccx = bcx.ccx();
+    unsafe {
+        let br = llvm::LLVMGetLastInstruction(bcx.llbb);
+        let name = "branch_weights";
+        let md = [
+            name.with_c_str(|buf| {
+                llvm::LLVMMDStringInContext(ccx.llcx, buf, name.len() as c_uint)
+            }),
+            C_i32(ccx, (taken / scale) as i32),
+            C_i32(ccx, (not_taken / scale) as i32),
+        ];
+        llvm::LLVMSetMetadata(br, lib::llvm::MD_prof as c_uint,
+                              llvm::LLVMMDNodeInContext(ccx.llcx, md.as_ptr(),
+                                                        md.len() as c_uint));
+    }
+}
@@ -357,10 +357,11 @@ pub struct ctxt {
     // Borrows
     pub upvar_borrow_map: RefCell<UpvarBorrowMap>,
 
-    // These two caches are used by const_eval when decoding external statics
-    // and variants that are found.
+    // These caches are used by const_eval when decoding external statics,
+    // variants and constant functions that are found.
     pub extern_const_statics: RefCell<DefIdMap<Option<@ast::Expr>>>,
     pub extern_const_variants: RefCell<DefIdMap<Option<@ast::Expr>>>,
+    pub extern_const_fns: RefCell<DefIdMap<Option<@ast::Item>>>,
 }
 
 pub enum tbox_flag {
@@ -987,7 +988,17 @@ pub struct TypeParameterDef {
     pub ident: ast::Ident,
     pub def_id: ast::DefId,
     pub bounds: @ParamBounds,
-    pub default: Option<ty::t>
+    pub default: Option<ty::t>,
+    /// True for the associated types of a trait, and for the parameters
+    /// that typeck adds to a function to stand for the associated types of
+    /// the bounds on its type parameters or for the types bounded by its
+    /// where clause. Associated parameters are never written out by the
+    /// user; they are always inferred or given by an impl.
+    pub associated: bool,
+    /// For a parameter added for the where clause `Vec<T>: Show` of a
+    /// function, the type it bounds, `Vec<T>`. The parameter always stands
+    /// for that type, and its bounds are those of the where clause.
+    pub where_ty: Option<ty::t>
 }
 
 #[deriving(Encodable, Decodable, Clone)]
@@ -1045,6 +1056,10 @@ pub struct ParameterEnvironment {
 
     /// Bounds on each numbered type parameter
     pub type_param_bounds: Vec<ParamBounds> ,
+
+    /// The types bounded by where clauses, each with the number of the type
+    /// parameter whose bounds are those of the clause
+    pub where_tys: Vec<(uint, ty::t)>,
 }
 
 /// A polytype.
@@ -1132,6 +1147,7 @@ pub fn mk_ctxt(s: Session,
         upvar_borrow_map: RefCell::new(HashMap::new()),
         extern_const_statics: RefCell::new(DefIdMap::new()),
         extern_const_variants: RefCell::new(DefIdMap::new()),
+        extern_const_fns: RefCell::new(DefIdMap::new()),
     }
 }
 
@@ -3858,7 +3874,8 @@ pub fn enum_variants(cx: &ctxt, id: ast::DefId) -> @Vec<@VariantInfo> {
                                           .span_err(e.span,
                                                     format!("expected \
                                                              constant: {}",
-                                                            *err));
+                                                            err.msg));
+                                        const_eval::note_const_eval_err(cx, err);
                                     }
                                 },
                                 None => {}
@@ -4269,10 +4286,11 @@ pub fn eval_repeat_count<T: ExprTyProvider>(tcx: &T, count_expr: &ast::Expr) ->
             return 0;
         }
       },
-      Err(..) => {
+      Err(ref err) => {
         tcx.ty_ctxt().sess.span_err(count_expr.span,
-                                    "expected constant integer for repeat count \
-                                     but found variable");
+                                    format!("expected constant integer for repeat count: {}",
+                                            err.msg));
+        const_eval::note_const_eval_err(tcx.ty_ctxt(), err);
         return 0;
       }
     }
@@ -4766,6 +4784,11 @@ pub fn construct_parameter_environment(
         }
     });
 
+    let where_tys_substd = item_type_params.iter().chain(method_type_params.iter())
+                                           .enumerate().filter_map(|(i, def)| {
+        def.where_ty.map(|t| (i, t.subst(tcx, &free_substs)))
+    }).collect();
+
     debug!("construct_parameter_environment: free_id={} \
            free_subst={} \
            self_param_bound={} \
@@ -4779,6 +4802,7 @@ pub fn construct_parameter_environment(
         free_substs: free_substs,
         self_param_bound: self_bound_substd,
         type_param_bounds: type_param_bounds_substd,
+        where_tys: where_tys_substd,
     }
 }
 
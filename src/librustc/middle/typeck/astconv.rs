@@ -50,6 +50,7 @@
  */
 
 
+use diagnostics;
 use middle::const_eval;
 use middle::subst::Subst;
 use middle::ty::{substs};
@@ -64,6 +65,7 @@ use syntax::abi;
 use syntax::{ast, ast_util};
 use syntax::codemap::Span;
 use syntax::owned_slice::OwnedSlice;
+use syntax::parse::token;
 use syntax::print::pprust::{lifetime_to_str, path_to_str};
 
 pub trait AstConv {
@@ -127,8 +129,9 @@ fn opt_ast_region_to_region<AC:AstConv,RS:RegionScope>(
             match rscope.anon_regions(default_span, 1) {
                 Err(()) => {
                     debug!("optional region in illegal location");
-                    this.tcx().sess.span_err(
-                        default_span, "missing lifetime specifier");
+                    this.tcx().sess.span_err_with_code(
+                        default_span, "missing lifetime specifier",
+                        diagnostics::E0024);
                     ty::ReStatic
                 }
 
@@ -174,12 +177,13 @@ fn ast_path_substs<AC:AstConv,RS:RegionScope>(
             rscope.anon_regions(path.span, expected_num_region_params);
 
         if supplied_num_region_params != 0 || anon_regions.is_err() {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 path.span,
                 format!("wrong number of lifetime parameters: \
                         expected {} but found {}",
                         expected_num_region_params,
-                        supplied_num_region_params));
+                        supplied_num_region_params),
+                diagnostics::E0025);
         }
 
         match anon_regions {
@@ -189,36 +193,48 @@ fn ast_path_substs<AC:AstConv,RS:RegionScope>(
         }
     };
 
-    // Convert the type parameters supplied by the user.
+    // Convert the type parameters supplied by the user. The associated
+    // types of a trait follow its other type parameters; they may be given
+    // along with all of the others, but are otherwise left to the caller.
     let supplied_ty_param_count = path.segments.iter().flat_map(|s| s.types.iter()).len();
-    let formal_ty_param_count = decl_generics.type_param_defs().len();
-    let required_ty_param_count = decl_generics.type_param_defs().iter()
-                                               .take_while(|x| x.default.is_none())
-                                               .len();
+    let type_param_defs = decl_generics.type_param_defs();
+    let associated_count = type_param_defs.iter().filter(|x| x.associated).len();
+    let type_param_defs = if supplied_ty_param_count == type_param_defs.len() {
+        type_param_defs
+    } else {
+        type_param_defs.slice_to(type_param_defs.len() - associated_count)
+    };
+    let formal_ty_param_count = type_param_defs.len();
+    let required_ty_param_count = type_param_defs.iter()
+                                                 .take_while(|x| x.default.is_none())
+                                                 .len();
     if supplied_ty_param_count < required_ty_param_count {
         let expected = if required_ty_param_count < formal_ty_param_count {
             "expected at least"
         } else {
             "expected"
         };
-        this.tcx().sess.span_fatal(path.span,
+        this.tcx().sess.span_fatal_with_code(path.span,
             format!("wrong number of type arguments: {} {} but found {}",
-                expected, required_ty_param_count, supplied_ty_param_count));
+                expected, required_ty_param_count, supplied_ty_param_count),
+            diagnostics::E0121);
     } else if supplied_ty_param_count > formal_ty_param_count {
         let expected = if required_ty_param_count < formal_ty_param_count {
             "expected at most"
         } else {
             "expected"
         };
-        this.tcx().sess.span_fatal(path.span,
+        this.tcx().sess.span_fatal_with_code(path.span,
             format!("wrong number of type arguments: {} {} but found {}",
-                expected, formal_ty_param_count, supplied_ty_param_count));
+                expected, formal_ty_param_count, supplied_ty_param_count),
+            diagnostics::E0121);
     }
 
     if supplied_ty_param_count > required_ty_param_count
         && !this.tcx().sess.features.default_type_params.get() {
-        this.tcx().sess.span_err(path.span, "default type parameters are \
-                                             experimental and possibly buggy");
+        this.tcx().sess.span_err_with_code(path.span, "default type parameters are \
+                                                       experimental and possibly buggy",
+                                           diagnostics::E0026);
         this.tcx().sess.span_note(path.span, "add #[feature(default_type_params)] \
                                               to the crate attributes to enable");
     }
@@ -233,8 +249,7 @@ fn ast_path_substs<AC:AstConv,RS:RegionScope>(
         tps: tps
     };
 
-    for param in decl_generics.type_param_defs()
-                              .slice_from(supplied_ty_param_count).iter() {
+    for param in type_param_defs.slice_from(supplied_ty_param_count).iter() {
         let ty = param.default.unwrap().subst_spanned(tcx, &substs, Some(path.span));
         substs.tps.push(ty);
     }
@@ -265,23 +280,140 @@ pub fn ast_path_to_trait_ref<AC:AstConv,RS:RegionScope>(
     rscope: &RS,
     trait_def_id: ast::DefId,
     self_ty: Option<ty::t>,
-    path: &ast::Path) -> @ty::TraitRef
+    path: &ast::Path,
+    associated_ty: |&ty::substs, &ty::TypeParameterDef| -> ty::t) -> @ty::TraitRef
 {
+    /*!
+     * Converts a reference to a trait. The associated types of the trait
+     * which `path` does not give are supplied by `associated_ty`, which
+     * receives the substitutions built so far along with the declaration
+     * of the associated type.
+     */
+
     let trait_def =
         this.get_trait_def(trait_def_id);
-    let substs =
+    let mut substs =
         ast_path_substs(
             this,
             rscope,
             &trait_def.generics,
             self_ty,
             path);
+    for def in trait_def.generics.type_param_defs()
+                        .slice_from(substs.tps.len()).iter() {
+        let ty = associated_ty(&substs, def);
+        substs.tps.push(ty);
+    }
     let trait_ref =
         @ty::TraitRef {def_id: trait_def_id,
                        substs: substs};
     return trait_ref;
 }
 
+pub fn unspecified_associated_type(tcx: &ty::ctxt,
+                                   span: Span,
+                                   trait_def_id: ast::DefId,
+                                   def: &ty::TypeParameterDef) -> ty::t {
+    /*!
+     * Reports that the associated type `def` of a trait must be given
+     * explicitly, for the places where it can't be inferred.
+     */
+
+    tcx.sess.span_err_with_code(
+        span,
+        format!("the associated type `{}` of trait `{}` must be specified here, \
+                 as a trailing type parameter",
+                token::get_ident(def.ident),
+                ty::item_path_str(tcx, trait_def_id)),
+        diagnostics::E0101);
+    ty::mk_err()
+}
+
+fn associated_type_of_param<AC:AstConv>(this: &AC,
+                                        span: Span,
+                                        param_id: ast::DefId,
+                                        param_name: ast::Ident,
+                                        name: ast::Ident) -> ty::t {
+    /*!
+     * Resolves the path type `T::Name`, where `T` is the type parameter
+     * `param_id`, to the type given for the associated type `Name` by the
+     * bounds of `T`.
+     */
+
+    let tcx = this.tcx();
+    let bounds = match tcx.ty_param_defs.borrow().find(&param_id.node) {
+        Some(def) => def.bounds,
+        None => {
+            // `T::Name` appears in the bounds of a parameter before `T`.
+            tcx.sess.span_err_with_code(
+                span,
+                format!("the associated type `{}::{}` is used before the bounds of `{}`",
+                        token::get_ident(param_name),
+                        token::get_ident(name),
+                        token::get_ident(param_name)),
+                diagnostics::E0105);
+            return ty::mk_err();
+        }
+    };
+    let mut candidates = Vec::new();
+    for trait_ref in bounds.trait_bounds.iter() {
+        let trait_def = this.get_trait_def(trait_ref.def_id);
+        for (i, def) in trait_def.generics.type_param_defs().iter().enumerate() {
+            if def.associated && def.ident.name == name.name {
+                candidates.push((trait_ref.def_id, *trait_ref.substs.tps.get(i)));
+            }
+        }
+    }
+    match candidates.len() {
+        1 => { let (_, ty) = *candidates.get(0); ty }
+        0 => {
+            tcx.sess.span_err_with_code(
+                span,
+                format!("none of the bounds on `{}` declare an associated type `{}`",
+                        token::get_ident(param_name),
+                        token::get_ident(name)),
+                diagnostics::E0105);
+            ty::mk_err()
+        }
+        _ => {
+            let traits: Vec<~str> = candidates.iter().map(|&(did, _)| {
+                format!("`{}`", ty::item_path_str(tcx, did))
+            }).collect();
+            tcx.sess.span_err_with_code(
+                span,
+                format!("ambiguous associated type `{}` of `{}`: it is declared by {}",
+                        token::get_ident(name),
+                        token::get_ident(param_name),
+                        traits.connect(" and ")),
+                diagnostics::E0106);
+            ty::mk_err()
+        }
+    }
+}
+
+fn associated_type_of_self<AC:AstConv>(this: &AC,
+                                       span: Span,
+                                       trait_id: ast::NodeId,
+                                       name: ast::Ident) -> ty::t {
+    /*!
+     * Resolves the path type `Self::Name` within the trait `trait_id`.
+     */
+
+    let trait_def = this.get_trait_def(ast_util::local_def(trait_id));
+    for (i, def) in trait_def.generics.type_param_defs().iter().enumerate() {
+        if def.associated && def.ident.name == name.name {
+            return ty::mk_param(this.tcx(), i, def.def_id);
+        }
+    }
+    this.tcx().sess.span_err_with_code(
+        span,
+        format!("trait `{}` has no associated type `{}`",
+                ty::item_path_str(this.tcx(), trait_def.trait_ref.def_id),
+                token::get_ident(name)),
+        diagnostics::E0105);
+    ty::mk_err()
+}
+
 pub fn ast_path_to_ty<AC:AstConv,RS:RegionScope>(
         this: &AC,
         rscope: &RS,
@@ -306,17 +438,19 @@ fn check_path_args(tcx: &ty::ctxt,
                    flags: uint) {
     if (flags & NO_TPS) != 0u {
         if !path.segments.iter().all(|s| s.types.is_empty()) {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 path.span,
-                "type parameters are not allowed on this type");
+                "type parameters are not allowed on this type",
+                diagnostics::E0011);
         }
     }
 
     if (flags & NO_REGIONS) != 0u {
         if !path.segments.last().unwrap().lifetimes.is_empty() {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 path.span,
-                "region parameters are not allowed on this type");
+                "region parameters are not allowed on this type",
+                diagnostics::E0011);
         }
     }
 }
@@ -353,8 +487,9 @@ pub fn ast_ty_to_prim_ty(tcx: &ty::ctxt, ast_ty: &ast::Ty) -> Option<ty::t> {
                             Some(ty::mk_mach_float(ft))
                         }
                         ast::TyStr => {
-                            tcx.sess.span_err(ast_ty.span,
-                                              "bare `str` is not a type");
+                            tcx.sess.span_err_with_code(ast_ty.span,
+                                                        "bare `str` is not a type",
+                                                        diagnostics::E0027);
                             // return /something/ so they can at least get more errors
                             Some(ty::mk_str(tcx, ty::VstoreUniq))
                         }
@@ -380,7 +515,8 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
         fn expect_vstore(&self, tcx: &ty::ctxt, span: Span, ty: &str) -> ty::Vstore {
             match *self {
                 Box => {
-                    tcx.sess.span_err(span, format!("managed {} are not supported", ty));
+                    tcx.sess.span_err_with_code(span, format!("managed {} are not supported", ty),
+                                                diagnostics::E0028);
                     // everything can be ~, so this is a worth substitute
                     ty::VstoreUniq
                 }
@@ -429,17 +565,20 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
                     }
                     Some(&ast::DefTrait(trait_def_id)) => {
                         let result = ast_path_to_trait_ref(
-                            this, rscope, trait_def_id, None, path);
+                            this, rscope, trait_def_id, None, path,
+                            |_, def| unspecified_associated_type(tcx, path.span,
+                                                                 trait_def_id, def));
                         let trait_store = match ptr_ty {
                             VStore(ty::VstoreUniq) => ty::UniqTraitStore,
                             VStore(ty::VstoreSlice(r, m)) => {
                                 ty::RegionTraitStore(r, m)
                             }
                             _ => {
-                                tcx.sess.span_err(
+                                tcx.sess.span_err_with_code(
                                     path.span,
                                     "~trait or &trait are the only supported \
-                                     forms of casting-to-trait");
+                                     forms of casting-to-trait",
+                                    diagnostics::E0029);
                                 return ty::mk_err();
                             }
                         };
@@ -465,10 +604,11 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
     match ast_ty_to_ty_cache.find(&ast_ty.id) {
         Some(&ty::atttce_resolved(ty)) => return ty,
         Some(&ty::atttce_unresolved) => {
-            tcx.sess.span_fatal(ast_ty.span,
-                                "illegal recursive type; insert an enum \
-                                 or struct in the cycle, if this is \
-                                 desired");
+            tcx.sess.span_fatal_with_code(ast_ty.span,
+                                          "illegal recursive type; insert an enum \
+                                           or struct in the cycle, if this is \
+                                           desired",
+                                          diagnostics::E0122);
         }
         None => { /* go on */ }
     }
@@ -486,7 +626,8 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
                            |ty| ty::mk_uniq(tcx, ty))
             }
             ast::TyVec(ty) => {
-                tcx.sess.span_err(ast_ty.span, "bare `[]` is not a type");
+                tcx.sess.span_err_with_code(ast_ty.span, "bare `[]` is not a type",
+                                            diagnostics::E0027);
                 // return /something/ so they can at least get more errors
                 ty::mk_vec(tcx, ast_ty_to_ty(this, rscope, ty), ty::VstoreUniq)
             }
@@ -510,16 +651,18 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
             }
             ast::TyBareFn(ref bf) => {
                 if bf.decl.variadic && bf.abi != abi::C {
-                    tcx.sess.span_err(ast_ty.span,
-                                      "variadic function must have C calling convention");
+                    tcx.sess.span_err_with_code(ast_ty.span,
+                                                "variadic function must have C calling convention",
+                                                diagnostics::E0030);
                 }
                 ty::mk_bare_fn(tcx, ty_of_bare_fn(this, ast_ty.id, bf.fn_style,
                                                   bf.abi, bf.decl))
             }
             ast::TyClosure(ref f) => {
                 if f.sigil == ast::ManagedSigil {
-                    tcx.sess.span_err(ast_ty.span,
-                                      "managed closures are not supported");
+                    tcx.sess.span_err_with_code(ast_ty.span,
+                                                "managed closures are not supported",
+                                                diagnostics::E0028);
                 }
 
                 let bounds = conv_builtin_bounds(this.tcx(), &f.bounds, match f.sigil {
@@ -556,17 +699,22 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
                     // But don't emit the error if the user meant to do a trait anyway.
                     ast::DefTrait(..) => { },
                     _ if bounds.is_some() =>
-                        tcx.sess.span_err(ast_ty.span,
-                                          "kind bounds can only be used on trait types"),
+                        tcx.sess.span_err_with_code(ast_ty.span,
+                                                    "kind bounds can only be used on trait types",
+                                                    diagnostics::E0031),
                     _ => { },
                 }
                 match a_def {
                     ast::DefTrait(_) => {
                         let path_str = path_to_str(path);
-                        tcx.sess.span_err(
+                        tcx.sess.span_err_with_code(
                             ast_ty.span,
                             format!("reference to trait `{name}` where a type is expected; \
-                                    try `~{name}` or `&{name}`", name=path_str));
+                                    try `~{name}` or `&{name}`", name=path_str),
+                            diagnostics::E0029);
+                        tcx.sess.span_suggestion(ast_ty.span,
+                                                 "use a trait object",
+                                                 "~" + path_str);
                         ty::mk_err()
                     }
                     ast::DefTy(did) | ast::DefStruct(did) => {
@@ -574,7 +722,18 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
                     }
                     ast::DefTyParam(id, n) => {
                         check_path_args(tcx, path, NO_TPS | NO_REGIONS);
-                        ty::mk_param(tcx, n, id)
+                        if path.segments.len() == 2 {
+                            associated_type_of_param(this, ast_ty.span, id,
+                                                     path.segments.get(0).identifier,
+                                                     path.segments.get(1).identifier)
+                        } else {
+                            ty::mk_param(tcx, n, id)
+                        }
+                    }
+                    ast::DefSelfTy(id) if path.segments.len() == 2 => {
+                        check_path_args(tcx, path, NO_TPS | NO_REGIONS);
+                        associated_type_of_self(this, ast_ty.span, id,
+                                                path.segments.get(1).identifier)
                     }
                     ast::DefSelfTy(id) => {
                         // n.b.: resolve guarantees that the this type only appears in a
@@ -585,16 +744,18 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
                         ty::mk_self(tcx, did)
                     }
                     ast::DefMod(id) => {
-                        tcx.sess.span_fatal(ast_ty.span,
+                        tcx.sess.span_fatal_with_code(ast_ty.span,
                             format!("found module name used as a type: {}",
-                                    tcx.map.node_to_str(id.node)));
+                                    tcx.map.node_to_str(id.node)),
+                            diagnostics::E0123);
                     }
                     ast::DefPrimTy(_) => {
                         fail!("DefPrimTy arm missed in previous ast_ty_to_prim_ty call");
                     }
                     _ => {
-                        tcx.sess.span_fatal(ast_ty.span,
-                            format!("found value name used as a type: {:?}", a_def));
+                        tcx.sess.span_fatal_with_code(ast_ty.span,
+                            format!("found value name used as a type: {:?}", a_def),
+                            diagnostics::E0123);
                     }
                 }
             }
@@ -609,15 +770,19 @@ pub fn ast_ty_to_ty<AC:AstConv, RS:RegionScope>(
                                 ty::mk_vec(tcx, ast_ty_to_ty(this, rscope, ty),
                                            ty::VstoreFixed(i as uint)),
                             _ => {
-                                tcx.sess.span_fatal(
-                                    ast_ty.span, "expected constant expr for vector length");
+                                tcx.sess.span_fatal_with_code(
+                                    ast_ty.span, "expected constant expr for vector length",
+                                    diagnostics::E0124);
                             }
                         }
                     }
-                    Err(ref r) => {
-                        tcx.sess.span_fatal(
+                    Err(ref err) => {
+                        tcx.sess.span_err_with_code(
                             ast_ty.span,
-                            format!("expected constant expr for vector length: {}", *r));
+                            format!("expected constant expr for vector length: {}", err.msg),
+                            diagnostics::E0124);
+                        const_eval::note_const_eval_err(tcx, err);
+                        ty::mk_err()
                     }
                 }
             }
@@ -830,10 +995,10 @@ fn conv_builtin_bounds(tcx: &ty::ctxt, ast_bounds: &Option<OwnedSlice<ast::TyPar
                             }
                             _ => { }
                         }
-                        tcx.sess.span_fatal(
+                        tcx.sess.span_fatal_with_code(
                             b.path.span,
-                            format!("only the builtin traits can be used \
-                                  as closure or object bounds"));
+                            "only the builtin traits can be used as closure or object bounds",
+                            diagnostics::E0125);
                     }
                     ast::RegionTyParamBound => {
                         builtin_bounds.add(ty::BoundStatic);
@@ -10,6 +10,7 @@
 
 #![allow(non_camel_case_types)]
 
+use diagnostics;
 use middle::pat_util::{PatIdMap, pat_id_map, pat_is_binding, pat_is_const};
 use middle::ty;
 use middle::typeck::check::demand;
@@ -170,7 +171,7 @@ pub fn check_pat_variant(pcx: &pat_ctxt, pat: &ast::Pat, path: &ast::Path,
                         format!("mismatched types: expected `{}` but found {}",
                              e, actual)})},
                              Some(expected), ~"a structure pattern",
-                             None);
+                             None, diagnostics::E0032);
                     fcx.write_error(pat.id);
                     kind_name = "[error]";
                     arg_types = subpats.clone()
@@ -221,7 +222,7 @@ pub fn check_pat_variant(pcx: &pat_ctxt, pat: &ast::Pat, path: &ast::Path,
                     format!("mismatched types: expected `{}` but found {}",
                          e, actual)})},
                     Some(expected), ~"an enum or structure pattern",
-                    None);
+                    None, diagnostics::E0032);
             fcx.write_error(pat.id);
             kind_name = "[error]";
             arg_types = subpats.clone()
@@ -253,7 +254,7 @@ pub fn check_pat_variant(pcx: &pat_ctxt, pat: &ast::Pat, path: &ast::Path,
                          npat = subpats_len,
                          kind = kind_name,
                          narg = arg_len);
-            tcx.sess.span_err(pat.span, s);
+            tcx.sess.span_err_with_code(pat.span, s, diagnostics::E0044);
             error_happened = true;
         }
 
@@ -265,12 +266,13 @@ pub fn check_pat_variant(pcx: &pat_ctxt, pat: &ast::Pat, path: &ast::Path,
             }
         }
     } else if subpats_len > 0 {
-        tcx.sess.span_err(pat.span,
-                          format!("this pattern has \
-                                   {npat, plural, =1{# field} other{# fields}}, \
-                                   but the corresponding {kind} has no fields",
-                               npat = subpats_len,
-                               kind = kind_name));
+        tcx.sess.span_err_with_code(pat.span,
+                                    format!("this pattern has \
+                                             {npat, plural, =1{# field} other{# fields}}, \
+                                             but the corresponding {kind} has no fields",
+                                         npat = subpats_len,
+                                         kind = kind_name),
+                                    diagnostics::E0044);
         error_happened = true;
     }
 
@@ -312,9 +314,10 @@ pub fn check_struct_pat_fields(pcx: &pat_ctxt,
     for field in fields.iter() {
         match field_map.find_mut(&field.ident.name) {
             Some(&(_, true)) => {
-                tcx.sess.span_err(span,
+                tcx.sess.span_err_with_code(span,
                     format!("field `{}` bound twice in pattern",
-                            token::get_ident(field.ident)));
+                            token::get_ident(field.ident)),
+                    diagnostics::E0045);
             }
             Some(&(index, ref mut used)) => {
                 *used = true;
@@ -331,10 +334,11 @@ pub fn check_struct_pat_fields(pcx: &pat_ctxt,
                 // Check the pattern anyway, so that attempts to look
                 // up its type won't fail
                 check_pat(pcx, field.pat, ty::mk_err());
-                tcx.sess.span_err(span,
+                tcx.sess.span_err_with_code(span,
                     format!("struct `{}` does not have a field named `{}`",
                          name,
-                         token::get_ident(field.ident)));
+                         token::get_ident(field.ident)),
+                    diagnostics::E0046);
             }
         }
     }
@@ -346,9 +350,10 @@ pub fn check_struct_pat_fields(pcx: &pat_ctxt,
                 continue;
             }
 
-            tcx.sess.span_err(span,
-                              format!("pattern does not mention field `{}`",
-                                  token::get_name(field.name)));
+            tcx.sess.span_err_with_code(span,
+                                        format!("pattern does not mention field `{}`",
+                                            token::get_name(field.name)),
+                                        diagnostics::E0047);
         }
     }
 }
@@ -371,10 +376,11 @@ pub fn check_struct_pat(pcx: &pat_ctxt, pat_id: ast::NodeId, span: Span,
         }
         Some(&ast::DefStruct(..)) | Some(&ast::DefVariant(..)) => {
             let name = pprust::path_to_str(path);
-            tcx.sess.span_err(span,
-                              format!("mismatched types: expected `{}` but found `{}`",
-                                   fcx.infcx().ty_to_str(expected),
-                                   name));
+            tcx.sess.span_err_with_code(span,
+                                        format!("mismatched types: expected `{}` but found `{}`",
+                                             fcx.infcx().ty_to_str(expected),
+                                             name),
+                                        diagnostics::E0032);
         }
         _ => {
             tcx.sess.span_bug(span, "resolve didn't write in struct ID");
@@ -409,11 +415,12 @@ pub fn check_struct_like_enum_variant_pat(pcx: &pat_ctxt,
         }
         Some(&ast::DefStruct(..)) | Some(&ast::DefVariant(..)) => {
             let name = pprust::path_to_str(path);
-            tcx.sess.span_err(span,
-                              format!("mismatched types: expected `{}` but \
-                                    found `{}`",
-                                   fcx.infcx().ty_to_str(expected),
-                                   name));
+            tcx.sess.span_err_with_code(span,
+                                        format!("mismatched types: expected `{}` but \
+                                              found `{}`",
+                                             fcx.infcx().ty_to_str(expected),
+                                             name),
+                                        diagnostics::E0032);
         }
         _ => {
             tcx.sess.span_bug(span, "resolve didn't write in variant");
@@ -450,16 +457,19 @@ pub fn check_pat(pcx: &pat_ctxt, pat: &ast::Pat, expected: ty::t) {
         {
             // no-op
         } else if !ty::type_is_numeric(b_ty) && !ty::type_is_char(b_ty) {
-            tcx.sess.span_err(pat.span, "non-numeric type used in range");
+            tcx.sess.span_err_with_code(pat.span, "non-numeric type used in range",
+                                        diagnostics::E0048);
         } else {
             match valid_range_bounds(fcx.ccx, begin, end) {
                 Some(false) => {
-                    tcx.sess.span_err(begin.span,
-                        "lower range bound must be less than upper");
+                    tcx.sess.span_err_with_code(begin.span,
+                        "lower range bound must be less than upper",
+                        diagnostics::E0048);
                 },
                 None => {
-                    tcx.sess.span_err(begin.span,
-                        "mismatched types in range");
+                    tcx.sess.span_err_with_code(begin.span,
+                        "mismatched types in range",
+                        diagnostics::E0048);
                 },
                 _ => { },
             }
@@ -544,7 +554,7 @@ pub fn check_pat(pcx: &pat_ctxt, pat: &ast::Pat, expected: ty::t) {
                                     format!("mismatched types: expected `{}` but found {}",
                                          e, actual)})},
                                          Some(expected), ~"a structure pattern",
-                                         None);
+                                         None, diagnostics::E0032);
                 match tcx.def_map.borrow().find(&pat.id) {
                     Some(&ast::DefStruct(supplied_def_id)) => {
                          check_struct_pat(pcx,
@@ -599,7 +609,8 @@ pub fn check_pat(pcx: &pat_ctxt, pat: &ast::Pat, expected: ty::t) {
                 fcx.infcx().type_error_message_str_with_expected(pat.span, |expected, actual| {
                 expected.map_or(~"", |e| {
                     format!("mismatched types: expected `{}` but found {}",
-                                     e, actual)})}, Some(expected), ~"tuple", Some(&type_error));
+                                     e, actual)})}, Some(expected), ~"tuple", Some(&type_error),
+                                     diagnostics::E0032);
                 fcx.write_error(pat.id);
             }
         }
@@ -628,7 +639,7 @@ pub fn check_pat(pcx: &pat_ctxt, pat: &ast::Pat, expected: ty::t) {
                                               longer supported"
                                            },
                                            expected,
-                                           None);
+                                           None, diagnostics::E0119);
                     (ty, default_region_var, ast::MutImmutable)
                 }
                 ty::VstoreFixed(_) => {
@@ -655,7 +666,7 @@ pub fn check_pat(pcx: &pat_ctxt, pat: &ast::Pat, expected: ty::t) {
                                e, actual)})},
                   Some(expected),
                   ~"a vector pattern",
-                  None);
+                  None, diagnostics::E0032);
               fcx.write_error(pat.id);
               return;
           }
@@ -714,7 +725,7 @@ pub fn check_pointer_pat(pcx: &pat_ctxt,
                     Send => "a `~`-box",
                     Borrowed => "an `&`-pointer"
                 }),
-                None);
+                None, diagnostics::E0032);
             fcx.write_error(pat_id);
           }
     }
@@ -80,6 +80,7 @@ obtained the type `Foo`, we would never match this method.
 */
 
 
+use diagnostics;
 use middle::subst::Subst;
 use middle::ty::*;
 use middle::ty;
@@ -452,6 +453,7 @@ impl<'a> LookupContext<'a> {
                 }
                 _ => { /* No bound methods in these types */ }
             }
+            self.push_inherent_candidates_from_where_clauses(self_ty, restrict_to);
 
             // Don't autoderef if we aren't supposed to.
             if self.autoderef_receiver == DontAutoderefReceiver {
@@ -553,6 +555,25 @@ impl<'a> LookupContext<'a> {
     }
 
 
+    fn push_inherent_candidates_from_where_clauses(&mut self,
+                                                   rcvr_ty: ty::t,
+                                                   restrict_to: Option<DefId>) {
+        debug!("push_inherent_candidates_from_where_clauses(rcvr_ty={})",
+               rcvr_ty.repr(self.tcx()));
+        let fcx = self.fcx;
+        let rcvr_ty = fcx.infcx().resolve_type_vars_if_possible(rcvr_ty);
+        let param_env = &fcx.inh.param_env;
+        for &(n, where_ty) in param_env.where_tys.iter() {
+            if where_ty == rcvr_ty {
+                self.push_inherent_candidates_from_bounds(
+                    rcvr_ty,
+                    param_env.type_param_bounds.get(n).trait_bounds.as_slice(),
+                    restrict_to,
+                    param_numbered(n));
+            }
+        }
+    }
+
     fn push_inherent_candidates_from_self(&mut self,
                                           rcvr_ty: ty::t,
                                           restrict_to: Option<DefId>) {
@@ -996,9 +1017,10 @@ impl<'a> LookupContext<'a> {
         }
 
         if relevant_candidates.len() > 1 {
-            self.tcx().sess.span_err(
+            self.tcx().sess.span_err_with_code(
                 self.span,
-                "multiple applicable methods in scope");
+                "multiple applicable methods in scope",
+                diagnostics::E0040);
             for (idx, candidate) in relevant_candidates.iter().enumerate() {
                 self.report_candidate(idx, &candidate.origin);
             }
@@ -1078,24 +1100,30 @@ impl<'a> LookupContext<'a> {
         // Determine the values for the generic parameters of the method.
         // If they were not explicitly supplied, just construct fresh
         // variables.
+        // The parameters standing for associated types are always inferred.
         let num_supplied_tps = self.supplied_tps.len();
         let num_method_tps = candidate.method_ty.generics.type_param_defs().len();
+        let num_associated_tps = candidate.method_ty.generics.type_param_defs()
+                                          .iter().rev().take_while(|d| d.associated).len();
         let m_substs = {
             if num_supplied_tps == 0u {
                 self.fcx.infcx().next_ty_vars(num_method_tps)
-            } else if num_method_tps == 0u {
-                tcx.sess.span_err(
+            } else if num_method_tps == num_associated_tps {
+                tcx.sess.span_err_with_code(
                     self.span,
-                    "this method does not take type parameters");
+                    "this method does not take type parameters",
+                    diagnostics::E0011);
                 self.fcx.infcx().next_ty_vars(num_method_tps)
-            } else if num_supplied_tps != num_method_tps {
-                tcx.sess.span_err(
+            } else if num_supplied_tps != num_method_tps - num_associated_tps {
+                tcx.sess.span_err_with_code(
                     self.span,
                     "incorrect number of type \
-                     parameters given for this method");
+                     parameters given for this method",
+                    diagnostics::E0058);
                 self.fcx.infcx().next_ty_vars(num_method_tps)
             } else {
                 Vec::from_slice(self.supplied_tps)
+                    .append(self.fcx.infcx().next_ty_vars(num_associated_tps).as_slice())
             }
         };
 
@@ -1204,17 +1232,19 @@ impl<'a> LookupContext<'a> {
 
         match candidate.method_ty.explicit_self {
             ast::SelfStatic => { // reason (a) above
-                self.tcx().sess.span_err(
+                self.tcx().sess.span_err_with_code(
                     self.span,
                     "cannot call a method without a receiver \
-                     through an object");
+                     through an object",
+                    diagnostics::E0078);
             }
 
             ast::SelfValue => { // reason (a) above
-                self.tcx().sess.span_err(
+                self.tcx().sess.span_err_with_code(
                     self.span,
                     "cannot call a method with a by-value receiver \
-                     through an object");
+                     through an object",
+                    diagnostics::E0078);
             }
 
             ast::SelfRegion(..) | ast::SelfUniq => {}
@@ -1223,10 +1253,11 @@ impl<'a> LookupContext<'a> {
         // reason (a) above
         let check_for_self_ty = |ty| {
             if ty::type_has_self(ty) {
-                self.tcx().sess.span_err(
+                self.tcx().sess.span_err_with_code(
                     self.span,
                     "cannot call a method whose type contains a \
-                     self-type through an object");
+                     self-type through an object",
+                    diagnostics::E0078);
                 true
             } else {
                 false
@@ -1245,9 +1276,10 @@ impl<'a> LookupContext<'a> {
         }
 
         if candidate.method_ty.generics.has_type_params() { // reason (b) above
-            self.tcx().sess.span_err(
+            self.tcx().sess.span_err_with_code(
                 self.span,
-                "cannot call a generic method through an object");
+                "cannot call a generic method through an object",
+                diagnostics::E0078);
         }
     }
 
@@ -1268,8 +1300,9 @@ impl<'a> LookupContext<'a> {
         }
 
         if bad {
-            self.tcx().sess.span_err(self.span,
-                                     "explicit call to destructor");
+            self.tcx().sess.span_err_with_code(self.span,
+                                               "explicit call to destructor",
+                                               diagnostics::E0079);
         }
     }
 
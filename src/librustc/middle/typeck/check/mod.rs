@@ -77,6 +77,7 @@ type parameter).
 */
 
 
+use diagnostics;
 use middle::const_eval;
 use middle::lang_items::{ExchangeHeapLangItem, GcLangItem};
 use middle::lang_items::{ManagedHeapLangItem};
@@ -113,8 +114,10 @@ use util::common::{block_query, indenter, loop_query};
 use util::ppaux;
 use util::ppaux::{UserString, Repr};
 use util::nodemap::{FnvHashMap, NodeMap};
+use util::profile;
 
 use std::cell::{Cell, RefCell};
+use std::cmp;
 use collections::HashMap;
 use std::mem::replace;
 use std::result;
@@ -294,7 +297,8 @@ fn blank_inherited_fields<'a>(ccx: &'a CrateCtxt<'a>) -> Inherited<'a> {
     // and statement context, but we might as well do write the code only once
     let param_env = ty::ParameterEnvironment { free_substs: substs::empty(),
                                                self_param_bound: None,
-                                               type_param_bounds: Vec::new() };
+                                               type_param_bounds: Vec::new(),
+                                               where_tys: Vec::new() };
     Inherited::new(ccx.tcx, param_env)
 }
 
@@ -328,6 +332,10 @@ fn check_bare_fn(ccx: &CrateCtxt,
                  id: ast::NodeId,
                  fty: ty::t,
                  param_env: ty::ParameterEnvironment) {
+    let _activity = profile::activity("typeck", || {
+        ty::item_path_str(ccx.tcx, local_def(id))
+    });
+
     // Compute the fty from point of view of inside fn
     // (replace any type-scheme with a type)
     let fty = fty.subst(ccx.tcx, &param_env.free_substs);
@@ -607,14 +615,17 @@ pub fn check_item(ccx: &CrateCtxt, it: &ast::Item) {
             for item in m.items.iter() {
                 let tpt = ty::lookup_item_type(ccx.tcx, local_def(item.id));
                 if tpt.generics.has_type_params() {
-                    ccx.tcx.sess.span_err(item.span, "foreign items may not have type parameters");
+                    ccx.tcx.sess.span_err_with_code(
+                        item.span, "foreign items may not have type parameters",
+                        diagnostics::E0049);
                 }
 
                 match item.node {
                     ast::ForeignItemFn(ref fn_decl, _) => {
                         if fn_decl.variadic && m.abi != abi::C {
-                            ccx.tcx.sess.span_err(
-                                item.span, "variadic function must have C calling convention");
+                            ccx.tcx.sess.span_err_with_code(
+                                item.span, "variadic function must have C calling convention",
+                                diagnostics::E0030);
                         }
                     }
                     _ => {}
@@ -697,11 +708,12 @@ fn check_impl_methods_against_trait(ccx: &CrateCtxt,
                                     &impl_trait_ref.substs);
             }
             None => {
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     impl_method.span,
                     format!("method `{}` is not a member of trait `{}`",
                             token::get_ident(impl_method_ty.ident),
-                            pprust::path_to_str(&ast_trait_ref.path)));
+                            pprust::path_to_str(&ast_trait_ref.path)),
+                    diagnostics::E0050);
             }
         }
     }
@@ -724,10 +736,11 @@ fn check_impl_methods_against_trait(ccx: &CrateCtxt,
     }
 
     if !missing_methods.is_empty() {
-        tcx.sess.span_err(
+        tcx.sess.span_err_with_code(
             impl_span,
             format!("not all trait methods implemented, missing: {}",
-                    missing_methods.connect(", ")));
+                    missing_methods.connect(", ")),
+            diagnostics::E0051);
     }
 }
 
@@ -766,21 +779,23 @@ fn compare_impl_method(tcx: &ty::ctxt,
     match (&trait_m.explicit_self, &impl_m.explicit_self) {
         (&ast::SelfStatic, &ast::SelfStatic) => {}
         (&ast::SelfStatic, _) => {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 impl_m_span,
                 format!("method `{}` has a `{}` declaration in the impl, \
                         but not in the trait",
                         token::get_ident(trait_m.ident),
-                        pprust::explicit_self_to_str(impl_m.explicit_self)));
+                        pprust::explicit_self_to_str(impl_m.explicit_self)),
+                diagnostics::E0052);
             return;
         }
         (_, &ast::SelfStatic) => {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 impl_m_span,
                 format!("method `{}` has a `{}` declaration in the trait, \
                         but not in the impl",
                         token::get_ident(trait_m.ident),
-                        pprust::explicit_self_to_str(trait_m.explicit_self)));
+                        pprust::explicit_self_to_str(trait_m.explicit_self)),
+                diagnostics::E0052);
             return;
         }
         _ => {
@@ -791,7 +806,7 @@ fn compare_impl_method(tcx: &ty::ctxt,
     let num_impl_m_type_params = impl_m.generics.type_param_defs().len();
     let num_trait_m_type_params = trait_m.generics.type_param_defs().len();
     if num_impl_m_type_params != num_trait_m_type_params {
-        tcx.sess.span_err(
+        tcx.sess.span_err_with_code(
             impl_m_span,
             format!("method `{method}` has {nimpl, plural, =1{# type parameter} \
                                                         other{# type parameters}}, \
@@ -799,12 +814,13 @@ fn compare_impl_method(tcx: &ty::ctxt,
                                                                  other{# type parameters}}",
                     method = token::get_ident(trait_m.ident),
                     nimpl = num_impl_m_type_params,
-                    ntrait = num_trait_m_type_params));
+                    ntrait = num_trait_m_type_params),
+            diagnostics::E0053);
         return;
     }
 
     if impl_m.fty.sig.inputs.len() != trait_m.fty.sig.inputs.len() {
-        tcx.sess.span_err(
+        tcx.sess.span_err_with_code(
             impl_m_span,
             format!("method `{method}` has {nimpl, plural, =1{# parameter} \
                                                         other{# parameters}} \
@@ -812,7 +828,8 @@ fn compare_impl_method(tcx: &ty::ctxt,
                  method = token::get_ident(trait_m.ident),
                  nimpl = impl_m.fty.sig.inputs.len(),
                  trait = ty::item_path_str(tcx, trait_m.def_id),
-                 ntrait = trait_m.fty.sig.inputs.len()));
+                 ntrait = trait_m.fty.sig.inputs.len()),
+            diagnostics::E0054);
         return;
     }
 
@@ -826,7 +843,7 @@ fn compare_impl_method(tcx: &ty::ctxt,
             impl_param_def.bounds.builtin_bounds -
             trait_param_def.bounds.builtin_bounds;
         if !extra_bounds.is_empty() {
-           tcx.sess.span_err(
+           tcx.sess.span_err_with_code(
                impl_m_span,
                format!("in method `{}`, \
                        type parameter {} requires `{}`, \
@@ -835,7 +852,8 @@ fn compare_impl_method(tcx: &ty::ctxt,
                        in the trait declaration",
                        token::get_ident(trait_m.ident),
                        i,
-                       extra_bounds.user_string(tcx)));
+                       extra_bounds.user_string(tcx)),
+               diagnostics::E0055);
            return;
         }
 
@@ -845,7 +863,7 @@ fn compare_impl_method(tcx: &ty::ctxt,
         if impl_param_def.bounds.trait_bounds.len() !=
             trait_param_def.bounds.trait_bounds.len()
         {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 impl_m_span,
                 format!("in method `{method}`, \
                         type parameter {typaram} has \
@@ -856,7 +874,8 @@ fn compare_impl_method(tcx: &ty::ctxt,
                         method = token::get_ident(trait_m.ident),
                         typaram = i,
                         nimpl = impl_param_def.bounds.trait_bounds.len(),
-                        ntrait = trait_param_def.bounds.trait_bounds.len()));
+                        ntrait = trait_param_def.bounds.trait_bounds.len()),
+                diagnostics::E0055);
             return;
         }
     }
@@ -921,11 +940,12 @@ fn compare_impl_method(tcx: &ty::ctxt,
                           impl_fty, trait_fty) {
         result::Ok(()) => {}
         result::Err(ref terr) => {
-            tcx.sess.span_err(
+            tcx.sess.span_err_with_code(
                 impl_m_span,
                 format!("method `{}` has an incompatible type for trait: {}",
                         token::get_ident(trait_m.ident),
-                        ty::type_err_to_str(tcx, terr)));
+                        ty::type_err_to_str(tcx, terr)),
+                diagnostics::E0056);
             ty::note_and_explain_type_err(tcx, terr);
         }
     }
@@ -1159,8 +1179,9 @@ impl<'a> FnCtxt<'a> {
                               sp: Span,
                               mk_msg: |~str| -> ~str,
                               actual_ty: ty::t,
-                              err: Option<&ty::type_err>) {
-        self.infcx().type_error_message(sp, mk_msg, actual_ty, err);
+                              err: Option<&ty::type_err>,
+                              code: &str) {
+        self.infcx().type_error_message(sp, mk_msg, actual_ty, err, code);
     }
 
     pub fn report_mismatched_return_types(&self,
@@ -1234,9 +1255,10 @@ pub fn autoderef<T>(fcx: &FnCtxt, sp: Span, base_ty: ty::t,
     }
 
     // We've reached the recursion limit, error gracefully.
-    fcx.tcx().sess.span_err(sp,
+    fcx.tcx().sess.span_err_with_code(sp,
         format!("reached the recursion limit while auto-dereferencing {}",
-                base_ty.repr(fcx.tcx())));
+                base_ty.repr(fcx.tcx())),
+        diagnostics::E0057);
     (ty::mk_err(), 0, None)
 }
 
@@ -1441,17 +1463,19 @@ fn check_type_parameter_positions_in_path(function_context: &FnCtxt,
         for lifetime in path.segments.get(i).lifetimes.iter() {
             function_context.tcx()
                 .sess
-                .span_err(lifetime.span,
-                          "lifetime parameters may not \
-                          appear here");
+                .span_err_with_code(lifetime.span,
+                                    "lifetime parameters may not \
+                                    appear here",
+                                    diagnostics::E0011);
             break;
         }
 
         for typ in path.segments.get(i).types.iter() {
             function_context.tcx()
                             .sess
-                            .span_err(typ.span,
-                                      "type parameters may not appear here");
+                            .span_err_with_code(typ.span,
+                                                "type parameters may not appear here",
+                                                diagnostics::E0011);
             break;
         }
     }
@@ -1488,13 +1512,15 @@ fn check_type_parameter_positions_in_path(function_context: &FnCtxt,
                 && supplied_region_parameter_count != 0 {
                 function_context.tcx()
                     .sess
-                    .span_err(path.span,
-                              format!("expected {nexpected, plural, =1{# lifetime parameter} \
-                                                                 other{# lifetime parameters}}, \
-                                       found {nsupplied, plural, =1{# lifetime parameter} \
-                                                              other{# lifetime parameters}}",
-                                      nexpected = trait_region_parameter_count,
-                                      nsupplied = supplied_region_parameter_count));
+                    .span_err_with_code(
+                        path.span,
+                        format!("expected {nexpected, plural, =1{# lifetime parameter} \
+                                                           other{# lifetime parameters}}, \
+                                 found {nsupplied, plural, =1{# lifetime parameter} \
+                                                        other{# lifetime parameters}}",
+                                nexpected = trait_region_parameter_count,
+                                nsupplied = supplied_region_parameter_count),
+                        diagnostics::E0025);
             }
 
             // Make sure the number of type parameters supplied on the trait
@@ -1525,7 +1551,7 @@ fn check_type_parameter_positions_in_path(function_context: &FnCtxt,
                             nexpected = required_ty_param_count,
                             nsupplied = supplied_ty_param_count)
                 };
-                function_context.tcx().sess.span_err(path.span, msg)
+                function_context.tcx().sess.span_err_with_code(path.span, msg, diagnostics::E0058)
             } else if supplied_ty_param_count > formal_ty_param_count {
                 let msg = if required_ty_param_count < generics.type_param_defs().len() {
                     format!("the {trait_or_impl} referenced by this path needs at most \
@@ -1546,7 +1572,7 @@ fn check_type_parameter_positions_in_path(function_context: &FnCtxt,
                             nexpected = formal_ty_param_count,
                             nsupplied = supplied_ty_param_count)
                 };
-                function_context.tcx().sess.span_err(path.span, msg)
+                function_context.tcx().sess.span_err_with_code(path.span, msg, diagnostics::E0058)
             }
         }
         _ => {
@@ -1556,17 +1582,19 @@ fn check_type_parameter_positions_in_path(function_context: &FnCtxt,
             for lifetime in segment.lifetimes.iter() {
                 function_context.tcx()
                     .sess
-                    .span_err(lifetime.span,
-                              "lifetime parameters may not
-                              appear here");
+                    .span_err_with_code(lifetime.span,
+                                        "lifetime parameters may not
+                                        appear here",
+                                        diagnostics::E0011);
                 break;
             }
             for typ in segment.types.iter() {
                 function_context.tcx()
                                 .sess
-                                .span_err(typ.span,
-                                          "type parameters may not appear \
-                                           here");
+                                .span_err_with_code(typ.span,
+                                                    "type parameters may not appear \
+                                                     here",
+                                                    diagnostics::E0011);
                 break;
             }
         }
@@ -1655,7 +1683,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                      nexpected = expected_arg_count,
                      nsupplied = supplied_arg_count);
 
-                tcx.sess.span_err(sp, msg);
+                tcx.sess.span_err_with_code(sp, msg, diagnostics::E0059);
 
                 err_args(supplied_arg_count)
             }
@@ -1668,7 +1696,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                  nexpected = expected_arg_count,
                  nsupplied = supplied_arg_count);
 
-            tcx.sess.span_err(sp, msg);
+            tcx.sess.span_err_with_code(sp, msg, diagnostics::E0059);
 
             err_args(supplied_arg_count)
         };
@@ -1751,17 +1779,18 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                     ty::ty_float(ast::TyF32) => {
                         fcx.type_error_message(arg.span,
                                 |t| format!("can't pass an {} to variadic function, \
-                                             cast to c_double", t), arg_ty, None);
+                                             cast to c_double", t), arg_ty, None,
+                                diagnostics::E0109);
                     }
                     ty::ty_int(ast::TyI8) | ty::ty_int(ast::TyI16) | ty::ty_bool => {
                         fcx.type_error_message(arg.span,
                                 |t| format!("can't pass {} to variadic function, cast to c_int",
-                                            t), arg_ty, None);
+                                            t), arg_ty, None, diagnostics::E0109);
                     }
                     ty::ty_uint(ast::TyU8) | ty::ty_uint(ast::TyU16) => {
                         fcx.type_error_message(arg.span,
                                 |t| format!("can't pass {} to variadic function, cast to c_uint",
-                                            t), arg_ty, None);
+                                            t), arg_ty, None, diagnostics::E0109);
                     }
                     _ => {}
                 }
@@ -1808,7 +1837,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
             _ => {
                 fcx.type_error_message(call_expr.span, |actual| {
                     format!("expected function but \
-                          found `{}`", actual) }, fn_ty, None);
+                          found `{}`", actual) }, fn_ty, None, diagnostics::E0110);
                 &error_fn_sig
             }
         };
@@ -1866,7 +1895,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                            actual, token::get_ident(method_name))
                   },
                   expr_t,
-                  None);
+                  None, diagnostics::E0111);
 
                 // Add error type for the result
                 fcx.write_error(expr.id);
@@ -2013,7 +2042,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                 format!("binary operation `{}` cannot be applied \
                       to type `{}`",
                      ast_util::binop_to_str(op), actual)},
-                                   lhs_t, None)
+                                   lhs_t, None, diagnostics::E0112)
 
         }
 
@@ -2029,7 +2058,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                                                 actual)
                                    },
                                    lhs_t,
-                                   None);
+                                   None, diagnostics::E0112);
             check_expr(fcx, rhs);
             ty::mk_err()
         };
@@ -2075,7 +2104,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
             fcx.type_error_message(ex.span, |actual| {
                 format!("binary operation `{}` cannot be applied to type `{}`",
                     ast_util::binop_to_str(op), actual)
-            }, lhs_resolved_t, None)
+            }, lhs_resolved_t, None, diagnostics::E0112)
         })
     }
 
@@ -2090,7 +2119,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                         trait_did, [rhs_expr], DontAutoderefReceiver, || {
             fcx.type_error_message(ex.span, |actual| {
                 format!("cannot apply unary operator `{}` to type `{}`", op_str, actual)
-            }, rhs_t, None);
+            }, rhs_t, None, diagnostics::E0113);
         })
     }
 
@@ -2268,7 +2297,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                         format!("attempted to take value of method `{}` on type `{}`",
                                 token::get_name(field), actual)
                     },
-                    expr_t, None);
+                    expr_t, None, diagnostics::E0019);
 
                 tcx.sess.span_note(expr.span,
                     "maybe a missing `()` to call it? If not, try an anonymous function.");
@@ -2282,7 +2311,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                                  but no field with that name was found",
                                 token::get_name(field), actual)
                     },
-                    expr_t, None);
+                    expr_t, None, diagnostics::E0114);
             }
         }
 
@@ -2320,14 +2349,15 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                       |actual| {
                           format!("structure `{}` has no field named `{}`",
                                   actual, token::get_ident(field.ident.node))
-                    }, struct_ty, None);
+                    }, struct_ty, None, diagnostics::E0115);
                     error_happened = true;
                 }
                 Some((_, true)) => {
-                    tcx.sess.span_err(
+                    tcx.sess.span_err_with_code(
                         field.ident.span,
                         format!("field `{}` specified more than once",
-                            token::get_ident(field.ident.node)));
+                            token::get_ident(field.ident.node)),
+                        diagnostics::E0060);
                     error_happened = true;
                 }
                 Some((field_id, false)) => {
@@ -2364,10 +2394,11 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                     }
                 }
 
-                tcx.sess.span_err(span,
+                tcx.sess.span_err_with_code(span,
                     format!("missing {nfields, plural, =1{field} other{fields}}: {fields}",
                             nfields = missing_fields.len(),
-                            fields = missing_fields.connect(", ")));
+                            fields = missing_fields.connect(", ")),
+                    diagnostics::E0061);
              }
         }
 
@@ -2567,7 +2598,8 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                                            .require(GcLangItem) {
                                       Ok(id) => id,
                                       Err(msg) => {
-                                          tcx.sess.span_err(expr.span, msg);
+                                          tcx.sess.span_err_with_code(expr.span, msg,
+                                                                      diagnostics::E0062);
                                           ast::DefId {
                                               krate: ast::CRATE_NODE_ID,
                                               node: ast::DUMMY_NODE_ID,
@@ -2597,9 +2629,10 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
           }
 
           if !checked {
-              tcx.sess.span_err(expr.span,
-                                "only the managed heap and exchange heap are \
-                                 currently supported")
+              tcx.sess.span_err_with_code(expr.span,
+                                          "only the managed heap and exchange heap are \
+                                           currently supported",
+                                          diagnostics::E0063)
           }
       }
 
@@ -2630,7 +2663,8 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
 
         let tcx = fcx.tcx();
         if !ty::expr_is_lval(tcx, fcx.ccx.method_map, lhs) {
-            tcx.sess.span_err(lhs.span, "illegal left-hand side expression");
+            tcx.sess.span_err_with_code(lhs.span, "illegal left-hand side expression",
+                                        diagnostics::E0064);
         }
 
         // Overwrite result of check_binop...this preserves existing behavior
@@ -2686,13 +2720,14 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                                 };
                                 if is_newtype {
                                     // This is an obsolete struct deref
-                                    tcx.sess.span_err(expr.span,
+                                    tcx.sess.span_err_with_code(expr.span,
                                         "single-field tuple-structs can \
-                                         no longer be dereferenced");
+                                         no longer be dereferenced",
+                                        diagnostics::E0065);
                                 } else {
                                     fcx.type_error_message(expr.span, |actual| {
                                         format!("type `{}` cannot be dereferenced", actual)
-                                    }, oprnd_t, None);
+                                    }, oprnd_t, None, diagnostics::E0116);
                                 }
                                 ty::mk_err()
                             }
@@ -2787,9 +2822,10 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                                     ret_ty, ty::mk_nil()) {
             result::Ok(_) => { /* fall through */ }
             result::Err(_) => {
-                tcx.sess.span_err(
+                tcx.sess.span_err_with_code(
                     expr.span,
-                    "`return;` in function returning non-nil");
+                    "`return;` in function returning non-nil",
+                    diagnostics::E0066);
             }
           },
           Some(e) => {
@@ -2807,7 +2843,8 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
 
         let tcx = fcx.tcx();
         if !ty::expr_is_lval(tcx, fcx.ccx.method_map, lhs) {
-            tcx.sess.span_err(lhs.span, "illegal left-hand side expression");
+            tcx.sess.span_err_with_code(lhs.span, "illegal left-hand side expression",
+                                        diagnostics::E0064);
         }
 
         let lhs_ty = fcx.expr_ty(lhs);
@@ -2930,12 +2967,12 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                         fcx.type_error_message(expr.span, |actual| {
                             format!("cast from nil: `{}` as `{}`", actual,
                                  fcx.infcx().ty_to_str(t_1))
-                        }, t_e, None);
+                        }, t_e, None, diagnostics::E0117);
                     } else if ty::type_is_nil(t_1) {
                         fcx.type_error_message(expr.span, |actual| {
                             format!("cast to nil: `{}` as `{}`", actual,
                                  fcx.infcx().ty_to_str(t_1))
-                        }, t_e, None);
+                        }, t_e, None, diagnostics::E0117);
                     }
 
                     let t1 = structurally_resolved_type(fcx, e.span, t_1);
@@ -2955,11 +2992,13 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                         if ty::get(te).sty != ty::ty_uint(ast::TyU8) {
                             fcx.type_error_message(expr.span, |actual| {
                                 format!("only `u8` can be cast as `char`, not `{}`", actual)
-                            }, t_e, None);
+                            }, t_e, None, diagnostics::E0117);
                         }
                     } else if ty::get(t1).sty == ty::ty_bool {
-                        fcx.tcx().sess.span_err(expr.span,
-                                                "cannot cast as `bool`, compare with zero instead");
+                        fcx.tcx().sess.span_err_with_code(
+                            expr.span,
+                            "cannot cast as `bool`, compare with zero instead",
+                            diagnostics::E0067);
                     } else if type_is_region_ptr(fcx, expr.span, t_e) &&
                         type_is_unsafe_ptr(fcx, expr.span, t_1) {
 
@@ -3010,7 +3049,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                         fcx.type_error_message(expr.span, |actual| {
                             format!("non-scalar cast: `{}` as `{}`", actual,
                                  fcx.infcx().ty_to_str(t_1))
-                        }, t_e, None);
+                        }, t_e, None, diagnostics::E0117);
                     }
                 }
             }
@@ -3130,7 +3169,7 @@ fn check_expr_with_unifier(fcx: &FnCtxt,
                                                      actual)
                                                },
                                                base_t,
-                                               None);
+                                               None, diagnostics::E0118);
                       });
                       fcx.write_ty(id, ret_ty);
                   }
@@ -3156,7 +3195,7 @@ pub fn require_uint(fcx: &FnCtxt, sp: Span, t: ty::t) {
         fcx.type_error_message(sp, |actual| {
             format!("mismatched types: expected `uint` type but found `{}`",
                  actual)
-        }, t, None);
+        }, t, None, diagnostics::E0032);
     }
 }
 
@@ -3165,7 +3204,7 @@ pub fn require_integral(fcx: &FnCtxt, sp: Span, t: ty::t) {
         fcx.type_error_message(sp, |actual| {
             format!("mismatched types: expected integral type but found `{}`",
                  actual)
-        }, t, None);
+        }, t, None, diagnostics::E0032);
     }
 }
 
@@ -3374,10 +3413,11 @@ pub fn check_representable(tcx: &ty::ctxt,
     // caught by case 1.
     match ty::is_type_representable(tcx, rty) {
       ty::SelfRecursive => {
-        tcx.sess.span_err(
+        tcx.sess.span_err_with_code(
           sp, format!("illegal recursive {} type; \
                        wrap the inner value in a box to make it representable",
-                      designation));
+                      designation),
+          diagnostics::E0068);
       }
       ty::Representable | ty::ContainsRecursive => (),
     }
@@ -3399,35 +3439,38 @@ pub fn check_instantiable(tcx: &ty::ctxt,
                           item_id: ast::NodeId) {
     let item_ty = ty::node_id_to_type(tcx, item_id);
     if !ty::is_instantiable(tcx, item_ty) {
-        tcx.sess.span_err(sp, format!("this type cannot be instantiated \
+        tcx.sess.span_err_with_code(sp, format!("this type cannot be instantiated \
                   without an instance of itself; \
                   consider using `Option<{}>`",
-                                   ppaux::ty_to_str(tcx, item_ty)));
+                                   ppaux::ty_to_str(tcx, item_ty)),
+                                    diagnostics::E0069);
     }
 }
 
 pub fn check_simd(tcx: &ty::ctxt, sp: Span, id: ast::NodeId) {
     let t = ty::node_id_to_type(tcx, id);
     if ty::type_needs_subst(t) {
-        tcx.sess.span_err(sp, "SIMD vector cannot be generic");
+        tcx.sess.span_err_with_code(sp, "SIMD vector cannot be generic", diagnostics::E0070);
         return;
     }
     match ty::get(t).sty {
         ty::ty_struct(did, ref substs) => {
             let fields = ty::lookup_struct_fields(tcx, did);
             if fields.is_empty() {
-                tcx.sess.span_err(sp, "SIMD vector cannot be empty");
+                tcx.sess.span_err_with_code(sp, "SIMD vector cannot be empty", diagnostics::E0070);
                 return;
             }
             let e = ty::lookup_field_type(tcx, did, fields.get(0).id, substs);
             if !fields.iter().all(
                          |f| ty::lookup_field_type(tcx, did, f.id, substs) == e) {
-                tcx.sess.span_err(sp, "SIMD vector should be homogeneous");
+                tcx.sess.span_err_with_code(sp, "SIMD vector should be homogeneous",
+                                            diagnostics::E0070);
                 return;
             }
             if !ty::type_is_machine(e) {
-                tcx.sess.span_err(sp, "SIMD vector element type should be \
-                                       machine type");
+                tcx.sess.span_err_with_code(sp, "SIMD vector element type should be \
+                                                 machine type",
+                                            diagnostics::E0070);
                 return;
             }
         }
@@ -3503,10 +3546,15 @@ pub fn check_enum_variants(ccx: &CrateCtxt,
                         Ok(const_eval::const_int(val)) => current_disr_val = val as Disr,
                         Ok(const_eval::const_uint(val)) => current_disr_val = val as Disr,
                         Ok(_) => {
-                            ccx.tcx.sess.span_err(e.span, "expected signed integer constant");
+                            ccx.tcx.sess.span_err_with_code(
+                                e.span, "expected signed integer constant",
+                                diagnostics::E0071);
                         }
                         Err(ref err) => {
-                            ccx.tcx.sess.span_err(e.span, format!("expected constant: {}", *err));
+                            ccx.tcx.sess.span_err_with_code(
+                                e.span, format!("expected constant: {}", err.msg),
+                                diagnostics::E0071);
+                            const_eval::note_const_eval_err(ccx.tcx, err);
                         }
                     }
                 },
@@ -3515,15 +3563,17 @@ pub fn check_enum_variants(ccx: &CrateCtxt,
 
             // Check for duplicate discriminant values
             if disr_vals.contains(&current_disr_val) {
-                ccx.tcx.sess.span_err(v.span, "discriminant value already exists");
+                ccx.tcx.sess.span_err_with_code(v.span, "discriminant value already exists",
+                                                diagnostics::E0072);
             }
             // Check for unrepresentable discriminant values
             match hint {
                 attr::ReprAny | attr::ReprExtern => (),
                 attr::ReprInt(sp, ity) => {
                     if !disr_in_range(ccx, ity, current_disr_val) {
-                        ccx.tcx.sess.span_err(v.span,
-                                              "discriminant value outside specified type");
+                        ccx.tcx.sess.span_err_with_code(v.span,
+                                                        "discriminant value outside specified type",
+                                                        diagnostics::E0073);
                         ccx.tcx.sess.span_note(sp, "discriminant type specified here");
                     }
                 }
@@ -3546,7 +3596,7 @@ pub fn check_enum_variants(ccx: &CrateCtxt,
         } else {
             "unsupported representation for zero-variant enum"
         };
-        ccx.tcx.sess.span_err(sp, msg)
+        ccx.tcx.sess.span_err_with_code(sp, msg, diagnostics::E0074)
     }
 
     let variants = do_check(ccx, vs, id, hint);
@@ -3628,9 +3678,15 @@ pub fn instantiate_path(fcx: &FnCtxt,
     debug!(">>> instantiate_path");
 
     let ty_param_count = tpt.generics.type_param_defs().len();
+    // The parameters standing for the associated types of bounds come last
+    // and are always inferred.
+    let associated_count = tpt.generics.type_param_defs().iter().rev()
+                                                        .take_while(|x| x.associated)
+                                                        .len();
     let ty_param_req = tpt.generics.type_param_defs().iter()
                                                    .take_while(|x| x.default.is_none())
                                                    .len();
+    let ty_param_req = cmp::min(ty_param_req, ty_param_count - associated_count);
     let mut ty_substs_len = 0;
     for segment in pth.segments.iter() {
         ty_substs_len += segment.types.len()
@@ -3650,14 +3706,15 @@ pub fn instantiate_path(fcx: &FnCtxt,
             |l| ast_region_to_region(fcx.tcx(), l)).collect())
     } else {
         if num_supplied_regions != 0 {
-            fcx.ccx.tcx.sess.span_err(
+            fcx.ccx.tcx.sess.span_err_with_code(
                 span,
                 format!("expected {nexpected, plural, =1{# lifetime parameter} \
                                                    other{# lifetime parameters}}, \
                          found {nsupplied, plural, =1{# lifetime parameter} \
                                                 other{# lifetime parameters}}",
                         nexpected = num_expected_regions,
-                        nsupplied = num_supplied_regions));
+                        nsupplied = num_supplied_regions),
+                diagnostics::E0025);
         }
 
         fcx.infcx().region_vars_for_defs(span, tpt.generics.region_param_defs.as_slice())
@@ -3674,18 +3731,19 @@ pub fn instantiate_path(fcx: &FnCtxt,
         ast::DefStaticMethod(_, provenance @ ast::FromTrait(_), _) => {
             let generics = generics_of_static_method_container(fcx.ccx.tcx,
                                                                provenance);
-            (ty_param_count - 1, ty_param_req - 1, Some(generics.type_param_defs().len()))
+            (ty_param_count - associated_count - 1, ty_param_req - 1,
+             Some(generics.type_param_defs().len()))
         }
-        _ => (ty_param_count, ty_param_req, None),
+        _ => (ty_param_count - associated_count, ty_param_req, None),
     };
 
     // determine values for type parameters, using the values given by
     // the user (if any) and otherwise using fresh type variables
     let (tps, regions) = if ty_substs_len == 0 {
         (fcx.infcx().next_ty_vars(ty_param_count), regions)
-    } else if ty_param_count == 0 {
-        fcx.ccx.tcx.sess.span_err
-            (span, "this item does not take type parameters");
+    } else if ty_param_count == associated_count {
+        fcx.ccx.tcx.sess.span_err_with_code
+            (span, "this item does not take type parameters", diagnostics::E0011);
         (fcx.infcx().next_ty_vars(ty_param_count), regions)
     } else if ty_substs_len > user_ty_param_count {
         let expected = if user_ty_param_req < user_ty_param_count {
@@ -3693,10 +3751,11 @@ pub fn instantiate_path(fcx: &FnCtxt,
         } else {
             "expected"
         };
-        fcx.ccx.tcx.sess.span_err
+        fcx.ccx.tcx.sess.span_err_with_code
             (span,
              format!("too many type parameters provided: {} {}, found {}",
-                  expected, user_ty_param_count, ty_substs_len));
+                  expected, user_ty_param_count, ty_substs_len),
+             diagnostics::E0058);
         (fcx.infcx().next_ty_vars(ty_param_count), regions)
     } else if ty_substs_len < user_ty_param_req {
         let expected = if user_ty_param_req < user_ty_param_count {
@@ -3704,16 +3763,18 @@ pub fn instantiate_path(fcx: &FnCtxt,
         } else {
             "expected"
         };
-        fcx.ccx.tcx.sess.span_err
+        fcx.ccx.tcx.sess.span_err_with_code
             (span,
              format!("not enough type parameters provided: {} {}, found {}",
-                  expected, user_ty_param_req, ty_substs_len));
+                  expected, user_ty_param_req, ty_substs_len),
+             diagnostics::E0058);
         (fcx.infcx().next_ty_vars(ty_param_count), regions)
     } else {
         if ty_substs_len > user_ty_param_req
             && !fcx.tcx().sess.features.default_type_params.get() {
-            fcx.tcx().sess.span_err(pth.span, "default type parameters are \
-                                               experimental and possibly buggy");
+            fcx.tcx().sess.span_err_with_code(pth.span, "default type parameters are \
+                                                         experimental and possibly buggy",
+                                              diagnostics::E0026);
             fcx.tcx().sess.span_note(pth.span, "add #[feature(default_type_params)] \
                                                 to the crate attributes to enable");
         }
@@ -3746,10 +3807,10 @@ pub fn instantiate_path(fcx: &FnCtxt,
                           .enumerate().filter_map(|(i, x)| {
             match self_parameter_index {
                 Some(index) if index == i => None,
-                _ => Some(x.default)
+                _ => Some(x)
             }
         });
-        for (i, default) in defaults.skip(ty_substs_len).enumerate() {
+        for (i, def) in defaults.skip(ty_substs_len).enumerate() {
             match self_parameter_index {
                 Some(index) if index == i + ty_substs_len => {
                     substs.tps.push(*fcx.infcx().next_ty_vars(1).get(0));
@@ -3757,11 +3818,14 @@ pub fn instantiate_path(fcx: &FnCtxt,
                 }
                 _ => {}
             }
-            match default {
+            match def.default {
                 Some(default) => {
                     let ty = default.subst_spanned(fcx.tcx(), &substs, Some(span));
                     substs.tps.push(ty);
                 }
+                None if def.associated => {
+                    substs.tps.push(*fcx.infcx().next_ty_vars(1).get(0));
+                }
                 None => {
                     fcx.tcx().sess.span_bug(span,
                         "missing default for a not explicitely provided type param")
@@ -3797,7 +3861,7 @@ pub fn structurally_resolved_type(fcx: &FnCtxt, sp: Span, tp: ty::t) -> ty::t {
         _ => {
             fcx.type_error_message(sp, |_actual| {
                 ~"the type of this value must be known in this context"
-            }, tp, None);
+            }, tp, None, diagnostics::E0042);
             demand::suptype(fcx, sp, ty::mk_err(), tp);
             tp
         }
@@ -3936,9 +4000,10 @@ pub fn check_bounds_are_used(ccx: &CrateCtxt,
 
     for (i, b) in tps_used.iter().enumerate() {
         if !*b {
-            ccx.tcx.sess.span_err(
+            ccx.tcx.sess.span_err_with_code(
                 span, format!("type parameter `{}` is unused",
-                              token::get_ident(tps.get(i).ident)));
+                              token::get_ident(tps.get(i).ident)),
+                diagnostics::E0075);
         }
     }
 }
@@ -3974,9 +4039,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
                 (0, Vec::new(), ty::mk_nil())
             }
             op => {
-                tcx.sess.span_err(it.span,
-                                  format!("unrecognized atomic operation function: `{}`",
-                                       op));
+                tcx.sess.span_err_with_code(it.span,
+                                            format!("unrecognized atomic operation function: `{}`",
+                                                 op),
+                                            diagnostics::E0076);
                 return;
             }
         }
@@ -4213,9 +4279,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
                 ty::mk_tup(tcx, vec!(ty::mk_u64(), ty::mk_bool()))),
 
             ref other => {
-                tcx.sess.span_err(it.span,
-                                  format!("unrecognized intrinsic function: `{}`",
-                                       *other));
+                tcx.sess.span_err_with_code(it.span,
+                                            format!("unrecognized intrinsic function: `{}`",
+                                                 *other),
+                                            diagnostics::E0076);
                 return;
             }
         }
@@ -4231,9 +4298,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
     let i_ty = ty::lookup_item_type(ccx.tcx, local_def(it.id));
     let i_n_tps = i_ty.generics.type_param_defs().len();
     if i_n_tps != n_tps {
-        tcx.sess.span_err(it.span, format!("intrinsic has wrong number \
-                                         of type parameters: found {}, \
-                                         expected {}", i_n_tps, n_tps));
+        tcx.sess.span_err_with_code(it.span, format!("intrinsic has wrong number \
+                                                   of type parameters: found {}, \
+                                                   expected {}", i_n_tps, n_tps),
+                                    diagnostics::E0077);
     } else {
         require_same_types(
             tcx, None, false, it.span, i_ty.ty, fty,
@@ -9,6 +9,7 @@
 // except according to those terms.
 
 
+use diagnostics;
 use middle::ty;
 use middle::ty::{AutoAddEnv, AutoDerefRef, AutoObject, param_ty};
 use middle::ty_fold::TypeFolder;
@@ -33,6 +34,7 @@ use std::result;
 use syntax::ast;
 use syntax::ast_util;
 use syntax::codemap::Span;
+use syntax::parse::token;
 use syntax::print::pprust::expr_to_str;
 use syntax::visit;
 use syntax::visit::Visitor;
@@ -90,17 +92,29 @@ fn lookup_vtables(vcx: &VtableContext,
            type_param_defs.repr(vcx.tcx()),
            substs.repr(vcx.tcx()));
 
-    // We do this backwards for reasons discussed above.
+    // We do this backwards for reasons discussed above. The parameters
+    // standing for associated types are inferred from the bounds which
+    // mention them, so they are only resolved after all the others. Those
+    // standing for the types bounded by where clauses are the types they
+    // bound, which may mention any other parameter, so they come last.
     assert_eq!(substs.tps.len(), type_param_defs.len());
-    let mut result: Vec<vtable_param_res> =
-        substs.tps.iter()
-        .rev()
-        .zip(type_param_defs.rev_iter())
-        .map(|(ty, def)|
-            lookup_vtables_for_param(vcx, span, Some(substs),
-                                     &*def.bounds, *ty, is_early))
-        .collect();
-    result.reverse();
+    let mut results: Vec<Option<vtable_param_res>> =
+        Vec::from_fn(type_param_defs.len(), |_| None);
+    for &(associated, where_clause) in [(false, false), (true, false), (true, true)].iter() {
+        for i in range(0, type_param_defs.len()).rev() {
+            let def = &type_param_defs[i];
+            if def.associated != associated || def.where_ty.is_some() != where_clause {
+                continue;
+            }
+            for &where_ty in def.where_ty.iter() {
+                relate_where_ty(vcx, span, substs, where_ty, *substs.tps.get(i));
+            }
+            *results.get_mut(i) =
+                Some(lookup_vtables_for_param(vcx, span, Some(substs), Some(def),
+                                              &*def.bounds, *substs.tps.get(i), is_early));
+        }
+    }
+    let result: Vec<vtable_param_res> = results.move_iter().map(|r| r.unwrap()).collect();
 
     assert_eq!(substs.tps.len(), result.len());
     debug!("lookup_vtables result(\
@@ -115,10 +129,31 @@ fn lookup_vtables(vcx: &VtableContext,
     @result
 }
 
+fn relate_where_ty(vcx: &VtableContext,
+                   span: Span,
+                   substs: &ty::substs,
+                   where_ty: ty::t,
+                   ty: ty::t) {
+    /*!
+     * Infers the parameter standing for the type `where_ty` bounded by a
+     * where clause, whose value is `ty`, to be that type.
+     */
+
+    let where_ty = where_ty.subst(vcx.tcx(), substs);
+    match infer::mk_eqty(vcx.infcx, false, infer::Misc(span), where_ty, ty) {
+        result::Ok(()) => {}
+        result::Err(ref err) => {
+            vcx.infcx.report_mismatched_types(span, where_ty, ty, err);
+        }
+    }
+}
+
 fn lookup_vtables_for_param(vcx: &VtableContext,
                             span: Span,
                             // None for substs means the identity
                             substs: Option<&ty::substs>,
+                            // The parameter the bounds belong to, if any
+                            type_param_def: Option<&ty::TypeParameterDef>,
                             type_param_bounds: &ty::ParamBounds,
                             ty: ty::t,
                             is_early: bool) -> vtable_param_res {
@@ -150,11 +185,25 @@ fn lookup_vtables_for_param(vcx: &VtableContext,
         match lookup_vtable(vcx, span, ty, trait_ref, is_early) {
             Some(vtable) => param_result.push(vtable),
             None => {
-                vcx.tcx().sess.span_fatal(span,
+                vcx.tcx().sess.span_err_with_code(span,
                     format!("failed to find an implementation of \
                           trait {} for {}",
                          vcx.infcx.trait_ref_to_str(trait_ref),
-                         vcx.infcx.ty_to_str(ty)));
+                         vcx.infcx.ty_to_str(ty)),
+                    diagnostics::E0120);
+                // The bound may come from a where clause, far from the
+                // parameter itself, so say which parameter it constrains.
+                for def in type_param_def.iter() {
+                    let what = if def.where_ty.is_some() {
+                        "the where clause on"
+                    } else {
+                        "the bounds on the type parameter"
+                    };
+                    vcx.tcx().sess.span_note(span,
+                        format!("required by {} `{}`", what,
+                                token::get_ident(def.ident)));
+                }
+                vcx.tcx().sess.abort_if_errors();
             }
         }
         true
@@ -204,11 +253,32 @@ fn relate_trait_refs(vcx: &VtableContext,
                 !ty::trait_ref_contains_error(&r_exp_trait_ref)
             {
                 let tcx = vcx.tcx();
-                tcx.sess.span_err(span,
-                    format!("expected {}, but found {} ({})",
-                         ppaux::trait_ref_to_str(tcx, &r_exp_trait_ref),
-                         ppaux::trait_ref_to_str(tcx, &r_act_trait_ref),
-                         ty::type_err_to_str(tcx, err)));
+                let trait_def = ty::lookup_trait_def(tcx, exp_trait_ref.def_id);
+                let mismatched_associated_type =
+                    trait_def.generics.type_param_defs().iter()
+                             .zip(r_exp_trait_ref.substs.tps.iter())
+                             .zip(r_act_trait_ref.substs.tps.iter())
+                             .find(|&((def, &exp), &act)| def.associated && exp != act);
+                match mismatched_associated_type {
+                    Some(((def, &exp), &act)) => {
+                        tcx.sess.span_err_with_code(span,
+                            format!("type mismatch resolving the associated type `{}` \
+                                     of `{}`: expected `{}` but found `{}`",
+                                 token::get_ident(def.ident),
+                                 ty::item_path_str(tcx, exp_trait_ref.def_id),
+                                 vcx.infcx.ty_to_str(exp),
+                                 vcx.infcx.ty_to_str(act)),
+                            diagnostics::E0032);
+                    }
+                    None => {
+                        tcx.sess.span_err_with_code(span,
+                            format!("expected {}, but found {} ({})",
+                                 ppaux::trait_ref_to_str(tcx, &r_exp_trait_ref),
+                                 ppaux::trait_ref_to_str(tcx, &r_act_trait_ref),
+                                 ty::type_err_to_str(tcx, err)),
+                            diagnostics::E0032);
+                    }
+                }
             }
         }
     }
@@ -268,6 +338,25 @@ fn lookup_vtable(vcx: &VtableContext,
 
     if vtable_opt.is_some() { return vtable_opt; }
 
+    // The type may be bounded by a where clause, in which case the vtable
+    // is passed in with the parameter standing for it.
+    for &(n, where_ty) in vcx.param_env.where_tys.iter() {
+        if where_ty != ty {
+            continue;
+        }
+        let type_param_bounds: &[@ty::TraitRef] =
+            vcx.param_env
+               .type_param_bounds
+               .get(n)
+               .trait_bounds
+               .as_slice();
+        let vtable_opt = lookup_vtable_from_bounds(vcx, span,
+                                                   type_param_bounds,
+                                                   param_numbered(n),
+                                                   trait_ref);
+        if vtable_opt.is_some() { return vtable_opt; }
+    }
+
     // If we aren't a self type or param, or it was, but we didn't find it,
     // do a search.
     search_for_vtable(vcx, span, ty, trait_ref, is_early)
@@ -450,7 +539,8 @@ fn search_for_vtable(vcx: &VtableContext,
         1 => return Some(found.get(0).clone()),
         _ => {
             if !is_early {
-                vcx.tcx().sess.span_err(span, "multiple applicable methods in scope");
+                vcx.tcx().sess.span_err_with_code(span, "multiple applicable methods in scope",
+                                                  diagnostics::E0040);
             }
             return Some(found.get(0).clone());
         }
@@ -487,10 +577,11 @@ fn fixup_ty(vcx: &VtableContext,
     match resolve_type(vcx.infcx, ty, resolve_and_force_all_but_regions) {
         Ok(new_type) => Some(new_type),
         Err(e) if !is_early => {
-            tcx.sess.span_fatal(span,
+            tcx.sess.span_fatal_with_code(span,
                 format!("cannot determine a type \
                       for this bounded type parameter: {}",
-                     fixup_err_to_str(e)))
+                     fixup_err_to_str(e)),
+                diagnostics::E0126)
         }
         Err(_) => {
             None
@@ -547,8 +638,9 @@ pub fn early_resolve_expr(ex: &ast::Expr, fcx: &FnCtxt, is_early: bool) {
               match (&ty::get(ty).sty, store) {
                   (&ty::ty_rptr(_, mt), ty::RegionTraitStore(_, mutbl))
                     if !mutability_allowed(mt.mutbl, mutbl) => {
-                      fcx.tcx().sess.span_err(ex.span,
-                                              format!("types differ in mutability"));
+                      fcx.tcx().sess.span_err_with_code(ex.span,
+                                                        format!("types differ in mutability"),
+                                                        diagnostics::E0041);
                   }
 
                   (&ty::ty_uniq(..), ty::UniqTraitStore) |
@@ -577,6 +669,7 @@ pub fn early_resolve_expr(ex: &ast::Expr, fcx: &FnCtxt, is_early: bool) {
                             lookup_vtables_for_param(&vcx,
                                                      ex.span,
                                                      None,
+                                                     None,
                                                      &param_bounds,
                                                      typ,
                                                      is_early);
@@ -602,19 +695,21 @@ pub fn early_resolve_expr(ex: &ast::Expr, fcx: &FnCtxt, is_early: bool) {
                   }
 
                   (_, ty::UniqTraitStore) => {
-                      fcx.ccx.tcx.sess.span_err(
+                      fcx.ccx.tcx.sess.span_err_with_code(
                           ex.span,
                           format!("can only cast an ~-pointer \
                                 to a ~-object, not a {}",
-                               ty::ty_sort_str(fcx.tcx(), ty)));
+                               ty::ty_sort_str(fcx.tcx(), ty)),
+                          diagnostics::E0041);
                   }
 
                   (_, ty::RegionTraitStore(..)) => {
-                      fcx.ccx.tcx.sess.span_err(
+                      fcx.ccx.tcx.sess.span_err_with_code(
                           ex.span,
                           format!("can only cast an &-pointer \
                                 to an &-object, not a {}",
-                               ty::ty_sort_str(fcx.tcx(), ty)));
+                               ty::ty_sort_str(fcx.tcx(), ty)),
+                          diagnostics::E0041);
                   }
               }
           }
@@ -770,7 +865,7 @@ pub fn resolve_impl(tcx: &ty::ctxt,
     // We will need to make one so we can use this information
     // for compiling default methods that refer to supertraits.
     let self_vtable_res =
-        lookup_vtables_for_param(&vcx, impl_item.span, None,
+        lookup_vtables_for_param(&vcx, impl_item.span, None, None,
                                  &param_bounds, t, false);
 
 
@@ -13,6 +13,7 @@
 // substitutions.
 
 
+use diagnostics;
 use middle::pat_util;
 use middle::ty;
 use middle::typeck::astconv::AstConv;
@@ -40,11 +41,12 @@ fn resolve_type_vars_in_type(fcx: &FnCtxt, sp: Span, typ: ty::t)
         Ok(new_type) => return Some(new_type),
         Err(e) => {
             if !fcx.ccx.tcx.sess.has_errors() {
-                fcx.ccx.tcx.sess.span_err(
+                fcx.ccx.tcx.sess.span_err_with_code(
                     sp,
                     format!("cannot determine a type \
                           for this expression: {}",
-                         infer::fixup_err_to_str(e)))
+                         infer::fixup_err_to_str(e)),
+                    diagnostics::E0042)
             }
             return None;
         }
@@ -162,8 +164,9 @@ fn resolve_type_vars_for_node(wbcx: &mut WbCtxt, sp: Span, id: ast::NodeId)
                                 Some(&ast::DefStaticMethod(..)) |
                                 Some(&ast::DefVariant(..)) |
                                 Some(&ast::DefStruct(_)) => {}
-                                _ => tcx.sess.span_err(sp,
-                                        "cannot coerce non-statically resolved bare fn")
+                                _ => tcx.sess.span_err_with_code(sp,
+                                        "cannot coerce non-statically resolved bare fn",
+                                        diagnostics::E0043)
                             }
 
                             let resolved_adj = @ty::AutoAddEnv(r1, s);
@@ -318,11 +321,12 @@ fn visit_local(l: &ast::Local, wbcx: &mut WbCtxt) {
             write_ty_to_tcx(wbcx.fcx.ccx.tcx, l.id, lty);
         }
         Err(e) => {
-            wbcx.fcx.ccx.tcx.sess.span_err(
+            wbcx.fcx.ccx.tcx.sess.span_err_with_code(
                 l.span,
                 format!("cannot determine a type \
                       for this local variable: {}",
-                     infer::fixup_err_to_str(e)));
+                     infer::fixup_err_to_str(e)),
+                diagnostics::E0042);
             wbcx.success = false;
         }
     }
@@ -15,6 +15,7 @@
 // each trait in the system to its implementations.
 
 
+use diagnostics;
 use metadata::csearch::{each_impl, get_impl_trait, each_implementation_for_trait};
 use metadata::csearch;
 use middle::ty::get;
@@ -187,10 +188,11 @@ impl<'a> visit::Visitor<()> for PrivilegedScopeVisitor<'a> {
                 if !self.cc.ast_type_is_defined_in_local_crate(ast_ty) {
                     // This is an error.
                     let session = &self.cc.crate_context.tcx.sess;
-                    session.span_err(item.span,
-                                     "cannot associate methods with a type outside the \
-                                     crate the type is defined in; define and implement \
-                                     a trait or new type instead");
+                    session.span_err_with_code(item.span,
+                                               "cannot associate methods with a type outside the \
+                                               crate the type is defined in; define and implement \
+                                               a trait or new type instead",
+                                               diagnostics::E0036);
                 }
             }
             ItemImpl(_, Some(ref trait_ref), _, _) => {
@@ -208,9 +210,10 @@ impl<'a> visit::Visitor<()> for PrivilegedScopeVisitor<'a> {
 
                     if trait_def_id.krate != LOCAL_CRATE {
                         let session = &self.cc.crate_context.tcx.sess;
-                        session.span_err(item.span,
+                        session.span_err_with_code(item.span,
                                 "cannot provide an extension implementation \
-                                where both trait and type are not defined in this crate");
+                                where both trait and type are not defined in this crate",
+                                diagnostics::E0037);
                     }
                 }
 
@@ -265,9 +268,10 @@ impl<'a> CoherenceChecker<'a> {
                                        self_type.ty) {
                 None => {
                     let session = &self.crate_context.tcx.sess;
-                    session.span_err(item.span,
-                                     "no base type found for inherent implementation; \
-                                      implement a trait or new type instead");
+                    session.span_err_with_code(item.span,
+                                               "no base type found for inherent implementation; \
+                                                implement a trait or new type instead",
+                                               diagnostics::E0036);
                 }
                 Some(_) => {
                     // Nothing to do.
@@ -425,11 +429,12 @@ impl<'a> CoherenceChecker<'a> {
 
                     if self.polytypes_unify(polytype_a.clone(), polytype_b) {
                         let session = &self.crate_context.tcx.sess;
-                        session.span_err(
+                        session.span_err_with_code(
                             self.span_of_impl(implementation_a),
                             format!("conflicting implementations for trait `{}`",
                                  ty::item_path_str(self.crate_context.tcx,
-                                                   trait_def_id)));
+                                                   trait_def_id)),
+                            diagnostics::E0038);
                         if implementation_b.did.krate == LOCAL_CRATE {
                             session.span_note(self.span_of_impl(implementation_b),
                                               "note conflicting implementation here");
@@ -707,10 +712,11 @@ impl<'a> CoherenceChecker<'a> {
                         {
                             match tcx.map.find(impl_info.did.node) {
                                 Some(ast_map::NodeItem(item)) => {
-                                    tcx.sess.span_err((*item).span,
-                                                      "the Drop trait may \
-                                                       only be implemented \
-                                                       on structures");
+                                    tcx.sess.span_err_with_code((*item).span,
+                                                                "the Drop trait may \
+                                                                 only be implemented \
+                                                                 on structures",
+                                                                diagnostics::E0039);
                                 }
                                 _ => {
                                     tcx.sess.bug("didn't find impl in ast \
@@ -31,6 +31,7 @@ are represented as `ty_param()` instances.
 */
 
 
+use diagnostics;
 use metadata::csearch;
 use middle::resolve_lifetime;
 use middle::ty::{ImplContainer, MethodContainer, TraitContainer, substs};
@@ -57,7 +58,7 @@ use syntax::codemap::Span;
 use syntax::codemap;
 use syntax::parse::token::special_idents;
 use syntax::parse::token;
-use syntax::print::pprust::{path_to_str};
+use syntax::print::pprust::{path_to_str, ty_to_str};
 use syntax::visit;
 use syntax::owned_slice::OwnedSlice;
 
@@ -131,8 +132,9 @@ impl<'a> AstConv for CrateCtxt<'a> {
     }
 
     fn ty_infer(&self, span: Span) -> ty::t {
-        self.tcx.sess.span_err(span, "the type placeholder `_` is not \
-                                      allowed within types on item signatures.");
+        self.tcx.sess.span_err_with_code(span, "the type placeholder `_` is not \
+                                                allowed within types on item signatures.",
+                                         diagnostics::E0080);
         ty::mk_err()
     }
 }
@@ -346,7 +348,9 @@ pub fn ensure_trait_methods(ccx: &CrateCtxt, trait_id: ast::NodeId) {
                 builtin_bounds: ty::EmptyBuiltinBounds(),
                 trait_bounds: vec!(self_trait_ref)
             },
-            default: None
+            default: None,
+            associated: false,
+            where_ty: None
         });
 
         // add in the type parameters from the method
@@ -379,12 +383,14 @@ pub fn ensure_trait_methods(ccx: &CrateCtxt, trait_id: ast::NodeId) {
                                  m_fn_style: &ast::FnStyle,
                                  m_decl: &ast::FnDecl) -> ty::Method
     {
-        let trait_self_ty = ty::mk_self(this.tcx, local_def(trait_id));
-        let fty = astconv::ty_of_method(this, *m_id, *m_fn_style, trait_self_ty,
-                                        *m_explicit_self, m_decl);
+        // The generics come first, so that the signature can refer to the
+        // associated types of their bounds.
         let num_trait_type_params = trait_generics.type_param_defs().len();
         let ty_generics = ty_generics_for_fn_or_method(this, m_generics,
                                                        num_trait_type_params);
+        let trait_self_ty = ty::mk_self(this.tcx, local_def(trait_id));
+        let fty = astconv::ty_of_method(this, *m_id, *m_fn_style, trait_self_ty,
+                                        *m_explicit_self, m_decl);
         ty::Method::new(
             *m_ident,
             ty_generics,
@@ -419,14 +425,18 @@ pub fn ensure_supertraits(ccx: &CrateCtxt,
         // FIXME(#8559): Need to instantiate the trait_ref whether or not it's a
         // builtin trait, so that the trait's node id appears in the tcx trait_ref
         // map. This is only needed for metadata; see the similar fixme in encoder.rs.
-        let trait_ref = instantiate_trait_ref(ccx, ast_trait_ref, self_ty);
+        let trait_ref = instantiate_trait_ref(ccx, ast_trait_ref, self_ty, |_, def| {
+            astconv::unspecified_associated_type(ccx.tcx, ast_trait_ref.path.span,
+                                                 trait_def_id, def)
+        });
         if !ty::try_add_builtin_trait(ccx.tcx, trait_def_id, &mut bounds) {
 
             // FIXME(#5527) Could have same trait multiple times
             if ty_trait_refs.iter().any(|other_trait| other_trait.def_id == trait_ref.def_id) {
                 // This means a trait inherited from the same supertrait more
                 // than once.
-                tcx.sess.span_err(sp, "duplicate supertrait in trait declaration");
+                tcx.sess.span_err_with_code(sp, "duplicate supertrait in trait declaration",
+                                            diagnostics::E0081);
                 break;
             } else {
                 ty_trait_refs.push(trait_ref);
@@ -463,7 +473,8 @@ fn convert_methods(ccx: &CrateCtxt,
     let mut seen_methods = HashSet::new();
     for m in ms.iter() {
         if !seen_methods.insert(m.ident.repr(ccx.tcx)) {
-            tcx.sess.span_err(m.span, "duplicate method in trait impl");
+            tcx.sess.span_err_with_code(m.span, "duplicate method in trait impl",
+                                        diagnostics::E0082);
         }
 
         let num_rcvr_ty_params = rcvr_ty_generics.type_param_defs().len();
@@ -540,10 +551,11 @@ pub fn ensure_no_ty_param_bounds(ccx: &CrateCtxt,
                                  thing: &'static str) {
     for ty_param in generics.ty_params.iter() {
         if ty_param.bounds.len() > 0 {
-            ccx.tcx.sess.span_err(
+            ccx.tcx.sess.span_err_with_code(
                 span,
                 format!("trait bounds are not allowed in {} definitions",
-                     thing));
+                     thing),
+                diagnostics::E0083);
         }
     }
 }
@@ -554,8 +566,9 @@ fn ensure_generics_abi(ccx: &CrateCtxt,
                        generics: &ast::Generics) {
     if generics.ty_params.len() > 0 &&
        !(abi == abi::Rust || abi == abi::RustIntrinsic) {
-        ccx.tcx.sess.span_err(span,
-                              "foreign functions may not use type parameters");
+        ccx.tcx.sess.span_err_with_code(span,
+                                        "foreign functions may not use type parameters",
+                                        diagnostics::E0049);
     }
 }
 
@@ -604,13 +617,14 @@ pub fn convert(ccx: &CrateCtxt, it: &ast::Item) {
                             parent_visibility);
 
             for trait_ref in opt_trait_ref.iter() {
-                let trait_ref = instantiate_trait_ref(ccx, trait_ref, selfty);
+                let trait_ref = instantiate_impl_trait_ref(ccx, trait_ref, selfty);
 
                 // Prevent the builtin kind traits from being manually implemented.
                 if tcx.lang_items.to_builtin_kind(trait_ref.def_id).is_some() {
-                    tcx.sess.span_err(it.span,
+                    tcx.sess.span_err_with_code(it.span,
                         "cannot provide an explicit implementation \
-                         for a builtin kind");
+                         for a builtin kind",
+                        diagnostics::E0084);
                 }
             }
         },
@@ -727,12 +741,16 @@ pub fn convert_foreign(ccx: &CrateCtxt, i: &ast::ForeignItem) {
 
 pub fn instantiate_trait_ref(ccx: &CrateCtxt,
                              ast_trait_ref: &ast::TraitRef,
-                             self_ty: ty::t) -> @ty::TraitRef
+                             self_ty: ty::t,
+                             associated_ty: |&ty::substs, &ty::TypeParameterDef| -> ty::t)
+                             -> @ty::TraitRef
 {
     /*!
      * Instantiates the path for the given trait reference, assuming that
      * it's bound to a valid trait type. Returns the def_id for the defining
-     * trait. Fails if the type is a type other than a trait type.
+     * trait. Fails if the type is a type other than a trait type. The
+     * associated types of the trait which the path leaves out are supplied
+     * by `associated_ty`.
      */
 
     // FIXME(#5121) -- distinguish early vs late lifetime params
@@ -742,19 +760,79 @@ pub fn instantiate_trait_ref(ccx: &CrateCtxt,
         ast::DefTrait(trait_did) => {
             let trait_ref =
                 astconv::ast_path_to_trait_ref(
-                    ccx, &rscope, trait_did, Some(self_ty), &ast_trait_ref.path);
+                    ccx, &rscope, trait_did, Some(self_ty), &ast_trait_ref.path,
+                    associated_ty);
 
             ccx.tcx.trait_refs.borrow_mut().insert(ast_trait_ref.ref_id,
                                                    trait_ref);
             return trait_ref;
         }
         _ => {
-            ccx.tcx.sess.span_fatal(
+            ccx.tcx.sess.span_fatal_with_code(
                 ast_trait_ref.path.span,
                 format!("`{}` is not a trait",
-                    path_to_str(&ast_trait_ref.path)));
+                    path_to_str(&ast_trait_ref.path)),
+                diagnostics::E0127);
+        }
+    }
+}
+
+fn instantiate_impl_trait_ref(ccx: &CrateCtxt,
+                              ast_trait_ref: &ast::TraitRef,
+                              self_ty: ty::t) -> @ty::TraitRef
+{
+    /*!
+     * Instantiates the trait reference of an impl, taking its associated
+     * types from the `type Name = T;` bindings in the impl, or from their
+     * defaults in the trait.
+     */
+
+    let tcx = ccx.tcx;
+    let mut used = Vec::from_elem(ast_trait_ref.bindings.len(), false);
+    let trait_ref = instantiate_trait_ref(ccx, ast_trait_ref, self_ty, |substs, def| {
+        let mut binding = None;
+        for (i, b) in ast_trait_ref.bindings.iter().enumerate() {
+            if b.ident.name != def.ident.name {
+                continue;
+            }
+            if binding.is_some() {
+                tcx.sess.span_err_with_code(
+                    b.span,
+                    format!("the associated type `{}` is bound more than once",
+                            token::get_ident(b.ident)),
+                    diagnostics::E0104);
+            } else {
+                binding = Some(b.ty);
+            }
+            *used.get_mut(i) = true;
+        }
+        match (binding, def.default) {
+            (Some(ty), _) => ccx.to_ty(&ExplicitRscope, ty),
+            (None, Some(default)) => default.subst(tcx, substs),
+            (None, None) => {
+                tcx.sess.span_err_with_code(
+                    ast_trait_ref.path.span,
+                    format!("the associated type `{}` of trait `{}` is not bound \
+                             in this impl",
+                            token::get_ident(def.ident),
+                            path_to_str(&ast_trait_ref.path)),
+                    diagnostics::E0102);
+                ty::mk_err()
+            }
+        }
+    });
+
+    for (b, &used) in ast_trait_ref.bindings.iter().zip(used.iter()) {
+        if !used {
+            tcx.sess.span_err_with_code(
+                b.span,
+                format!("trait `{}` has no associated type `{}`",
+                        path_to_str(&ast_trait_ref.path),
+                        token::get_ident(b.ident)),
+                diagnostics::E0103);
         }
     }
+    trait_ref
 }
 
 fn get_trait_def(ccx: &CrateCtxt, trait_id: ast::DefId) -> @ty::TraitDef {
@@ -916,7 +994,8 @@ pub fn ty_of_foreign_item(ccx: &CrateCtxt,
 pub fn ty_generics_for_type(ccx: &CrateCtxt,
                             generics: &ast::Generics)
                             -> ty::Generics {
-    ty_generics(ccx, &generics.lifetimes, &generics.ty_params, 0)
+    ty_generics(ccx, &generics.lifetimes, &generics.ty_params,
+                generics.where_predicates.as_slice(), 0, false)
 }
 
 pub fn ty_generics_for_fn_or_method(ccx: &CrateCtxt,
@@ -924,45 +1003,152 @@ pub fn ty_generics_for_fn_or_method(ccx: &CrateCtxt,
                                     base_index: uint)
                                     -> ty::Generics {
     let early_lifetimes = resolve_lifetime::early_bound_lifetimes(generics);
-    ty_generics(ccx, &early_lifetimes, &generics.ty_params, base_index)
+    ty_generics(ccx, &early_lifetimes, &generics.ty_params,
+                generics.where_predicates.as_slice(), base_index, true)
 }
 
 pub fn ty_generics(ccx: &CrateCtxt,
                    lifetimes: &Vec<ast::Lifetime>,
                    ty_params: &OwnedSlice<ast::TyParam>,
-                   base_index: uint) -> ty::Generics {
+                   where_predicates: &[ast::WherePredicate],
+                   base_index: uint,
+                   infer_associated: bool) -> ty::Generics {
+    /*!
+     * Converts the generics of an item. With `infer_associated`, a bound
+     * `T: Trait` which leaves out the associated types of `Trait` gets a
+     * new type parameter for each of them, which is what `T::Name` refers
+     * to and which callers infer from the impl they use. Otherwise the
+     * associated types must be given in the bound.
+     *
+     * The bounds of a where clause are added to those of the parameter
+     * they name. With `infer_associated`, that is for functions and
+     * methods, a where clause can bound any other type as well, such as
+     * `Vec<T>: Show`, and gets a new type parameter standing for that type;
+     * elsewhere resolve has checked that it only names the parameters of
+     * this item, or their associated types.
+     */
+
+    let first_associated = base_index + ty_params.len();
+    let mut next_associated = first_associated;
+    let mut type_param_defs: Vec<ty::TypeParameterDef> =
+            ty_params.iter().enumerate().map(|(offset, param)| {
+        let existing_def_opt = {
+            let ty_param_defs = ccx.tcx.ty_param_defs.borrow();
+            ty_param_defs.find(&param.id).map(|&def| def)
+        };
+        existing_def_opt.unwrap_or_else(|| {
+            let param_ty = ty::param_ty {idx: base_index + offset,
+                                         def_id: local_def(param.id)};
+            let mut bounds = compute_bounds(ccx, param_ty, param.ident, &param.bounds,
+                                            if infer_associated {
+                                                Some(&mut next_associated)
+                                            } else {
+                                                None
+                                            });
+            for predicate in where_predicates.iter() {
+                if where_subject(ccx, predicate) == Some((param.id, false)) {
+                    let where_bounds = compute_bounds(ccx, param_ty, param.ident,
+                                                      &predicate.bounds,
+                                                      if infer_associated {
+                                                          Some(&mut next_associated)
+                                                      } else {
+                                                          None
+                                                      });
+                    add_bounds(&mut bounds, where_bounds);
+                }
+            }
+            let default = param.default.map(|x| ast_ty_to_ty(ccx, &ExplicitRscope, x));
+            let def = ty::TypeParameterDef {
+                ident: param.ident,
+                def_id: local_def(param.id),
+                bounds: @bounds,
+                default: default,
+                associated: param.associated,
+                where_ty: None
+            };
+            debug!("def for param: {}", def.repr(ccx.tcx));
+            ccx.tcx.ty_param_defs.borrow_mut().insert(param.id, def);
+
+            // Now that `T` is defined, `T::Name` can be converted too.
+            for predicate in where_predicates.iter() {
+                if where_subject(ccx, predicate) == Some((param.id, true)) {
+                    bound_associated_type(ccx, predicate, first_associated,
+                                          if infer_associated {
+                                              Some(&mut next_associated)
+                                          } else {
+                                              None
+                                          });
+                }
+            }
+            def
+        })
+    }).collect();
+
+    // The parameters standing for associated types come last. They are
+    // found again through the bounds which mention them, so that converting
+    // the same generics twice gives the same parameters.
+    let mut associated = Vec::new();
+    let mut unscanned: Vec<@ty::ParamBounds> =
+        type_param_defs.iter().map(|def| def.bounds).collect();
+    while !unscanned.is_empty() {
+        let bounds = unscanned.pop().unwrap();
+        for trait_ref in bounds.trait_bounds.iter() {
+            for &ty in trait_ref.substs.tps.iter() {
+                match ty::get(ty).sty {
+                    ty::ty_param(p) if p.idx >= first_associated &&
+                                       !associated.contains(&(p.idx, p.def_id)) => {
+                        associated.push((p.idx, p.def_id));
+                        let def = ccx.tcx.ty_param_defs.borrow().get_copy(&p.def_id.node);
+                        unscanned.push(def.bounds);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    associated.sort();
+    for &(_, def_id) in associated.iter() {
+        type_param_defs.push(ccx.tcx.ty_param_defs.borrow().get_copy(&def_id.node));
+    }
+
+    // The parameters standing for the types bounded by where clauses come
+    // after all the others. They are keyed by the bounded type, so that
+    // converting the same generics twice gives the same parameters.
+    if infer_associated {
+        for predicate in where_predicates.iter() {
+            let ty = ast_ty_to_ty(ccx, &ExplicitRscope, predicate.ty);
+            match ty::get(ty).sty {
+                // A parameter of this item, or one of its associated types,
+                // whose bounds already include those of the where clause.
+                ty::ty_param(p) if p.idx >= base_index => continue,
+                ty::ty_err => continue,
+                _ => {}
+            }
+            let existing_def_opt = {
+                let ty_param_defs = ccx.tcx.ty_param_defs.borrow();
+                ty_param_defs.find(&predicate.ty.id).map(|&def| def)
+            };
+            let idx = base_index + type_param_defs.len();
+            type_param_defs.push(existing_def_opt.unwrap_or_else(|| {
+                where_type_param(ccx, idx, predicate, ty)
+            }));
+        }
+    }
+
     return ty::Generics {
         region_param_defs: Rc::new(lifetimes.iter().map(|l| {
                 ty::RegionParameterDef { name: l.name,
                                          def_id: local_def(l.id) }
             }).collect()),
-        type_param_defs: Rc::new(ty_params.iter().enumerate().map(|(offset, param)| {
-            let existing_def_opt = {
-                let ty_param_defs = ccx.tcx.ty_param_defs.borrow();
-                ty_param_defs.find(&param.id).map(|&def| def)
-            };
-            existing_def_opt.unwrap_or_else(|| {
-                let param_ty = ty::param_ty {idx: base_index + offset,
-                                             def_id: local_def(param.id)};
-                let bounds = @compute_bounds(ccx, param_ty, &param.bounds);
-                let default = param.default.map(|x| ast_ty_to_ty(ccx, &ExplicitRscope, x));
-                let def = ty::TypeParameterDef {
-                    ident: param.ident,
-                    def_id: local_def(param.id),
-                    bounds: bounds,
-                    default: default
-                };
-                debug!("def for param: {}", def.repr(ccx.tcx));
-                ccx.tcx.ty_param_defs.borrow_mut().insert(param.id, def);
-                def
-            })
-        }).collect()),
+        type_param_defs: Rc::new(type_param_defs),
     };
 
     fn compute_bounds(
         ccx: &CrateCtxt,
         param_ty: ty::param_ty,
-        ast_bounds: &OwnedSlice<ast::TyParamBound>) -> ty::ParamBounds
+        param_ident: ast::Ident,
+        ast_bounds: &OwnedSlice<ast::TyParamBound>,
+        mut next_associated: Option<&mut uint>) -> ty::ParamBounds
     {
         /*!
          * Translate the AST's notion of ty param bounds (which are an
@@ -979,7 +1165,20 @@ pub fn ty_generics(ccx: &CrateCtxt,
             match *ast_bound {
                 TraitTyParamBound(ref b) => {
                     let ty = ty::mk_param(ccx.tcx, param_ty.idx, param_ty.def_id);
-                    let trait_ref = instantiate_trait_ref(ccx, b, ty);
+                    let trait_ref = instantiate_trait_ref(ccx, b, ty, |substs, def| {
+                        match next_associated {
+                            Some(ref mut next) => {
+                                let idx = **next;
+                                **next += 1;
+                                associated_type_param(ccx, idx, param_ident, substs, def)
+                            }
+                            None => {
+                                let trait_did = ty::trait_ref_to_def_id(ccx.tcx, b);
+                                astconv::unspecified_associated_type(ccx.tcx, b.path.span,
+                                                                     trait_did, def)
+                            }
+                        }
+                    });
                     if !ty::try_add_builtin_trait(
                         ccx.tcx, trait_ref.def_id,
                         &mut param_bounds.builtin_bounds)
@@ -997,6 +1196,125 @@ pub fn ty_generics(ccx: &CrateCtxt,
 
         param_bounds
     }
+
+    fn where_subject(ccx: &CrateCtxt,
+                     predicate: &ast::WherePredicate) -> Option<(ast::NodeId, bool)> {
+        /*!
+         * Returns the type parameter `T` which `predicate` bounds, and
+         * whether it bounds one of its associated types, `T::Name`, rather
+         * than `T` itself.
+         */
+
+        match predicate.ty.node {
+            ast::TyPath(ref path, _, path_id) => {
+                match ccx.tcx.def_map.borrow().find(&path_id) {
+                    Some(&ast::DefTyParam(did, _)) if did.krate == ast::LOCAL_CRATE => {
+                        Some((did.node, path.segments.len() == 2))
+                    }
+                    _ => None
+                }
+            }
+            _ => None
+        }
+    }
+
+    fn add_bounds(bounds: &mut ty::ParamBounds, other: ty::ParamBounds) {
+        bounds.builtin_bounds = bounds.builtin_bounds.union(other.builtin_bounds);
+        bounds.trait_bounds.push_all_move(other.trait_bounds);
+    }
+
+    fn bound_associated_type(ccx: &CrateCtxt,
+                             predicate: &ast::WherePredicate,
+                             first_associated: uint,
+                             next_associated: Option<&mut uint>) {
+        /*!
+         * Adds the bounds of the where clause `T::Name: Bounds` to the type
+         * parameter standing for `T::Name`.
+         */
+
+        let ty = ast_ty_to_ty(ccx, &ExplicitRscope, predicate.ty);
+        match ty::get(ty).sty {
+            ty::ty_param(p) if p.idx >= first_associated => {
+                let mut def = ccx.tcx.ty_param_defs.borrow().get_copy(&p.def_id.node);
+                let mut bounds = ty::ParamBounds {
+                    builtin_bounds: def.bounds.builtin_bounds,
+                    trait_bounds: def.bounds.trait_bounds.clone()
+                };
+                add_bounds(&mut bounds, compute_bounds(ccx, p, def.ident, &predicate.bounds,
+                                                       next_associated));
+                def.bounds = @bounds;
+                ccx.tcx.ty_param_defs.borrow_mut().insert(p.def_id.node, def);
+            }
+            ty::ty_err => {}
+            // Functions and methods can bound other types than their
+            // parameters; the where clause gets a parameter of its own.
+            _ if next_associated.is_some() => {}
+            _ => {
+                ccx.tcx.sess.span_err_with_code(
+                    predicate.ty.span,
+                    format!("`{}` is `{}` here, so it can't be bounded by a where clause",
+                            ty_to_str(predicate.ty),
+                            ppaux::ty_to_str(ccx.tcx, ty)),
+                    diagnostics::E0108);
+            }
+        }
+    }
+
+    fn where_type_param(ccx: &CrateCtxt,
+                        idx: uint,
+                        predicate: &ast::WherePredicate,
+                        ty: ty::t) -> ty::TypeParameterDef {
+        /*!
+         * Creates the type parameter standing for the type `ty` bounded by
+         * the where clause `predicate`, which callers infer to be `ty`. The
+         * associated types of its bounds must be given. The parameter is
+         * named `__whereN`, as the name is written to metadata, which can't
+         * hold the `::` of a path; diagnostics print `ty` instead.
+         */
+
+        let def_id = local_def(predicate.ty.id);
+        let param_ty = ty::param_ty { idx: idx, def_id: def_id };
+        let ident = token::str_to_ident(format!("__where{}", idx));
+        let bounds = compute_bounds(ccx, param_ty, ident, &predicate.bounds, None);
+        let param_def = ty::TypeParameterDef {
+            ident: ident,
+            def_id: def_id,
+            bounds: @bounds,
+            default: None,
+            associated: true,
+            where_ty: Some(ty)
+        };
+        debug!("def for where clause param: {}", param_def.repr(ccx.tcx));
+        ccx.tcx.ty_param_defs.borrow_mut().insert(predicate.ty.id, param_def);
+        param_def
+    }
+
+    fn associated_type_param(ccx: &CrateCtxt,
+                             idx: uint,
+                             param_ident: ast::Ident,
+                             substs: &ty::substs,
+                             def: &ty::TypeParameterDef) -> ty::t {
+        /*!
+         * Creates the type parameter standing for the associated type `def`
+         * of a bound on the parameter `param_ident`, which is known to the
+         * user as `param_ident::Name`.
+         */
+
+        let def_id = local_def(ccx.tcx.sess.next_node_id());
+        let name = format!("{}::{}", token::get_ident(param_ident),
+                           token::get_ident(def.ident));
+        let param_def = ty::TypeParameterDef {
+            ident: token::str_to_ident(name),
+            def_id: def_id,
+            bounds: def.bounds.subst(ccx.tcx, substs),
+            default: None,
+            associated: true,
+            where_ty: None
+        };
+        debug!("def for associated type param: {}", param_def.repr(ccx.tcx));
+        ccx.tcx.ty_param_defs.borrow_mut().insert(def_id.node, param_def);
+        ty::mk_param(ccx.tcx, idx, def_id)
+    }
 }
 
 pub fn ty_of_foreign_fn_decl(ccx: &CrateCtxt,
@@ -1010,8 +1328,9 @@ pub fn ty_of_foreign_fn_decl(ccx: &CrateCtxt,
         match (*i).pat.node {
             ast::PatIdent(_, _, _) => (),
             ast::PatWild => (),
-            _ => ccx.tcx.sess.span_err((*i).pat.span,
-                    "patterns aren't allowed in foreign function declarations")
+            _ => ccx.tcx.sess.span_err_with_code((*i).pat.span,
+                    "patterns aren't allowed in foreign function declarations",
+                    diagnostics::E0085)
         }
     }
 
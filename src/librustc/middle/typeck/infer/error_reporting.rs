@@ -60,6 +60,7 @@ time of error detection.
 */
 
 use collections::HashSet;
+use diagnostics;
 use middle::ty;
 use middle::ty::{Region, ReFree};
 use middle::typeck::infer;
@@ -336,12 +337,13 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
             infer::IfExpression(_) => "if and else have incompatible types",
         };
 
-        self.tcx.sess.span_err(
+        self.tcx.sess.span_err_with_code(
             trace.origin.span(),
             format!("{}: {} ({})",
                  message_root_str,
                  expected_found_str,
-                 ty::type_err_to_str(self.tcx, terr)));
+                 ty::type_err_to_str(self.tcx, terr)),
+            diagnostics::E0032);
     }
 
     fn report_and_explain_type_error(&self,
@@ -396,10 +398,11 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                 self.report_and_explain_type_error(trace, &terr);
             }
             infer::Reborrow(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     "lifetime of reference outlines \
-                     lifetime of borrowed content...");
+                     lifetime of borrowed content...",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "...the reference is valid for ",
@@ -412,11 +415,12 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::ReborrowUpvar(span, ref upvar_id) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     format!("lifetime of borrowed pointer outlives \
                             lifetime of captured variable `{}`...",
-                            ty::local_var_name_str(self.tcx, upvar_id.var_id).get().to_str()));
+                            ty::local_var_name_str(self.tcx, upvar_id.var_id).get().to_str()),
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "...the borrowed pointer is valid for ",
@@ -430,9 +434,10 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::InfStackClosure(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    "closure outlives stack frame");
+                    "closure outlives stack frame",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "...the closure must be valid for ",
@@ -445,9 +450,10 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::InvokeClosure(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    "cannot invoke closure outside of its lifetime");
+                    "cannot invoke closure outside of its lifetime",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the closure is only valid for ",
@@ -455,9 +461,10 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::DerefPointer(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    "dereference of reference outside its lifetime");
+                    "dereference of reference outside its lifetime",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the reference is only valid for ",
@@ -465,11 +472,12 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::FreeVariable(span, id) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     format!("captured variable `{}` does not \
                             outlive the enclosing closure",
-                            ty::local_var_name_str(self.tcx, id).get().to_str()));
+                            ty::local_var_name_str(self.tcx, id).get().to_str()),
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "captured variable is valid for ",
@@ -482,9 +490,10 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::IndexSlice(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    format!("index of slice outside its lifetime"));
+                    format!("index of slice outside its lifetime"),
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the slice is only valid for ",
@@ -492,10 +501,11 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::RelateObjectBound(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     "lifetime of the source pointer does not outlive \
-                     lifetime bound of the object type");
+                     lifetime bound of the object type",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "object type is valid for ",
@@ -508,10 +518,11 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::CallRcvr(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     "lifetime of method receiver does not outlive \
-                     the method call");
+                     the method call",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the receiver is only valid for ",
@@ -519,10 +530,11 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::CallArg(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     "lifetime of function argument does not outlive \
-                     the function call");
+                     the function call",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the function argument is only valid for ",
@@ -530,10 +542,11 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::CallReturn(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     "lifetime of return value does not outlive \
-                     the function call");
+                     the function call",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the return value is only valid for ",
@@ -541,10 +554,11 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::AddrOf(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     "reference is not valid \
-                     at the time of borrow");
+                     at the time of borrow",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the borrow is only valid for ",
@@ -552,10 +566,11 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::AutoBorrow(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     "automatically reference is not valid \
-                     at the time of borrow");
+                     at the time of borrow",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the automatic borrow is only valid for ",
@@ -563,9 +578,10 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::BindingTypeIsNotValidAtDecl(span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
-                    "lifetime of variable does not enclose its declaration");
+                    "lifetime of variable does not enclose its declaration",
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the variable is only valid for ",
@@ -573,11 +589,12 @@ impl<'a> ErrorReporting for InferCtxt<'a> {
                     "");
             }
             infer::ReferenceOutlivesReferent(ty, span) => {
-                self.tcx.sess.span_err(
+                self.tcx.sess.span_err_with_code(
                     span,
                     format!("in type `{}`, pointer has a longer lifetime than \
                           the data it references",
-                         ty.user_string(self.tcx)));
+                         ty.user_string(self.tcx)),
+                    diagnostics::E0034);
                 note_and_explain_region(
                     self.tcx,
                     "the pointer is valid for ",
@@ -812,6 +829,7 @@ impl<'a> Rebuilder<'a> {
                 id: ty_param.id,
                 bounds: bounds,
                 default: ty_param.default,
+                associated: ty_param.associated,
             }
         })
     }
@@ -843,6 +861,7 @@ impl<'a> Rebuilder<'a> {
                     ast::TraitTyParamBound(ast::TraitRef {
                         path: new_path,
                         ref_id: tr.ref_id,
+                        bindings: tr.bindings.clone(),
                     })
                 }
             }
@@ -866,7 +885,8 @@ impl<'a> Rebuilder<'a> {
         }
         ast::Generics {
             lifetimes: lifetimes,
-            ty_params: ty_params
+            ty_params: ty_params,
+            where_predicates: generics.where_predicates.clone()
         }
     }
 
@@ -1155,11 +1175,12 @@ impl<'a> ErrorReportingHelpers for InferCtxt<'a> {
             }
         };
 
-        self.tcx.sess.span_err(
+        self.tcx.sess.span_err_with_code(
             var_origin.span(),
             format!("cannot infer an appropriate lifetime{} \
                     due to conflicting requirements",
-                    var_description));
+                    var_description),
+            diagnostics::E0035);
     }
 
     fn note_region_origin(&self, origin: SubregionOrigin) {
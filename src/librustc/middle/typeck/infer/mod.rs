@@ -23,6 +23,7 @@ pub use middle::typeck::infer::resolve::{resolve_rvar};
 
 use collections::HashMap;
 use collections::SmallIntMap;
+use diagnostics;
 use middle::ty::{TyVid, IntVid, FloatVid, RegionVid, Vid};
 use middle::ty;
 use middle::ty_fold;
@@ -714,8 +715,9 @@ impl<'a> InferCtxt<'a> {
                                   sp: Span,
                                   mk_msg: |Option<~str>, ~str| -> ~str,
                                   actual_ty: ~str,
-                                  err: Option<&ty::type_err>) {
-        self.type_error_message_str_with_expected(sp, mk_msg, None, actual_ty, err)
+                                  err: Option<&ty::type_err>,
+                                  code: &str) {
+        self.type_error_message_str_with_expected(sp, mk_msg, None, actual_ty, err, code)
     }
 
     pub fn type_error_message_str_with_expected(&self,
@@ -725,7 +727,8 @@ impl<'a> InferCtxt<'a> {
                                                          -> ~str,
                                                 expected_ty: Option<ty::t>,
                                                 actual_ty: ~str,
-                                                err: Option<&ty::type_err>) {
+                                                err: Option<&ty::type_err>,
+                                                code: &str) {
         debug!("hi! expected_ty = {:?}, actual_ty = {}", expected_ty, actual_ty);
 
         let error_str = err.map_or(~"", |t_err| {
@@ -736,11 +739,12 @@ impl<'a> InferCtxt<'a> {
         });
         if !resolved_expected.map_or(false, |e| { ty::type_is_error(e) }) {
             match resolved_expected {
-                None => self.tcx.sess.span_err(sp,
-                            format!("{}{}", mk_msg(None, actual_ty), error_str)),
+                None => self.tcx.sess.span_err_with_code(sp,
+                            format!("{}{}", mk_msg(None, actual_ty), error_str), code),
                 Some(e) => {
-                    self.tcx.sess.span_err(sp,
-                        format!("{}{}", mk_msg(Some(self.ty_to_str(e)), actual_ty), error_str));
+                    self.tcx.sess.span_err_with_code(sp,
+                        format!("{}{}", mk_msg(Some(self.ty_to_str(e)), actual_ty), error_str),
+                        code);
                 }
             }
             for err in err.iter() {
@@ -753,7 +757,8 @@ impl<'a> InferCtxt<'a> {
                               sp: Span,
                               mk_msg: |~str| -> ~str,
                               actual_ty: ty::t,
-                              err: Option<&ty::type_err>) {
+                              err: Option<&ty::type_err>,
+                              code: &str) {
         let actual_ty = self.resolve_type_vars_if_possible(actual_ty);
 
         // Don't report an error if actual type is ty_err.
@@ -761,7 +766,8 @@ impl<'a> InferCtxt<'a> {
             return;
         }
 
-        self.type_error_message_str(sp, |_e, a| { mk_msg(a) }, self.ty_to_str(actual_ty), err);
+        self.type_error_message_str(sp, |_e, a| { mk_msg(a) }, self.ty_to_str(actual_ty), err,
+                                    code);
     }
 
     pub fn report_mismatched_types(&self,
@@ -782,7 +788,7 @@ impl<'a> InferCtxt<'a> {
                 }
             }
         };
-        self.type_error_message(sp, mk_msg, a, Some(err));
+        self.type_error_message(sp, mk_msg, a, Some(err), diagnostics::E0032);
     }
 
     pub fn replace_late_bound_regions_with_fresh_regions(&self,
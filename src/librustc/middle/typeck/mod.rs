@@ -61,6 +61,7 @@ independently:
 
 #![allow(non_camel_case_types)]
 
+use diagnostics;
 use driver::session;
 
 use middle::resolve;
@@ -307,8 +308,9 @@ pub fn require_same_types(tcx: &ty::ctxt,
     match result {
         Ok(_) => true,
         Err(ref terr) => {
-            tcx.sess.span_err(span, msg() + ": " +
-                              ty::type_err_to_str(tcx, terr));
+            tcx.sess.span_err_with_code(span, msg() + ": " +
+                                        ty::type_err_to_str(tcx, terr),
+                                        diagnostics::E0032);
             ty::note_and_explain_type_err(tcx, terr);
             false
         }
@@ -346,9 +348,10 @@ fn check_main_fn_ty(ccx: &CrateCtxt,
                     match it.node {
                         ast::ItemFn(_, _, _, ref ps, _)
                         if ps.is_parameterized() => {
-                            tcx.sess.span_err(
+                            tcx.sess.span_err_with_code(
                                 main_span,
-                                "main function is not allowed to have type parameters");
+                                "main function is not allowed to have type parameters",
+                                diagnostics::E0033);
                             return;
                         }
                         _ => ()
@@ -391,9 +394,10 @@ fn check_start_fn_ty(ccx: &CrateCtxt,
                     match it.node {
                         ast::ItemFn(_,_,_,ref ps,_)
                         if ps.is_parameterized() => {
-                            tcx.sess.span_err(
+                            tcx.sess.span_err_with_code(
                                 start_span,
-                                "start function is not allowed to have type parameters");
+                                "start function is not allowed to have type parameters",
+                                diagnostics::E0033);
                             return;
                         }
                         _ => ()
@@ -14,12 +14,15 @@ use syntax::ast;
 use syntax::visit;
 use syntax::visit::Visitor;
 
+use util::profile;
+
 use std::local_data;
 
 use time;
 
 pub fn time<T, U>(do_it: bool, what: &str, u: U, f: |U| -> T) -> T {
     local_data_key!(depth: uint);
+    let _activity = profile::activity("pass", || what.to_owned());
     if !do_it { return f(u); }
 
     let old = local_data::get(depth, |d| d.map(|a| *a).unwrap_or(0));
@@ -391,8 +391,12 @@ pub fn ty_to_str(cx: &ctxt, typ: t) -> ~str {
       ty_infer(infer_ty) => infer_ty.to_str(),
       ty_err => ~"[type error]",
       ty_param(param_ty {idx: id, def_id: did}) => {
-          let ident = match cx.ty_param_defs.borrow().find(&did.node) {
-              Some(def) => token::get_ident(def.ident).get().to_str(),
+          let def = cx.ty_param_defs.borrow().find(&did.node)
+                      .map(|def| (def.ident, def.where_ty));
+          let ident = match def {
+              // The parameter of a where clause stands for the type it bounds.
+              Some((_, Some(where_ty))) => ty_to_str(cx, where_ty),
+              Some((ident, None)) => token::get_ident(ident).get().to_str(),
               // This should not happen...
               None => format!("BUG[{:?}]", id)
           };
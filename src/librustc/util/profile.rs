@@ -0,0 +1,204 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Time and memory spent per pass and per item (`-Z self-profile`).
+//!
+//! Each pass run through `util::common::time` and each item checked or
+//! translated is an *activity*. Activities nest: the time of an activity
+//! less that of the activities nested within it is its self time, so a
+//! generic function which is expensive to translate shows up on its own
+//! rather than inflating whatever first instantiated it.
+//!
+//! Activities with the same category and name are added up, and the totals
+//! are written out as tab-separated columns, most expensive first:
+//!
+//!     category  name  count  total_ms  self_ms  rss_kb
+//!
+//! `rss_kb` is the growth of the resident set size over all runs of the
+//! activity. Reading it costs a system call or two, which would swamp the
+//! self time of a single item, so it is only measured for passes; it is `-`
+//! for everything else, and on platforms where it cannot be measured.
+
+use collections::HashMap;
+use std::io;
+use std::local_data;
+use time;
+
+local_data_key!(profiler_key: Profiler)
+
+struct Profiler {
+    // Activities in progress, innermost last.
+    stack: Vec<Frame>,
+    totals: HashMap<(&'static str, ~str), Totals>,
+}
+
+struct Frame {
+    start: u64,
+    // Only measured for passes.
+    start_rss: Option<u64>,
+    // Time spent in activities nested within this one so far.
+    nested: u64,
+}
+
+struct Totals {
+    count: uint,
+    total: u64,
+    self_time: u64,
+    rss: Option<i64>,
+}
+
+/// An activity being profiled, which is recorded when dropped.
+pub struct Activity {
+    category: &'static str,
+    // `None` when profiling is off.
+    name: Option<~str>,
+}
+
+/// Start profiling an activity. `name` is only called when profiling is on.
+pub fn activity(category: &'static str, name: || -> ~str) -> Activity {
+    let on = local_data::get_mut(profiler_key, |p| {
+        match p {
+            Some(p) => {
+                p.stack.push(Frame {
+                    start: time::precise_time_ns(),
+                    start_rss: if category == "pass" {
+                        resident_memory()
+                    } else {
+                        None
+                    },
+                    nested: 0,
+                });
+                true
+            }
+            None => false,
+        }
+    });
+    Activity {
+        category: category,
+        name: if on { Some(name()) } else { None },
+    }
+}
+
+impl Drop for Activity {
+    fn drop(&mut self) {
+        let name = match self.name.take() {
+            Some(name) => name,
+            None => return,
+        };
+        let category = self.category;
+        local_data::get_mut(profiler_key, |p| {
+            let p = match p {
+                Some(p) => p,
+                None => return,
+            };
+            let frame = p.stack.pop().unwrap();
+            let elapsed = time::precise_time_ns() - frame.start;
+            let rss = frame.start_rss.and_then(|start| {
+                resident_memory().map(|end| end as i64 - start as i64)
+            });
+            match p.stack.mut_last() {
+                Some(parent) => parent.nested += elapsed,
+                None => {}
+            }
+            let totals = p.totals.find_or_insert((category, name), Totals {
+                count: 0,
+                total: 0,
+                self_time: 0,
+                rss: Some(0),
+            });
+            totals.count += 1;
+            totals.total += elapsed;
+            totals.self_time += elapsed - frame.nested;
+            totals.rss = match (totals.rss, rss) {
+                (Some(a), Some(b)) => Some(a + b),
+                _ => None,
+            };
+        })
+    }
+}
+
+/// Profiling of the current task, from `start` until this is dropped.
+pub struct Profile {
+    report: Option<Path>,
+}
+
+/// Turn profiling on for the current task.
+pub fn start() -> Profile {
+    local_data::set(profiler_key, Profiler {
+        stack: Vec::new(),
+        totals: HashMap::new(),
+    });
+    Profile { report: None }
+}
+
+impl Profile {
+    /// Write the report to `path` once profiling is over.
+    pub fn report_to(&mut self, path: Path) {
+        self.report = Some(path);
+    }
+}
+
+impl Drop for Profile {
+    fn drop(&mut self) {
+        let profiler = match local_data::pop(profiler_key) {
+            Some(profiler) => profiler,
+            None => return,
+        };
+        let path = match self.report.take() {
+            Some(path) => path,
+            None => return,
+        };
+        let result = io::File::create(&path).and_then(|mut file| {
+            write_report(&mut file, profiler)
+        });
+        match result {
+            Ok(()) => {}
+            Err(e) => {
+                // The session is gone by now, so this can't go through it.
+                let _ = writeln!(&mut io::stderr(),
+                                 "warning: could not write self-profile report `{}`: {}",
+                                 path.display(), e);
+            }
+        }
+    }
+}
+
+fn write_report(w: &mut Writer, profiler: Profiler) -> io::IoResult<()> {
+    let mut totals: Vec<((&'static str, ~str), Totals)> =
+        profiler.totals.move_iter().collect();
+    totals.sort_by(|&(_, ref a), &(_, ref b)| b.self_time.cmp(&a.self_time));
+
+    let ms = |ns: u64| ns as f64 / 1e6;
+    try!(writeln!(w, "category\tname\tcount\ttotal_ms\tself_ms\trss_kb"));
+    for &((category, ref name), ref t) in totals.iter() {
+        let rss = match t.rss {
+            Some(bytes) => (bytes / 1024).to_str(),
+            None => ~"-",
+        };
+        try!(writeln!(w, "{}\t{}\t{}\t{:.3f}\t{:.3f}\t{}",
+                      category, name, t.count, ms(t.total), ms(t.self_time), rss));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn resident_memory() -> Option<u64> {
+    use std::os;
+
+    // The second field of statm is the resident set size in pages.
+    let statm = io::File::open(&Path::new("/proc/self/statm")).read_to_str();
+    let pages = statm.ok().and_then(|s| s.words().nth(1).and_then(from_str::<u64>));
+    pages.map(|pages| pages * os::page_size() as u64)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_memory() -> Option<u64> {
+    None
+}
@@ -342,13 +342,31 @@ impl Clean<Lifetime> for ast::Lifetime {
 pub struct Generics {
     pub lifetimes: Vec<Lifetime>,
     pub type_params: Vec<TyParam>,
+    pub where_predicates: Vec<WherePredicate>,
 }
 
 impl Clean<Generics> for ast::Generics {
     fn clean(&self) -> Generics {
         Generics {
             lifetimes: self.lifetimes.clean().move_iter().collect(),
-            type_params: self.ty_params.clean().move_iter().collect(),
+            type_params: self.ty_params.iter().filter(|tp| !tp.associated)
+                                 .map(|tp| tp.clean()).collect(),
+            where_predicates: self.where_predicates.clean().move_iter().collect(),
+        }
+    }
+}
+
+#[deriving(Clone, Encodable, Decodable)]
+pub struct WherePredicate {
+    pub ty: Type,
+    pub bounds: Vec<TyParamBound>,
+}
+
+impl Clean<WherePredicate> for ast::WherePredicate {
+    fn clean(&self) -> WherePredicate {
+        WherePredicate {
+            ty: self.ty.clean(),
+            bounds: self.bounds.clean().move_iter().collect(),
         }
     }
 }
@@ -563,10 +581,18 @@ pub struct Trait {
     pub methods: Vec<TraitMethod>,
     pub generics: Generics,
     pub parents: Vec<Type>,
+    pub assoc_types: Vec<AssociatedType>,
 }
 
 impl Clean<Item> for doctree::Trait {
     fn clean(&self) -> Item {
+        let assoc_types = self.generics.ty_params.iter().filter(|tp| tp.associated).map(|tp| {
+            AssociatedType {
+                name: tp.ident.clean(),
+                bounds: tp.bounds.clean().move_iter().collect(),
+                default: tp.default.clean(),
+            }
+        }).collect();
         Item {
             name: Some(self.name.clean()),
             attrs: self.attrs.clean(),
@@ -577,11 +603,19 @@ impl Clean<Item> for doctree::Trait {
                 methods: self.methods.clean(),
                 generics: self.generics.clean(),
                 parents: self.parents.clean(),
+                assoc_types: assoc_types,
             }),
         }
     }
 }
 
+#[deriving(Clone, Encodable, Decodable)]
+pub struct AssociatedType {
+    pub name: ~str,
+    pub bounds: Vec<TyParamBound>,
+    pub default: Option<Type>,
+}
+
 impl Clean<Type> for ast::TraitRef {
     fn clean(&self) -> Type {
         resolve_type(self.path.clean(), None, self.ref_id)
@@ -974,6 +1008,7 @@ impl Clean<BareFunctionDecl> for ast::BareFnTy {
             generics: Generics {
                 lifetimes: self.lifetimes.clean().move_iter().collect(),
                 type_params: Vec::new(),
+                where_predicates: Vec::new(),
             },
             decl: self.decl.clean(),
             abi: self.abi.to_str(),
@@ -1031,6 +1066,7 @@ pub struct Impl {
     pub for_: Type,
     pub methods: Vec<Item>,
     pub derived: bool,
+    pub type_bindings: Vec<TypeBinding>,
 }
 
 impl Clean<Item> for doctree::Impl {
@@ -1058,11 +1094,30 @@ impl Clean<Item> for doctree::Impl {
                 for_: self.for_.clean(),
                 methods: self.methods.clean(),
                 derived: derived,
+                type_bindings: match self.trait_ {
+                    Some(ref t) => t.bindings.clean(),
+                    None => Vec::new(),
+                },
             }),
         }
     }
 }
 
+#[deriving(Clone, Encodable, Decodable)]
+pub struct TypeBinding {
+    pub name: ~str,
+    pub ty: Type,
+}
+
+impl Clean<TypeBinding> for ast::TypeBinding {
+    fn clean(&self) -> TypeBinding {
+        TypeBinding {
+            name: self.ident.clean(),
+            ty: self.ty.clean(),
+        }
+    }
+}
+
 #[deriving(Clone, Encodable, Decodable)]
 pub struct ViewItem {
     pub inner: ViewItemInner,
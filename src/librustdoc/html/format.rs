@@ -35,6 +35,9 @@ pub struct VisSpace(pub Option<ast::Visibility>);
 pub struct FnStyleSpace(pub ast::FnStyle);
 /// Wrapper struct for properly emitting a method declaration.
 pub struct Method<'a>(pub &'a clean::SelfTy, pub &'a clean::FnDecl);
+/// Wrapper struct for emitting the where clause of some generics, with a
+/// space before it (if there is one)
+pub struct WhereClause<'a>(pub &'a clean::Generics);
 
 impl VisSpace {
     pub fn get(&self) -> Option<ast::Visibility> {
@@ -87,6 +90,28 @@ impl fmt::Show for clean::Generics {
     }
 }
 
+impl<'a> fmt::Show for WhereClause<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let WhereClause(generics) = *self;
+        if generics.where_predicates.len() == 0 { return Ok(()) }
+        try!(f.buf.write(" where ".as_bytes()));
+
+        for (i, predicate) in generics.where_predicates.iter().enumerate() {
+            if i > 0 {
+                try!(f.buf.write(", ".as_bytes()));
+            }
+            try!(write!(f.buf, "{}: ", predicate.ty));
+            for (i, bound) in predicate.bounds.iter().enumerate() {
+                if i > 0 {
+                    try!(f.buf.write(" + ".as_bytes()));
+                }
+                try!(write!(f.buf, "{}", *bound));
+            }
+        }
+        Ok(())
+    }
+}
+
 impl fmt::Show for clean::Lifetime {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         try!(f.buf.write("'".as_bytes()));
@@ -95,6 +120,26 @@ impl fmt::Show for clean::Lifetime {
     }
 }
 
+impl fmt::Show for clean::AssociatedType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f.buf, "type {}", self.name));
+        for (i, bound) in self.bounds.iter().enumerate() {
+            try!(f.buf.write(if i == 0 { ": " } else { " + " }.as_bytes()));
+            try!(write!(f.buf, "{}", *bound));
+        }
+        match self.default {
+            Some(ref ty) => write!(f.buf, " = {}", *ty),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Show for clean::TypeBinding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f.buf, "type {} = {}", self.name, self.ty)
+    }
+}
+
 impl fmt::Show for clean::TyParamBound {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
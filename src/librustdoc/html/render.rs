@@ -52,7 +52,7 @@ use rustc::util::nodemap::NodeSet;
 use clean;
 use doctree;
 use fold::DocFolder;
-use html::format::{VisSpace, Method, FnStyleSpace};
+use html::format::{VisSpace, Method, FnStyleSpace, WhereClause};
 use html::layout;
 use html::markdown;
 use html::markdown::Markdown;
@@ -1195,12 +1195,13 @@ fn item_module(w: &mut Writer, cx: &Context,
 fn item_function(w: &mut Writer, it: &clean::Item,
                  f: &clean::Function) -> fmt::Result {
     try!(write!(w, "<pre class='rust fn'>{vis}{fn_style}fn \
-                    {name}{generics}{decl}</pre>",
+                    {name}{generics}{decl}{where_clause}</pre>",
            vis = VisSpace(it.visibility),
            fn_style = FnStyleSpace(f.fn_style),
            name = it.name.get_ref().as_slice(),
            generics = f.generics,
-           decl = f.decl));
+           decl = f.decl,
+           where_clause = WhereClause(&f.generics)));
     document(w, it)
 }
 
@@ -1216,18 +1217,25 @@ fn item_trait(w: &mut Writer, it: &clean::Item,
     }
 
     // Output the trait definition
-    try!(write!(w, "<pre class='rust trait'>{}trait {}{}{} ",
+    try!(write!(w, "<pre class='rust trait'>{}trait {}{}{}{} ",
                   VisSpace(it.visibility),
                   it.name.get_ref().as_slice(),
                   t.generics,
-                  parents));
+                  parents,
+                  WhereClause(&t.generics)));
     let required = t.methods.iter().filter(|m| m.is_req()).collect::<Vec<&clean::TraitMethod>>();
     let provided = t.methods.iter().filter(|m| !m.is_req()).collect::<Vec<&clean::TraitMethod>>();
 
-    if t.methods.len() == 0 {
+    if t.methods.len() == 0 && t.assoc_types.len() == 0 {
         try!(write!(w, "\\{ \\}"));
     } else {
         try!(write!(w, "\\{\n"));
+        for ty in t.assoc_types.iter() {
+            try!(write!(w, "    {};\n", *ty));
+        }
+        if t.assoc_types.len() > 0 && t.methods.len() > 0 {
+            try!(w.write("\n".as_bytes()));
+        }
         for m in required.iter() {
             try!(write!(w, "    "));
             try!(render_method(w, m.item()));
@@ -1312,7 +1320,7 @@ fn render_method(w: &mut Writer, meth: &clean::Item) -> fmt::Result {
            g: &clean::Generics, selfty: &clean::SelfTy,
            d: &clean::FnDecl) -> fmt::Result {
         write!(w, "{}fn <a href='\\#{ty}.{name}' class='fnname'>{name}</a>\
-                   {generics}{decl}",
+                   {generics}{decl}{where_clause}",
                match fn_style {
                    ast::UnsafeFn => "unsafe ",
                    _ => "",
@@ -1320,7 +1328,8 @@ fn render_method(w: &mut Writer, meth: &clean::Item) -> fmt::Result {
                ty = shortty(it),
                name = it.name.get_ref().as_slice(),
                generics = *g,
-               decl = Method(selfty, d))
+               decl = Method(selfty, d),
+               where_clause = WhereClause(g))
     }
     match meth.inner {
         clean::TyMethodItem(ref m) => {
@@ -1571,7 +1580,7 @@ fn render_impl(w: &mut Writer, i: &clean::Impl,
         }
         None => None
     };
-    try!(write!(w, "{}</code></h3>", i.for_));
+    try!(write!(w, "{}{}</code></h3>", i.for_, WhereClause(&i.generics)));
     match *dox {
         Some(ref dox) => {
             try!(write!(w, "<div class='docblock'>{}</div>",
@@ -1595,6 +1604,9 @@ fn render_impl(w: &mut Writer, i: &clean::Impl,
     }
 
     try!(write!(w, "<div class='methods'>"));
+    for binding in i.type_bindings.iter() {
+        try!(write!(w, "<h4 class='type'><code>{};</code></h4>\n", *binding));
+    }
     for meth in i.methods.iter() {
         if try!(docmeth(w, meth)) {
             continue
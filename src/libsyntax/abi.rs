@@ -10,7 +10,7 @@
 
 use std::fmt;
 
-#[deriving(Eq)]
+#[deriving(Eq, Clone)]
 pub enum Os { OsWin32, OsMacos, OsLinux, OsAndroid, OsFreebsd, }
 
 #[deriving(Eq, TotalEq, Hash, Encodable, Decodable, Clone)]
@@ -33,7 +33,7 @@ pub enum Abi {
 }
 
 #[allow(non_camel_case_types)]
-#[deriving(Eq)]
+#[deriving(Eq, Clone)]
 pub enum Architecture {
     // NB. You cannot change the ordering of these
     // constants without adjusting IntelBits below.
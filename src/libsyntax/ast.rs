@@ -181,13 +181,25 @@ pub struct TyParam {
     pub ident: Ident,
     pub id: NodeId,
     pub bounds: OwnedSlice<TyParamBound>,
-    pub default: Option<P<Ty>>
+    pub default: Option<P<Ty>>,
+    // True for the associated types declared in the body of a trait,
+    // `type Item;`, which follow the trait's ordinary type parameters.
+    pub associated: bool,
 }
 
 #[deriving(Clone, Eq, TotalEq, Encodable, Decodable, Hash)]
 pub struct Generics {
     pub lifetimes: Vec<Lifetime>,
     pub ty_params: OwnedSlice<TyParam>,
+    pub where_predicates: Vec<WherePredicate>,
+}
+
+/// A bound in a `where` clause, `where T: Show` or `where T::Item: Clone`.
+#[deriving(Clone, Eq, TotalEq, Encodable, Decodable, Hash)]
+pub struct WherePredicate {
+    pub span: Span,
+    pub ty: P<Ty>,
+    pub bounds: OwnedSlice<TyParamBound>,
 }
 
 impl Generics {
@@ -1051,6 +1063,16 @@ pub struct Attribute_ {
 pub struct TraitRef {
     pub path: Path,
     pub ref_id: NodeId,
+    // The associated types bound in the body of an impl, `type Item = int;`.
+    // Always empty for trait references appearing anywhere else.
+    pub bindings: Vec<TypeBinding>,
+}
+
+#[deriving(Clone, Eq, TotalEq, Encodable, Decodable, Hash)]
+pub struct TypeBinding {
+    pub ident: Ident,
+    pub ty: P<Ty>,
+    pub span: Span,
 }
 
 #[deriving(Clone, Eq, TotalEq, Encodable, Decodable, Hash)]
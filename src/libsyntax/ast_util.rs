@@ -318,7 +318,8 @@ pub static as_prec: uint = 12u;
 
 pub fn empty_generics() -> Generics {
     Generics {lifetimes: Vec::new(),
-              ty_params: OwnedSlice::empty()}
+              ty_params: OwnedSlice::empty(),
+              where_predicates: Vec::new()}
 }
 
 // ______________________________________________________________________
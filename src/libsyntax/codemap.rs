@@ -284,14 +284,48 @@ impl FileMap {
     }
 }
 
+/// Prefixes of source paths to replace wherever the paths end up in what is
+/// built (`--remap-path-prefix FROM=TO`), so that it doesn't depend on where
+/// the source happened to be.
+#[deriving(Clone)]
+pub struct FilePathMapping {
+    mapping: Vec<(~str, ~str)>,
+}
+
+impl FilePathMapping {
+    pub fn empty() -> FilePathMapping {
+        FilePathMapping { mapping: Vec::new() }
+    }
+
+    pub fn new(mapping: Vec<(~str, ~str)>) -> FilePathMapping {
+        FilePathMapping { mapping: mapping }
+    }
+
+    /// `path` with its prefix replaced by the last mapping which applies.
+    pub fn map_prefix(&self, path: &str) -> ~str {
+        for &(ref from, ref to) in self.mapping.iter().rev() {
+            if path.starts_with(from.as_slice()) {
+                return format!("{}{}", *to, path.slice_from(from.len()))
+            }
+        }
+        path.to_owned()
+    }
+}
+
 pub struct CodeMap {
-    pub files: RefCell<Vec<Rc<FileMap>>>
+    pub files: RefCell<Vec<Rc<FileMap>>>,
+    pub path_mapping: FilePathMapping,
 }
 
 impl CodeMap {
     pub fn new() -> CodeMap {
+        CodeMap::with_path_mapping(FilePathMapping::empty())
+    }
+
+    pub fn with_path_mapping(path_mapping: FilePathMapping) -> CodeMap {
         CodeMap {
             files: RefCell::new(Vec::new()),
+            path_mapping: path_mapping,
         }
     }
 
@@ -472,7 +506,7 @@ impl CodeMap {
         }
     }
 
-    fn lookup_byte_offset(&self, bpos: BytePos) -> FileMapAndBytePos {
+    pub fn lookup_byte_offset(&self, bpos: BytePos) -> FileMapAndBytePos {
         let idx = self.lookup_filemap_idx(bpos);
         let fm = self.files.borrow().get(idx).clone();
         let offset = bpos - fm.start_pos;
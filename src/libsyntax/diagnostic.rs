@@ -18,6 +18,7 @@ use std::fmt;
 use std::io;
 use std::iter::range;
 use std::strbuf::StrBuf;
+use serialize::{json, Encodable};
 use term;
 
 // maximum number of lines we will print for each error; arbitrary.
@@ -51,9 +52,23 @@ impl RenderSpan {
 
 pub trait Emitter {
     fn emit(&mut self, cmsp: Option<(&codemap::CodeMap, Span)>,
-            msg: &str, lvl: Level);
+            msg: &str, code: Option<&str>, lvl: Level);
     fn custom_emit(&mut self, cm: &codemap::CodeMap,
                    sp: RenderSpan, msg: &str, lvl: Level);
+    fn suggest(&mut self, cm: &codemap::CodeMap, sugg: &Suggestion);
+}
+
+/// A replacement for the source text covered by `span` which would fix the
+/// diagnostic it was reported alongside. `msg` describes the change.
+#[deriving(Clone)]
+pub struct Suggestion {
+    pub span: Span,
+    pub msg: ~str,
+    pub replacement: ~str,
+    /// Whether the replacement is certainly what was meant, so that it can be
+    /// applied without anyone looking at it. Guesses such as a similarly
+    /// named item are only shown.
+    pub machine_applicable: bool,
 }
 
 /// This structure is used to signify that a task has failed with a fatal error
@@ -71,6 +86,9 @@ pub struct ExplicitBug;
 pub struct SpanHandler {
     pub handler: Handler,
     pub cm: codemap::CodeMap,
+    // Every suggestion reported so far, kept so that the driver can apply
+    // them to the source.
+    pub suggestions: RefCell<Vec<Suggestion>>,
 }
 
 impl SpanHandler {
@@ -78,13 +96,24 @@ impl SpanHandler {
         self.handler.emit(Some((&self.cm, sp)), msg, Fatal);
         fail!(FatalError);
     }
+    pub fn span_fatal_with_code(&self, sp: Span, msg: &str, code: &str) -> ! {
+        self.handler.emit_with_code(Some((&self.cm, sp)), msg, code, Fatal);
+        fail!(FatalError);
+    }
     pub fn span_err(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Error);
         self.handler.bump_err_count();
     }
+    pub fn span_err_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.handler.emit_with_code(Some((&self.cm, sp)), msg, code, Error);
+        self.handler.bump_err_count();
+    }
     pub fn span_warn(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Warning);
     }
+    pub fn span_warn_with_code(&self, sp: Span, msg: &str, code: &str) {
+        self.handler.emit_with_code(Some((&self.cm, sp)), msg, code, Warning);
+    }
     pub fn span_note(&self, sp: Span, msg: &str) {
         self.handler.emit(Some((&self.cm, sp)), msg, Note);
     }
@@ -94,6 +123,20 @@ impl SpanHandler {
     pub fn fileline_note(&self, sp: Span, msg: &str) {
         self.handler.custom_emit(&self.cm, FileLine(sp), msg, Note);
     }
+    /// Suggests a replacement which may not be what was meant; see
+    /// `suggest` for one which can be applied automatically.
+    pub fn span_suggestion(&self, sp: Span, msg: &str, replacement: ~str) {
+        self.suggest(Suggestion {
+            span: sp,
+            msg: msg.to_owned(),
+            replacement: replacement,
+            machine_applicable: false,
+        });
+    }
+    pub fn suggest(&self, sugg: Suggestion) {
+        self.handler.suggest(&self.cm, &sugg);
+        self.suggestions.borrow_mut().push(sugg);
+    }
     pub fn span_bug(&self, sp: Span, msg: &str) -> ! {
         self.handler.emit(Some((&self.cm, sp)), msg, Bug);
         fail!(ExplicitBug);
@@ -116,11 +159,11 @@ pub struct Handler {
 
 impl Handler {
     pub fn fatal(&self, msg: &str) -> ! {
-        self.emit.borrow_mut().emit(None, msg, Fatal);
+        self.emit.borrow_mut().emit(None, msg, None, Fatal);
         fail!(FatalError);
     }
     pub fn err(&self, msg: &str) {
-        self.emit.borrow_mut().emit(None, msg, Error);
+        self.emit.borrow_mut().emit(None, msg, None, Error);
         self.bump_err_count();
     }
     pub fn bump_err_count(&self) {
@@ -145,13 +188,13 @@ impl Handler {
         self.fatal(s);
     }
     pub fn warn(&self, msg: &str) {
-        self.emit.borrow_mut().emit(None, msg, Warning);
+        self.emit.borrow_mut().emit(None, msg, None, Warning);
     }
     pub fn note(&self, msg: &str) {
-        self.emit.borrow_mut().emit(None, msg, Note);
+        self.emit.borrow_mut().emit(None, msg, None, Note);
     }
     pub fn bug(&self, msg: &str) -> ! {
-        self.emit.borrow_mut().emit(None, msg, Bug);
+        self.emit.borrow_mut().emit(None, msg, None, Bug);
         fail!(ExplicitBug);
     }
     pub fn unimpl(&self, msg: &str) -> ! {
@@ -161,18 +204,29 @@ impl Handler {
                 cmsp: Option<(&codemap::CodeMap, Span)>,
                 msg: &str,
                 lvl: Level) {
-        self.emit.borrow_mut().emit(cmsp, msg, lvl);
+        self.emit.borrow_mut().emit(cmsp, msg, None, lvl);
+    }
+    pub fn emit_with_code(&self,
+                          cmsp: Option<(&codemap::CodeMap, Span)>,
+                          msg: &str,
+                          code: &str,
+                          lvl: Level) {
+        self.emit.borrow_mut().emit(cmsp, msg, Some(code), lvl);
     }
     pub fn custom_emit(&self, cm: &codemap::CodeMap,
                        sp: RenderSpan, msg: &str, lvl: Level) {
         self.emit.borrow_mut().custom_emit(cm, sp, msg, lvl);
     }
+    pub fn suggest(&self, cm: &codemap::CodeMap, sugg: &Suggestion) {
+        self.emit.borrow_mut().suggest(cm, sugg);
+    }
 }
 
 pub fn mk_span_handler(handler: Handler, cm: codemap::CodeMap) -> SpanHandler {
     SpanHandler {
         handler: handler,
         cm: cm,
+        suggestions: RefCell::new(Vec::new()),
     }
 }
 
@@ -217,6 +271,17 @@ impl Level {
             Note => term::color::BRIGHT_GREEN
         }
     }
+
+    /// The name of the level in JSON output, as in `Diagnostic::level`.
+    pub fn json_name(self) -> &'static str {
+        match self {
+            Bug => "bug",
+            Fatal => "fatal",
+            Error => "error",
+            Warning => "warning",
+            Note => "note",
+        }
+    }
 }
 
 fn print_maybe_styled(w: &mut EmitterWriter,
@@ -288,7 +353,14 @@ impl Emitter for EmitterWriter {
     fn emit(&mut self,
             cmsp: Option<(&codemap::CodeMap, Span)>,
             msg: &str,
+            code: Option<&str>,
             lvl: Level) {
+        // Lints pass their name as the code too, but their messages already
+        // say which lint fired, so only error codes are shown.
+        let msg = match code {
+            Some(code) if is_error_code(code) => format!("{} [{}]", msg, code),
+            _ => msg.to_owned(),
+        };
         let error = match cmsp {
             Some((cm, sp)) => emit(self, cm, FullSpan(sp), msg, lvl, false),
             None => print_diagnostic(self, "", lvl, msg),
@@ -307,6 +379,197 @@ impl Emitter for EmitterWriter {
             Err(e) => fail!("failed to print diagnostics: {}", e),
         }
     }
+
+    fn suggest(&mut self, cm: &codemap::CodeMap, sugg: &Suggestion) {
+        match emit_suggestion(self, cm, sugg) {
+            Ok(()) => {}
+            Err(e) => fail!("failed to print diagnostics: {}", e),
+        }
+    }
+}
+
+/// A diagnostic as written out by the `JsonEmitter`. Notes following an
+/// error or warning are attached to it as `children` rather than being
+/// reported on their own.
+#[deriving(Clone, Encodable, Decodable)]
+pub struct Diagnostic {
+    /// One of "error", "warning", "note", "fatal" or "bug".
+    pub level: ~str,
+    pub message: ~str,
+    /// The error code (such as `E0032`) or the name of the lint which
+    /// produced this diagnostic, if any.
+    pub code: Option<~str>,
+    pub span: Option<DiagnosticSpan>,
+    pub children: Vec<Diagnostic>,
+}
+
+impl Diagnostic {
+    /// The diagnostic for a message passed to `Emitter::emit`.
+    pub fn new(cmsp: Option<(&codemap::CodeMap, Span)>, msg: &str,
+               code: Option<&str>, lvl: Level) -> Diagnostic {
+        Diagnostic {
+            level: lvl.json_name().to_owned(),
+            message: msg.to_owned(),
+            code: code.map(|c| c.to_owned()),
+            span: cmsp.and_then(|(cm, sp)| DiagnosticSpan::from_span(cm, sp)),
+            children: Vec::new(),
+        }
+    }
+
+    /// The note for a suggestion passed to `Emitter::suggest`, its span
+    /// carrying the replacement.
+    pub fn from_suggestion(cm: &codemap::CodeMap, sugg: &Suggestion) -> Diagnostic {
+        let span = match DiagnosticSpan::from_span(cm, sugg.span) {
+            Some(mut span) => {
+                span.suggested_replacement = Some(sugg.replacement.clone());
+                Some(span)
+            }
+            None => None,
+        };
+        Diagnostic {
+            level: Note.json_name().to_owned(),
+            message: sugg.msg.clone(),
+            code: None,
+            span: span,
+            children: Vec::new(),
+        }
+    }
+
+    /// Attaches this diagnostic, of level `lvl`, to `parent`, the diagnostic
+    /// reported before it, if it is a note. Anything else is given back to
+    /// be reported on its own.
+    pub fn attach_to(self, lvl: Level, parent: Option<&mut Diagnostic>)
+                     -> Option<Diagnostic> {
+        if lvl == Note {
+            match parent {
+                Some(parent) => {
+                    parent.children.push(self);
+                    return None;
+                }
+                None => {}
+            }
+        }
+        Some(self)
+    }
+}
+
+/// The source location a `Diagnostic` points at. Byte offsets are relative
+/// to the start of the file; lines and columns are 1-based, with columns
+/// counted in characters.
+#[deriving(Clone, Encodable, Decodable)]
+pub struct DiagnosticSpan {
+    pub file_name: ~str,
+    pub byte_start: uint,
+    pub byte_end: uint,
+    pub line_start: uint,
+    pub line_end: uint,
+    pub column_start: uint,
+    pub column_end: uint,
+    /// For a suggestion, the text which should replace this span.
+    pub suggested_replacement: Option<~str>,
+}
+
+impl DiagnosticSpan {
+    pub fn from_span(cm: &codemap::CodeMap, sp: Span) -> Option<DiagnosticSpan> {
+        if cm.files.borrow().len() == 0 {
+            return None;
+        }
+        let lo = cm.lookup_char_pos(sp.lo);
+        let hi = cm.lookup_char_pos(sp.hi);
+        Some(DiagnosticSpan {
+            file_name: lo.file.name.clone(),
+            byte_start: (sp.lo - lo.file.start_pos).to_uint(),
+            byte_end: (sp.hi - lo.file.start_pos).to_uint(),
+            line_start: lo.line,
+            line_end: hi.line,
+            column_start: lo.col.to_uint() + 1,
+            column_end: hi.col.to_uint() + 1,
+            suggested_replacement: None,
+        })
+    }
+}
+
+/// An emitter which writes each diagnostic as a single line of JSON, for
+/// consumption by editors and other tools. The output can be read back with
+/// `json::Decoder` into a `Diagnostic`.
+pub struct JsonEmitter {
+    dst: ~Writer:Send,
+    // The last error or warning seen, held back so that the notes which
+    // follow it can be attached.
+    pending: Option<Diagnostic>,
+}
+
+impl JsonEmitter {
+    pub fn stderr() -> JsonEmitter {
+        JsonEmitter::new(~io::stderr())
+    }
+
+    pub fn new(dst: ~Writer:Send) -> JsonEmitter {
+        JsonEmitter { dst: dst, pending: None }
+    }
+
+    fn push(&mut self, diag: Diagnostic, lvl: Level) {
+        let diag = match diag.attach_to(lvl, self.pending.as_mut()) {
+            Some(diag) => diag,
+            None => return,
+        };
+        self.flush();
+        match lvl {
+            // The task is about to fail, so there won't be anything else to
+            // attach to this one.
+            Bug | Fatal => self.write(&diag),
+            Error | Warning | Note => self.pending = Some(diag),
+        }
+    }
+
+    fn flush(&mut self) {
+        match self.pending.take() {
+            Some(diag) => self.write(&diag),
+            None => {}
+        }
+    }
+
+    fn write(&mut self, diag: &Diagnostic) {
+        let result = {
+            let mut encoder = json::Encoder::new(&mut *self.dst as &mut Writer);
+            diag.encode(&mut encoder)
+        };
+        match result.and_then(|()| self.dst.write_str("\n")) {
+            Ok(()) => {}
+            Err(e) => fail!("failed to print diagnostics: {}", e),
+        }
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self,
+            cmsp: Option<(&codemap::CodeMap, Span)>,
+            msg: &str,
+            code: Option<&str>,
+            lvl: Level) {
+        self.push(Diagnostic::new(cmsp, msg, code, lvl), lvl);
+    }
+
+    fn custom_emit(&mut self, cm: &codemap::CodeMap,
+                   sp: RenderSpan, msg: &str, lvl: Level) {
+        self.emit(Some((cm, sp.span())), msg, None, lvl);
+    }
+
+    fn suggest(&mut self, cm: &codemap::CodeMap, sugg: &Suggestion) {
+        self.push(Diagnostic::from_suggestion(cm, sugg), Note);
+    }
+}
+
+impl Drop for JsonEmitter {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+// Error codes are an `E` followed by four digits, like `E0001`.
+fn is_error_code(code: &str) -> bool {
+    code.len() == 5 && code.starts_with("E") &&
+        code.slice_from(1).chars().all(|c| c.is_digit())
 }
 
 fn emit(dst: &mut EmitterWriter, cm: &codemap::CodeMap, rsp: RenderSpan,
@@ -333,6 +596,40 @@ fn emit(dst: &mut EmitterWriter, cm: &codemap::CodeMap, rsp: RenderSpan,
     print_macro_backtrace(dst, cm, sp)
 }
 
+// Suggestions render as a note pointing at the span to be replaced,
+// followed by the line as it would read with the replacement made. Spans
+// covering several lines just show the replacement text.
+fn emit_suggestion(dst: &mut EmitterWriter, cm: &codemap::CodeMap,
+                   sugg: &Suggestion) -> io::IoResult<()> {
+    let sp = sugg.span;
+    try!(print_diagnostic(dst, cm.span_to_str(sp), Note,
+                          format!("suggestion: {}", sugg.msg)));
+    let lines = cm.span_to_lines(sp);
+    if lines.lines.len() != 1 {
+        return write!(&mut dst.dst, "{}\n", sugg.replacement);
+    }
+    let fm = &*lines.file;
+    let line = *lines.lines.get(0);
+    let lo = cm.lookup_char_pos(sp.lo);
+    let hi = cm.lookup_char_pos(sp.hi);
+    let orig = fm.get_line(line as int);
+    let mut s = StrBuf::new();
+    for (i, c) in orig.chars().enumerate() {
+        if i == lo.col.to_uint() {
+            s.push_str(sugg.replacement);
+        }
+        if i < lo.col.to_uint() || i >= hi.col.to_uint() {
+            s.push_char(c);
+        }
+    }
+    if lo.col.to_uint() >= orig.char_len() {
+        s.push_str(sugg.replacement);
+    }
+    try!(write!(&mut dst.dst, "{}:{} ", fm.name, line + 1));
+    print_maybe_styled(dst, s.into_owned() + "\n",
+                       term::attr::ForegroundColor(Note.color()))
+}
+
 fn highlight_lines(err: &mut EmitterWriter,
                    cm: &codemap::CodeMap,
                    sp: Span,
@@ -291,9 +291,17 @@ pub fn syntax_expander_table() -> SyntaxEnv {
     syntax_expanders
 }
 
+/// A `macro_rules!` definition exported by another crate with
+/// `#[macro_export]`, as read back from that crate's metadata.
+pub struct ExportedMacro {
+    pub name: ast::Ident,
+    /// The body of the definition, i.e. the rules passed to `macro_rules!`.
+    pub tts: Vec<ast::TokenTree>,
+}
+
 pub struct MacroCrate {
     pub lib: Option<Path>,
-    pub macros: Vec<~str>,
+    pub macros: Vec<ExportedMacro>,
     pub registrar_symbol: Option<~str>,
 }
 
@@ -334,6 +342,7 @@ impl<'a> ExtCtxt<'a> {
                     let mut expander = expand::MacroExpander {
                         extsbox: syntax_expander_table(),
                         cx: self,
+                        block_depth: 0,
                     };
                     e = expand::expand_expr(e, &mut expander);
                 }
@@ -23,8 +23,9 @@ use crateid::CrateId;
 use ext::base::*;
 use fold::*;
 use parse;
-use parse::token::{fresh_mark, fresh_name, intern};
+use parse::token::{fresh_mark, fresh_name, intern, InternedString};
 use parse::token;
+use print::pprust;
 use visit;
 use visit::Visitor;
 use util::small_vector::SmallVector;
@@ -32,6 +33,7 @@ use util::small_vector::SmallVector;
 use std::cast;
 use std::unstable::dynamic_lib::DynamicLibrary;
 use std::os;
+use std::rc::Rc;
 
 pub fn expand_expr(e: @ast::Expr, fld: &mut MacroExpander) -> @ast::Expr {
     match e.node {
@@ -478,6 +480,11 @@ pub fn expand_view_item(vi: &ast::ViewItem,
 
             if should_load {
                 load_extern_macros(vi, fld);
+            } else {
+                for attr in vi.attrs.iter().filter(|a| a.name().get() == "macro_import") {
+                    fld.cx.span_err(attr.span,
+                                    "`#[macro_import]` requires `#[phase(syntax)]`");
+                }
             }
         }
         ast::ViewItemUse(_) => {}
@@ -494,17 +501,51 @@ fn load_extern_macros(krate: &ast::ViewItem, fld: &mut MacroExpander) {
         ast::ViewItemExternCrate(name, _, _) => name,
         _ => unreachable!()
     };
-    let name = format!("<{} macros>", token::get_ident(crate_name));
-
-    for source in macros.iter() {
-        let item = parse::parse_item_from_source_str(name.clone(),
-                                                     (*source).clone(),
-                                                     fld.cx.cfg(),
-                                                     fld.cx.parse_sess())
-                .expect("expected a serialized item");
+    let mut imports = macro_imports(krate, fld.cx);
+    // `::name` only reaches the crate if `name` is bound at the crate root.
+    let at_root = fld.cx.mod_path.is_empty() && fld.block_depth == 0;
+    let mut anchored = false;
+
+    for exported in macros.move_iter() {
+        if !take_import(&mut imports, exported.name.name) {
+            continue
+        }
+        let tts = crate_macro_tts(fld.cx, crate_name, exported.tts.as_slice());
+        let tts = anchor_crate_tts(tts.as_slice(), crate_name, &mut anchored);
+        let path = fld.cx.path_ident(krate.span, fld.cx.ident_of("macro_rules"));
+        let item = @ast::Item {
+            ident: exported.name,
+            attrs: Vec::new(),
+            id: ast::DUMMY_NODE_ID,
+            node: ItemMac(codemap::respan(krate.span,
+                                          MacInvocTT(path, tts, ast::EMPTY_CTXT))),
+            vis: ast::Inherited,
+            span: krate.span,
+        };
         expand_item_mac(item, fld);
     }
 
+    if anchored && !at_root {
+        let msg = format!("crate `{}` exports macros using `$crate`, so it must be imported \
+                           at the crate root", token::get_ident(crate_name));
+        fld.cx.span_err(krate.span, msg);
+    }
+
+    load_registrar_macros(krate, lib, registrar_symbol, &mut imports, fld);
+
+    for names in imports.iter() {
+        for &(ref name, sp) in names.iter() {
+            fld.cx.span_err(sp, format!("crate `{}` does not export a macro named `{}`",
+                                        token::get_ident(crate_name), name.get()));
+        }
+    }
+}
+
+fn load_registrar_macros(krate: &ast::ViewItem,
+                         lib: Option<Path>,
+                         registrar_symbol: Option<~str>,
+                         imports: &mut Option<Vec<(InternedString, Span)>>,
+                         fld: &mut MacroExpander) {
     let path = match lib {
         Some(path) => path,
         None => return
@@ -532,6 +573,9 @@ fn load_extern_macros(krate: &ast::ViewItem, fld: &mut MacroExpander) {
             Err(err) => fld.cx.span_fatal(krate.span, err)
         };
         registrar(|name, extension| {
+            if !take_import(imports, name) {
+                return
+            }
             let extension = match extension {
                 NormalTT(ext, _) => NormalTT(ext, Some(krate.span)),
                 IdentTT(ext, _) => IdentTT(ext, Some(krate.span)),
@@ -548,6 +592,88 @@ fn load_extern_macros(krate: &ast::ViewItem, fld: &mut MacroExpander) {
     }
 }
 
+// The macro names listed by `#[macro_import(a, b)]` on an `extern crate`,
+// or None to import every macro the crate exports.
+fn macro_imports(krate: &ast::ViewItem,
+                 cx: &ExtCtxt) -> Option<Vec<(InternedString, Span)>> {
+    let mut names = Vec::new();
+    let mut restricted = false;
+    for attr in krate.attrs.iter() {
+        if attr.name().get() != "macro_import" {
+            continue
+        }
+        restricted = true;
+        match attr.meta_item_list() {
+            Some(items) => {
+                for item in items.iter() {
+                    match item.node {
+                        ast::MetaWord(ref name) => names.push((name.clone(), item.span)),
+                        _ => cx.span_err(item.span, "expected a macro name"),
+                    }
+                }
+            }
+            None => cx.span_err(attr.span, "expected `#[macro_import(name, ...)]`"),
+        }
+    }
+    if restricted { Some(names) } else { None }
+}
+
+// Whether the macro `name` is to be imported; it is then struck off the
+// names still expected by `#[macro_import]`.
+fn take_import(imports: &mut Option<Vec<(InternedString, Span)>>, name: ast::Name) -> bool {
+    match *imports {
+        None => true,
+        Some(ref mut names) => {
+            let name = token::get_name(name);
+            let expected = names.len();
+            names.retain(|&(ref n, _)| *n != name);
+            names.len() != expected
+        }
+    }
+}
+
+// The spans of a macro definition aren't kept in crate metadata, so give
+// its tokens a file of their own, `<name macros>`, for errors in its
+// expansions to point at.
+fn crate_macro_tts(cx: &ExtCtxt, crate_name: Ident, tts: &[TokenTree]) -> Vec<TokenTree> {
+    let name = format!("<{} macros>", token::get_ident(crate_name));
+    let filemap = parse::string_to_filemap(cx.parse_sess(), pprust::tts_to_str(tts), name);
+    parse::filemap_to_tts(cx.parse_sess(), filemap)
+}
+
+// Replaces `$crate` in the definition of a macro imported from another
+// crate with `::name`, `name` being what the importing crate calls it,
+// and sets `anchored` if there was any. This relies on the `extern crate`
+// being at the crate root.
+fn anchor_crate_tts(tts: &[TokenTree], crate_name: Ident, anchored: &mut bool)
+                    -> Vec<TokenTree> {
+    let mut result = Vec::with_capacity(tts.len());
+    let mut i = 0;
+    while i < tts.len() {
+        match (&tts[i], tts.get(i + 1)) {
+            (&ast::TTTok(dollar_sp, token::DOLLAR), Some(&ast::TTTok(crate_sp, ref next)))
+                    if token::is_keyword(token::keywords::Crate, next) => {
+                result.push(ast::TTTok(dollar_sp, token::MOD_SEP));
+                result.push(ast::TTTok(crate_sp, token::IDENT(crate_name, false)));
+                *anchored = true;
+                i += 2;
+                continue
+            }
+            (&ast::TTDelim(ref delimed), _) => {
+                let delimed = anchor_crate_tts(delimed.as_slice(), crate_name, anchored);
+                result.push(ast::TTDelim(Rc::new(delimed)));
+            }
+            (&ast::TTSeq(seq_sp, ref seq, ref sep, zerok), _) => {
+                let seq = anchor_crate_tts(seq.as_slice(), crate_name, anchored);
+                result.push(ast::TTSeq(seq_sp, Rc::new(seq), sep.clone(), zerok));
+            }
+            (tt, _) => result.push(tt.clone()),
+        }
+        i += 1;
+    }
+    result
+}
+
 // expand a stmt
 pub fn expand_stmt(s: &Stmt, fld: &mut MacroExpander) -> SmallVector<@Stmt> {
     // why the copying here and not in expand_expr?
@@ -769,9 +895,12 @@ pub fn new_name_finder(idents: Vec<ast::Ident> ) -> NewNameFinderContext {
 
 // expand a block. pushes a new exts_frame, then calls expand_block_elts
 pub fn expand_block(blk: &Block, fld: &mut MacroExpander) -> P<Block> {
+    fld.block_depth += 1;
     // see note below about treatment of exts table
-    with_exts_frame!(fld.extsbox,false,
-                     expand_block_elts(blk, fld))
+    let result = with_exts_frame!(fld.extsbox,false,
+                                  expand_block_elts(blk, fld));
+    fld.block_depth -= 1;
+    result
 }
 
 // expand the elements of a block.
@@ -840,6 +969,9 @@ pub fn new_span(cx: &ExtCtxt, sp: Span) -> Span {
 pub struct MacroExpander<'a, 'b> {
     pub extsbox: SyntaxEnv,
     pub cx: &'a mut ExtCtxt<'b>,
+    // How many blocks the expander is inside of; items in a block aren't
+    // at the crate root even outside of any `mod`.
+    pub block_depth: uint,
 }
 
 impl<'a, 'b> Folder for MacroExpander<'a, 'b> {
@@ -881,6 +1013,7 @@ pub fn expand_crate(parse_sess: &parse::ParseSess,
     let mut expander = MacroExpander {
         extsbox: syntax_expander_table(),
         cx: &mut cx,
+        block_depth: 0,
     };
 
     let ret = expander.fold_crate(c);
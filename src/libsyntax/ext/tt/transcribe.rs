@@ -96,6 +96,10 @@ fn lookup_cur_matched(r: &TtReader, name: Ident) -> Rc<NamedMatch> {
     }
 }
 
+fn is_crate_anchor(name: Ident) -> bool {
+    name.name == token::keywords::Crate.to_ident().name
+}
+
 #[deriving(Clone)]
 enum LockstepIterSize {
     LisUnconstrained,
@@ -130,6 +134,7 @@ fn lockstep_iter_size(t: &TokenTree, r: &TtReader) -> LockstepIterSize {
             })
         }
         TTTok(..) => LisUnconstrained,
+        TTNonterminal(_, name) if is_crate_anchor(name) => LisUnconstrained,
         TTNonterminal(_, name) => match *lookup_cur_matched(r, name) {
             MatchedNonterminal(_) => LisUnconstrained,
             MatchedSeq(ref ads, _) => LisConstraint(ads.len(), name)
@@ -247,6 +252,13 @@ pub fn tt_next_token(r: &mut TtReader) -> TokenAndSpan {
                     }
                 }
             }
+            // `$crate` in a macro defined in this crate: the path it starts
+            // is then relative to the crate root already, so it expands to
+            // nothing. Macros imported from other crates had it replaced
+            // when they were loaded.
+            TTNonterminal(_, ident) if is_crate_anchor(ident) => {
+                r.stack.mut_last().unwrap().idx += 1;
+            }
             // FIXME #2887: think about span stuff here
             TTNonterminal(sp, ident) => {
                 r.stack.mut_last().unwrap().idx += 1;
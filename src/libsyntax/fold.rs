@@ -422,7 +422,8 @@ pub fn fold_ty_param<T: Folder>(tp: &TyParam, fld: &mut T) -> TyParam {
         ident: tp.ident,
         id: fld.new_id(tp.id),
         bounds: tp.bounds.map(|x| fold_ty_param_bound(x, fld)),
-        default: tp.default.map(|x| fld.fold_ty(x))
+        default: tp.default.map(|x| fld.fold_ty(x)),
+        associated: tp.associated
     }
 }
 
@@ -451,7 +452,18 @@ pub fn fold_opt_lifetime<T: Folder>(o_lt: &Option<Lifetime>, fld: &mut T)
 
 pub fn fold_generics<T: Folder>(generics: &Generics, fld: &mut T) -> Generics {
     Generics {ty_params: fold_ty_params(&generics.ty_params, fld),
-              lifetimes: fold_lifetimes(&generics.lifetimes, fld)}
+              lifetimes: fold_lifetimes(&generics.lifetimes, fld),
+              where_predicates: generics.where_predicates.iter().map(|p| {
+                  fold_where_predicate(p, fld)
+              }).collect()}
+}
+
+fn fold_where_predicate<T: Folder>(p: &WherePredicate, fld: &mut T) -> WherePredicate {
+    WherePredicate {
+        span: fld.new_span(p.span),
+        ty: fld.fold_ty(p.ty),
+        bounds: p.bounds.map(|x| fold_ty_param_bound(x, fld)),
+    }
 }
 
 fn fold_struct_def<T: Folder>(struct_def: @StructDef, fld: &mut T) -> @StructDef {
@@ -465,6 +477,13 @@ fn fold_trait_ref<T: Folder>(p: &TraitRef, fld: &mut T) -> TraitRef {
     ast::TraitRef {
         path: fld.fold_path(&p.path),
         ref_id: fld.new_id(p.ref_id),
+        bindings: p.bindings.iter().map(|b| {
+            ast::TypeBinding {
+                ident: b.ident,
+                ty: fld.fold_ty(b.ty),
+                span: fld.new_span(b.span)
+            }
+        }).collect(),
     }
 }
 
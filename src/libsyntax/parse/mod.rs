@@ -662,6 +662,7 @@ mod test {
                                     ast::Generics{ // no idea on either of these:
                                         lifetimes: Vec::new(),
                                         ty_params: OwnedSlice::empty(),
+                                        where_predicates: Vec::new(),
                                     },
                                     ast::P(ast::Block {
                                         view_items: Vec::new(),
@@ -50,13 +50,13 @@ use ast::{SelfRegion, SelfStatic, SelfUniq, SelfValue};
 use ast::{TokenTree, TraitMethod, TraitRef, TTDelim, TTSeq, TTTok};
 use ast::{TTNonterminal, TupleVariantKind, Ty, Ty_, TyBot, TyBox};
 use ast::{TypeField, TyFixedLengthVec, TyClosure, TyBareFn, TyTypeof};
-use ast::{TyInfer, TypeMethod};
+use ast::{TyInfer, TypeBinding, TypeMethod};
 use ast::{TyNil, TyParam, TyParamBound, TyPath, TyPtr, TyRptr};
 use ast::{TyTup, TyU32, TyUniq, TyVec, UnUniq};
 use ast::{UnnamedField, UnsafeBlock, UnsafeFn, ViewItem};
 use ast::{ViewItem_, ViewItemExternCrate, ViewItemUse};
 use ast::{ViewPath, ViewPathGlob, ViewPathList, ViewPathSimple};
-use ast::Visibility;
+use ast::{Visibility, WherePredicate};
 use ast;
 use ast_util::{as_prec, lit_is_str, operator_prec};
 use ast_util;
@@ -514,6 +514,16 @@ impl<'a> Parser<'a> {
         is_kw
     }
 
+    // is the next token the word `where`? It is only a keyword after the
+    // generics and signature of an item, so that it can still be used as
+    // an identifier everywhere else.
+    fn is_where_keyword(&mut self) -> bool {
+        match self.token {
+            token::IDENT(sid, false) => token::get_ident(sid).get() == "where",
+            _ => false
+        }
+    }
+
     // if the given word is not a keyword, signal an error.
     // if the next token is not the given word, signal an error.
     // otherwise, eat it.
@@ -1034,78 +1044,106 @@ impl<'a> Parser<'a> {
         (decl, lifetimes)
     }
 
-    // parse the methods in a trait declaration
-    pub fn parse_trait_methods(&mut self) -> Vec<TraitMethod> {
-        self.parse_unspanned_seq(
-            &token::LBRACE,
-            &token::RBRACE,
-            seq_sep_none(),
-            |p| {
-            let attrs = p.parse_outer_attributes();
-            let lo = p.span.lo;
-
-            let vis_span = p.span;
-            let vis = p.parse_visibility();
-            let style = p.parse_fn_style();
-            // NB: at the moment, trait methods are public by default; this
-            // could change.
-            let ident = p.parse_ident();
-
-            let generics = p.parse_generics();
-
-            let (explicit_self, d) = p.parse_fn_decl_with_self(|p| {
-                // This is somewhat dubious; We don't want to allow argument
-                // names to be left off if there is a definition...
-                p.parse_arg_general(false)
-            });
+    // parse the items in a trait declaration: its methods and its
+    // associated types
+    pub fn parse_trait_items(&mut self) -> (Vec<TyParam>, Vec<TraitMethod>) {
+        let mut assoc_tys = Vec::new();
+        let mut meths = Vec::new();
+        self.expect(&token::LBRACE);
+        while !self.eat(&token::RBRACE) {
+            let attrs = self.parse_outer_attributes();
+            if self.eat_keyword(keywords::Type) {
+                self.forbid_associated_type_attrs(attrs.as_slice());
+                assoc_tys.push(self.parse_associated_type());
+            } else {
+                meths.push(self.parse_trait_method(attrs));
+            }
+        }
+        (assoc_tys, meths)
+    }
 
-            let hi = p.last_span.hi;
-            match p.token {
-              token::SEMI => {
-                p.bump();
-                debug!("parse_trait_methods(): parsing required method");
-                // NB: at the moment, visibility annotations on required
-                // methods are ignored; this could change.
-                if vis != ast::Inherited {
-                    p.obsolete(vis_span, ObsoleteTraitFuncVisibility);
-                }
-                Required(TypeMethod {
-                    ident: ident,
-                    attrs: attrs,
-                    fn_style: style,
-                    decl: d,
-                    generics: generics,
-                    explicit_self: explicit_self,
-                    id: ast::DUMMY_NODE_ID,
-                    span: mk_sp(lo, hi)
-                })
-              }
-              token::LBRACE => {
-                debug!("parse_trait_methods(): parsing provided method");
-                let (inner_attrs, body) =
-                    p.parse_inner_attrs_and_block();
-                let attrs = attrs.append(inner_attrs.as_slice());
-                Provided(@ast::Method {
-                    ident: ident,
-                    attrs: attrs,
-                    generics: generics,
-                    explicit_self: explicit_self,
-                    fn_style: style,
-                    decl: d,
-                    body: body,
-                    id: ast::DUMMY_NODE_ID,
-                    span: mk_sp(lo, hi),
-                    vis: vis,
-                })
-              }
+    // associated types, declared in traits or bound in impls, can't carry
+    // attributes yet
+    fn forbid_associated_type_attrs(&mut self, attrs: &[Attribute]) {
+        for attr in attrs.iter() {
+            self.span_err(attr.span, "attributes are not yet allowed on associated types");
+        }
+    }
 
-              _ => {
-                  let token_str = p.this_token_to_str();
-                  p.fatal(format!("expected `;` or `\\{` but found `{}`",
-                                  token_str))
-              }
+    // parse an associated type declaration, after the `type` keyword:
+    // IDENT optbounds ( EQ ty )? SEMI
+    fn parse_associated_type(&mut self) -> TyParam {
+        let ty_param = self.parse_ty_param();
+        self.expect(&token::SEMI);
+        TyParam { associated: true, ..ty_param }
+    }
+
+    // parse a method in a trait declaration, after its outer attributes
+    fn parse_trait_method(&mut self, attrs: Vec<Attribute>) -> TraitMethod {
+        let lo = self.span.lo;
+
+        let vis_span = self.span;
+        let vis = self.parse_visibility();
+        let style = self.parse_fn_style();
+        // NB: at the moment, trait methods are public by default; this
+        // could change.
+        let ident = self.parse_ident();
+
+        let mut generics = self.parse_generics();
+
+        let (explicit_self, d) = self.parse_fn_decl_with_self(|p| {
+            // This is somewhat dubious; We don't want to allow argument
+            // names to be left off if there is a definition...
+            p.parse_arg_general(false)
+        });
+        self.parse_where_clause(&mut generics);
+
+        let hi = self.last_span.hi;
+        match self.token {
+          token::SEMI => {
+            self.bump();
+            debug!("parse_trait_method(): parsing required method");
+            // NB: at the moment, visibility annotations on required
+            // methods are ignored; this could change.
+            if vis != ast::Inherited {
+                self.obsolete(vis_span, ObsoleteTraitFuncVisibility);
             }
-        })
+            Required(TypeMethod {
+                ident: ident,
+                attrs: attrs,
+                fn_style: style,
+                decl: d,
+                generics: generics,
+                explicit_self: explicit_self,
+                id: ast::DUMMY_NODE_ID,
+                span: mk_sp(lo, hi)
+            })
+          }
+          token::LBRACE => {
+            debug!("parse_trait_method(): parsing provided method");
+            let (inner_attrs, body) =
+                self.parse_inner_attrs_and_block();
+            let attrs = attrs.append(inner_attrs.as_slice());
+            Provided(@ast::Method {
+                ident: ident,
+                attrs: attrs,
+                generics: generics,
+                explicit_self: explicit_self,
+                fn_style: style,
+                decl: d,
+                body: body,
+                id: ast::DUMMY_NODE_ID,
+                span: mk_sp(lo, hi),
+                vis: vis,
+            })
+          }
+
+          _ => {
+              let token_str = self.this_token_to_str();
+              self.fatal(format!("expected `;` or `\\{` but found `{}`",
+                                 token_str))
+          }
+        }
     }
 
     // parse a possibly mutable type
@@ -2055,6 +2093,10 @@ impl<'a> Parser<'a> {
                         Spanned { node, .. } => node,
                     };
                     TTSeq(mk_sp(sp.lo, p.span.hi), Rc::new(seq), s, z)
+                } else if p.is_keyword(keywords::Crate) {
+                    // `$crate`, the root of the crate defining the macro
+                    p.bump();
+                    TTNonterminal(sp, keywords::Crate.to_ident())
                 } else {
                     TTNonterminal(sp, p.parse_ident())
                 }
@@ -3413,7 +3455,8 @@ impl<'a> Parser<'a> {
             ident: ident,
             id: ast::DUMMY_NODE_ID,
             bounds: bounds,
-            default: default
+            default: default,
+            associated: false
         }
     }
 
@@ -3435,12 +3478,59 @@ impl<'a> Parser<'a> {
                 }
                 ty_param
             });
-            ast::Generics { lifetimes: lifetimes, ty_params: ty_params }
+            ast::Generics {
+                lifetimes: lifetimes,
+                ty_params: ty_params,
+                where_predicates: Vec::new()
+            }
         } else {
             ast_util::empty_generics()
         }
     }
 
+    // parse an optional where clause and add its predicates to `generics`
+    // matches where_clause = ( WHERE predicate ( , predicate )* ( , )? )?
+    // where   predicate = ty : bounds
+    fn parse_where_clause(&mut self, generics: &mut ast::Generics) {
+        if !self.is_where_keyword() {
+            return;
+        }
+        self.bump();
+        loop {
+            let lo = self.span.lo;
+            let ty = self.parse_ty(false);
+            // A path type takes the bounds along with it; move them into the
+            // predicate.
+            let (ty, bounds) = match ty.node {
+                TyPath(ref path, Some(ref bounds), id) => {
+                    (P(Ty { id: ty.id, node: TyPath(path.clone(), None, id), span: ty.span }),
+                     Some(bounds.clone()))
+                }
+                _ => {
+                    let (_, bounds) = self.parse_optional_ty_param_bounds(false);
+                    (ty, bounds)
+                }
+            };
+            let bounds = match bounds {
+                Some(bounds) => bounds,
+                None => {
+                    let token_str = self.this_token_to_str();
+                    self.fatal(format!("expected `:` after the type in a `where` clause, \
+                                        found `{}`", token_str))
+                }
+            };
+            generics.where_predicates.push(WherePredicate {
+                span: mk_sp(lo, self.last_span.hi),
+                ty: ty,
+                bounds: bounds,
+            });
+            if !self.eat(&token::COMMA) || self.token == token::LBRACE ||
+                    self.token == token::SEMI {
+                break;
+            }
+        }
+    }
+
     fn parse_generic_values_after_lt(&mut self) -> (Vec<ast::Lifetime>, Vec<P<Ty>> ) {
         let lifetimes = self.parse_lifetimes();
         let result = self.parse_seq_to_gt(
@@ -3756,8 +3846,9 @@ impl<'a> Parser<'a> {
 
     // parse an item-position function declaration.
     fn parse_item_fn(&mut self, fn_style: FnStyle, abi: abi::Abi) -> ItemInfo {
-        let (ident, generics) = self.parse_fn_header();
+        let (ident, mut generics) = self.parse_fn_header();
         let decl = self.parse_fn_decl(false);
+        self.parse_where_clause(&mut generics);
         let (inner_attrs, body) = self.parse_inner_attrs_and_block();
         (ident, ItemFn(decl, fn_style, abi, generics, body), Some(inner_attrs))
     }
@@ -3775,10 +3866,11 @@ impl<'a> Parser<'a> {
         let visa = self.parse_visibility();
         let fn_style = self.parse_fn_style();
         let ident = self.parse_ident();
-        let generics = self.parse_generics();
+        let mut generics = self.parse_generics();
         let (explicit_self, decl) = self.parse_fn_decl_with_self(|p| {
             p.parse_arg()
         });
+        self.parse_where_clause(&mut generics);
 
         let (inner_attrs, body) = self.parse_inner_attrs_and_block();
         let hi = body.span.hi;
@@ -3800,18 +3892,31 @@ impl<'a> Parser<'a> {
     // parse trait Foo { ... }
     fn parse_item_trait(&mut self) -> ItemInfo {
         let ident = self.parse_ident();
-        let tps = self.parse_generics();
+        let mut tps = self.parse_generics();
 
         // Parse traits, if necessary.
         let traits;
         if self.token == token::COLON {
             self.bump();
-            traits = self.parse_trait_ref_list(&token::LBRACE);
+            traits = self.parse_trait_ref_list();
         } else {
             traits = Vec::new();
         }
+        self.parse_where_clause(&mut tps);
 
-        let meths = self.parse_trait_methods();
+        // The associated types follow the trait's other type parameters.
+        let (assoc_tys, meths) = self.parse_trait_items();
+        let tps = if assoc_tys.is_empty() {
+            tps
+        } else {
+            let mut ty_params = tps.ty_params.into_vec();
+            ty_params.push_all_move(assoc_tys);
+            ast::Generics {
+                lifetimes: tps.lifetimes,
+                ty_params: OwnedSlice::from_vec(ty_params),
+                where_predicates: tps.where_predicates
+            }
+        };
         (ident, ItemTrait(tps, traits, meths), None)
     }
 
@@ -3820,7 +3925,7 @@ impl<'a> Parser<'a> {
     //    impl<T> ToStr for ~[T] { ... }
     fn parse_item_impl(&mut self) -> ItemInfo {
         // First, parse type parameters if necessary.
-        let generics = self.parse_generics();
+        let mut generics = self.parse_generics();
 
         // Special case: if the next identifier that follows is '(', don't
         // allow this to be parsed as a trait.
@@ -3836,7 +3941,8 @@ impl<'a> Parser<'a> {
                 TyPath(ref path, None, node_id) => {
                     Some(TraitRef {
                         path: /* bad */ (*path).clone(),
-                        ref_id: node_id
+                        ref_id: node_id,
+                        bindings: Vec::new()
                     })
                 }
                 TyPath(..) => {
@@ -3855,36 +3961,83 @@ impl<'a> Parser<'a> {
         } else {
             None
         };
+        self.parse_where_clause(&mut generics);
 
         let mut meths = Vec::new();
+        let mut bindings = Vec::new();
         self.expect(&token::LBRACE);
         let (inner_attrs, next) = self.parse_inner_attrs_and_next();
         let mut method_attrs = Some(next);
         while !self.eat(&token::RBRACE) {
-            meths.push(self.parse_method(method_attrs));
-            method_attrs = None;
+            let attrs = match method_attrs.take() {
+                Some(attrs) => attrs,
+                None => self.parse_outer_attributes(),
+            };
+            if self.is_keyword(keywords::Type) {
+                self.forbid_associated_type_attrs(attrs.as_slice());
+                bindings.push(self.parse_type_binding());
+            } else {
+                meths.push(self.parse_method(Some(attrs)));
+            }
         }
 
+        let opt_trait = if bindings.is_empty() {
+            opt_trait
+        } else {
+            match opt_trait {
+                Some(trait_ref) => Some(TraitRef { bindings: bindings, ..trait_ref }),
+                None => {
+                    self.span_err(bindings.get(0).span,
+                                  "associated types can only be bound in trait \
+                                   implementations");
+                    None
+                }
+            }
+        };
+
         let ident = ast_util::impl_pretty_name(&opt_trait, ty);
 
         (ident, ItemImpl(generics, opt_trait, ty, meths), Some(inner_attrs))
     }
 
+    // parse the binding of an associated type in an impl:
+    // TYPE IDENT EQ ty SEMI
+    fn parse_type_binding(&mut self) -> TypeBinding {
+        let lo = self.span.lo;
+        self.expect_keyword(keywords::Type);
+        let ident = self.parse_ident();
+        self.expect(&token::EQ);
+        let ty = self.parse_ty(false);
+        self.expect(&token::SEMI);
+        ast::TypeBinding {
+            ident: ident,
+            ty: ty,
+            span: mk_sp(lo, self.last_span.hi)
+        }
+    }
+
     // parse a::B<~str,int>
     fn parse_trait_ref(&mut self) -> TraitRef {
         ast::TraitRef {
             path: self.parse_path(LifetimeAndTypesWithoutColons).path,
             ref_id: ast::DUMMY_NODE_ID,
+            bindings: Vec::new(),
         }
     }
 
-    // parse B + C<~str,int> + D
-    fn parse_trait_ref_list(&mut self, ket: &token::Token) -> Vec<TraitRef> {
-        self.parse_seq_to_before_end(
-            ket,
-            seq_sep_trailing_disallowed(token::BINOP(token::PLUS)),
-            |p| p.parse_trait_ref()
-        )
+    // parse B + C<~str,int> + D, which may be empty before the body or
+    // the where clause of a trait
+    fn parse_trait_ref_list(&mut self) -> Vec<TraitRef> {
+        let mut traits = Vec::new();
+        if self.token == token::LBRACE || self.is_where_keyword() {
+            return traits;
+        }
+        loop {
+            traits.push(self.parse_trait_ref());
+            if !self.eat(&token::BINOP(token::PLUS)) {
+                return traits;
+            }
+        }
     }
 
     // parse struct Foo { ... }
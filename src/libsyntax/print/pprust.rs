@@ -477,7 +477,8 @@ impl<'a> State<'a> {
             ast::TyBareFn(f) => {
                 let generics = ast::Generics {
                     lifetimes: f.lifetimes.clone(),
-                    ty_params: OwnedSlice::empty()
+                    ty_params: OwnedSlice::empty(),
+                    where_predicates: Vec::new()
                 };
                 try!(self.print_ty_fn(Some(f.abi), None, &None,
                                    f.fn_style, ast::Many, f.decl, None, &None,
@@ -486,7 +487,8 @@ impl<'a> State<'a> {
             ast::TyClosure(f) => {
                 let generics = ast::Generics {
                     lifetimes: f.lifetimes.clone(),
-                    ty_params: OwnedSlice::empty()
+                    ty_params: OwnedSlice::empty(),
+                    where_predicates: Vec::new()
                 };
                 try!(self.print_ty_fn(None, Some(f.sigil), &f.region,
                                    f.fn_style, f.onceness, f.decl, None, &f.bounds,
@@ -641,10 +643,16 @@ impl<'a> State<'a> {
                 }
 
                 try!(self.print_type(ty));
+                try!(self.print_where_clause(generics));
 
                 try!(space(&mut self.s));
                 try!(self.bopen());
                 try!(self.print_inner_attributes(item.attrs.as_slice()));
+                for t in opt_trait.iter() {
+                    for binding in t.bindings.iter() {
+                        try!(self.print_type_binding(binding));
+                    }
+                }
                 for meth in methods.iter() {
                     try!(self.print_method(*meth));
                 }
@@ -664,8 +672,12 @@ impl<'a> State<'a> {
                         try!(self.print_path(&trait_.path, false));
                     }
                 }
+                try!(self.print_where_clause(generics));
                 try!(word(&mut self.s, " "));
                 try!(self.bopen());
+                for param in generics.ty_params.iter().filter(|param| param.associated) {
+                    try!(self.print_associated_type(param));
+                }
                 for meth in methods.iter() {
                     try!(self.print_trait_method(meth));
                 }
@@ -869,6 +881,25 @@ impl<'a> State<'a> {
                               &None,
                               Some(&m.generics),
                               Some(m.explicit_self.node)));
+        try!(self.print_where_clause(&m.generics));
+        word(&mut self.s, ";")
+    }
+
+    pub fn print_associated_type(&mut self, param: &ast::TyParam) -> IoResult<()> {
+        try!(self.hardbreak_if_not_bol());
+        try!(self.word_space("type"));
+        try!(self.print_ty_param(param));
+        word(&mut self.s, ";")
+    }
+
+    pub fn print_type_binding(&mut self, binding: &ast::TypeBinding) -> IoResult<()> {
+        try!(self.hardbreak_if_not_bol());
+        try!(self.maybe_print_comment(binding.span.lo));
+        try!(self.word_space("type"));
+        try!(self.print_ident(binding.ident));
+        try!(space(&mut self.s));
+        try!(self.word_space("="));
+        try!(self.print_type(binding.ty));
         word(&mut self.s, ";")
     }
 
@@ -1721,7 +1752,8 @@ impl<'a> State<'a> {
         try!(self.nbsp());
         try!(self.print_ident(name));
         try!(self.print_generics(generics));
-        self.print_fn_args_and_ret(decl, opt_explicit_self)
+        try!(self.print_fn_args_and_ret(decl, opt_explicit_self));
+        self.print_where_clause(generics)
     }
 
     pub fn print_fn_args(&mut self, decl: &ast::FnDecl,
@@ -1861,7 +1893,10 @@ impl<'a> State<'a> {
 
     pub fn print_generics(&mut self,
                           generics: &ast::Generics) -> IoResult<()> {
-        let total = generics.lifetimes.len() + generics.ty_params.len();
+        // Associated types are printed in the body of their trait instead.
+        let ty_params: Vec<&ast::TyParam> =
+            generics.ty_params.iter().filter(|param| !param.associated).collect();
+        let total = generics.lifetimes.len() + ty_params.len();
         if total > 0 {
             try!(word(&mut self.s, "<"));
 
@@ -1878,17 +1913,7 @@ impl<'a> State<'a> {
                         s.print_lifetime(lifetime)
                     } else {
                         let idx = idx - generics.lifetimes.len();
-                        let param = generics.ty_params.get(idx);
-                        try!(s.print_ident(param.ident));
-                        try!(s.print_bounds(&None, &param.bounds, false));
-                        match param.default {
-                            Some(default) => {
-                                try!(space(&mut s.s));
-                                try!(s.word_space("="));
-                                s.print_type(default)
-                            }
-                            _ => Ok(())
-                        }
+                        s.print_ty_param(*ty_params.get(idx))
                     }
                 }));
             word(&mut self.s, ">")
@@ -1897,6 +1922,31 @@ impl<'a> State<'a> {
         }
     }
 
+    pub fn print_where_clause(&mut self, generics: &ast::Generics) -> IoResult<()> {
+        if generics.where_predicates.is_empty() {
+            return Ok(());
+        }
+        try!(space(&mut self.s));
+        try!(self.word_space("where"));
+        self.commasep(Inconsistent, generics.where_predicates.as_slice(), |s, predicate| {
+            try!(s.print_type(predicate.ty));
+            s.print_bounds(&None, &predicate.bounds, false)
+        })
+    }
+
+    pub fn print_ty_param(&mut self, param: &ast::TyParam) -> IoResult<()> {
+        try!(self.print_ident(param.ident));
+        try!(self.print_bounds(&None, &param.bounds, false));
+        match param.default {
+            Some(default) => {
+                try!(space(&mut self.s));
+                try!(self.word_space("="));
+                self.print_type(default)
+            }
+            _ => Ok(())
+        }
+    }
+
     pub fn print_meta_item(&mut self, item: &ast::MetaItem) -> IoResult<()> {
         try!(self.ibox(indent_unit));
         match item.node {
@@ -56,6 +56,7 @@ pub fn generics_of_fn(fk: &FnKind) -> Generics {
             Generics {
                 lifetimes: Vec::new(),
                 ty_params: OwnedSlice::empty(),
+                where_predicates: Vec::new(),
             }
         }
     }
@@ -197,7 +198,10 @@ fn walk_explicit_self<E: Clone, V: Visitor<E>>(visitor: &mut V,
 pub fn walk_trait_ref_helper<E: Clone, V: Visitor<E>>(visitor: &mut V,
                                                       trait_ref: &TraitRef,
                                                       env: E) {
-    visitor.visit_path(&trait_ref.path, trait_ref.ref_id, env)
+    visitor.visit_path(&trait_ref.path, trait_ref.ref_id, env.clone());
+    for binding in trait_ref.bindings.iter() {
+        visitor.visit_ty(binding.ty, env.clone())
+    }
 }
 
 pub fn walk_item<E: Clone, V: Visitor<E>>(visitor: &mut V, item: &Item, env: E) {
@@ -478,6 +482,10 @@ pub fn walk_generics<E: Clone, V: Visitor<E>>(visitor: &mut V,
             None => {}
         }
     }
+    for predicate in generics.where_predicates.iter() {
+        visitor.visit_ty(predicate.ty, env.clone());
+        walk_ty_param_bounds(visitor, &predicate.bounds, env.clone());
+    }
     walk_lifetime_decls(visitor, &generics.lifetimes, env);
 }
 
@@ -11,6 +11,8 @@
 #include "rustllvm.h"
 #include "llvm/Object/Archive.h"
 #include "llvm/Object/ObjectFile.h"
+#include "llvm/ADT/SmallPtrSet.h"
+#include "llvm/Support/ThreadLocal.h"
 
 //===----------------------------------------------------------------------===
 //
@@ -23,20 +25,31 @@ using namespace llvm;
 using namespace llvm::sys;
 using namespace llvm::object;
 
-const char *LLVMRustError;
+// Codegen units are emitted on several threads at once, so every thread has
+// its own last error. It is a copy, since error messages usually come from a
+// std::string which dies with the function reporting the error.
+static ThreadLocal<char> LLVMRustError;
+
+void LLVMRustSetLastError(const char *err) {
+  free(LLVMRustError.get());
+  LLVMRustError.set(strdup(err));
+}
+
+extern "C" const char *LLVMRustGetLastError(void) {
+  return LLVMRustError.get();
+}
 
 extern "C" LLVMMemoryBufferRef
 LLVMRustCreateMemoryBufferWithContentsOfFile(const char *Path) {
   LLVMMemoryBufferRef MemBuf = NULL;
-  LLVMCreateMemoryBufferWithContentsOfFile(Path, &MemBuf,
-    const_cast<char **>(&LLVMRustError));
+  char *err = NULL;
+  if (LLVMCreateMemoryBufferWithContentsOfFile(Path, &MemBuf, &err)) {
+    LLVMRustSetLastError(err);
+    LLVMDisposeMessage(err);
+  }
   return MemBuf;
 }
 
-extern "C" const char *LLVMRustGetLastError(void) {
-  return LLVMRustError;
-}
-
 extern "C" void
 LLVMRustSetNormalizedTarget(LLVMModuleRef M, const char *triple) {
     unwrap(M)->setTargetTriple(Triple::normalize(triple));
@@ -587,14 +600,14 @@ LLVMRustLinkInExternalBitcode(LLVMModuleRef dst, char *bc, size_t len) {
     MemoryBuffer* buf = MemoryBuffer::getMemBufferCopy(StringRef(bc, len));
     ErrorOr<Module *> Src = llvm::getLazyBitcodeModule(buf, Dst->getContext());
     if (!Src) {
-        LLVMRustError = Src.getError().message().c_str();
+        LLVMRustSetLastError(Src.getError().message().c_str());
         delete buf;
         return false;
     }
 
     std::string Err;
     if (Linker::LinkModules(Dst, *Src, Linker::DestroySource, &Err)) {
-        LLVMRustError = Err.c_str();
+        LLVMRustSetLastError(Err.c_str());
         return false;
     }
     return true;
@@ -607,30 +620,320 @@ LLVMRustLinkInExternalBitcode(LLVMModuleRef dst, char *bc, size_t len) {
     std::string Err;
     Module *Src = llvm::getLazyBitcodeModule(buf, Dst->getContext(), &Err);
     if (!Src) {
-        LLVMRustError = Err.c_str();
+        LLVMRustSetLastError(Err.c_str());
         delete buf;
         return false;
     }
 
     if (Linker::LinkModules(Dst, Src, Linker::DestroySource, &Err)) {
-        LLVMRustError = Err.c_str();
+        LLVMRustSetLastError(Err.c_str());
+        return false;
+    }
+    return true;
+}
+#endif
+
+// Links the functions named by `syms` from the module in `bc` into `dst`,
+// keeping `dst`'s definition of any externally visible symbol that both of
+// them define. Used to pull functions that weren't translated again out of
+// the incremental compilation cache; anything else in it, such as functions
+// which have since been removed, is dropped.
+extern "C" bool
+LLVMRustLinkInCachedBitcode(LLVMModuleRef dst, char *bc, size_t len,
+                            const char **syms, size_t nsyms) {
+    Module *Dst = unwrap(dst);
+    MemoryBuffer* buf = MemoryBuffer::getMemBuffer(StringRef(bc, len), "", false);
+    std::string Err;
+#if LLVM_VERSION_MINOR >= 5
+    ErrorOr<Module *> Parsed = llvm::parseBitcodeFile(buf, Dst->getContext());
+    delete buf;
+    if (!Parsed) {
+        LLVMRustSetLastError(Parsed.getError().message().c_str());
+        return false;
+    }
+    Module *Src = *Parsed;
+#else
+    Module *Src = llvm::ParseBitcodeFile(buf, Dst->getContext(), &Err);
+    delete buf;
+    if (!Src) {
+        LLVMRustSetLastError(Err.c_str());
+        return false;
+    }
+#endif
+
+    StringSet<> Reused;
+    for (size_t i = 0; i < nsyms; i++)
+        Reused.insert(syms[i]);
+
+    for (Module::iterator I = Src->begin(), E = Src->end(); I != E; ++I) {
+        if (I->isDeclaration() || I->hasLocalLinkage())
+            continue;
+        Function *F = Dst->getFunction(I->getName());
+        if ((F && !F->isDeclaration()) || !Reused.count(I->getName()))
+            I->deleteBody();
+    }
+    // The globals `dst` doesn't define are only kept if a reused function
+    // still refers to them. Until the dead code is gone they are made
+    // internal, so that they look discardable.
+    std::vector<std::pair<std::string, GlobalValue::LinkageTypes> > Kept;
+    for (Module::global_iterator I = Src->global_begin(),
+                                 E = Src->global_end(); I != E; ++I) {
+        if (I->isDeclaration() || I->hasLocalLinkage() || I->hasAppendingLinkage())
+            continue;
+        GlobalVariable *G = Dst->getGlobalVariable(I->getName());
+        if (G && !G->isDeclaration()) {
+            I->setInitializer(NULL);
+            I->setLinkage(GlobalValue::ExternalLinkage);
+        } else {
+            Kept.push_back(std::make_pair(I->getName().str(), I->getLinkage()));
+            I->setLinkage(GlobalValue::InternalLinkage);
+        }
+    }
+
+    PassManager passes;
+    passes.add(llvm::createGlobalDCEPass());
+    passes.run(*Src);
+    for (size_t i = 0; i < Kept.size(); i++) {
+        GlobalVariable *G = Src->getGlobalVariable(Kept[i].first, true);
+        if (G)
+            G->setLinkage(Kept[i].second);
+    }
+
+    if (Linker::LinkModules(Dst, Src, Linker::DestroySource, &Err)) {
+        LLVMRustSetLastError(Err.c_str());
+        delete Src;
         return false;
     }
+    delete Src;
     return true;
 }
+
+static unsigned
+LLVMRustInstructionCount(const Function &F) {
+    unsigned n = 0;
+    for (Function::const_iterator B = F.begin(), E = F.end(); B != E; ++B)
+        n += B->size();
+    return n;
+}
+
+// Whether `Root` uses, directly or through the local functions it calls, a
+// local global whose copy in another module would not be interchangeable
+// with the original, such as a private `static mut`. Unnamed constants, like
+// string literals, can be duplicated freely.
+static bool
+LLVMRustUsesLocalState(const Function &Root) {
+    SmallVector<const Value*, 32> Work;
+    SmallPtrSet<const Value*, 32> Seen;
+    Work.push_back(&Root);
+    while (!Work.empty()) {
+        const Value *V = Work.pop_back_val();
+        if (!Seen.insert(V))
+            continue;
+        if (const Function *F = dyn_cast<Function>(V)) {
+            if (F != &Root && !F->hasLocalLinkage())
+                continue;
+            for (Function::const_iterator B = F->begin(), BE = F->end(); B != BE; ++B)
+                for (BasicBlock::const_iterator I = B->begin(), IE = B->end(); I != IE; ++I)
+                    for (unsigned i = 0; i < I->getNumOperands(); i++)
+                        Work.push_back(I->getOperand(i));
+        } else if (const GlobalVariable *G = dyn_cast<GlobalVariable>(V)) {
+            if (!G->hasLocalLinkage())
+                continue;
+            if (!G->isConstant() || !G->hasUnnamedAddr())
+                return true;
+            if (G->hasInitializer())
+                Work.push_back(G->getInitializer());
+        } else if (const GlobalAlias *A = dyn_cast<GlobalAlias>(V)) {
+            if (A->hasLocalLinkage())
+                return true;
+        } else if (const Constant *C = dyn_cast<Constant>(V)) {
+            for (unsigned i = 0; i < C->getNumOperands(); i++)
+                Work.push_back(C->getOperand(i));
+        }
+    }
+    return false;
+}
+
+// Links the functions of the module in `bc` which `dst` calls and which are
+// either hinted for inlining or at most `limit` instructions long into `dst`,
+// as available_externally definitions: they may be inlined, but are otherwise
+// still taken from the object they came from. Nothing else is linked in but
+// the local definitions they use, so functions using local state (see
+// LLVMRustUsesLocalState) are left alone. Returns how many functions were
+// imported, or -1 on error.
+extern "C" int
+LLVMRustImportFunctions(LLVMModuleRef dst, char *bc, size_t len, unsigned limit) {
+    Module *Dst = unwrap(dst);
+    MemoryBuffer* buf = MemoryBuffer::getMemBuffer(StringRef(bc, len), "", false);
+    std::string Err;
+#if LLVM_VERSION_MINOR >= 5
+    ErrorOr<Module *> Parsed = llvm::parseBitcodeFile(buf, Dst->getContext());
+    delete buf;
+    if (!Parsed) {
+        LLVMRustSetLastError(Parsed.getError().message().c_str());
+        return -1;
+    }
+    Module *Src = *Parsed;
+#else
+    Module *Src = llvm::ParseBitcodeFile(buf, Dst->getContext(), &Err);
+    delete buf;
+    if (!Src) {
+        LLVMRustSetLastError(Err.c_str());
+        return -1;
+    }
+#endif
+
+    std::vector<Function*> Imports;
+    for (Module::iterator I = Src->begin(), E = Src->end(); I != E; ++I) {
+        if (I->isDeclaration() || I->hasLocalLinkage())
+            continue;
+        Function *F = Dst->getFunction(I->getName());
+        bool wanted = F && F->isDeclaration() &&
+                      !I->hasFnAttribute(Attribute::NoInline) &&
+                      (I->hasFnAttribute(Attribute::InlineHint) ||
+                       LLVMRustInstructionCount(*I) <= limit) &&
+                      !LLVMRustUsesLocalState(*I);
+        if (wanted) {
+            // Until the dead code is gone, the imports must not look
+            // discardable.
+            I->setLinkage(GlobalValue::ExternalLinkage);
+            Imports.push_back(&*I);
+        } else {
+            I->deleteBody();
+        }
+    }
+    std::vector<GlobalVariable*> Dead;
+    for (Module::global_iterator I = Src->global_begin(),
+                                 E = Src->global_end(); I != E; ++I) {
+        if (I->isDeclaration() || I->hasLocalLinkage())
+            continue;
+        if (I->hasAppendingLinkage()) {
+            Dead.push_back(&*I);
+        } else {
+            I->setInitializer(NULL);
+            I->setLinkage(GlobalValue::ExternalLinkage);
+        }
+    }
+    for (size_t i = 0; i < Dead.size(); i++)
+        Dead[i]->eraseFromParent();
+
+    PassManager passes;
+    passes.add(llvm::createGlobalDCEPass());
+    passes.run(*Src);
+    for (size_t i = 0; i < Imports.size(); i++)
+        Imports[i]->setLinkage(GlobalValue::AvailableExternallyLinkage);
+
+    if (Linker::LinkModules(Dst, Src, Linker::DestroySource, &Err)) {
+        LLVMRustSetLastError(Err.c_str());
+        delete Src;
+        return -1;
+    }
+    delete Src;
+    return Imports.size();
+}
+
+// Which of `units` codegen units the global `GV` is defined in. This only
+// depends on its name, so that it's the same from one build to the next.
+static unsigned
+LLVMRustCodegenUnitOf(const GlobalValue &GV, unsigned units) {
+    // Appending globals (llvm.used, llvm.global_ctors) only go in one unit.
+    if (GV.hasAppendingLinkage())
+        return 0;
+    StringRef Name = GV.getName();
+    uint32_t Hash = 2166136261u;
+    for (size_t i = 0; i < Name.size(); i++) {
+        Hash ^= (unsigned char) Name[i];
+        Hash *= 16777619u;
+    }
+    return Hash % units;
+}
+
+static void
+LLVMRustExposeLocal(GlobalValue &GV, const char *prefix) {
+    if (GV.isDeclaration() || !GV.hasLocalLinkage())
+        return;
+    GV.setName(Twine(prefix) + GV.getName());
+    GV.setLinkage(GlobalValue::ExternalLinkage);
+    GV.setVisibility(GlobalValue::HiddenVisibility);
+}
+
+// Gives every local definition in `M` a hidden external symbol, so that it
+// can still be referenced once `M` is split into codegen units.
+extern "C" void
+LLVMRustPrepareCodegenUnits(LLVMModuleRef M, const char *prefix) {
+    Module *Mod = unwrap(M);
+    for (Module::iterator I = Mod->begin(), E = Mod->end(); I != E; ++I)
+        LLVMRustExposeLocal(*I, prefix);
+    for (Module::global_iterator I = Mod->global_begin(),
+                                 E = Mod->global_end(); I != E; ++I)
+        LLVMRustExposeLocal(*I, prefix);
+}
+
+// Reads the module at `path` into `C`, keeping only the definitions which
+// belong to codegen unit `unit` of `units`. Definitions which may be
+// discarded when unused (e.g. inlined from other crates) are kept by every
+// unit, as any of them may need their own copy.
+extern "C" LLVMModuleRef
+LLVMRustLoadCodegenUnit(LLVMContextRef C, const char *path,
+                        unsigned units, unsigned unit) {
+    OwningPtr<MemoryBuffer> buf;
+    error_code err = MemoryBuffer::getFile(path, buf);
+    if (err) {
+        LLVMRustSetLastError(err.message().c_str());
+        return NULL;
+    }
+#if LLVM_VERSION_MINOR >= 5
+    ErrorOr<Module *> Parsed = llvm::parseBitcodeFile(buf.get(), *unwrap(C));
+    if (!Parsed) {
+        LLVMRustSetLastError(Parsed.getError().message().c_str());
+        return NULL;
+    }
+    Module *Mod = *Parsed;
+#else
+    std::string Err;
+    Module *Mod = llvm::ParseBitcodeFile(buf.get(), *unwrap(C), &Err);
+    if (!Mod) {
+        LLVMRustSetLastError(Err.c_str());
+        return NULL;
+    }
 #endif
 
+    for (Module::iterator I = Mod->begin(), E = Mod->end(); I != E; ++I) {
+        if (I->isDeclaration() || I->isDiscardableIfUnused())
+            continue;
+        if (LLVMRustCodegenUnitOf(*I, units) != unit)
+            I->deleteBody();
+    }
+    std::vector<GlobalVariable*> Dead;
+    for (Module::global_iterator I = Mod->global_begin(),
+                                 E = Mod->global_end(); I != E; ++I) {
+        if (I->isDeclaration() || I->isDiscardableIfUnused())
+            continue;
+        if (LLVMRustCodegenUnitOf(*I, units) == unit)
+            continue;
+        if (I->hasAppendingLinkage()) {
+            Dead.push_back(&*I);
+        } else {
+            I->setInitializer(NULL);
+            I->setLinkage(GlobalValue::ExternalLinkage);
+        }
+    }
+    for (size_t i = 0; i < Dead.size(); i++)
+        Dead[i]->eraseFromParent();
+    return wrap(Mod);
+}
+
 extern "C" void*
 LLVMRustOpenArchive(char *path) {
     OwningPtr<MemoryBuffer> buf;
     error_code err = MemoryBuffer::getFile(path, buf);
     if (err) {
-        LLVMRustError = err.message().c_str();
+        LLVMRustSetLastError(err.message().c_str());
         return NULL;
     }
     Archive *ret = new Archive(buf.take(), err);
     if (err) {
-        LLVMRustError = err.message().c_str();
+        LLVMRustSetLastError(err.message().c_str());
         return NULL;
     }
     return ret;
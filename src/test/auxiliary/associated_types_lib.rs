@@ -0,0 +1,34 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(associated_types)]
+
+pub trait Parser {
+    type Output;
+
+    fn parse(&self, s: &str) -> Option<Self::Output>;
+}
+
+pub struct Digits;
+
+impl Parser for Digits {
+    type Output = uint;
+
+    fn parse(&self, s: &str) -> Option<uint> {
+        from_str(s)
+    }
+}
+
+pub fn parse_twice<P: Parser>(p: &P, a: &str, b: &str) -> Option<(P::Output, P::Output)> {
+    match (p.parse(a), p.parse(b)) {
+        (Some(a), Some(b)) => Some((a, b)),
+        _ => None
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(const_fn)]
+
+static BASE: uint = 10;
+
+#[const_fn]
+pub fn pow(base: uint, exp: uint) -> uint {
+    let mut result = 1;
+    let mut i = 0;
+    while i < exp {
+        result *= base;
+        i += 1;
+    }
+    result
+}
+
+#[const_fn]
+pub fn digits(n: uint) -> uint {
+    if n < BASE { 1 } else { 1 + digits(n / BASE) }
+}
@@ -0,0 +1,40 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(macro_rules)]
+
+pub fn double(x: int) -> int { x * 2 }
+
+pub mod inner {
+    pub static OFFSET: int = 10;
+}
+
+#[macro_export]
+macro_rules! double(
+    ($e:expr) => ($crate::double($e))
+)
+
+#[macro_export]
+macro_rules! offset(
+    ($e:expr) => ($e + $crate::inner::OFFSET)
+)
+
+#[macro_export]
+macro_rules! add_one(
+    ($e:expr) => ({ let x = 1; $e + x })
+)
+
+#[macro_export]
+macro_rules! sum(
+    ($($e:expr),*) => (0 $(+ $crate::double($e) / 2)*)
+)
+
+// `$crate` also works in the defining crate
+pub fn quadruple(x: int) -> int { double!(double!(x)) }
@@ -0,0 +1,18 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+use std::fmt::Show;
+
+// The where clause bounds a type written with a module path, which must
+// survive being written to metadata.
+pub fn show_all<T>(v: std::vec::Vec<T>) -> ~str where std::vec::Vec<T>: Show {
+    format!("{}", v)
+}
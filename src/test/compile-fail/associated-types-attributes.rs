@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(associated_types)]
+
+trait Container {
+    /// The type of the elements.
+    //~^ ERROR: attributes are not yet allowed on associated types
+    type Item;
+    #[doc = "The first element."]
+    fn first(&self) -> Self::Item;
+}
+
+impl Container for int {
+    #[cfg(foo)] //~ ERROR: attributes are not yet allowed on associated types
+    type Item = int;
+    #[inline]
+    fn first(&self) -> int { *self }
+}
+
+fn main() {}
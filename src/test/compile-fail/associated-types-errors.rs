@@ -0,0 +1,44 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(associated_types)]
+
+trait Container {
+    type Item;
+    fn first(&self) -> Self::Item;
+    fn last(&self) -> Self::Last; //~ ERROR: trait `Container` has no associated type `Last`
+}
+
+impl Container for int { //~ ERROR: the associated type `Item` of trait `Container` is not bound
+    fn first(&self) -> int { *self }
+    fn last(&self) -> int { *self }
+}
+
+impl Container for uint {
+    type Item = uint;
+    type Index = uint; //~ ERROR: trait `Container` has no associated type `Index`
+    fn first(&self) -> uint { *self }
+    fn last(&self) -> uint { *self }
+}
+
+impl Container for char {
+    type Item = char;
+    type Item = int; //~ ERROR: the associated type `Item` is bound more than once
+    fn first(&self) -> char { *self }
+    fn last(&self) -> char { *self }
+}
+
+fn elem<C: Container>(c: &C) -> C::Elem { //~ ERROR: none of the bounds on `C` declare
+    c.first()
+}
+
+fn object(c: &Container) {} //~ ERROR: the associated type `Item` of trait `Container` must be
+
+fn main() {}
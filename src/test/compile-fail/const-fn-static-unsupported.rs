@@ -0,0 +1,38 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --no-trans
+
+// The statics are evaluated when the crate is checked, so every one which
+// can't be is reported, even without translation.
+
+#![feature(const_fn)]
+
+#[const_fn]
+fn sum(n: uint) -> uint {
+    let mut total = 0;
+    for i in range(0, n) { //~ NOTE the constant evaluation stopped here
+        total += i;
+    }
+    total
+}
+
+#[const_fn]
+fn first(v: &[uint]) -> uint {
+    v[0]
+}
+
+static SUM: uint = sum(4);
+//~^ ERROR `for` loops are not supported in constant functions
+static FIRST: uint = first(&[1, 2]);
+//~^ ERROR this expression is not supported in constant functions
+//~^^ NOTE the constant evaluation stopped here
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// Loop checking doesn't stop the compilation, so the constant evaluator still
+// sees the stray `break`.
+
+#![feature(const_fn)]
+
+#[const_fn]
+fn stray() -> uint { //~ NOTE the constant evaluation stopped here
+    break; //~ ERROR `break` outside of loop
+    1
+}
+
+fn main() {
+    let _a = [0, ..stray()];
+    //~^ ERROR `break` and `continue` outside of loops are not supported in constant functions
+}
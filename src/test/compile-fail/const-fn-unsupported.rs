@@ -0,0 +1,41 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(const_fn)]
+
+#[const_fn]
+fn sum(n: uint) -> uint {
+    let mut total = 0;
+    for i in range(0, n) { //~ NOTE the constant evaluation stopped here
+        total += i;
+    }
+    total
+}
+
+#[const_fn]
+fn forever() -> uint {
+    let mut i = 0u;
+    loop {
+        i += 1;
+    }
+}
+
+fn not_const() -> uint { 3 }
+
+fn main() {
+    let _a = [0, ..sum(4)];
+    //~^ ERROR `for` loops are not supported in constant functions
+    let _b = [0, ..forever()];
+    //~^ ERROR constant evaluation took more than 1000000 steps
+    let _c = [0, ..not_const()];
+    //~^ ERROR `not_const` is not a constant function
+    //~^^ NOTE the constant evaluation stopped here
+}
@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_crate_anchor.rs
+// ignore-stage1
+
+#![feature(phase)]
+
+mod nested {
+    // `$crate` would become `::macro_crate_anchor`, which isn't bound at
+    // the crate root.
+    #[phase(syntax, link)]
+    extern crate macro_crate_anchor;
+    //~^ ERROR crate `macro_crate_anchor` exports macros using `$crate`, so it must be imported at the crate root
+}
+
+fn main() {
+    #[phase(syntax, link)]
+    extern crate macro_crate_anchor;
+    //~^ ERROR crate `macro_crate_anchor` exports macros using `$crate`, so it must be imported at the crate root
+}
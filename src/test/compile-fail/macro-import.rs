@@ -0,0 +1,27 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_crate_anchor.rs
+// ignore-stage1
+
+#![feature(phase)]
+
+#[phase(syntax, link)]
+#[macro_import(double, triple)]
+//~^ ERROR crate `macro_crate_anchor` does not export a macro named `triple`
+extern crate macro_crate_anchor;
+
+#[macro_import(double)] //~ ERROR `#[macro_import]` requires `#[phase(syntax)]`
+extern crate other = "macro_crate_anchor";
+
+fn main() {
+    double!(1);
+    offset!(1); //~ ERROR macro undefined: 'offset'
+}
@@ -12,6 +12,6 @@
 
 fn main() {
     fn bar(n: int) {
-        let _x = [0, ..n]; //~ ERROR expected constant integer for repeat count but found variable
+        let _x = [0, ..n]; //~ ERROR expected constant integer for repeat count: non-constant path
     }
 }
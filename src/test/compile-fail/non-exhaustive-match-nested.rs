@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// error-pattern: non-exhaustive patterns
+// error-pattern: non-exhaustive patterns: `a(c)` not covered
 enum t { a(u), b }
 enum u { c, d }
 
@@ -0,0 +1,25 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+fn main() {
+    match Some(1) {
+        Some(_) => (), //~ NOTE this pattern already matches everything the unreachable one does
+        None => (),
+        Some(1) => () //~ ERROR unreachable pattern
+    }
+
+    match (true, false) {
+        (true, _) => (), //~ NOTE together with the other noted patterns
+        (_, true) => (), //~ NOTE together with the other noted patterns
+        (false, false) => (), //~ NOTE together with the other noted patterns
+        _ => () //~ ERROR unreachable pattern
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt::Show;
+
+struct Wrapper<T> {
+    v: Vec<T>,
+}
+
+impl<T> Wrapper<T> where Vec<T>: Show { //~ ERROR where clauses on impls and traits can only bound
+    fn show(&self) {
+        println!("{}", self.v);
+    }
+}
+
+trait Shown<T> where Vec<T>: Show { //~ ERROR where clauses on impls and traits can only bound
+}
+
+fn main() {}
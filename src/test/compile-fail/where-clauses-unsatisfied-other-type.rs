@@ -0,0 +1,21 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt::Show;
+
+struct NotShow;
+
+fn show_all<T>(v: Vec<T>) -> ~str where Vec<T>: Show {
+    format!("{}", v)
+}
+
+fn main() {
+    show_all(vec!(NotShow)); //~ ERROR failed to find an implementation of trait std::fmt::Show
+}
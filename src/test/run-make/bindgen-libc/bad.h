@@ -0,0 +1,13 @@
+union value {
+    int i;
+    double d;
+};
+
+struct flags {
+    unsigned readable : 1;
+};
+
+int twice(int x);
+long twice(int x);
+
+int ok(void);
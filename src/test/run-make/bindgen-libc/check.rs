@@ -0,0 +1,49 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The generated bindings must compile, and agree with liblibc where they
+// overlap with it.
+
+#![feature(globs)]
+#![allow(dead_code, non_camel_case_types, non_uppercase_statics)]
+
+extern crate real_libc = "libc";
+
+mod libc {
+    pub use real_libc::*;
+    pub use real_libc::types::os::arch::c95::c_uint;
+}
+
+mod constants;
+mod functions;
+mod misc;
+mod types;
+
+#[cfg(target_os = "linux", target_arch = "x86_64")]
+fn check() {
+    use std::mem::size_of;
+    use real_libc::types::os::common::{posix01, bsd44};
+
+    assert_eq!(size_of::<types::timeval>(), size_of::<posix01::timeval>());
+    assert_eq!(size_of::<types::timespec>(), size_of::<posix01::timespec>());
+    assert_eq!(size_of::<types::sockaddr_in>(), size_of::<bsd44::sockaddr_in>());
+    assert_eq!(constants::O_CREAT, real_libc::O_CREAT);
+    assert_eq!(constants::S_IFMT, real_libc::S_IFMT);
+    assert_eq!(constants::EOF, real_libc::EOF);
+}
+
+#[cfg(not(target_os = "linux"))]
+#[cfg(not(target_arch = "x86_64"))]
+fn check() {}
+
+fn main() {
+    check();
+    assert_eq!(misc::MODE_BOTH, misc::MODE_READ | misc::MODE_WRITE);
+}
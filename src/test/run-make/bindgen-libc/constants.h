@@ -0,0 +1,29 @@
+/* liblibc's Linux constants, as the C headers define them. */
+
+#define EOF (-1)
+#define EXIT_FAILURE 1
+#define EXIT_SUCCESS 0
+#define SEEK_SET 0
+#define SEEK_CUR 1
+#define SEEK_END 2
+
+#define O_RDONLY 00
+#define O_WRONLY 01
+#define O_RDWR 02
+#define O_CREAT 0100
+#define O_EXCL 0200
+#define O_TRUNC 01000
+#define O_APPEND 02000
+
+#define S_IFMT 0170000
+#define S_IFDIR 0040000
+#define S_IFCHR 0020000
+#define S_IFBLK 0060000
+#define S_IFREG 0100000
+#define S_IFIFO 0010000
+#define S_IFLNK 0120000
+
+/* Not integer constants. */
+#define _FCNTL_H
+#define S_ISDIR(mode) (((mode) & S_IFMT) == S_IFDIR)
+#define P_tmpdir "/tmp"
@@ -0,0 +1,22 @@
+use libc::{c_int};
+
+pub static EOF: c_int = -1;
+pub static EXIT_FAILURE: c_int = 1;
+pub static EXIT_SUCCESS: c_int = 0;
+pub static SEEK_SET: c_int = 0;
+pub static SEEK_CUR: c_int = 1;
+pub static SEEK_END: c_int = 2;
+pub static O_RDONLY: c_int = 0;
+pub static O_WRONLY: c_int = 1;
+pub static O_RDWR: c_int = 2;
+pub static O_CREAT: c_int = 64;
+pub static O_EXCL: c_int = 128;
+pub static O_TRUNC: c_int = 512;
+pub static O_APPEND: c_int = 1024;
+pub static S_IFMT: c_int = 61440;
+pub static S_IFDIR: c_int = 16384;
+pub static S_IFCHR: c_int = 8192;
+pub static S_IFBLK: c_int = 24576;
+pub static S_IFREG: c_int = 32768;
+pub static S_IFIFO: c_int = 4096;
+pub static S_IFLNK: c_int = 40960;
@@ -0,0 +1,21 @@
+/* Some of the functions of liblibc's c95 and posix88 modules. */
+
+#include <stddef.h>
+#include <sys/types.h>
+
+size_t strlen(const char *cs);
+int strcmp(const char *cs, const char *ct);
+int strncmp(const char *cs, const char *ct, size_t n);
+int memcmp(const void *cx, const void *ct, size_t n);
+
+void *malloc(size_t size);
+void *realloc(void *p, size_t size);
+void free(void *p);
+int rand(void);
+
+int close(int fd);
+pid_t getpid(void);
+ssize_t read(int fd, void *buf, size_t count);
+ssize_t write(int fd, const void *buf, size_t count);
+int nanosleep(const struct timespec *rqtp, struct timespec *rmtp);
+int fcntl(int fd, int cmd, ...);
@@ -0,0 +1,20 @@
+use libc::{c_char, c_int, c_void, pid_t, size_t, ssize_t};
+
+pub enum timespec { }
+
+extern "C" {
+    pub fn strlen(cs: *c_char) -> size_t;
+    pub fn strcmp(cs: *c_char, ct: *c_char) -> c_int;
+    pub fn strncmp(cs: *c_char, ct: *c_char, n: size_t) -> c_int;
+    pub fn memcmp(cx: *c_void, ct: *c_void, n: size_t) -> c_int;
+    pub fn malloc(size: size_t) -> *mut c_void;
+    pub fn realloc(p: *mut c_void, size: size_t) -> *mut c_void;
+    pub fn free(p: *mut c_void);
+    pub fn rand() -> c_int;
+    pub fn close(fd: c_int) -> c_int;
+    pub fn getpid() -> pid_t;
+    pub fn read(fd: c_int, buf: *mut c_void, count: size_t) -> ssize_t;
+    pub fn write(fd: c_int, buf: *c_void, count: size_t) -> ssize_t;
+    pub fn nanosleep(rqtp: *timespec, rmtp: *mut timespec) -> c_int;
+    pub fn fcntl(fd: c_int, cmd: c_int, ...) -> c_int;
+}
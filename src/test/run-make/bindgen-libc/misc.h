@@ -0,0 +1,39 @@
+/* Declarations without a counterpart in liblibc. */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    MODE_READ = 1,
+    MODE_WRITE = 2,
+    MODE_BOTH = MODE_READ | MODE_WRITE
+} mode;
+
+enum { BUFFER_SIZE = 1 << 12 };
+
+typedef struct {
+    const char *name;
+    void (*callback)(int, void *);
+    unsigned char flags[4];
+} handler;
+
+typedef struct session session_t;
+
+struct buffer {
+    int size;
+};
+
+int buffer(struct buffer *b);
+
+extern int verbose;
+
+int register_handler(const handler *h, mode type);
+
+static inline int handler_count(void) {
+    return 0;
+}
+
+#ifdef __cplusplus
+}
+#endif
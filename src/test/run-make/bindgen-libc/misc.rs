@@ -0,0 +1,28 @@
+use libc::{c_char, c_int, c_uchar, c_uint, c_void};
+
+pub type mode = c_uint;
+
+pub static MODE_READ: mode = 1;
+pub static MODE_WRITE: mode = 2;
+pub static MODE_BOTH: mode = 3;
+pub static BUFFER_SIZE: c_int = 4096;
+
+pub struct handler {
+    pub name: *c_char,
+    pub callback: Option<extern "C" fn(c_int, *mut c_void)>,
+    pub flags: [c_uchar, ..4],
+}
+
+pub enum session { }
+
+pub type session_t = session;
+
+pub struct buffer {
+    pub size: c_int,
+}
+
+extern "C" {
+    pub fn buffer(b: *mut buffer) -> c_int;
+    pub static verbose: c_int;
+    pub fn register_handler(h: *handler, type_: mode) -> c_int;
+}
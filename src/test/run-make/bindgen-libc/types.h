@@ -0,0 +1,38 @@
+/* liblibc's x86_64 Linux types, spelled with fixed width integers. */
+
+#include <stdint.h>
+
+typedef int64_t time_t;
+typedef int64_t suseconds_t;
+typedef uint64_t dev_t;
+typedef uint64_t ino_t;
+typedef int32_t pid_t;
+typedef uint32_t uid_t;
+typedef uint32_t gid_t;
+typedef uint32_t mode_t;
+typedef int64_t ssize_t;
+
+typedef uint16_t sa_family_t;
+typedef uint16_t in_port_t;
+typedef uint32_t in_addr_t;
+
+struct timeval {
+    time_t tv_sec;
+    suseconds_t tv_usec;
+};
+
+struct timespec {
+    time_t tv_sec;
+    long tv_nsec;
+};
+
+struct in_addr {
+    in_addr_t s_addr;
+};
+
+struct sockaddr_in {
+    sa_family_t sin_family;
+    in_port_t sin_port;
+    struct in_addr sin_addr;
+    uint8_t sin_zero[8];
+};
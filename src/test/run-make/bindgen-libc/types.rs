@@ -0,0 +1,35 @@
+use libc::{c_long};
+
+pub type time_t = i64;
+pub type suseconds_t = i64;
+pub type dev_t = u64;
+pub type ino_t = u64;
+pub type pid_t = i32;
+pub type uid_t = u32;
+pub type gid_t = u32;
+pub type mode_t = u32;
+pub type ssize_t = i64;
+pub type sa_family_t = u16;
+pub type in_port_t = u16;
+pub type in_addr_t = u32;
+
+pub struct timeval {
+    pub tv_sec: time_t,
+    pub tv_usec: suseconds_t,
+}
+
+pub struct timespec {
+    pub tv_sec: time_t,
+    pub tv_nsec: c_long,
+}
+
+pub struct in_addr {
+    pub s_addr: in_addr_t,
+}
+
+pub struct sockaddr_in {
+    pub sin_family: sa_family_t,
+    pub sin_port: in_port_t,
+    pub sin_addr: in_addr,
+    pub sin_zero: [u8, ..8],
+}
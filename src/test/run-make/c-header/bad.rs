@@ -0,0 +1,27 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "staticlib"]
+
+#[no_mangle]
+pub extern "C" fn sum(pair: (i32, i32)) -> i32 {
+    let (a, b) = pair;
+    a + b
+}
+
+pub struct Unrepr {
+    pub x: i32,
+}
+
+// Behind a pointer this would be fine.
+#[no_mangle]
+pub extern "C" fn take(u: Unrepr) -> i32 {
+    u.x
+}
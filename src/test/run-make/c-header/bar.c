@@ -0,0 +1,13 @@
+#include <assert.h>
+#include "foo.h"
+
+int main() {
+    struct Point a = { 1, 2 };
+    struct Point b = add_points(a, &ORIGIN);
+    assert(b.x == 1 && b.y == 2);
+    assert(shape_sides(Shape_Square) == 4);
+    struct Opaque *opaque = opaque_new();
+    assert(opaque_len(opaque) == 6);
+    opaque_free(opaque);
+    return 0;
+}
@@ -0,0 +1,45 @@
+/* Generated by rustc from crate `foo`. Do not edit. */
+
+#ifndef FOO_H
+#define FOO_H
+
+#include <stdbool.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+struct Point;
+struct Segment;
+struct Opaque;
+
+struct Point {
+    int32_t x;
+    int32_t y;
+};
+
+enum Shape {
+    Shape_Circle = 0,
+    Shape_Square = 4,
+};
+
+struct Segment {
+    struct Point from;
+    const struct Point *to;
+    bool (*visit)(int32_t);
+};
+
+extern const struct Point ORIGIN;
+struct Point add_points(struct Point a, const struct Point *b);
+uint32_t shape_sides(enum Shape shape);
+int64_t segment_width(const struct Segment *segment);
+struct Opaque *opaque_new(void);
+uintptr_t opaque_len(const struct Opaque *opaque);
+void opaque_free(struct Opaque *opaque);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FOO_H */
@@ -0,0 +1,73 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "staticlib"]
+
+use std::cast;
+
+#[repr(C)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[repr(C)]
+pub enum Shape {
+    Circle,
+    Square = 4,
+}
+
+#[repr(C)]
+pub struct Segment {
+    pub from: Point,
+    pub to: *Point,
+    pub visit: extern "C" fn(i32) -> bool,
+}
+
+// Not part of the C interface.
+pub struct Opaque {
+    pub value: ~str,
+}
+
+#[no_mangle]
+pub static ORIGIN: Point = Point { x: 0, y: 0 };
+
+#[no_mangle]
+pub extern "C" fn add_points(a: Point, b: &Point) -> Point {
+    Point { x: a.x + b.x, y: a.y + b.y }
+}
+
+#[no_mangle]
+pub extern "C" fn shape_sides(shape: Shape) -> u32 {
+    match shape {
+        Circle => 0,
+        Square => 4,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn segment_width(segment: *Segment) -> i64 {
+    unsafe { ((*(*segment).to).x - (*segment).from.x) as i64 }
+}
+
+#[no_mangle]
+pub extern "C" fn opaque_new() -> *mut Opaque {
+    unsafe { cast::transmute(~Opaque { value: ~"opaque" }) }
+}
+
+#[no_mangle]
+pub extern "C" fn opaque_len(opaque: &Opaque) -> uint {
+    opaque.value.len()
+}
+
+#[no_mangle]
+pub extern "C" fn opaque_free(opaque: *mut Opaque) {
+    let _: ~Opaque = unsafe { cast::transmute(opaque) };
+}
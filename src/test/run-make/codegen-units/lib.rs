@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "rlib"]
+
+static mut COUNTER: uint = 0;
+
+fn bump() -> uint {
+    unsafe {
+        COUNTER += 1;
+        COUNTER
+    }
+}
+
+pub fn triangle(n: uint) -> uint {
+    range(0, n).map(|_| bump()).fold(0, |a, b| a + b)
+}
+
+pub fn greeting() -> ~str {
+    format!("hello {}", triangle(4))
+}
@@ -0,0 +1,42 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Sends each of its arguments but the first, the daemon's socket, as a
+// request and prints the replies.
+
+use std::io::BufferedReader;
+use std::io::net::unix::UnixStream;
+use std::io::timer;
+use std::os;
+
+fn main() {
+    let args = os::args();
+    let socket = Path::new(args[1].as_slice());
+
+    // The daemon may not have started listening yet.
+    let mut stream = None;
+    for _ in range(0, 600) {
+        match UnixStream::connect(&socket) {
+            Ok(s) => { stream = Some(s); break }
+            Err(..) => timer::sleep(100),
+        }
+    }
+    let stream = stream.expect("the daemon never started listening");
+
+    let mut reader = BufferedReader::new(stream.clone());
+    let mut writer = stream;
+    for request in args.slice_from(2).iter() {
+        writer.write_line(*request).unwrap();
+        if request.as_slice() == "shutdown" {
+            break
+        }
+        print!("{}", reader.read_line().unwrap());
+    }
+}
@@ -0,0 +1,22 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![freestanding]
+
+#[no_mangle]
+pub extern fn checksum(data: *u8, len: uint) -> u8 {
+    let mut sum = 0u8;
+    let mut i = 0;
+    while i < len {
+        sum ^= unsafe { *((data as uint + i) as *u8) };
+        i += 1;
+    }
+    sum
+}
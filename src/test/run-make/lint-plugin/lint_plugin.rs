@@ -0,0 +1,44 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "dylib"]
+#![feature(lint_registrar)]
+
+extern crate rustc;
+extern crate syntax;
+
+use rustc::middle::lint;
+use rustc::middle::lint::{Context, Lint, LintPass, LintRegistry};
+use rustc::middle::ty;
+use syntax::ast;
+
+struct FloatEquality {
+    lint: Lint,
+}
+
+impl LintPass for FloatEquality {
+    fn check_expr(&mut self, cx: &Context, e: &ast::Expr) {
+        match e.node {
+            ast::ExprBinary(ast::BiEq, lhs, _) | ast::ExprBinary(ast::BiNe, lhs, _) => {
+                if ty::type_is_fp(ty::expr_ty(cx.tcx(), lhs)) {
+                    cx.span_lint(self.lint, e.span, "comparing floats for equality");
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[lint_registrar]
+pub fn registrar(reg: &mut LintRegistry) {
+    let lint = reg.register_lint("float_equality", lint::warn,
+                                 "comparisons of floats with `==` or `!=`");
+    reg.register_pass(~FloatEquality { lint: lint } as ~LintPass);
+}
@@ -0,0 +1,26 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(phase)]
+
+#[phase(syntax)]
+extern crate lint_plugin;
+
+#[allow(float_equality)]
+fn is_one(x: f64) -> bool {
+    x == 1.0
+}
+
+fn main() {
+    let x = 0.1f64;
+    if x == 0.1 || is_one(x) {
+        println!("equal");
+    }
+}
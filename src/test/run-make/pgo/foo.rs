@@ -0,0 +1,32 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+use std::os;
+
+fn never_called() -> uint {
+    os::args().len() * 2
+}
+
+fn main() {
+    let n = os::args().len();
+    let mut total = 0;
+    let mut i = 0;
+    while i < 1000 {
+        if i % 10 == n {
+            total += i;
+        }
+        i += 1;
+    }
+    if n > 100 {
+        total += never_called();
+    }
+    println!("{}", total);
+}
@@ -0,0 +1,30 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "rlib"]
+
+pub fn double(x: int) -> int {
+    x * 2
+}
+
+static mut BUMPS: uint = 0;
+
+// Small enough to be imported, but it has to keep using this crate's `BUMPS`.
+pub fn bump() -> uint {
+    unsafe {
+        BUMPS += 1;
+        BUMPS
+    }
+}
+
+#[inline(never)]
+pub fn bumps() -> uint {
+    unsafe { BUMPS }
+}
@@ -0,0 +1,38 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:associated_types_lib.rs
+
+#![feature(associated_types)]
+
+extern crate associated_types_lib;
+
+use associated_types_lib::{Digits, Parser, parse_twice};
+
+struct Words;
+
+impl Parser for Words {
+    type Output = ~str;
+
+    fn parse(&self, s: &str) -> Option<~str> {
+        Some(s.to_owned())
+    }
+}
+
+fn parse_one<P: Parser>(p: &P, s: &str) -> Option<P::Output> {
+    p.parse(s)
+}
+
+pub fn main() {
+    assert_eq!(parse_one(&Digits, "42"), Some(42u));
+    assert_eq!(parse_one(&Words, "hello"), Some(~"hello"));
+    assert_eq!(parse_twice(&Digits, "1", "2"), Some((1u, 2u)));
+    assert_eq!(parse_twice(&Digits, "1", "x"), None);
+}
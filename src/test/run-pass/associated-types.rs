@@ -0,0 +1,56 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(associated_types)]
+
+trait Container {
+    type Item;
+    type Index = uint;
+
+    fn get(&self, i: Self::Index) -> Self::Item;
+    fn first(&self) -> Self::Item;
+}
+
+impl Container for Vec<int> {
+    type Item = int;
+
+    fn get(&self, i: uint) -> int { *self.get(i) }
+    fn first(&self) -> int { *self.get(0) }
+}
+
+struct Letters;
+
+impl Container for Letters {
+    type Item = char;
+    type Index = u8;
+
+    fn get(&self, i: u8) -> char { ('a' as u8 + i) as char }
+    fn first(&self) -> char { 'a' }
+}
+
+fn first_of<C: Container>(c: &C) -> C::Item {
+    c.first()
+}
+
+// Outside of the bounds of functions, the associated types are given after
+// the other type parameters.
+fn first_of_object(c: &Container<int, uint>) -> int {
+    c.first()
+}
+
+pub fn main() {
+    let v = vec!(3, 4, 5);
+    assert_eq!(first_of(&v), 3);
+    assert_eq!(first_of_object(&v as &Container<int, uint>), 3);
+    let x: int = first_of(&v);
+    assert_eq!(x, 3);
+    assert_eq!(first_of(&Letters), 'a');
+    assert_eq!(Letters.get(2u8), 'c');
+}
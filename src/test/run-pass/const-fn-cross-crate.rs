@@ -0,0 +1,25 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// aux-build:const_fn_lib.rs
+
+extern crate const_fn_lib;
+
+use const_fn_lib::{pow, digits};
+
+static KILO: uint = pow(2, 10);
+static BUFFER: [u8, ..digits(12345)] = [0, ..digits(12345)];
+
+pub fn main() {
+    assert_eq!(KILO, 1024);
+    assert_eq!(BUFFER.len(), 5);
+    assert_eq!(pow(3, 4), 81);
+}
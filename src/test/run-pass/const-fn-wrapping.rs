@@ -0,0 +1,52 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Integers evaluated at compile time wrap to the width of their type, as
+// they do at run time.
+
+#![feature(const_fn)]
+
+#[const_fn]
+fn halve_double(x: u8) -> u8 {
+    let y: u8 = x * 2;
+    y / 2
+}
+
+#[const_fn]
+fn add_hundred(x: i8) -> i8 {
+    let mut y = x;
+    y += 100;
+    y
+}
+
+#[const_fn]
+fn halve_sum(x: u8) -> uint {
+    let y = x + x;
+    (y / 2) as uint
+}
+
+// The length is evaluated while collecting the signature, before any
+// function body has been type checked, and wraps all the same.
+fn length(v: [u8, ..halve_sum(200)]) -> uint { v.len() }
+
+static HALVED: u8 = halve_double(200);
+static ADDED: i8 = add_hundred(100);
+static TRUNCATED: u8 = 300 as u8;
+static EXTENDED: u16 = -1i as u16;
+
+pub fn main() {
+    assert_eq!(HALVED, halve_double(200));
+    assert_eq!(HALVED, 72);
+    assert_eq!(ADDED, add_hundred(100));
+    assert_eq!(ADDED, -56);
+    assert_eq!(TRUNCATED, 44);
+    assert_eq!(EXTENDED, 65535);
+    assert_eq!(length([0, ..72]), 72);
+}
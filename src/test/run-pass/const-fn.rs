@@ -0,0 +1,86 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(const_fn)]
+
+#[const_fn]
+fn square(n: uint) -> uint { n * n }
+
+#[const_fn]
+fn fib(n: uint) -> uint {
+    let mut a = 0;
+    let mut b = 1;
+    let mut i = 0;
+    while i < n {
+        let next = a + b;
+        a = b;
+        b = next;
+        i += 1;
+    }
+    a
+}
+
+#[const_fn]
+fn collatz_steps(mut n: u64) -> u64 {
+    let mut steps = 0;
+    loop {
+        if n == 1 {
+            return steps;
+        }
+        n = if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        steps += 1;
+    }
+}
+
+#[const_fn]
+fn digit_name(d: uint) -> &'static str {
+    match d {
+        0 => "zero",
+        1 | 2 | 3 => "small",
+        4..9 => "large",
+        _ => "not a digit"
+    }
+}
+
+#[const_fn]
+fn is_power_of_two(n: uint) -> bool {
+    n != 0 && n & (n - 1) == 0
+}
+
+#[const_fn]
+fn average(a: f64, b: f64) -> f64 {
+    (a + b) / 2.0
+}
+
+static SQUARE: uint = square(12);
+static FIB: uint = fib(10) + 1;
+static STEPS: u64 = collatz_steps(27);
+static NAME: &'static str = digit_name(7);
+static POWER: bool = is_power_of_two(64);
+static AVERAGE: f64 = average(1.0, 2.0);
+static TABLE: [uint, ..square(3)] = [0, ..square(3)];
+
+pub fn main() {
+    assert_eq!(SQUARE, 144);
+    assert_eq!(FIB, 56);
+    assert_eq!(STEPS, 111);
+    assert_eq!(NAME, "large");
+    assert!(POWER);
+    assert_eq!(AVERAGE, 1.5);
+    assert_eq!(TABLE.len(), 9);
+
+    let v: [u8, ..fib(7)] = [0, ..fib(7)];
+    assert_eq!(v.len(), 13);
+
+    // Constant functions can still be called at runtime.
+    assert_eq!(square(5), 25);
+    assert!(!is_power_of_two(12));
+}
@@ -0,0 +1,39 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_crate_anchor.rs
+// ignore-stage1
+
+#![feature(phase)]
+
+// The crate is bound under a different name than its own; `$crate` must
+// still find it.
+#[phase(syntax, link)]
+extern crate anchor = "macro_crate_anchor";
+
+mod nested {
+    // A path relative to this module couldn't reach `anchor`.
+    pub fn check() {
+        assert_eq!(double!(4), 8);
+    }
+}
+
+pub fn main() {
+    assert_eq!(double!(3), 6);
+    assert_eq!(offset!(1), 11);
+    assert_eq!(sum!(1, 2, 3), 6);
+    assert_eq!(anchor::quadruple(2), 8);
+
+    // The macro's own `x` doesn't capture ours.
+    let x = 5;
+    assert_eq!(add_one!(x), 6);
+
+    nested::check();
+}
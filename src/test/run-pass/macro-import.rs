@@ -0,0 +1,28 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:macro_crate_anchor.rs
+// ignore-stage1
+
+#![feature(phase, macro_rules)]
+
+#[phase(syntax, link)]
+#[macro_import(double)]
+extern crate macro_crate_anchor;
+
+// Not imported, so this doesn't clash.
+macro_rules! offset(
+    ($e:expr) => ($e - 1)
+)
+
+pub fn main() {
+    assert_eq!(double!(3), 6);
+    assert_eq!(offset!(1), 0);
+}
@@ -0,0 +1,42 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Where clauses can bound the associated types of a type parameter.
+
+#![feature(associated_types)]
+
+use std::fmt::Show;
+
+trait Container {
+    type Item;
+
+    fn first(&self) -> Self::Item;
+}
+
+impl Container for Vec<int> {
+    type Item = int;
+
+    fn first(&self) -> int { *self.get(0) }
+}
+
+impl Container for ~str {
+    type Item = char;
+
+    fn first(&self) -> char { self.char_at(0) }
+}
+
+fn show_first<C: Container>(c: &C) -> ~str where C::Item: Show {
+    format!("{}", c.first())
+}
+
+pub fn main() {
+    assert_eq!(show_first(&vec!(1, 2)), ~"1");
+    assert_eq!(show_first(&~"abc"), ~"a");
+}
@@ -0,0 +1,68 @@
+// Copyright 2014 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt::Show;
+
+trait Describe<T> where T: Show {
+    fn describe(&self, x: T) -> ~str {
+        format!("<{}>", x)
+    }
+}
+
+struct Describer;
+
+impl<T> Describe<T> for Describer where T: Show { }
+
+struct Pair<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A, B> Pair<A, B> where A: Clone, B: Clone {
+    fn swap(&self) -> Pair<B, A> {
+        Pair { a: self.b.clone(), b: self.a.clone() }
+    }
+}
+
+impl<A, B> Pair<A, B> where A: Show, B: Show {
+    fn show(&self) -> ~str {
+        format!("({}, {})", self.a, self.b)
+    }
+}
+
+impl<A, B> Pair<A, B> {
+    fn first(&self) -> A where A: Clone {
+        self.a.clone()
+    }
+}
+
+fn show_all<T>(v: Vec<T>) -> ~str where Vec<T>: Show {
+    format!("{}", v)
+}
+
+fn largest<T>(xs: &[T]) -> T where T: Ord + Clone, {
+    let mut largest = xs[0].clone();
+    for x in xs.iter() {
+        if *x > largest {
+            largest = x.clone();
+        }
+    }
+    largest
+}
+
+pub fn main() {
+    assert_eq!(largest(&[3, 7, 2]), 7);
+    assert_eq!(largest(&[~"a", ~"c", ~"b"]), ~"c");
+    assert_eq!(Describer.describe(42), ~"<42>");
+    let p = Pair { a: 1, b: ~"one" };
+    assert_eq!(p.swap().show(), ~"(one, 1)");
+    assert_eq!(p.first(), 1);
+    assert_eq!(show_all(vec!(1, 2)), ~"[1, 2]");
+}